@@ -106,12 +106,13 @@ where
     l2_provider.set_cursor(cursor.clone());
 
     let da_provider =
-        EthereumDataSource::new_from_parts(l1_provider.clone(), beacon, &rollup_config);
+        EthereumDataSource::new_from_parts(l1_provider.clone(), beacon.clone(), &rollup_config);
     let pipeline = OraclePipeline::new(
         rollup_config.clone(),
         cursor.clone(),
         oracle.clone(),
         da_provider,
+        beacon,
         l1_provider.clone(),
         l2_provider.clone(),
     )
@@ -107,12 +107,13 @@ where
 
     let evm_factory = FpvmOpEvmFactory::new(hint_client, oracle_client);
     let da_provider =
-        EthereumDataSource::new_from_parts(l1_provider.clone(), beacon, &rollup_config);
+        EthereumDataSource::new_from_parts(l1_provider.clone(), beacon.clone(), &rollup_config);
     let pipeline = OraclePipeline::new(
         rollup_config.clone(),
         cursor.clone(),
         oracle.clone(),
         da_provider,
+        beacon,
         l1_provider.clone(),
         l2_provider.clone(),
     )
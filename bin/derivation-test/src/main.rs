@@ -3,18 +3,21 @@
 //! Usage: cargo run --release -p derivation-test -- --l2-rpc https://mainnet.facet.org --block-number 721318
 
 use alloy_eips::{eip2718::Encodable2718, BlockNumHash};
-use alloy_primitives::hex;
+use alloy_primitives::{hex, Bytes};
 use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_types_eth::{BlockNumberOrTag, BlockTransactions};
 use clap::Parser;
 use eyre::Result;
-use kona_derive::{attributes::StatefulAttributesBuilder, traits::AttributesBuilder};
+use kona_derive::{
+    attributes::StatefulAttributesBuilder, compare_transactions, traits::AttributesBuilder,
+    TxMatchStatus,
+};
 use kona_genesis::RollupConfig;
 use kona_protocol::{BatchValidationProvider, BlockInfo, L2BlockInfo};
-use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider};
+use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider, RpcEndpoint};
 use op_alloy_network::Optimism;
 use std::sync::Arc;
-use tracing::{info, warn};
+use tracing::info;
 
 #[derive(Parser)]
 #[command(about = "Test derivation with detailed output similar to execution-fixture")]
@@ -22,48 +25,22 @@ struct Args {
     #[arg(short = 'b', long)]
     block_number: u64,
     
+    /// L1 RPC endpoint: an http(s):// or ws(s):// URL, or the path of an IPC socket
     #[arg(long, env = "L1_RPC")]
     l1_rpc: String,
     
+    /// L2 RPC endpoint: an http(s):// or ws(s):// URL, or the path of an IPC socket
     #[arg(long, short = 'r', env = "L2_RPC")]
     l2_rpc: String,
 }
 
-fn create_facet_rollup_config() -> Result<RollupConfig> {
-    let mut config = RollupConfig::default();
-    
-    // Set Facet-specific values
-    config.l2_chain_id = 0xface7;
-    config.block_time = 12;
-    config.max_sequencer_drift = 600;
-    config.seq_window_size = 3600;
-    config.channel_timeout = 300;
-    config.granite_channel_timeout = 50;
-    
-    // Set addresses
-    config.batch_inbox_address = "0xFACEC003e8e0cF7152467C26D37634925A9ce65B".parse()?;
-    config.deposit_contract_address = "0x00000000000000000000000000000000000face7".parse()?;
-    
-    // Enable all hardforks from genesis
-    config.hardforks.regolith_time = Some(0);
-    config.hardforks.canyon_time = Some(0);
-    config.hardforks.delta_time = Some(0);
-    config.hardforks.ecotone_time = Some(0);
-    config.hardforks.fjord_time = Some(0);
-    config.hardforks.granite_time = Some(0);
-    
-    Ok(config)
-}
-
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
     let args = Args::parse();
     
-    warn!("⚠️  Chain ID 1027303 not found in registry, using custom facet config");
-    
     // Get actual block first for comparison
-    let l2_provider: RootProvider<Optimism> = RootProvider::new_http(args.l2_rpc.parse()?);
+    let l2_provider: RootProvider<Optimism> = args.l2_rpc.parse::<RpcEndpoint>()?.connect().await?;
     let actual_block = l2_provider
         .get_block_by_number(BlockNumberOrTag::Number(args.block_number))
         .full()
@@ -99,8 +76,8 @@ async fn main() -> Result<()> {
     info!(block_number = args.block_number, "Beginning derivation");
     
     // Setup providers and config
-    let l1_provider: RootProvider = RootProvider::new_http(args.l1_rpc.parse()?);
-    let rollup_config = Arc::new(create_facet_rollup_config()?);
+    let l1_provider: RootProvider = args.l1_rpc.parse::<RpcEndpoint>()?.connect().await?;
+    let rollup_config = Arc::new(RollupConfig::facet());
     let l1_chain_provider = AlloyChainProvider::new(l1_provider.clone(), 100);
     let l2_chain_provider = AlloyL2ChainProvider::new(
         l2_provider.clone(),
@@ -161,24 +138,29 @@ async fn main() -> Result<()> {
     println!();
     
     // Compare like execution-fixture
-    let mut all_match = true;
-    for (i, (geth_tx, kona_tx_bytes)) in actual_txs.iter().zip(kona_txs.iter()).enumerate() {
-        let geth_bytes = geth_tx.inner.inner.encoded_2718();
-        
-        if &geth_bytes != kona_tx_bytes {
-            println!("Transaction {}: ❌ MISMATCH", i);
-            println!("  Geth: {} bytes", geth_bytes.len());
-            println!("  Kona: {} bytes", kona_tx_bytes.len());
-            all_match = false;
-        } else {
-            println!("Transaction {}: ✅ Match ({} bytes)", i, geth_bytes.len());
+    let geth_txs: Vec<Bytes> =
+        actual_txs.iter().map(|tx| Bytes::from(tx.inner.inner.encoded_2718())).collect();
+    let comparison = compare_transactions(kona_txs, &geth_txs);
+    for tx in &comparison.transactions {
+        match tx.status {
+            TxMatchStatus::Match => {
+                println!("Transaction {}: ✅ Match ({} bytes)",
+                    tx.index, tx.actual_len.unwrap_or_default());
+            }
+            TxMatchStatus::Mismatch { first_byte_offset } => {
+                println!("Transaction {}: ❌ MISMATCH", tx.index);
+                println!("  Geth: {} bytes", tx.actual_len.unwrap_or_default());
+                println!("  Kona: {} bytes", tx.derived_len.unwrap_or_default());
+                println!("  First difference at byte {}", first_byte_offset.unwrap_or_default());
+            }
+            TxMatchStatus::MissingDerived | TxMatchStatus::MissingActual => {}
         }
     }
     
-    if kona_txs.len() != actual_txs.len() {
+    if comparison.count_mismatch() {
         println!("\n❌ Transaction count mismatch!");
-        all_match = false;
     }
+    let all_match = comparison.is_match();
     
     println!("\n=== Derivation Validation ===");
     if all_match {
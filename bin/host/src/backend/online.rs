@@ -4,7 +4,7 @@ use crate::SharedKeyValueStore;
 use anyhow::Result;
 use async_trait::async_trait;
 use kona_preimage::{
-    HintRouter, PreimageFetcher, PreimageKey,
+    HintRouter, PreimageFetcher, PreimageKey, PreimageKeyType,
     errors::{PreimageOracleError, PreimageOracleResult},
 };
 use kona_proof::{Hint, errors::HintParsingError};
@@ -131,6 +131,13 @@ where
         // Drop the read lock before beginning the retry loop.
         drop(kv_lock);
 
+        // Local keys are served from the host's configuration alone, so no hint can fetch one that
+        // is missing.
+        if preimage.is_none() && key.key_type() == PreimageKeyType::Local {
+            error!(target: "host_backend", "Local pre-image not available. Key: {key}");
+            return Err(PreimageOracleError::KeyNotFound);
+        }
+
         // Use a loop to keep retrying the prefetch as long as the key is not found
         while preimage.is_none() {
             if let Some(hint) = self.last_hint.read().await.as_ref() {
@@ -150,3 +157,60 @@ where
         preimage.ok_or(PreimageOracleError::KeyNotFound)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MemoryKeyValueStore;
+    use std::time::Duration;
+
+    struct TestCfg;
+
+    #[derive(Debug, Clone, Hash, PartialEq, Eq)]
+    struct TestHintType;
+
+    impl FromStr for TestHintType {
+        type Err = HintParsingError;
+
+        fn from_str(_: &str) -> Result<Self, Self::Err> {
+            Ok(Self)
+        }
+    }
+
+    impl OnlineHostBackendCfg for TestCfg {
+        type HintType = TestHintType;
+        type Providers = ();
+    }
+
+    /// A [HintHandler] that fetches nothing.
+    struct NoopHintHandler;
+
+    #[async_trait]
+    impl HintHandler for NoopHintHandler {
+        type Cfg = TestCfg;
+
+        async fn fetch_hint(
+            _: Hint<TestHintType>,
+            _: &TestCfg,
+            _: &(),
+            _: SharedKeyValueStore,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_local_key_is_not_found() {
+        let kv = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend = OnlineHostBackend::new(TestCfg, kv, (), NoopHintHandler);
+        backend.route_hint("test 0x".to_string()).await.unwrap();
+
+        let preimage = tokio::time::timeout(
+            Duration::from_secs(1),
+            backend.get_preimage(PreimageKey::new_local(1)),
+        )
+        .await
+        .expect("a missing local key must not be retried");
+        assert!(matches!(preimage, Err(PreimageOracleError::KeyNotFound)));
+    }
+}
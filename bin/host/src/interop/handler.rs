@@ -507,7 +507,7 @@ impl HintHandler for InteropHintHandler {
 
                         let da_provider = EthereumDataSource::new_from_parts(
                             l1_provider.clone(),
-                            beacon,
+                            beacon.clone(),
                             &rollup_config,
                         );
                         let pipeline = OraclePipeline::new(
@@ -515,6 +515,7 @@ impl HintHandler for InteropHintHandler {
                             cursor.clone(),
                             oracle,
                             da_provider,
+                            beacon,
                             l1_provider,
                             l2_provider.clone(),
                         )
@@ -1,7 +1,7 @@
 //! Contains a concrete implementation of the [KeyValueStore] trait that stores data on disk,
 //! using the [SingleChainHost] config.
 
-use super::SingleChainHost;
+use super::{SingleChainHost, SingleChainHostError};
 use crate::KeyValueStore;
 use alloy_primitives::B256;
 use anyhow::Result;
@@ -12,6 +12,17 @@ use kona_proof::boot::{
 };
 use tracing::{error, trace};
 
+/// An error resolving the L2 chain ID served for [L2_CHAIN_ID_KEY].
+#[derive(Debug, thiserror::Error)]
+pub enum ChainIdResolveError {
+    /// Neither an L2 chain ID nor a rollup config path is configured.
+    #[error("No L2 chain ID or rollup config path is configured")]
+    NoSource,
+    /// The rollup config could not be read.
+    #[error("Failed to read the rollup config: {0}")]
+    ConfigReadFailed(#[source] SingleChainHostError),
+}
+
 /// A simple, synchronous key-value store that returns data from a [SingleChainHost] config.
 #[derive(Debug)]
 pub struct SingleChainLocalInputs {
@@ -23,6 +34,24 @@ impl SingleChainLocalInputs {
     pub const fn new(cfg: SingleChainHost) -> Self {
         Self { cfg }
     }
+
+    /// Resolves the L2 chain ID. A directly configured `l2_chain_id` takes precedence over the
+    /// chain ID of the rollup config at `rollup_config_path`.
+    ///
+    /// There is no default: serving a made up chain ID would send the program down the wrong
+    /// chain's fork schedule.
+    pub fn resolve_l2_chain_id(&self) -> Result<u64, ChainIdResolveError> {
+        if let Some(chain_id) = self.cfg.l2_chain_id {
+            return Ok(chain_id);
+        }
+        if self.cfg.rollup_config_path.is_none() {
+            return Err(ChainIdResolveError::NoSource);
+        }
+        self.cfg
+            .read_rollup_config()
+            .map(|rollup_config| rollup_config.l2_chain_id)
+            .map_err(ChainIdResolveError::ConfigReadFailed)
+    }
 }
 
 impl KeyValueStore for SingleChainLocalInputs {
@@ -35,31 +64,16 @@ impl KeyValueStore for SingleChainLocalInputs {
             L2_CLAIM_BLOCK_NUMBER_KEY => {
                 Some(self.cfg.claimed_l2_block_number.to_be_bytes().to_vec())
             }
-            L2_CHAIN_ID_KEY => {
-                trace!(target: "local_kv", "L2_CHAIN_ID_KEY requested");
-                // If l2_chain_id is set directly, use it
-                if let Some(chain_id) = self.cfg.l2_chain_id {
-                    trace!(target: "local_kv", "Using direct l2_chain_id: {}", chain_id);
+            L2_CHAIN_ID_KEY => match self.resolve_l2_chain_id() {
+                Ok(chain_id) => {
+                    trace!(target: "local_kv", "Resolved L2 chain ID: {}", chain_id);
                     Some(chain_id.to_be_bytes().to_vec())
-                } else if self.cfg.rollup_config_path.is_some() {
-                    // If using rollup config path, extract chain ID from the rollup config
-                    trace!(target: "local_kv", "Reading rollup config to get chain ID");
-                    match self.cfg.read_rollup_config() {
-                        Ok(rollup_config) => {
-                            trace!(target: "local_kv", "Successfully read rollup config, chain ID: {}", rollup_config.l2_chain_id);
-                            Some(rollup_config.l2_chain_id.to_be_bytes().to_vec())
-                        }
-                        Err(e) => {
-                            error!(target: "local_kv", "Failed to read rollup config: {:?}", e);
-                            None
-                        }
-                    }
-                } else {
-                    // Default to 0 if neither is set
-                    trace!(target: "local_kv", "No chain ID source, defaulting to 0");
-                    Some(0u64.to_be_bytes().to_vec())
                 }
-            }
+                Err(e) => {
+                    error!(target: "local_kv", "Failed to resolve L2 chain ID: {}", e);
+                    None
+                }
+            },
             L2_ROLLUP_CONFIG_KEY => {
                 trace!(target: "local_kv", "Reading rollup config for L2_ROLLUP_CONFIG_KEY");
                 let rollup_config = match self.cfg.read_rollup_config() {
@@ -89,3 +103,53 @@ impl KeyValueStore for SingleChainLocalInputs {
         unreachable!("LocalKeyValueStore is read-only")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use kona_genesis::RollupConfig;
+
+    fn chain_id_value(inputs: &SingleChainLocalInputs) -> Option<Vec<u8>> {
+        inputs.get(PreimageKey::new_local(L2_CHAIN_ID_KEY.to()).into())
+    }
+
+    #[test]
+    fn test_chain_id_resolution_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let rollup_config_path = dir.path().join("rollup.json");
+        let rollup_config = RollupConfig { l2_chain_id: 0xface7, ..Default::default() };
+        std::fs::write(&rollup_config_path, serde_json::to_vec(&rollup_config).unwrap()).unwrap();
+
+        // A direct chain ID wins over the rollup config.
+        let cfg = SingleChainHost {
+            l2_chain_id: Some(10),
+            rollup_config_path: Some(rollup_config_path.clone()),
+            ..Default::default()
+        };
+        let inputs = SingleChainLocalInputs::new(cfg);
+        assert_eq!(inputs.resolve_l2_chain_id().unwrap(), 10);
+        assert_eq!(chain_id_value(&inputs), Some(10u64.to_be_bytes().to_vec()));
+
+        // Otherwise the rollup config's chain ID is used.
+        let cfg =
+            SingleChainHost { rollup_config_path: Some(rollup_config_path), ..Default::default() };
+        let inputs = SingleChainLocalInputs::new(cfg);
+        assert_eq!(inputs.resolve_l2_chain_id().unwrap(), 0xface7);
+
+        // Without either source the key is missing rather than zero.
+        let inputs = SingleChainLocalInputs::new(SingleChainHost::default());
+        assert!(matches!(inputs.resolve_l2_chain_id(), Err(ChainIdResolveError::NoSource)));
+        assert_eq!(chain_id_value(&inputs), None);
+
+        let cfg = SingleChainHost {
+            rollup_config_path: Some(dir.path().join("missing.json")),
+            ..Default::default()
+        };
+        let inputs = SingleChainLocalInputs::new(cfg);
+        assert!(matches!(
+            inputs.resolve_l2_chain_id(),
+            Err(ChainIdResolveError::ConfigReadFailed(_))
+        ));
+        assert_eq!(chain_id_value(&inputs), None);
+    }
+}
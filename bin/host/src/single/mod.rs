@@ -4,7 +4,7 @@ mod cfg;
 pub use cfg::{SingleChainHost, SingleChainHostError, SingleChainProviders};
 
 mod local_kv;
-pub use local_kv::SingleChainLocalInputs;
+pub use local_kv::{ChainIdResolveError, SingleChainLocalInputs};
 
 mod handler;
 pub use handler::SingleChainHintHandler;
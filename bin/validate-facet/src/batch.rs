@@ -0,0 +1,148 @@
+use crate::types::ValidationError;
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types_eth::BlockNumberOrTag;
+use eyre::Result;
+use op_alloy_network::Optimism;
+use std::future::Future;
+use tracing::debug;
+
+/// Full L2 block as returned by `eth_getBlockByNumber`
+pub type RpcBlock = alloy_rpc_types_eth::Block<op_alloy_rpc_types::Transaction>;
+
+/// Source of full L2 blocks, one request per block or several in a single JSON-RPC batch
+pub trait BlockSource {
+    fn block(&self, number: u64) -> impl Future<Output = Result<Option<RpcBlock>>> + Send;
+
+    fn block_batch(
+        &self,
+        numbers: &[u64],
+    ) -> impl Future<Output = Result<Vec<Option<RpcBlock>>>> + Send;
+}
+
+impl BlockSource for RootProvider<Optimism> {
+    async fn block(&self, number: u64) -> Result<Option<RpcBlock>> {
+        self.get_block_by_number(BlockNumberOrTag::Number(number))
+            .full()
+            .await
+            .map_err(|e| ValidationError::rpc(e).into())
+    }
+
+    async fn block_batch(&self, numbers: &[u64]) -> Result<Vec<Option<RpcBlock>>> {
+        let client = self.client();
+        let mut batch = client.new_batch();
+        let waiters = numbers
+            .iter()
+            .map(|number| {
+                batch.add_call::<_, Option<RpcBlock>>(
+                    "eth_getBlockByNumber",
+                    &(BlockNumberOrTag::Number(*number), true),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        batch.send().await.map_err(ValidationError::rpc)?;
+
+        let mut blocks = Vec::with_capacity(waiters.len());
+        for waiter in waiters {
+            blocks.push(waiter.await.map_err(ValidationError::rpc)?);
+        }
+        Ok(blocks)
+    }
+}
+
+/// Fetch full blocks in a single JSON-RPC batch, falling back to one request per block if the
+/// node or transport rejects the batch
+pub async fn fetch_blocks<S: BlockSource>(source: &S, numbers: &[u64]) -> Result<Vec<RpcBlock>> {
+    let blocks = match source.block_batch(numbers).await {
+        Ok(blocks) => blocks,
+        Err(e) => {
+            debug!("Batch request for blocks {:?} failed, fetching one by one: {}", numbers, e);
+            let mut blocks = Vec::with_capacity(numbers.len());
+            for number in numbers {
+                blocks.push(source.block(*number).await?);
+            }
+            blocks
+        }
+    };
+
+    numbers
+        .iter()
+        .zip(blocks)
+        .map(|(number, block)| {
+            block.ok_or_else(|| {
+                ValidationError::NotFound(format!("Block {} not found", number)).into()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retry::classify_error;
+    use crate::types::ErrorType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Node holding blocks up to `head`, counting the round trips it serves
+    struct CountingNode {
+        head: u64,
+        supports_batch: bool,
+        round_trips: AtomicUsize,
+    }
+
+    impl CountingNode {
+        fn new(head: u64, supports_batch: bool) -> Self {
+            Self { head, supports_batch, round_trips: AtomicUsize::new(0) }
+        }
+
+        fn lookup(&self, number: u64) -> Option<RpcBlock> {
+            (number <= self.head).then(|| RpcBlock {
+                header: alloy_rpc_types_eth::Header::new(alloy_consensus::Header {
+                    number,
+                    ..Default::default()
+                }),
+                uncles: vec![],
+                transactions: alloy_rpc_types_eth::BlockTransactions::Hashes(vec![]),
+                withdrawals: None,
+            })
+        }
+    }
+
+    impl BlockSource for CountingNode {
+        async fn block(&self, number: u64) -> Result<Option<RpcBlock>> {
+            self.round_trips.fetch_add(1, Ordering::Relaxed);
+            Ok(self.lookup(number))
+        }
+
+        async fn block_batch(&self, numbers: &[u64]) -> Result<Vec<Option<RpcBlock>>> {
+            self.round_trips.fetch_add(1, Ordering::Relaxed);
+            if !self.supports_batch {
+                return Err(eyre::eyre!("batch requests are not supported"));
+            }
+            Ok(numbers.iter().map(|number| self.lookup(*number)).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_round_trips() {
+        // Parent and target block, where the target is also the block compared against
+        let numbers = [99, 100];
+
+        let batching = CountingNode::new(100, true);
+        let blocks = fetch_blocks(&batching, &numbers).await.unwrap();
+        assert_eq!(blocks.iter().map(|b| b.header.inner.number).collect::<Vec<_>>(), numbers);
+        assert_eq!(batching.round_trips.load(Ordering::Relaxed), 1);
+
+        // The rejected batch costs one extra round trip before the sequential fallback
+        let sequential = CountingNode::new(100, false);
+        let fallback = fetch_blocks(&sequential, &numbers).await.unwrap();
+        assert_eq!(fallback, blocks);
+        assert_eq!(sequential.round_trips.load(Ordering::Relaxed), 1 + numbers.len());
+    }
+
+    #[tokio::test]
+    async fn test_missing_block_is_not_found() {
+        let node = CountingNode::new(99, true);
+        let err = fetch_blocks(&node, &[99, 100]).await.unwrap_err();
+        assert_eq!(classify_error(&err), ErrorType::NotFound);
+    }
+}
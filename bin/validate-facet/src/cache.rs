@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+use kona_derive::traits::L2ChainProvider;
+use kona_genesis::{RollupConfig, SystemConfig};
+use kona_protocol::{to_system_config, BatchValidationProvider, L2BlockInfo};
+use kona_providers_alloy::{AlloyL2ChainProvider, AlloyL2ChainProviderError};
+use lru::LruCache;
+use op_alloy_consensus::OpBlock;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// LRU cache of full L2 blocks by number, shared by all workers of a run.
+///
+/// Contiguous ranges fetch every block several times, as the target of one block and the parent
+/// of the next. [`L2BlockInfo`]s are built from the cached block, so they are cached as well.
+#[derive(Debug)]
+pub struct BlockCache {
+    blocks: Mutex<LruCache<u64, OpBlock>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Hit and miss counts of a [`BlockCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Percentage of lookups served from the cache
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64 * 100.0
+    }
+}
+
+impl BlockCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            blocks: Mutex::new(LruCache::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Cache for the derivation of a single block, used when no cache is shared across the run
+    pub fn per_block() -> Arc<Self> {
+        Arc::new(Self::new(NonZeroUsize::new(100).unwrap()))
+    }
+
+    pub fn get(&self, number: u64) -> Option<OpBlock> {
+        let block = self.blocks.lock().unwrap().get(&number).cloned();
+        let counter = if block.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        block
+    }
+
+    /// Whether `number` is cached, without counting a hit or miss or refreshing its recency
+    pub fn contains(&self, number: u64) -> bool {
+        self.blocks.lock().unwrap().contains(&number)
+    }
+
+    pub fn insert(&self, number: u64, block: OpBlock) {
+        self.blocks.lock().unwrap().put(number, block);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// [`AlloyL2ChainProvider`] that looks blocks up in a shared [`BlockCache`] before fetching them
+#[derive(Debug, Clone)]
+pub struct CachedL2ChainProvider {
+    inner: AlloyL2ChainProvider,
+    rollup_config: Arc<RollupConfig>,
+    cache: Arc<BlockCache>,
+}
+
+impl CachedL2ChainProvider {
+    pub fn new(
+        inner: AlloyL2ChainProvider,
+        rollup_config: Arc<RollupConfig>,
+        cache: Arc<BlockCache>,
+    ) -> Self {
+        Self { inner, rollup_config, cache }
+    }
+}
+
+#[async_trait]
+impl BatchValidationProvider for CachedL2ChainProvider {
+    type Error = AlloyL2ChainProviderError;
+
+    async fn l2_block_info_by_number(&mut self, number: u64) -> Result<L2BlockInfo, Self::Error> {
+        let block = self.block_by_number(number).await?;
+        L2BlockInfo::from_block_and_genesis(&block, &self.rollup_config.genesis)
+            .map_err(|_| AlloyL2ChainProviderError::L2BlockInfoConstruction(number))
+    }
+
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+        if let Some(block) = self.cache.get(number) {
+            return Ok(block);
+        }
+        let block = self.inner.block_by_number(number).await?;
+        self.cache.insert(number, block.clone());
+        Ok(block)
+    }
+}
+
+#[async_trait]
+impl L2ChainProvider for CachedL2ChainProvider {
+    type Error = AlloyL2ChainProviderError;
+
+    async fn system_config_by_number(
+        &mut self,
+        number: u64,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Result<SystemConfig, <Self as BatchValidationProvider>::Error> {
+        let block = self.block_by_number(number).await?;
+        to_system_config(&block, &rollup_config)
+            .map_err(|_| AlloyL2ChainProviderError::SystemConfigConversion(number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_counts_hits_and_evicts() {
+        let cache = BlockCache::new(NonZeroUsize::new(2).unwrap());
+        assert!(cache.get(1).is_none());
+
+        cache.insert(1, OpBlock::default());
+        cache.insert(2, OpBlock::default());
+        assert!(cache.get(1).is_some());
+
+        // Block 2 is the least recently used and makes room for block 3
+        cache.insert(3, OpBlock::default());
+        assert!(cache.get(2).is_none());
+        assert!(cache.get(3).is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats, CacheStats { hits: 2, misses: 2 });
+        assert_eq!(stats.hit_rate(), 50.0);
+    }
+}
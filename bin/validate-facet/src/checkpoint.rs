@@ -0,0 +1,215 @@
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Name of the checkpoint file inside a results directory
+pub const CHECKPOINT_FILE: &str = "checkpoint.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub processed_blocks: Vec<u64>,
+    /// Highest block such that every block from the run's start block up to it was processed
+    #[serde(default)]
+    pub watermark: Option<u64>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Highest block `h` such that every block in `start_block..=h` is in `processed_blocks`
+pub fn contiguous_watermark(processed_blocks: &[u64], start_block: u64) -> Option<u64> {
+    let mut blocks: Vec<u64> =
+        processed_blocks.iter().copied().filter(|b| *b >= start_block).collect();
+    blocks.sort_unstable();
+    blocks.dedup();
+
+    let mut next = start_block;
+    for block in blocks {
+        if block != next {
+            break;
+        }
+        next += 1;
+    }
+    (next > start_block).then(|| next - 1)
+}
+
+impl Checkpoint {
+    /// Load the checkpoint from a results directory
+    pub fn load(results_dir: &Path) -> Result<Self> {
+        let path = results_dir.join(CHECKPOINT_FILE);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| eyre::eyre!("Failed to read {}: {}", path.display(), e))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the checkpoint to a results directory.
+    ///
+    /// The checkpoint is written to a uniquely named temporary file, synced to disk and then renamed
+    /// over the previous one, so a crash mid-write, a concurrent reader or a concurrent save never
+    /// sees or publishes a partial checkpoint.
+    pub fn save(&self, results_dir: &Path) -> Result<()> {
+        let mut tmp = tempfile::NamedTempFile::new_in(results_dir)?;
+        tmp.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+        tmp.as_file().sync_all()?;
+        tmp.persist(results_dir.join(CHECKPOINT_FILE)).map_err(|e| e.error)?;
+        Ok(())
+    }
+
+    /// Block a `--resume-from-watermark` run continues after.
+    ///
+    /// Checkpoints written before the watermark was persisted fall back to the highest block
+    /// contiguous with the lowest processed block.
+    pub fn resume_watermark(&self) -> Option<u64> {
+        self.watermark.or_else(|| self.summarize().highest_contiguous)
+    }
+
+    /// Summarize which blocks a resume from this checkpoint would skip
+    pub fn summarize(&self) -> CheckpointSummary {
+        let mut blocks = self.processed_blocks.clone();
+        blocks.sort_unstable();
+        blocks.dedup();
+
+        let mut gaps = Vec::new();
+        for pair in blocks.windows(2) {
+            if pair[1] > pair[0] + 1 {
+                gaps.push((pair[0] + 1, pair[1] - 1));
+            }
+        }
+
+        // Highest block reachable from the lowest processed block without crossing a gap
+        let highest_contiguous = match gaps.first() {
+            Some((gap_start, _)) => Some(gap_start - 1),
+            None => blocks.last().copied(),
+        };
+
+        CheckpointSummary {
+            processed: blocks.len(),
+            first_block: blocks.first().copied(),
+            last_block: blocks.last().copied(),
+            highest_contiguous,
+            gaps,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Human-oriented view of a [`Checkpoint`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckpointSummary {
+    pub processed: usize,
+    pub first_block: Option<u64>,
+    pub last_block: Option<u64>,
+    pub highest_contiguous: Option<u64>,
+    /// Inclusive ranges of unprocessed blocks between the first and last processed block
+    pub gaps: Vec<(u64, u64)>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl CheckpointSummary {
+    pub fn missing_blocks(&self) -> u64 {
+        self.gaps.iter().map(|(start, end)| end - start + 1).sum()
+    }
+
+    pub fn print(&self, dir: &Path) {
+        println!("📂 Checkpoint: {}", dir.join(CHECKPOINT_FILE).display());
+        println!("  Written at: {}", self.timestamp);
+        println!("  Processed blocks: {}", self.processed);
+        match (self.first_block, self.last_block) {
+            (Some(first), Some(last)) => println!("  Range covered: {} - {}", first, last),
+            _ => println!("  Range covered: (empty)"),
+        }
+        if let Some(highest) = self.highest_contiguous {
+            println!("  Highest contiguous block: {}", highest);
+        }
+        if self.gaps.is_empty() {
+            println!("  Gaps: none");
+        } else {
+            println!("  Gaps: {} ({} blocks missing)", self.gaps.len(), self.missing_blocks());
+            for (start, end) in self.gaps.iter().take(20) {
+                if start == end {
+                    println!("    {}", start);
+                } else {
+                    println!("    {} - {}", start, end);
+                }
+            }
+            if self.gaps.len() > 20 {
+                println!("    ... and {} more", self.gaps.len() - 20);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_identifies_gaps() {
+        let checkpoint = Checkpoint {
+            // Out of order, with a duplicate, as written by concurrent workers
+            processed_blocks: vec![103, 100, 101, 102, 107, 110, 108, 101],
+            watermark: None,
+            timestamp: chrono::Utc::now(),
+        };
+
+        let summary = checkpoint.summarize();
+        assert_eq!(summary.processed, 7);
+        assert_eq!(summary.first_block, Some(100));
+        assert_eq!(summary.last_block, Some(110));
+        assert_eq!(summary.highest_contiguous, Some(103));
+        assert_eq!(summary.gaps, vec![(104, 106), (109, 109)]);
+        assert_eq!(summary.missing_blocks(), 4);
+    }
+
+    #[test]
+    fn test_summary_roundtrips_from_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint = Checkpoint {
+            processed_blocks: vec![5, 6, 7],
+            watermark: Some(7),
+            timestamp: chrono::Utc::now(),
+        };
+        fs::write(dir.path().join(CHECKPOINT_FILE), serde_json::to_string(&checkpoint).unwrap())
+            .unwrap();
+
+        let summary = Checkpoint::load(dir.path()).unwrap().summarize();
+        assert_eq!(summary.highest_contiguous, Some(7));
+        assert!(summary.gaps.is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_reader_never_sees_partial_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint = |blocks: u64| Checkpoint {
+            processed_blocks: (0..blocks).collect(),
+            watermark: blocks.checked_sub(1),
+            timestamp: chrono::Utc::now(),
+        };
+        checkpoint(0).save(dir.path()).unwrap();
+
+        let results_dir = dir.path().to_path_buf();
+        let writer = std::thread::spawn(move || {
+            // Large checkpoints make a torn write likely if the file were written in place
+            for blocks in (0..100).map(|i| i * 200) {
+                checkpoint(blocks).save(&results_dir).unwrap();
+            }
+        });
+        while !writer.is_finished() {
+            let loaded = Checkpoint::load(dir.path()).unwrap();
+            assert_eq!(loaded.watermark, loaded.processed_blocks.last().copied());
+        }
+        writer.join().unwrap();
+
+        assert_eq!(Checkpoint::load(dir.path()).unwrap().processed_blocks.len(), 99 * 200);
+        // No temporary file is left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_watermark_stops_at_first_gap() {
+        // Block 100 is still in flight, so nothing is contiguous with the start yet
+        assert_eq!(contiguous_watermark(&[101, 102], 100), None);
+        assert_eq!(contiguous_watermark(&[102, 100, 101, 104, 106, 99], 100), Some(102));
+        assert_eq!(contiguous_watermark(&[], 100), None);
+    }
+}
@@ -1,129 +1,478 @@
-use crate::retry::{calculate_backoff, classify_error, CircuitBreaker};
-use crate::types::{ErrorType, TestResult};
-use alloy_provider::{Provider, RootProvider};
+use crate::batch::fetch_blocks;
+use crate::cache::BlockCache;
+use crate::providers::Providers;
+use crate::retry::{
+    classify_error, BackoffPolicy, BackoffStrategy, CircuitBreaker, RetryBudget, RetryStop,
+    StageRetries,
+};
+use crate::types::{BlockRefs, ErrorType, TestResult, ValidationError};
+use alloy_consensus::{BlockBody, Eip658Value, Header, Receipt, Transaction, TxEnvelope};
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    eip4895::Withdrawal,
+    BlockNumHash,
+};
+use alloy_primitives::{Address, Bytes, B256, B64};
 use eyre::Result;
 use kona_derive::attributes::StatefulAttributesBuilder;
-use kona_derive::traits::AttributesBuilder;
+use kona_derive::compare_transactions;
+use kona_derive::traits::{AttributesBuilder, ChainProvider};
 use kona_genesis::RollupConfig;
-use kona_protocol::BatchValidationProvider;
-use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider};
-use op_alloy_network::Optimism;
+use kona_protocol::{
+    decode_facet_payload, BatchValidationProvider, FacetParentError, FctMintCalculator,
+    L1BlockInfoFacet, L1BlockInfoTx, L2BlockInfo, FACET_INBOX_ADDRESS, FACET_LOG_INBOX_EVENT_SIG,
+};
+use kona_providers_alloy::{AlloyChainProviderError, AlloyL2ChainProviderError};
+use op_alloy_consensus::{OpBlock, OpTxEnvelope};
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, warn};
 
 pub async fn validate_derivation(
     block: u64,
-    l1_rpc: &str,
-    l2_rpc: &str,
-    max_retries: u32,
-) -> Result<TestResult> {
-    let mut retries = 0;
+    providers: &Providers,
+    backoff_strategy: BackoffStrategy,
+    backoff_policy: &BackoffPolicy,
+    retry_budget: &RetryBudget,
+    mut chain: Option<&mut SequentialChain>,
+) -> Result<(TestResult, BlockRefs)> {
+    let mut retries = StageRetries::new(backoff_strategy, backoff_policy, retry_budget);
     let mut last_error = None;
     let mut last_error_type = None;
     let mut circuit_breaker = CircuitBreaker::new(5, Duration::from_secs(60));
-    let mut effective_max_retries = max_retries;
     
     loop {
         // Check circuit breaker
         if circuit_breaker.is_open() {
-            warn!("Circuit breaker open for block {} derivation, skipping", block);
-            return Ok(TestResult {
+            warn!(block, stage = "derivation", "Circuit breaker open, skipping");
+            return Ok((TestResult {
                 success: false,
                 error: Some("Circuit breaker open - too many consecutive network failures".to_string()),
                 error_type: Some(ErrorType::Network),
-                retries,
-            });
+                retries: retries.retries(),
+                duration_ms: None,
+            }, BlockRefs::default()));
         }
         
-        match run_derivation_test(block, l1_rpc, l2_rpc).await {
-            Ok(_) => {
+        match run_derivation_test(block, providers, chain.as_deref_mut()).await {
+            Ok(refs) => {
                 circuit_breaker.record_success();
-                return Ok(TestResult {
+                return Ok((TestResult {
                     success: true,
                     error: None,
                     error_type: None,
-                    retries,
-                });
+                    retries: retries.retries(),
+                    duration_ms: None,
+                }, refs));
             }
             Err(e) => {
                 let error_type = classify_error(&e);
                 last_error = Some(e.to_string());
                 last_error_type = Some(error_type);
                 
-                // Update effective max retries based on error type
-                effective_max_retries = effective_max_retries.min(error_type.max_retries());
-                
                 // Record failure in circuit breaker for network errors
                 if error_type == ErrorType::Network || error_type == ErrorType::RateLimit {
                     circuit_breaker.record_failure();
                 }
                 
-                // Don't retry if it's a validation error
-                if !error_type.should_retry() {
-                    debug!("Block {} derivation failed with non-retryable error: {:?}", block, error_type);
-                    break;
-                }
-                
-                // Check if we've exceeded retries for this error type
-                if retries >= effective_max_retries {
-                    debug!("Block {} derivation exceeded max retries ({}) for error type {:?}", 
-                        block, effective_max_retries, error_type);
-                    break;
+                match retries.next_backoff(error_type) {
+                    Ok(backoff) => {
+                        debug!(
+                            "Block {} derivation retry {} after {:?} (error type: {:?})",
+                            block, retries.retries(), backoff, error_type
+                        );
+                        tokio::time::sleep(backoff).await;
+                    }
+                    Err(RetryStop::BudgetExhausted) => {
+                        // Fail fast once the run has used up its retries
+                        debug!("Block {} derivation retry budget exhausted", block);
+                        last_error = Some(format!(
+                            "Retry budget exhausted (--max-total-retries): {}",
+                            last_error.unwrap_or_default()
+                        ));
+                        last_error_type = Some(ErrorType::System);
+                        break;
+                    }
+                    Err(stop) => {
+                        debug!(
+                            "Block {} derivation not retried: {:?} for error type {:?}",
+                            block, stop, error_type
+                        );
+                        break;
+                    }
                 }
-                
-                retries += 1;
-                
-                let backoff = calculate_backoff(retries - 1, error_type);
-                debug!(
-                    "Block {} derivation retry {}/{} after {:?} (error type: {:?})",
-                    block, retries, effective_max_retries, backoff, error_type
-                );
-                tokio::time::sleep(backoff).await;
             }
         }
     }
     
-    Ok(TestResult {
+    Ok((TestResult {
         success: false,
         error: last_error,
         error_type: last_error_type,
-        retries,
-    })
+        retries: retries.retries(),
+        duration_ms: None,
+    }, BlockRefs::default()))
 }
 
-async fn run_derivation_test(block: u64, l1_rpc: &str, l2_rpc: &str) -> Result<()> {
+async fn run_derivation_test(
+    block: u64,
+    providers: &Providers,
+    mut chain: Option<&mut SequentialChain>,
+) -> Result<BlockRefs> {
     debug!("Testing derivation for block {}", block);
     
-    // Create providers
-    let l1_provider: RootProvider = RootProvider::new_http(l1_rpc.parse()?);
-    let l2_provider: RootProvider<Optimism> = RootProvider::new_http(l2_rpc.parse()?);
+    let providers = providers.for_block();
+    
+    // In sequential mode the parent was validated just before and carries over from it
+    let carry = chain.as_ref().and_then(|chain| chain.carry.clone());
+    let has_carry = carry.as_ref().is_some_and(|carry| carry.info.block_info.number + 1 == block);
+    
+    // Fetch the parent and the node's block in one round trip. Both are cached, so deriving reads
+    // the parent and target block info without further requests.
+    let numbers = blocks_to_fetch(block, has_carry, providers.l2_blocks.as_deref());
+    let mut fetched = fetch_blocks(&providers.l2, &numbers).await?;
+    fetched.iter().for_each(|b| providers.cache_l2_block(b));
+    let actual_block = fetched.pop().expect("the target block is always fetched");
+    
+    let carry = usable_carry(carry, block, actual_block.header.inner.parent_hash);
+    if let Some(chain) = chain.as_deref_mut() {
+        chain.carry = None;
+        if carry.is_none() {
+            debug!("Block {} parent not carried over, deriving from the node's parent", block);
+            chain.rpc_parents += 1;
+        }
+    }
+    // The attributes builder takes the FCT mint rate and period gas of the derived parent rather
+    // than reading the node's
+    if let Some(carry) = &carry {
+        debug!(
+            "Block {} continues FCT mint rate {} and period gas {} from the derived parent",
+            block, carry.fct_mint_rate, carry.fct_mint_period_l1_data_gas
+        );
+    }
+    
+    let DerivedBlock { transactions: kona_txs, l1_origin, withdrawals, attributes } =
+        derive_block_with_origin(block, &providers, carry.as_ref()).await?;
+    
+    // Compare with actual block from RPC
+    let refs = BlockRefs {
+        l1_origin_hash: Some(l1_origin.hash),
+        l1_origin_number: Some(l1_origin.number),
+        l2_block_hash: Some(actual_block.header.hash),
+    };
+    
+    let actual_txs = match &actual_block.transactions {
+        alloy_rpc_types_eth::BlockTransactions::Full(txs) => txs,
+        _ => return Err(eyre::eyre!("Expected full transactions in block")),
+    };
+    
+    check_withdrawals_root(block, withdrawals.as_deref(), actual_block.header.withdrawals_root)?;
+    let holocene = RollupConfig::facet().is_holocene_active(actual_block.header.timestamp);
+    check_payload_attributes(block, &attributes, &actual_block.header.inner, holocene)?;
+    
+    // Compare each transaction
+    let geth_txs: Vec<Bytes> =
+        actual_txs.iter().map(|tx| Bytes::from(tx.inner.inner.encoded_2718())).collect();
+    let comparison = compare_transactions(&kona_txs, &geth_txs);
+    if comparison.count_mismatch() {
+        return Err(ValidationError::TxCountMismatch {
+            geth: comparison.actual_count,
+            kona: comparison.derived_count,
+        }
+        .into());
+    }
+    if let Some(tx) = comparison.first_mismatch() {
+        return Err(ValidationError::Mismatch {
+            block,
+            index: tx.index,
+            geth_len: tx.actual_len.unwrap_or_default(),
+            kona_len: tx.derived_len.unwrap_or_default(),
+        }
+        .into());
+    }
+    
+    // Recompute the FCT mint period gas independently of the attributes builder
+    let parent_data_gas = carry.map(|carry| carry.fct_mint_period_l1_data_gas);
+    verify_fct_period_data_gas(block, actual_txs, &providers, l1_origin, parent_data_gas).await?;
+    
+    if let Some(chain) = chain {
+        chain.carry = Some(ChainCarry::from_derived(actual_block.header.inner, &kona_txs)?);
+    }
+    
+    Ok(refs)
+}
+
+/// L2 blocks to fetch to validate `block`: the block itself, which is compared against in full,
+/// and its parent unless it is carried over or already cached
+fn blocks_to_fetch(block: u64, has_carry: bool, cache: Option<&BlockCache>) -> Vec<u64> {
+    if block > 1 && !has_carry && !cache.is_some_and(|cache| cache.contains(block - 1)) {
+        vec![block - 1, block]
+    } else {
+        vec![block]
+    }
+}
+
+/// State a validated block hands to its child in `--sequential` mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainCarry {
+    /// Block info of the block, built from its derived L1 info tx
+    pub info: L2BlockInfo,
+    /// FCT mint rate of the derived L1 info tx
+    pub fct_mint_rate: u128,
+    /// FCT mint period gas of the derived L1 info tx
+    pub fct_mint_period_l1_data_gas: u128,
+}
+
+impl ChainCarry {
+    fn from_derived(header: Header, transactions: &[Bytes]) -> Result<Self> {
+        let number = header.number;
+        let transactions = transactions
+            .iter()
+            .map(|tx| OpTxEnvelope::decode_2718(&mut tx.as_ref()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| eyre::eyre!("Failed to decode derived transaction: {}", e))?;
+        let l1_info = facet_l1_info(&transactions)?;
+        let block = OpBlock {
+            header,
+            body: BlockBody { transactions, ommers: vec![], withdrawals: None },
+        };
+        let info = L2BlockInfo::from_block_and_genesis(&block, &RollupConfig::facet().genesis)
+            .map_err(|e| eyre::eyre!("Failed to build block info of {}: {}", number, e))?;
+        Ok(Self {
+            info,
+            fct_mint_rate: l1_info.fct_mint_rate,
+            fct_mint_period_l1_data_gas: l1_info.fct_mint_period_l1_data_gas,
+        })
+    }
+}
+
+/// Carried-over state of a `--sequential` pass over a range
+#[derive(Debug, Default)]
+pub struct SequentialChain {
+    /// State of the last block, if it passed
+    carry: Option<ChainCarry>,
+    /// Blocks whose parent was taken from the node instead of carried over
+    pub rpc_parents: usize,
+}
+
+/// The carried state, if it is of the parent the node's block builds on. A carry of another block
+/// means a gap in the range, and a different hash means the chain reorged since it was validated.
+fn usable_carry(carry: Option<ChainCarry>, block: u64, parent_hash: B256) -> Option<ChainCarry> {
+    carry.filter(|carry| {
+        carry.info.block_info.number + 1 == block && carry.info.block_info.hash == parent_hash
+    })
+}
+
+/// Check the `fct_mint_period_l1_data_gas` in the block's L1 info tx against the parent's value
+/// plus the data gas of the facet payloads in the block's L1 epoch
+async fn verify_fct_period_data_gas(
+    block: u64,
+    actual_txs: &[op_alloy_rpc_types::Transaction],
+    providers: &Providers,
+    l1_origin: BlockNumHash,
+    parent_data_gas: Option<u128>,
+) -> Result<()> {
+    let info = facet_l1_info(actual_txs)?;
+    
+    let parent_data_gas = if let Some(parent_data_gas) = parent_data_gas {
+        parent_data_gas
+    } else {
+        let mut l2_chain_provider = providers.l2_chain_provider();
+        let (_, parent_data_gas) =
+            L1BlockInfoFacet::from_l2_parent(&mut l2_chain_provider, block.saturating_sub(1))
+                .await
+                .map_err(|e| match e {
+                    FacetParentError::Provider(e) => l2_provider_error(e),
+                    e => ValidationError::Invalid(e.to_string()),
+                })?;
+        parent_data_gas
+    };
+    
+    // Facet deposits are only derived in the first block of an epoch
+    let epoch_data_gas = if info.sequence_number == 0 {
+        let mut l1_chain_provider = providers.l1_chain_provider(1);
+        let (_, txs) = l1_chain_provider
+            .block_info_and_transactions_by_hash(l1_origin.hash)
+            .await
+            .map_err(l1_provider_error)?;
+        let receipts = l1_chain_provider
+            .receipts_by_hash(l1_origin.hash)
+            .await
+            .map_err(l1_provider_error)?;
+        let l2_chain_id = RollupConfig::facet().l2_chain_id;
+        Some(epoch_facet_data_gas(&txs, &receipts, l2_chain_id))
+    } else {
+        None
+    };
+    
+    check_fct_period_data_gas(
+        block,
+        expected_fct_period_data_gas(block, parent_data_gas, epoch_data_gas),
+        info.fct_mint_period_l1_data_gas,
+    )
+}
+
+/// Decode the Facet L1 info tx, the first transaction of every L2 block
+fn facet_l1_info<T: Transaction>(txs: &[T]) -> Result<L1BlockInfoFacet> {
+    let invalid = |message: String| eyre::Report::new(ValidationError::Invalid(message));
+    let l1_info_tx =
+        txs.first().ok_or_else(|| invalid("Block has no L1 info transaction".to_string()))?;
+    match L1BlockInfoTx::decode_calldata(l1_info_tx.input()) {
+        Ok(L1BlockInfoTx::Facet(info)) => Ok(info),
+        Ok(_) => Err(invalid("L1 info transaction is not in the Facet format".to_string())),
+        Err(e) => Err(invalid(format!("Failed to decode L1 info transaction: {}", e))),
+    }
+}
+
+/// Sum the L1 data gas of every valid facet payload in an L1 block, counting the full inbox
+/// calldata or log data of each payload
+fn epoch_facet_data_gas(txs: &[TxEnvelope], receipts: &[Receipt], l2_chain_id: u64) -> u128 {
+    let mut total = 0u128;
+    for (tx, receipt) in txs.iter().zip(receipts) {
+        if receipt.status != Eip658Value::Eip658(true) {
+            continue;
+        }
+        if tx.to() == Some(FACET_INBOX_ADDRESS)
+            && decode_facet_payload(tx.input(), l2_chain_id, false).is_ok()
+        {
+            total += FctMintCalculator::calculate_data_gas_used(tx.input(), false) as u128;
+        }
+        for log in &receipt.logs {
+            if log.data.topics().first() == Some(&FACET_LOG_INBOX_EVENT_SIG)
+                && decode_facet_payload(&log.data.data, l2_chain_id, true).is_ok()
+            {
+                total += FctMintCalculator::calculate_data_gas_used(&log.data.data, true) as u128;
+            }
+        }
+    }
+    total
+}
+
+/// Expected `fct_mint_period_l1_data_gas` of `block`.
+///
+/// `epoch_data_gas` is `None` when the block continues its parent's epoch, in which case the
+/// parent's value carries over unchanged.
+fn expected_fct_period_data_gas(block: u64, parent_data_gas: u128, epoch_data_gas: Option<u128>) -> u128 {
+    match epoch_data_gas {
+        None => parent_data_gas,
+        Some(gas) if FctMintCalculator::is_first_block_in_period(block) => gas,
+        Some(gas) => parent_data_gas + gas,
+    }
+}
+
+fn check_fct_period_data_gas(block: u64, expected: u128, actual: u128) -> Result<()> {
+    if expected != actual {
+        return Err(ValidationError::Invalid(format!(
+            "FCT mint period data gas validation failed at block {}: L1 info tx has {} but facet payloads sum to {} (delta {})",
+            block,
+            actual,
+            expected,
+            actual as i128 - expected as i128
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Derive the transactions of an L2 block from its parent and L1 origin
+pub async fn derive_block(block: u64, providers: &Providers) -> Result<Vec<Bytes>> {
+    Ok(derive_block_with_origin(block, providers, None).await?.transactions)
+}
+
+/// Parts of the derived payload attributes checked against the node's block
+struct DerivedBlock {
+    transactions: Vec<Bytes>,
+    /// L1 origin the block was derived from
+    l1_origin: BlockNumHash,
+    withdrawals: Option<Vec<Withdrawal>>,
+    attributes: DerivedAttributes,
+}
+
+/// Header-level fields of the derived payload attributes
+#[derive(Debug, Clone, PartialEq)]
+struct DerivedAttributes {
+    timestamp: u64,
+    suggested_fee_recipient: Address,
+    prev_randao: B256,
+    gas_limit: Option<u64>,
+    parent_beacon_block_root: Option<B256>,
+    /// Holocene EIP-1559 params, with zero params already resolved to the Canyon defaults the
+    /// executor writes into the header
+    eip_1559_params: Option<B64>,
+}
+
+/// [`derive_block`], also returning the L1 origin and withdrawals of the derived block
+async fn derive_block_with_origin(
+    block: u64,
+    providers: &Providers,
+    parent: Option<&ChainCarry>,
+) -> Result<DerivedBlock> {
+    let (attributes, l1_epoch) = derive_attributes(block, providers, parent).await?;
+    let rollup_config = RollupConfig::facet();
+    
+    let eip_1559_params = attributes.eip_1559_params.map(|params| {
+        if params == B64::ZERO {
+            canyon_eip_1559_params(&rollup_config)
+        } else {
+            params
+        }
+    });
+    let derived_attributes = DerivedAttributes {
+        timestamp: attributes.payload_attributes.timestamp,
+        suggested_fee_recipient: attributes.payload_attributes.suggested_fee_recipient,
+        prev_randao: attributes.payload_attributes.prev_randao,
+        gas_limit: attributes.gas_limit,
+        parent_beacon_block_root: attributes.payload_attributes.parent_beacon_block_root,
+        eip_1559_params,
+    };
     
+    let transactions = attributes.transactions
+        .ok_or_else(|| eyre::eyre!("No transactions in derived attributes"))?;
+    Ok(DerivedBlock {
+        transactions,
+        l1_origin: l1_epoch,
+        withdrawals: attributes.payload_attributes.withdrawals,
+        attributes: derived_attributes,
+    })
+}
+
+/// Derive the payload attributes of an L2 block from its parent and L1 origin, returning them with
+/// the L1 origin. The parent's block info and FCT values are read from the node unless a derived
+/// parent is given.
+pub async fn derive_attributes(
+    block: u64,
+    providers: &Providers,
+    parent: Option<&ChainCarry>,
+) -> Result<(OpPayloadAttributes, BlockNumHash)> {
     // Create rollup config for Facet
-    let rollup_config = Arc::new(create_facet_rollup_config()?);
+    let rollup_config = Arc::new(RollupConfig::facet());
     
-    // Create chain providers
-    let l1_chain_provider = AlloyChainProvider::new(l1_provider.clone(), 100);
-    let l2_chain_provider = AlloyL2ChainProvider::new(
-        l2_provider.clone(),
-        rollup_config.clone(),
-        100
-    );
+    // Create chain providers on top of the shared transports
+    let l1_chain_provider = providers.l1_chain_provider(100);
+    let l2_chain_provider = providers.l2_chain_provider();
     
-    // Create attributes builder
+    // Create attributes builder, cross-checking the deposit count of every epoch
     let mut builder = StatefulAttributesBuilder::new(
         rollup_config.clone(),
         l2_chain_provider.clone(),
         l1_chain_provider.clone(),
-    );
+    )
+    .with_deposit_count_check();
     
     // Get parent block info
     let parent_num = block.saturating_sub(1);
     let mut l2_provider_mut = l2_chain_provider.clone();
     
-    let parent_info = if parent_num == 0 {
-        use kona_protocol::{L2BlockInfo, BlockInfo};
-        use alloy_eips::BlockNumHash;
+    let parent_info = if let Some(parent) = parent {
+        builder = builder.with_parent_fct(
+            parent.info.block_info.number,
+            parent.fct_mint_rate,
+            parent.fct_mint_period_l1_data_gas,
+        );
+        parent.info
+    } else if parent_num == 0 {
+        use kona_protocol::BlockInfo;
         L2BlockInfo {
             block_info: BlockInfo {
                 number: 0,
@@ -148,6 +497,11 @@ async fn run_derivation_test(block: u64, l1_rpc: &str, l2_rpc: &str) -> Result<(
         .l2_block_info_by_number(block)
         .await?;
     
+    // The genesis parent is a placeholder without a hash
+    if parent_num != 0 {
+        check_parent_link(block, &parent_info, &target_block_info)?;
+    }
+    
     let l1_epoch = if target_block_info.l1_origin.number != parent_info.l1_origin.number {
         target_block_info.l1_origin
     } else {
@@ -157,68 +511,427 @@ async fn run_derivation_test(block: u64, l1_rpc: &str, l2_rpc: &str) -> Result<(
     // Derive attributes
     let attributes = builder.prepare_payload_attributes(parent_info, l1_epoch).await?;
     
-    let kona_txs = attributes.transactions.as_ref()
-        .ok_or_else(|| eyre::eyre!("No transactions in derived attributes"))?;
-    
-    // Compare with actual block from RPC
-    let actual_block = l2_provider
-        .get_block_by_number(alloy_rpc_types_eth::BlockNumberOrTag::Number(block))
-        .full()
-        .await?
-        .ok_or_else(|| eyre::eyre!("Block {} not found", block))?;
-    
-    let actual_txs = match &actual_block.transactions {
-        alloy_rpc_types_eth::BlockTransactions::Full(txs) => txs,
-        _ => return Err(eyre::eyre!("Expected full transactions in block")),
-    };
+    // The L2 block must not be dated before its L1 origin. The builder already fetched the origin
+    // header and wrote its timestamp into the L1 info tx, so read it from there.
+    let l1_info_tx = attributes
+        .transactions
+        .as_ref()
+        .and_then(|txs| txs.first())
+        .map(|tx| OpTxEnvelope::decode_2718(&mut tx.as_ref()))
+        .transpose()
+        .map_err(|e| {
+            ValidationError::Invalid(format!("Failed to decode L1 info transaction: {}", e))
+        })?;
+    let l1_info = facet_l1_info(l1_info_tx.as_slice())?;
+    check_l1_origin_timestamp(l1_info.time, attributes.payload_attributes.timestamp)?;
     
-    // Verify transaction count matches
-    if actual_txs.len() != kona_txs.len() {
-        return Err(eyre::eyre!(
-            "Transaction count mismatch: Geth {} vs Kona {}",
-            actual_txs.len(),
-            kona_txs.len()
-        ));
+    Ok((attributes, l1_epoch))
+}
+
+/// Keep a missing block distinguishable from a failed request
+fn l2_provider_error(error: AlloyL2ChainProviderError) -> ValidationError {
+    match error {
+        AlloyL2ChainProviderError::Transport(e) => ValidationError::rpc(e),
+        AlloyL2ChainProviderError::BlockNotFound(number) => {
+            ValidationError::NotFound(format!("Block {} not found", number))
+        }
+        e => ValidationError::Invalid(e.to_string()),
     }
-    
-    // Compare each transaction
-    for (i, (geth_tx, kona_tx_bytes)) in actual_txs.iter().zip(kona_txs.iter()).enumerate() {
-        use alloy_eips::eip2718::Encodable2718;
-        let geth_bytes = geth_tx.inner.inner.encoded_2718();
-        
-        if &geth_bytes != kona_tx_bytes {
-            return Err(eyre::eyre!(
-                "Transaction {} differs at block {}: Geth {} bytes vs Kona {} bytes",
-                i, block, geth_bytes.len(), kona_tx_bytes.len()
-            ));
+}
+
+/// Keep a missing L1 block distinguishable from a failed request
+fn l1_provider_error(error: AlloyChainProviderError) -> ValidationError {
+    match error {
+        AlloyChainProviderError::Transport(e) => ValidationError::rpc(e),
+        AlloyChainProviderError::BlockNotFound(id) => {
+            ValidationError::NotFound(format!("L1 block {} not found", id))
+        }
+        e => ValidationError::Invalid(e.to_string()),
+    }
+}
+
+/// Holocene encoding of the Canyon base fee params, used when the attributes carry zero params
+fn canyon_eip_1559_params(rollup_config: &RollupConfig) -> B64 {
+    let params = rollup_config.chain_op_config.as_canyon_base_fee_params();
+    let mut buf = B64::ZERO;
+    buf[..4].copy_from_slice(&(params.max_change_denominator as u32).to_be_bytes());
+    buf[4..].copy_from_slice(&(params.elasticity_multiplier as u32).to_be_bytes());
+    buf
+}
+
+/// Check the derived payload attributes against the fields of the node's block header, reporting
+/// the first field that differs.
+///
+/// Two pipelines can derive the same transactions while disagreeing on these, so this catches
+/// attribute bugs that the transaction comparison misses.
+fn check_payload_attributes(
+    block: u64,
+    derived: &DerivedAttributes,
+    header: &Header,
+    holocene: bool,
+) -> Result<()> {
+    // The header carries the params after the version byte of its Holocene extra data
+    let header_eip_1559_params = if holocene {
+        Some(header.extra_data.get(1..9).and_then(|params| B64::try_from(params).ok()))
+    } else {
+        None
+    };
+    let fields: [(&'static str, String, String); 6] = [
+        ("timestamp", header.timestamp.to_string(), derived.timestamp.to_string()),
+        (
+            "suggested_fee_recipient",
+            header.beneficiary.to_string(),
+            derived.suggested_fee_recipient.to_string(),
+        ),
+        ("prev_randao", header.mix_hash.to_string(), derived.prev_randao.to_string()),
+        ("gas_limit", format!("{:?}", Some(header.gas_limit)), format!("{:?}", derived.gas_limit)),
+        (
+            "parent_beacon_block_root",
+            format!("{:?}", header.parent_beacon_block_root),
+            format!("{:?}", derived.parent_beacon_block_root),
+        ),
+        (
+            "eip_1559_params",
+            format!("{:?}", header_eip_1559_params.flatten()),
+            format!("{:?}", derived.eip_1559_params),
+        ),
+    ];
+
+    for (field, geth, kona) in fields {
+        if geth != kona {
+            return Err(ValidationError::AttributeMismatch { block, field, geth, kona }.into());
         }
     }
-    
     Ok(())
 }
 
-fn create_facet_rollup_config() -> Result<RollupConfig> {
-    let mut config = RollupConfig::default();
-    
-    // Set Facet-specific values
-    config.l2_chain_id = 0xface7;
-    config.block_time = 12;
-    config.max_sequencer_drift = 600;
-    config.seq_window_size = 3600;
-    config.channel_timeout = 300;
-    config.granite_channel_timeout = 50;
-    
-    // Set addresses
-    config.batch_inbox_address = "0xFACEC003e8e0cF7152467C26D37634925A9ce65B".parse()?;
-    config.deposit_contract_address = "0x00000000000000000000000000000000000face7".parse()?;
-    
-    // Enable all hardforks from genesis
-    config.hardforks.regolith_time = Some(0);
-    config.hardforks.canyon_time = Some(0);
-    config.hardforks.delta_time = Some(0);
-    config.hardforks.ecotone_time = Some(0);
-    config.hardforks.fjord_time = Some(0);
-    config.hardforks.granite_time = Some(0);
-    
-    Ok(config)
-}
\ No newline at end of file
+/// Check the block's withdrawals root against the root of the derived withdrawals.
+///
+/// Canyon is active from Facet genesis and Facet never derives withdrawals, so both must be the
+/// empty-trie root rather than zero or absent.
+fn check_withdrawals_root(
+    block: u64,
+    derived_withdrawals: Option<&[Withdrawal]>,
+    withdrawals_root: Option<B256>,
+) -> Result<()> {
+    let derived_root = derived_withdrawals.map(alloy_consensus::proofs::calculate_withdrawals_root);
+    if derived_root != Some(alloy_consensus::EMPTY_ROOT_HASH) {
+        return Err(ValidationError::Invalid(format!(
+            "Withdrawals validation failed at block {}: derived withdrawals root {:?}, expected the empty root",
+            block,
+            derived_root
+        ))
+        .into());
+    }
+    if withdrawals_root != derived_root {
+        return Err(ValidationError::Invalid(format!(
+            "Withdrawals root validation failed at block {}: Geth {:?} vs Kona {:?}",
+            block,
+            withdrawals_root,
+            derived_root
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Check that the target block builds on the parent it is derived from.
+///
+/// The parent and target are fetched separately, so a reorg in between pairs the target with a
+/// stale parent and picks the wrong epoch. That is a transient network condition worth retrying,
+/// not a derivation failure.
+fn check_parent_link(block: u64, parent: &L2BlockInfo, target: &L2BlockInfo) -> Result<()> {
+    if target.block_info.parent_hash != parent.block_info.hash {
+        return Err(ValidationError::Network(format!(
+            "Reorg detected at block {}: parent hash {} vs parent block {} hash {}",
+            block,
+            target.block_info.parent_hash,
+            parent.block_info.number,
+            parent.block_info.hash
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Check that the derived L2 timestamp is not before the L1 origin's timestamp.
+///
+/// A violation means the L1 provider returned the wrong block or a skewed timestamp.
+fn check_l1_origin_timestamp(l1_origin_timestamp: u64, l2_timestamp: u64) -> Result<()> {
+    if l1_origin_timestamp > l2_timestamp {
+        return Err(ValidationError::Invalid(format!(
+            "L1 origin timestamp validation failed: origin timestamp {} is after L2 block timestamp {}",
+            l1_origin_timestamp,
+            l2_timestamp
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheStats;
+
+    #[test]
+    fn test_future_dated_l1_origin_is_validation_failure() {
+        let err = check_l1_origin_timestamp(1_700_000_024, 1_700_000_012).unwrap_err();
+        assert_eq!(classify_error(&err), ErrorType::Validation);
+
+        // An L2 block may share its L1 origin's timestamp
+        assert!(check_l1_origin_timestamp(1_700_000_012, 1_700_000_012).is_ok());
+        assert!(check_l1_origin_timestamp(1_700_000_000, 1_700_000_012).is_ok());
+    }
+
+    #[test]
+    fn test_reorged_parent_is_retryable() {
+        use kona_protocol::BlockInfo;
+
+        let info = |number: u64, hash: u8, parent_hash: u8| L2BlockInfo {
+            block_info: BlockInfo {
+                number,
+                timestamp: number * 12,
+                hash: B256::repeat_byte(hash),
+                parent_hash: B256::repeat_byte(parent_hash),
+            },
+            ..Default::default()
+        };
+        let parent = info(99, 0x11, 0x10);
+        assert!(check_parent_link(100, &parent, &info(100, 0x12, 0x11)).is_ok());
+
+        // The target was fetched after a reorg replaced the parent
+        let err = check_parent_link(100, &parent, &info(100, 0x22, 0x21)).unwrap_err();
+        assert_eq!(classify_error(&err), ErrorType::Network);
+    }
+
+    #[test]
+    fn test_wrong_withdrawals_root_is_validation_failure() {
+        let empty = Some(alloy_consensus::EMPTY_ROOT_HASH);
+        assert!(check_withdrawals_root(100, Some(&[]), empty).is_ok());
+
+        // A zeroed or missing header root diverges from the empty-trie root
+        let err = check_withdrawals_root(100, Some(&[]), Some(B256::ZERO)).unwrap_err();
+        assert_eq!(classify_error(&err), ErrorType::Validation);
+        let err = check_withdrawals_root(100, Some(&[]), None).unwrap_err();
+        assert_eq!(classify_error(&err), ErrorType::Validation);
+
+        // Derived attributes without the empty withdrawals list are rejected even if the header
+        // carries the empty root
+        let err = check_withdrawals_root(100, None, empty).unwrap_err();
+        assert_eq!(classify_error(&err), ErrorType::Validation);
+    }
+
+    /// L1 epoch with an inbox calldata payload, an inbox log payload, a reverted inbox tx and an
+    /// unrelated transfer
+    fn recorded_epoch(l2_chain_id: u64) -> (Vec<TxEnvelope>, Vec<Receipt>) {
+        use alloy_consensus::{Signed, TxLegacy};
+        use alloy_primitives::{Address, Log, LogData, Signature, TxKind, U256};
+
+        let payload = |data: &[u8]| {
+            kona_protocol::encode_facet_payload(
+                l2_chain_id,
+                Some(Address::repeat_byte(0x11)),
+                U256::ZERO,
+                1_000_000,
+                data,
+                &[],
+            )
+        };
+        let tx = |to: Address, input: Bytes| {
+            let legacy = TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: TxKind::Call(to),
+                input,
+                ..Default::default()
+            };
+            TxEnvelope::Legacy(Signed::new_unchecked(
+                legacy,
+                Signature::test_signature(),
+                Default::default(),
+            ))
+        };
+        let receipt = |success: bool, logs: Vec<Log>| Receipt {
+            status: Eip658Value::Eip658(success),
+            logs,
+            ..Default::default()
+        };
+        let inbox_log = Log {
+            address: Address::repeat_byte(0x22),
+            data: LogData::new_unchecked(vec![FACET_LOG_INBOX_EVENT_SIG], payload(&[0x00, 0x56])),
+        };
+
+        let txs = vec![
+            tx(FACET_INBOX_ADDRESS, payload(&[0x12, 0x34])),
+            tx(Address::repeat_byte(0x22), Bytes::new()),
+            tx(FACET_INBOX_ADDRESS, payload(&[0xff])),
+            tx(Address::repeat_byte(0x33), Bytes::from_static(&[0x01])),
+        ];
+        let receipts = vec![
+            receipt(true, vec![]),
+            receipt(true, vec![inbox_log]),
+            receipt(false, vec![]),
+            receipt(true, vec![]),
+        ];
+        (txs, receipts)
+    }
+
+    #[test]
+    fn test_fct_period_data_gas_matches_derivation() {
+        let l2_chain_id = RollupConfig::facet().l2_chain_id;
+        let (txs, receipts) = recorded_epoch(l2_chain_id);
+        // Both payloads encode to 36 bytes without a zero byte: 36 * 16 gas for the calldata,
+        // 36 * 8 gas for the contract-initiated log. The reverted tx does not count.
+        let epoch_data_gas = epoch_facet_data_gas(&txs, &receipts, l2_chain_id);
+        assert_eq!(epoch_data_gas, 576 + 288);
+
+        // Mid-period and period-start blocks, as recorded in the L1 info tx by derivation
+        for (block, expected) in [(101, 5_000 + 864), (FctMintCalculator::ADJUSTMENT_PERIOD, 864)] {
+            let (_, _, recorded) = kona_derive::derive_facet_deposits(
+                &txs,
+                &receipts,
+                l2_chain_id,
+                block,
+                FctMintCalculator::MAX_RATE,
+                5_000,
+            )
+            .unwrap();
+            assert_eq!(recorded, expected);
+            assert_eq!(expected_fct_period_data_gas(block, 5_000, Some(epoch_data_gas)), expected);
+            assert!(check_fct_period_data_gas(block, expected, recorded).is_ok());
+        }
+
+        // Blocks that continue an epoch carry the parent's value
+        assert_eq!(expected_fct_period_data_gas(102, 5_000, None), 5_000);
+    }
+
+    #[test]
+    fn test_fct_period_data_gas_mismatch_is_validation_failure() {
+        let err = check_fct_period_data_gas(101, 5_576, 5_560).unwrap_err();
+        assert_eq!(classify_error(&err), ErrorType::Validation);
+        assert!(err.to_string().contains("delta -16"));
+    }
+
+    fn attributes() -> DerivedAttributes {
+        DerivedAttributes {
+            timestamp: 1_725_557_176,
+            suggested_fee_recipient: Address::ZERO,
+            prev_randao: B256::repeat_byte(0x22),
+            gas_limit: Some(30_000_000),
+            parent_beacon_block_root: Some(B256::repeat_byte(0x33)),
+            eip_1559_params: None,
+        }
+    }
+
+    fn header() -> Header {
+        Header {
+            timestamp: 1_725_557_176,
+            beneficiary: Address::ZERO,
+            mix_hash: B256::repeat_byte(0x22),
+            gas_limit: 30_000_000,
+            parent_beacon_block_root: Some(B256::repeat_byte(0x33)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_payload_attributes_match_header() {
+        assert!(check_payload_attributes(100, &attributes(), &header(), false).is_ok());
+
+        // Holocene params are read from the extra data after the version byte
+        let mut holocene_header = header();
+        holocene_header.extra_data = Bytes::from_static(&[0, 0, 0, 0, 8, 0, 0, 0, 2]);
+        let mut holocene = attributes();
+        holocene.eip_1559_params = Some(canyon_eip_1559_params(&RollupConfig::facet()));
+        assert!(check_payload_attributes(100, &holocene, &holocene_header, true).is_ok());
+    }
+
+    #[test]
+    fn test_first_divergent_attribute_is_reported() {
+        // Identical transactions, but the gas limit and beacon root both diverge
+        let mut derived = attributes();
+        derived.gas_limit = Some(25_000_000);
+        derived.parent_beacon_block_root = None;
+
+        let err = check_payload_attributes(100, &derived, &header(), false).unwrap_err();
+        assert_eq!(classify_error(&err), ErrorType::Validation);
+        match err.downcast_ref::<ValidationError>() {
+            Some(ValidationError::AttributeMismatch { block, field, geth, kona }) => {
+                assert_eq!((*block, *field), (100, "gas_limit"));
+                assert_eq!((geth.as_str(), kona.as_str()), ("Some(30000000)", "Some(25000000)"));
+            }
+            other => panic!("expected an attribute mismatch, got {:?}", other),
+        }
+
+        // Pre-Holocene blocks must not derive EIP-1559 params
+        let mut derived = attributes();
+        derived.eip_1559_params = Some(B64::ZERO);
+        let err = check_payload_attributes(100, &derived, &header(), false).unwrap_err();
+        assert!(err.to_string().contains("eip_1559_params"));
+    }
+
+    #[test]
+    fn test_cached_parent_is_not_fetched_again() {
+        let cache = BlockCache::new(std::num::NonZeroUsize::new(10).unwrap());
+        assert_eq!(blocks_to_fetch(100, false, Some(&cache)), vec![99, 100]);
+        assert_eq!(blocks_to_fetch(100, true, Some(&cache)), vec![100]);
+        assert_eq!(blocks_to_fetch(1, false, None), vec![1]);
+
+        cache.insert(99, OpBlock::default());
+        assert_eq!(blocks_to_fetch(100, false, Some(&cache)), vec![100]);
+        // Looking ahead does not count towards the cache statistics
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+
+    #[test]
+    fn test_carry_only_continues_the_parent() {
+        let carry = ChainCarry {
+            info: L2BlockInfo {
+                block_info: kona_protocol::BlockInfo {
+                    number: 99,
+                    hash: B256::repeat_byte(0x99),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            fct_mint_rate: FctMintCalculator::INITIAL_RATE,
+            fct_mint_period_l1_data_gas: 576,
+        };
+
+        let parent_hash = B256::repeat_byte(0x99);
+        assert_eq!(usable_carry(Some(carry.clone()), 100, parent_hash), Some(carry.clone()));
+        // A gap in the range
+        assert_eq!(usable_carry(Some(carry.clone()), 101, parent_hash), None);
+        // The node's block 100 builds on another block 99
+        assert_eq!(usable_carry(Some(carry), 100, B256::repeat_byte(0xaa)), None);
+        assert_eq!(usable_carry(None, 100, parent_hash), None);
+    }
+
+    #[test]
+    fn test_carry_takes_fct_values_from_derived_l1_info() {
+        use alloy_eips::eip2718::Encodable2718;
+        use alloy_primitives::Sealed;
+        use op_alloy_consensus::TxDeposit;
+
+        let l1_info = L1BlockInfoTx::Facet(L1BlockInfoFacet {
+            number: 19_000_000,
+            block_hash: B256::repeat_byte(0x11),
+            sequence_number: 2,
+            fct_mint_rate: FctMintCalculator::INITIAL_RATE / 2,
+            fct_mint_period_l1_data_gas: 12_345,
+            ..Default::default()
+        });
+        let deposit = TxDeposit { input: l1_info.encode_calldata(), ..Default::default() };
+        let l1_info_tx = OpTxEnvelope::Deposit(Sealed::new(deposit)).encoded_2718();
+        let header = Header { number: 100, ..Default::default() };
+
+        let carry = ChainCarry::from_derived(header.clone(), &[l1_info_tx.into()]).unwrap();
+        assert_eq!(carry.fct_mint_rate, FctMintCalculator::INITIAL_RATE / 2);
+        assert_eq!(carry.fct_mint_period_l1_data_gas, 12_345);
+        assert_eq!(carry.info.block_info.number, 100);
+        assert_eq!(carry.info.l1_origin.number, 19_000_000);
+        assert_eq!(carry.info.seq_num, 2);
+        assert_eq!(carry.info.block_info.hash, header.hash_slow());
+    }
+}
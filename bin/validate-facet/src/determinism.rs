@@ -0,0 +1,172 @@
+use crate::derivation::derive_block;
+use crate::providers::Providers;
+use alloy_primitives::{keccak256, Bytes, B256};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+
+/// Produces the derived transactions of an L2 block
+pub trait BlockDeriver {
+    fn derive(&self, block: u64) -> impl Future<Output = Result<Vec<Bytes>>> + Send;
+}
+
+/// [`BlockDeriver`] that runs the attributes builder against live L1/L2 RPC endpoints
+pub struct RpcDeriver {
+    providers: Providers,
+}
+
+impl RpcDeriver {
+    pub fn new(providers: Providers) -> Self {
+        Self { providers }
+    }
+}
+
+impl BlockDeriver for RpcDeriver {
+    async fn derive(&self, block: u64) -> Result<Vec<Bytes>> {
+        derive_block(block, &self.providers).await
+    }
+}
+
+/// Derived transactions of a block as recorded by one process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivationRecord {
+    pub block: u64,
+    pub transactions: Vec<Bytes>,
+    pub digest: B256,
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Outcome of re-deriving a recorded block
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayReport {
+    pub block: u64,
+    pub recorded: usize,
+    pub replayed: usize,
+    /// Index of the first transaction that differs, or the shorter length if one list is a prefix
+    pub first_mismatch: Option<usize>,
+}
+
+impl ReplayReport {
+    pub fn is_deterministic(&self) -> bool {
+        self.first_mismatch.is_none()
+    }
+}
+
+/// Digest over the ordered, length-prefixed transaction bytes
+pub fn digest(transactions: &[Bytes]) -> B256 {
+    let mut buf = Vec::new();
+    for tx in transactions {
+        buf.extend_from_slice(&(tx.len() as u64).to_be_bytes());
+        buf.extend_from_slice(tx);
+    }
+    keccak256(buf)
+}
+
+/// Derive `block` and write the result to `out`
+pub async fn record_derivation<D: BlockDeriver>(
+    deriver: &D,
+    block: u64,
+    out: &Path,
+) -> Result<DerivationRecord> {
+    let transactions = deriver.derive(block).await?;
+    let record = DerivationRecord {
+        block,
+        digest: digest(&transactions),
+        transactions,
+        recorded_at: chrono::Utc::now(),
+    };
+    fs::write(out, serde_json::to_string_pretty(&record)?)
+        .map_err(|e| eyre::eyre!("Failed to write {}: {}", out.display(), e))?;
+    Ok(record)
+}
+
+/// Re-derive the block recorded in `file` and compare against the recorded transactions
+pub async fn replay_derivation<D: BlockDeriver>(deriver: &D, file: &Path) -> Result<ReplayReport> {
+    let content = fs::read_to_string(file)
+        .map_err(|e| eyre::eyre!("Failed to read {}: {}", file.display(), e))?;
+    let record: DerivationRecord = serde_json::from_str(&content)?;
+    if digest(&record.transactions) != record.digest {
+        return Err(eyre::eyre!("Derivation record {} is corrupt: digest mismatch", file.display()));
+    }
+
+    let replayed = deriver.derive(record.block).await?;
+    let first_mismatch = record
+        .transactions
+        .iter()
+        .zip(replayed.iter())
+        .position(|(a, b)| a != b)
+        .or_else(|| {
+            (record.transactions.len() != replayed.len())
+                .then(|| record.transactions.len().min(replayed.len()))
+        });
+
+    Ok(ReplayReport {
+        block: record.block,
+        recorded: record.transactions.len(),
+        replayed: replayed.len(),
+        first_mismatch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Stub deriver whose output depends only on the block number
+    struct FixedDeriver;
+
+    impl BlockDeriver for FixedDeriver {
+        async fn derive(&self, block: u64) -> Result<Vec<Bytes>> {
+            Ok(vec![
+                Bytes::from(block.to_be_bytes().to_vec()),
+                Bytes::from_static(&[0x7e, 0x01, 0x02]),
+            ])
+        }
+    }
+
+    /// Stub deriver that leaks per-process state into its output
+    struct DriftingDeriver {
+        calls: AtomicUsize,
+    }
+
+    impl BlockDeriver for DriftingDeriver {
+        async fn derive(&self, _block: u64) -> Result<Vec<Bytes>> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) as u8;
+            Ok(vec![Bytes::from_static(&[0x7e]), Bytes::from(vec![0x46, call])])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_matches_deterministic_deriver() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("record.json");
+
+        let record = record_derivation(&FixedDeriver, 42, &path).await.unwrap();
+        assert_eq!(record.transactions.len(), 2);
+
+        let report = replay_derivation(&FixedDeriver, &path).await.unwrap();
+        assert!(report.is_deterministic());
+        assert_eq!((report.block, report.recorded, report.replayed), (42, 2, 2));
+    }
+
+    #[tokio::test]
+    async fn test_replay_detects_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("record.json");
+        let deriver = DriftingDeriver { calls: AtomicUsize::new(0) };
+
+        record_derivation(&deriver, 7, &path).await.unwrap();
+        let report = replay_derivation(&deriver, &path).await.unwrap();
+        assert_eq!(report.first_mismatch, Some(1));
+    }
+
+    #[test]
+    fn test_digest_is_length_prefixed() {
+        let split = [Bytes::from_static(&[1, 2]), Bytes::from_static(&[3])];
+        let joined = [Bytes::from_static(&[1]), Bytes::from_static(&[2, 3])];
+        assert_ne!(digest(&split), digest(&joined));
+    }
+}
@@ -0,0 +1,200 @@
+use crate::derivation::derive_block;
+use crate::execution::execute_block;
+use crate::providers::Providers;
+use crate::types::ValidationError;
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Bytes, B256};
+use alloy_provider::{Provider, RootProvider};
+use eyre::Result;
+use kona_derive::{compare_transactions, DerivationComparison, TxMatchStatus};
+use op_alloy_network::Optimism;
+use serde::{Deserialize, Serialize};
+
+/// First derived transaction that differs from the node's
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxDiff {
+    pub index: usize,
+    /// Offset of the first differing byte, or the shorter length if one encoding is a prefix of
+    /// the other. `None` if the transaction is missing on one side.
+    pub byte_offset: Option<usize>,
+    pub geth_len: Option<usize>,
+    pub kona_len: Option<usize>,
+}
+
+/// Derivation and execution differences of a single block
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockDiff {
+    pub block: u64,
+    pub geth_tx_count: Option<usize>,
+    pub kona_tx_count: Option<usize>,
+    pub first_difference: Option<TxDiff>,
+    pub derivation_error: Option<String>,
+    pub geth_state_root: Option<B256>,
+    pub kona_state_root: Option<B256>,
+    pub execution_error: Option<String>,
+}
+
+impl BlockDiff {
+    /// Whether derivation and execution both ran and agree with the node
+    pub fn is_clean(&self) -> bool {
+        self.derivation_error.is_none() &&
+            self.execution_error.is_none() &&
+            self.first_difference.is_none() &&
+            self.geth_tx_count == self.kona_tx_count &&
+            self.kona_state_root.is_some() &&
+            self.geth_state_root == self.kona_state_root
+    }
+
+    pub fn print(&self) {
+        let opt = |v: Option<usize>| v.map_or_else(|| "-".to_string(), |v| v.to_string());
+
+        println!("🔍 Block {}", self.block);
+        println!("  Derivation:");
+        if let Some(error) = &self.derivation_error {
+            println!("    ⚠️  {}", error);
+        }
+        println!("    Transactions: Geth {} vs Kona {}", opt(self.geth_tx_count), opt(self.kona_tx_count));
+        match &self.first_difference {
+            Some(diff) => println!(
+                "    ❌ Transaction {} differs at byte {} (Geth {} bytes vs Kona {} bytes)",
+                diff.index,
+                opt(diff.byte_offset),
+                opt(diff.geth_len),
+                opt(diff.kona_len)
+            ),
+            None if self.derivation_error.is_none() => println!("    ✅ Transactions match"),
+            None => {}
+        }
+
+        println!("  Execution:");
+        if let Some(error) = &self.execution_error {
+            println!("    ⚠️  {}", error);
+        }
+        let root = |r: Option<B256>| r.map_or_else(|| "-".to_string(), |r| r.to_string());
+        println!("    Geth state root: {}", root(self.geth_state_root));
+        println!("    Kona state root: {}", root(self.kona_state_root));
+        if self.kona_state_root.is_some() {
+            if self.geth_state_root == self.kona_state_root {
+                println!("    ✅ State roots match");
+            } else {
+                println!("    ❌ State roots differ");
+            }
+        }
+    }
+}
+
+impl TxDiff {
+    /// First transaction of the comparison that does not match, with the node's block as Geth
+    pub fn first_of(comparison: &DerivationComparison) -> Option<Self> {
+        let tx = comparison.first_mismatch()?;
+        let byte_offset = match tx.status {
+            TxMatchStatus::Mismatch { first_byte_offset } => first_byte_offset,
+            _ => None,
+        };
+        Some(Self { index: tx.index, byte_offset, geth_len: tx.actual_len, kona_len: tx.derived_len })
+    }
+}
+
+/// Derive and execute `block`, collecting where Kona diverges from the node.
+///
+/// Failures of either stage are recorded in the diff rather than returned, so one stage failing
+/// still reports the other.
+pub async fn diff_block(block: u64, providers: &Providers) -> Result<BlockDiff> {
+    let mut diff = BlockDiff { block, ..Default::default() };
+
+    match fetch_block_transactions(block, &providers.l2).await {
+        Ok(geth_txs) => {
+            diff.geth_tx_count = Some(geth_txs.len());
+            match derive_block(block, providers).await {
+                Ok(kona_txs) => {
+                    diff.kona_tx_count = Some(kona_txs.len());
+                    let comparison = compare_transactions(&kona_txs, &geth_txs);
+                    diff.first_difference = TxDiff::first_of(&comparison);
+                }
+                Err(e) => diff.derivation_error = Some(e.to_string()),
+            }
+        }
+        Err(e) => diff.derivation_error = Some(e.to_string()),
+    }
+
+    match execute_block(block, providers).await {
+        Ok((geth, kona)) => {
+            diff.geth_state_root = Some(geth.state_root);
+            diff.kona_state_root = Some(kona.state_root);
+        }
+        Err(e) => diff.execution_error = Some(e.to_string()),
+    }
+
+    Ok(diff)
+}
+
+/// EIP-2718 encoded transactions of the node's block
+async fn fetch_block_transactions(
+    block: u64,
+    l2_provider: &RootProvider<Optimism>,
+) -> Result<Vec<Bytes>> {
+    let actual_block = l2_provider
+        .get_block_by_number(alloy_rpc_types_eth::BlockNumberOrTag::Number(block))
+        .full()
+        .await
+        .map_err(ValidationError::rpc)?
+        .ok_or_else(|| ValidationError::NotFound(format!("Block {} not found", block)))?;
+
+    match &actual_block.transactions {
+        alloy_rpc_types_eth::BlockTransactions::Full(txs) => {
+            Ok(txs.iter().map(|tx| Bytes::from(tx.inner.inner.encoded_2718())).collect())
+        }
+        _ => Err(eyre::eyre!("Expected full transactions in block")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_tx_difference() {
+        let tx = |bytes: &'static [u8]| Bytes::from_static(bytes);
+        let geth = [tx(&[0x7e, 1, 2]), tx(&[0x7e, 3, 4, 5]), tx(&[0x02, 6])];
+
+        assert_eq!(TxDiff::first_of(&compare_transactions(&geth, &geth)), None);
+
+        let kona = [tx(&[0x7e, 1, 2]), tx(&[0x7e, 3, 9, 5]), tx(&[0x02, 7])];
+        assert_eq!(
+            TxDiff::first_of(&compare_transactions(&kona, &geth)),
+            Some(TxDiff { index: 1, byte_offset: Some(2), geth_len: Some(4), kona_len: Some(4) })
+        );
+
+        // Kona derived one transaction fewer
+        assert_eq!(
+            TxDiff::first_of(&compare_transactions(&geth[..2], &geth)),
+            Some(TxDiff { index: 2, byte_offset: None, geth_len: Some(2), kona_len: None })
+        );
+    }
+
+    #[test]
+    fn test_diff_json() {
+        let diff = BlockDiff {
+            block: 7,
+            geth_tx_count: Some(3),
+            kona_tx_count: Some(3),
+            first_difference: Some(TxDiff {
+                index: 0,
+                byte_offset: Some(40),
+                geth_len: Some(260),
+                kona_len: Some(260),
+            }),
+            geth_state_root: Some(B256::repeat_byte(0x22)),
+            kona_state_root: Some(B256::repeat_byte(0x22)),
+            ..Default::default()
+        };
+        assert!(!diff.is_clean());
+
+        let json: serde_json::Value = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["first_difference"]["byte_offset"], 40);
+        assert_eq!(serde_json::from_value::<BlockDiff>(json).unwrap(), diff);
+
+        let clean = BlockDiff { first_difference: None, ..diff };
+        assert!(clean.is_clean());
+    }
+}
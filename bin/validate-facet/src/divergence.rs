@@ -0,0 +1,66 @@
+use eyre::Result;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Blocks with an expected, documented divergence, e.g. during a migration, whose failures are
+/// reported separately instead of counting towards the failure rate
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownDivergences {
+    reasons: BTreeMap<u64, String>,
+}
+
+/// One entry of a `--known-divergences` file
+#[derive(Debug, Deserialize)]
+struct Entry {
+    block: u64,
+    reason: String,
+}
+
+impl KnownDivergences {
+    /// Load the JSON list of `{"block", "reason"}` entries at `path`
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&content)
+            .map_err(|e| eyre::eyre!("Invalid known divergences {}: {}", path.display(), e))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let entries: Vec<Entry> = serde_json::from_str(content)?;
+        let reasons = entries.into_iter().map(|entry| (entry.block, entry.reason)).collect();
+        Ok(Self { reasons })
+    }
+
+    /// Documented reason of the divergence of `block`, if it is a known divergence
+    pub fn reason(&self, block: u64) -> Option<&str> {
+        self.reasons.get(&block).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.reasons.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_known_divergences() {
+        let known = KnownDivergences::parse(
+            r#"[
+                {"block": 100, "reason": "gas limit migration"},
+                {"block": 205, "reason": "mint rate rounding"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(known.len(), 2);
+        assert_eq!(known.reason(205), Some("mint rate rounding"));
+        assert_eq!(known.reason(101), None);
+
+        assert!(KnownDivergences::parse(r#"[{"block": 100}]"#).is_err());
+        assert!(KnownDivergences::parse(r#"{"100": "reason"}"#).is_err());
+    }
+}
@@ -1,160 +1,404 @@
-use crate::retry::{calculate_backoff, classify_error, CircuitBreaker};
-use crate::types::{ErrorType, TestResult};
+use crate::providers::Providers;
+use crate::retry::{
+    classify_error, BackoffPolicy, BackoffStrategy, CircuitBreaker, RetryBudget, RetryStop,
+    StageRetries,
+};
+use crate::types::{BlockRefs, ErrorType, TestResult, ValidationError};
+use alloy_consensus::Header;
+use alloy_eips::eip2718::Encodable2718;
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_op_evm::OpEvmFactory;
+use alloy_primitives::{Address, Bytes, Sealable};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types_engine::PayloadAttributes;
+use alloy_rpc_types_eth::BlockTransactions;
 use eyre::Result;
+use kona_executor::{ExecutorError, RpcTrieDBProvider, RpcTrieDBProviderError, StatelessL2Builder};
+use kona_genesis::RollupConfig;
+use kona_mpt::NoopTrieHinter;
+use op_alloy_network::Optimism;
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+use std::future::Future;
 use std::path::Path;
-use std::process::Command;
 use std::time::Duration;
-use tempfile::TempDir;
 use tracing::{debug, warn};
 
+/// Reports whether the L2 node still holds the state trie for a given block.
+///
+/// Executing block N reads the post-state of block N-1 through `debug_dbGet`, which only archive
+/// nodes can serve for old blocks.
+pub trait StateProbe {
+    fn has_state_at(&self, block: u64) -> impl Future<Output = Result<bool>> + Send;
+}
+
+/// [`StateProbe`] backed by `eth_getProof`, which fails on pruned nodes for historical blocks
+pub struct RpcStateProbe {
+    provider: RootProvider<Optimism>,
+}
+
+impl RpcStateProbe {
+    pub fn new(provider: RootProvider<Optimism>) -> Self {
+        Self { provider }
+    }
+}
+
+impl StateProbe for RpcStateProbe {
+    async fn has_state_at(&self, block: u64) -> Result<bool> {
+        match self.provider.get_proof(Address::ZERO, vec![]).block_id(BlockId::number(block)).await {
+            Ok(_) => Ok(true),
+            Err(e) if is_missing_state_error(&e.to_string()) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Whether an RPC error message indicates the requested historical state has been pruned
+fn is_missing_state_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("missing trie node") ||
+        message.contains("historical state") ||
+        message.contains("pruned") ||
+        message.contains("state not available") ||
+        message.contains("state is not available")
+}
+
+/// Check up front whether the parent state of `block` is available.
+///
+/// Returns a failed [`TestResult`] classified as [`ErrorType::NotFound`] when the node has pruned
+/// the state, so the block isn't misreported after a failed fixture build.
+pub async fn preflight_state<P: StateProbe>(probe: &P, block: u64) -> Result<Option<TestResult>> {
+    let parent = block.saturating_sub(1);
+    if probe.has_state_at(parent).await? {
+        return Ok(None);
+    }
+
+    debug!("Block {} parent state not available on L2 node (pruned)", block);
+    Ok(Some(TestResult {
+        success: false,
+        error: Some(format!(
+            "Historical state not found for block {} (parent {}): L2 node is pruned, an archive node is required",
+            block, parent
+        )),
+        error_type: Some(ErrorType::NotFound),
+        retries: 0,
+        duration_ms: None,
+    }))
+}
+
 pub async fn validate_execution(
     block: u64,
-    l2_rpc: &str,
-    max_retries: u32,
+    providers: &Providers,
+    backoff_strategy: BackoffStrategy,
+    backoff_policy: &BackoffPolicy,
+    retry_budget: &RetryBudget,
     results_dir: &Path,
-) -> Result<TestResult> {
-    let mut retries = 0;
+) -> Result<(TestResult, BlockRefs)> {
+    // A pruned parent state will never succeed, so don't execute or retry
+    match preflight_state(&RpcStateProbe::new(providers.l2.clone()), block).await {
+        Ok(Some(pruned)) => return Ok((pruned, BlockRefs::default())),
+        Ok(None) => {}
+        Err(e) => debug!("Block {} state preflight inconclusive: {}", block, e),
+    }
+
+    let mut retries = StageRetries::new(backoff_strategy, backoff_policy, retry_budget);
     let mut last_error = None;
     let mut last_error_type = None;
     let mut circuit_breaker = CircuitBreaker::new(5, Duration::from_secs(60));
-    let mut effective_max_retries = max_retries;
     
     loop {
         // Check circuit breaker
         if circuit_breaker.is_open() {
-            warn!("Circuit breaker open for block {} execution, skipping", block);
-            return Ok(TestResult {
+            warn!(block, stage = "execution", "Circuit breaker open, skipping");
+            return Ok((TestResult {
                 success: false,
                 error: Some("Circuit breaker open - too many consecutive network failures".to_string()),
                 error_type: Some(ErrorType::Network),
-                retries,
-            });
+                retries: retries.retries(),
+                duration_ms: None,
+            }, BlockRefs::default()));
         }
         
-        match run_execution_test(block, l2_rpc, results_dir).await {
-            Ok(_) => {
+        match run_execution_test(block, providers, results_dir).await {
+            Ok(refs) => {
                 circuit_breaker.record_success();
-                return Ok(TestResult {
+                return Ok((TestResult {
                     success: true,
                     error: None,
                     error_type: None,
-                    retries,
-                });
+                    retries: retries.retries(),
+                    duration_ms: None,
+                }, refs));
             }
             Err(e) => {
                 let error_type = classify_error(&e);
                 last_error = Some(e.to_string());
                 last_error_type = Some(error_type);
                 
-                // Update effective max retries based on error type
-                effective_max_retries = effective_max_retries.min(error_type.max_retries());
-                
                 // Record failure in circuit breaker for network errors
                 if error_type == ErrorType::Network || error_type == ErrorType::RateLimit {
                     circuit_breaker.record_failure();
                 }
                 
-                // Don't retry if it's a validation error
-                if !error_type.should_retry() {
-                    debug!("Block {} execution failed with non-retryable error: {:?}", block, error_type);
-                    break;
-                }
-                
-                // Check if we've exceeded retries for this error type
-                if retries >= effective_max_retries {
-                    debug!("Block {} execution exceeded max retries ({}) for error type {:?}", 
-                        block, effective_max_retries, error_type);
-                    break;
+                match retries.next_backoff(error_type) {
+                    Ok(backoff) => {
+                        debug!(
+                            "Block {} execution retry {} after {:?} (error type: {:?})",
+                            block, retries.retries(), backoff, error_type
+                        );
+                        tokio::time::sleep(backoff).await;
+                    }
+                    Err(RetryStop::BudgetExhausted) => {
+                        // Fail fast once the run has used up its retries
+                        debug!("Block {} execution retry budget exhausted", block);
+                        last_error = Some(format!(
+                            "Retry budget exhausted (--max-total-retries): {}",
+                            last_error.unwrap_or_default()
+                        ));
+                        last_error_type = Some(ErrorType::System);
+                        break;
+                    }
+                    Err(stop) => {
+                        debug!(
+                            "Block {} execution not retried: {:?} for error type {:?}",
+                            block, stop, error_type
+                        );
+                        break;
+                    }
                 }
-                
-                retries += 1;
-                
-                let backoff = calculate_backoff(retries - 1, error_type);
-                debug!(
-                    "Block {} execution retry {}/{} after {:?} (error type: {:?})",
-                    block, retries, effective_max_retries, backoff, error_type
-                );
-                tokio::time::sleep(backoff).await;
             }
         }
     }
     
-    Ok(TestResult {
+    Ok((TestResult {
         success: false,
         error: last_error,
         error_type: last_error_type,
-        retries,
-    })
+        retries: retries.retries(),
+        duration_ms: None,
+    }, BlockRefs::default()))
 }
 
-async fn run_execution_test(block: u64, l2_rpc: &str, results_dir: &Path) -> Result<()> {
-    let temp_dir = TempDir::new()?;
+async fn run_execution_test(
+    block: u64,
+    providers: &Providers,
+    results_dir: &Path,
+) -> Result<BlockRefs> {
     let log_file = results_dir.join("logs").join(format!("exec_{}.log", block));
-    
-    // Run execution-fixture
-    let mut cmd = Command::new("./target/release/execution-fixture");
-    cmd.args(&[
-        "--l2-rpc", l2_rpc,
-        "--block-number", &block.to_string(),
-        "--output-dir", temp_dir.path().to_str().unwrap(),
-    ]);
-    
-    let output = cmd.output()?;
-    
+
+    let (expected, built) = execute_block(block, providers).await?;
+    let mismatch = header_mismatch(&expected, &built);
+
     // Save logs
-    std::fs::write(&log_file, &output.stdout)?;
-    if !output.stderr.is_empty() {
-        std::fs::write(log_file.with_extension("err"), &output.stderr)?;
+    let log_content = format!(
+        "=== Execution Test ===\nExpected: {}\nBuilt: {}\n{}\n",
+        expected.hash_slow(),
+        built.hash_slow(),
+        mismatch.as_ref().map_or_else(|| "passed".to_string(), ToString::to_string)
+    );
+    std::fs::write(&log_file, log_content)?;
+
+    if let Some(mismatch) = mismatch {
+        return Err(mismatch.into());
     }
-    
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        
-        // Check for common error patterns
-        if stderr.contains("error sending request") || 
-           stderr.contains("transport error") ||
-           stdout.contains("error sending request") ||
-           stdout.contains("transport error") ||
-           stderr.contains("500 Internal Server Error") ||
-           stderr.contains("HttpError") {
-            return Err(eyre::eyre!("execution-fixture failed: network error - {}", stderr));
+    Ok(BlockRefs { l2_block_hash: Some(expected.hash_slow()), ..Default::default() })
+}
+
+/// Execute `block` with Kona on top of the node's parent state, returning the node's header and
+/// the header Kona built.
+pub async fn execute_block(
+    block: u64,
+    providers: &Providers,
+) -> Result<(Header, Header), ValidationError> {
+    let parent = block.checked_sub(1).ok_or_else(|| {
+        ValidationError::Invalid("The genesis block has no parent to execute on".to_string())
+    })?;
+    let executing = providers
+        .l2
+        .get_block_by_number(BlockNumberOrTag::Number(block))
+        .full()
+        .await
+        .map_err(ValidationError::rpc)?
+        .ok_or_else(|| ValidationError::NotFound(format!("Block {} not found", block)))?;
+    let parent_header = fetch_header(&providers.l2, parent).await?;
+
+    let BlockTransactions::Full(transactions) = &executing.transactions else {
+        return Err(ValidationError::Invalid(format!(
+            "Block {} was returned without full transactions",
+            block
+        )));
+    };
+    let header = executing.header.inner;
+    let rollup_config = RollupConfig::facet();
+    let eip_1559_params = if rollup_config.is_holocene_active(header.timestamp) {
+        let params = header.extra_data.get(1..).and_then(|params| params.try_into().ok());
+        Some(params.ok_or_else(|| {
+            ValidationError::Invalid(format!("Block {} has invalid Holocene extra data", block))
+        })?)
+    } else {
+        None
+    };
+    let attributes = OpPayloadAttributes {
+        payload_attributes: PayloadAttributes {
+            timestamp: header.timestamp,
+            parent_beacon_block_root: header.parent_beacon_block_root,
+            prev_randao: header.mix_hash,
+            withdrawals: Default::default(),
+            suggested_fee_recipient: header.beneficiary,
+        },
+        gas_limit: Some(header.gas_limit),
+        transactions: Some(
+            transactions.iter().map(|tx| Bytes::from(tx.inner.inner.encoded_2718())).collect(),
+        ),
+        no_tx_pool: None,
+        eip_1559_params,
+    };
+
+    let built = build_block(block, parent_header, attributes, providers)?;
+    Ok((header, built))
+}
+
+/// Build `block` from `attributes` with Kona on top of `parent`, serving the parent state from the
+/// L2 node.
+///
+/// Must be called on a multi-threaded runtime, as the state is fetched by blocking on the node.
+pub fn build_block(
+    block: u64,
+    parent: Header,
+    attributes: OpPayloadAttributes,
+    providers: &Providers,
+) -> Result<Header, ValidationError> {
+    let rollup_config = RollupConfig::facet();
+    let trie_provider = RpcTrieDBProvider::new(providers.l2.clone());
+    let mut executor = StatelessL2Builder::new(
+        &rollup_config,
+        OpEvmFactory::default(),
+        trie_provider.clone(),
+        NoopTrieHinter,
+        parent.seal_slow(),
+    );
+    let outcome = tokio::task::block_in_place(|| executor.build_block(attributes))
+        .map_err(|e| build_error(block, e, trie_provider.take_error()))?;
+    Ok(outcome.header.into_inner())
+}
+
+/// Classify a failed block build by the first failure of its trie provider, if any.
+///
+/// The builder only reports provider failures as strings, so an RPC failure has to be taken from
+/// the provider to tell missing state apart from an invalid block.
+fn build_error(
+    block: u64,
+    error: ExecutorError,
+    provider_error: Option<RpcTrieDBProviderError>,
+) -> ValidationError {
+    match provider_error {
+        Some(RpcTrieDBProviderError::Rpc(_, e)) => ValidationError::rpc(e.as_ref()),
+        Some(RpcTrieDBProviderError::Rlp(_)) | None => {
+            ValidationError::Invalid(format!("Failed to execute block {}: {}", block, error))
         }
-        
-        // Preimage not found errors are infrastructure issues, not validation failures
-        if stderr.contains("Preimage not found") {
-            return Err(eyre::eyre!("execution-fixture failed: missing preimage data - {}", stderr));
+    }
+}
+
+/// Compare the header Kona built against the node's, reporting the fields that commit to the
+/// execution before the hash
+fn header_mismatch(expected: &Header, built: &Header) -> Option<ValidationError> {
+    if expected == built {
+        return None;
+    }
+    let field = [
+        ("state root", expected.state_root != built.state_root),
+        ("receipts root", expected.receipts_root != built.receipts_root),
+        ("gas used", expected.gas_used != built.gas_used),
+        ("transactions root", expected.transactions_root != built.transactions_root),
+    ]
+    .into_iter()
+    .find_map(|(field, differs)| differs.then_some(field))
+    .unwrap_or("header");
+    Some(ValidationError::Invalid(format!(
+        "Produced {} of block {} does not match the node's: expected {} vs built {}",
+        field,
+        expected.number,
+        expected.hash_slow(),
+        built.hash_slow()
+    )))
+}
+
+/// Header of an L2 block of the node
+pub async fn fetch_header(
+    provider: &RootProvider<Optimism>,
+    block: u64,
+) -> Result<Header, ValidationError> {
+    Ok(provider
+        .get_block_by_number(BlockNumberOrTag::Number(block))
+        .await
+        .map_err(ValidationError::rpc)?
+        .ok_or_else(|| ValidationError::NotFound(format!("Block {} not found", block)))?
+        .header
+        .inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stub node that only holds state from `oldest_state` onwards
+    struct PrunedNode {
+        oldest_state: u64,
+    }
+
+    impl StateProbe for PrunedNode {
+        async fn has_state_at(&self, block: u64) -> Result<bool> {
+            Ok(block >= self.oldest_state)
         }
-        
-        return Err(eyre::eyre!("execution-fixture failed: {}", stderr));
     }
-    
-    // Check if fixture was created
-    let fixture_path = temp_dir.path().join(format!("block-{}.tar.gz", block));
-    if !fixture_path.exists() {
-        return Err(eyre::eyre!("Fixture not created"));
+
+    #[tokio::test]
+    async fn test_preflight_classifies_pruned_state() {
+        let node = PrunedNode { oldest_state: 1_000 };
+
+        let result = preflight_state(&node, 500).await.unwrap().expect("state should be pruned");
+        assert!(!result.success);
+        assert_eq!(result.error_type, Some(ErrorType::NotFound));
+        assert_eq!(result.retries, 0);
+
+        // The parent of block 1_001 is the oldest retained state
+        assert!(preflight_state(&node, 1_001).await.unwrap().is_none());
+        assert!(preflight_state(&node, 1_000).await.unwrap().is_some());
     }
-    
-    // Run validation test
-    let mut cmd = Command::new("cargo");
-    cmd.args(&[
-        "test", "-p", "kona-executor",
-        "test_validate_single_fixture",
-        "--release", "--", "--nocapture"
-    ]);
-    cmd.env("FIXTURE_PATH", fixture_path);
-    
-    let output = cmd.output()?;
-    
-    // Append test logs
-    let mut log_content = std::fs::read(&log_file)?;
-    log_content.extend_from_slice(b"\n=== Validation Test ===\n");
-    log_content.extend_from_slice(&output.stdout);
-    std::fs::write(&log_file, log_content)?;
-    
-    if !output.status.success() {
-        return Err(eyre::eyre!("Validation test failed"));
+
+    #[test]
+    fn test_build_errors_are_classified() {
+        let error = || ExecutorError::MissingGasLimit;
+        assert_eq!(build_error(7, error(), None).error_type(), ErrorType::Validation);
+
+        let missing = alloy_json_rpc::RpcError::ErrorResp(alloy_json_rpc::ErrorPayload {
+            code: -32000,
+            message: "missing trie node 0xab".into(),
+            data: None,
+        });
+        let provider_error = RpcTrieDBProviderError::Rpc(Default::default(), missing.into());
+        assert_eq!(build_error(7, error(), Some(provider_error)).error_type(), ErrorType::NotFound);
     }
-    
-    Ok(())
-}
\ No newline at end of file
+
+    #[test]
+    fn test_header_mismatch_names_the_first_differing_field() {
+        let expected = Header { number: 7, gas_used: 21_000, ..Default::default() };
+        assert!(header_mismatch(&expected, &expected.clone()).is_none());
+
+        let built = Header { gas_used: 42_000, ..expected.clone() };
+        let mismatch = header_mismatch(&expected, &built).unwrap();
+        assert_eq!(mismatch.error_type(), ErrorType::Validation);
+        assert!(mismatch.to_string().starts_with("Produced gas used of block 7"), "{mismatch}");
+
+        let built = Header { extra_data: Bytes::from_static(&[1]), ..expected.clone() };
+        assert!(
+            header_mismatch(&expected, &built).unwrap().to_string().starts_with("Produced header")
+        );
+    }
+
+    #[test]
+    fn test_missing_state_error_messages() {
+        assert!(is_missing_state_error("missing trie node 0xabc (path )"));
+        assert!(is_missing_state_error("historical state not available in path scheme yet"));
+        assert!(!is_missing_state_error("error sending request for url"));
+    }
+}
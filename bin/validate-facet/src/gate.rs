@@ -0,0 +1,175 @@
+use crate::types::ValidationResult;
+
+/// Exit code of a run whose success rate falls below `--min-success-rate`
+pub const GATE_FAILED_EXIT_CODE: i32 = 1;
+
+/// End-of-run check of the final success rate against a CI threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuccessGate {
+    /// Minimum success rate, in percent, for the run to pass
+    pub min_success_rate: f64,
+    /// Ignore blocks that only failed on infrastructure errors, by
+    /// [`ValidationResult::is_infrastructure_failure`] like `--failure-threshold`
+    pub validation_only: bool,
+}
+
+/// Result of evaluating a [`SuccessGate`] over a run's results
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateOutcome {
+    pub gate: SuccessGate,
+    /// Blocks counted towards the success rate
+    pub counted: usize,
+    pub failed: usize,
+    pub success_rate: f64,
+}
+
+impl SuccessGate {
+    /// Evaluate the gate over the contents of a `results.jsonl` file
+    pub fn evaluate(&self, results_jsonl: &str) -> GateOutcome {
+        let mut counted = 0;
+        let mut failed = 0;
+
+        for line in results_jsonl.lines() {
+            let Ok(result) = serde_json::from_str::<ValidationResult>(line) else {
+                continue;
+            };
+            // A failing known divergence is counted as passed
+            let any_failed = result.counts_as_failure();
+            if self.validation_only && any_failed && result.is_infrastructure_failure() {
+                continue;
+            }
+            counted += 1;
+            if any_failed {
+                failed += 1;
+            }
+        }
+
+        let success_rate = if counted > 0 {
+            ((counted - failed) as f64 / counted as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        GateOutcome { gate: *self, counted, failed, success_rate }
+    }
+}
+
+impl GateOutcome {
+    pub fn passed(&self) -> bool {
+        self.counted > 0 && self.success_rate >= self.gate.min_success_rate
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        if self.passed() {
+            0
+        } else {
+            GATE_FAILED_EXIT_CODE
+        }
+    }
+
+    /// One-line `PASS`/`FAIL` summary for CI logs
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: success rate {:.2}% ({}/{} blocks{}) {} minimum {:.2}%",
+            if self.passed() { "PASS" } else { "FAIL" },
+            self.success_rate,
+            self.counted - self.failed,
+            self.counted,
+            if self.gate.validation_only { ", validation failures only" } else { "" },
+            if self.passed() { ">=" } else { "<" },
+            self.gate.min_success_rate,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ErrorType, TestResult};
+
+    fn stage(error_type: Option<ErrorType>) -> TestResult {
+        TestResult {
+            success: error_type.is_none(),
+            error: error_type.map(|t| format!("{:?} error", t)),
+            error_type,
+            retries: 0,
+            duration_ms: None,
+        }
+    }
+
+    /// 20 blocks: 17 pass, 1 fails validation, 2 fail on the network
+    fn results() -> String {
+        (0..20u64)
+            .map(|block| {
+                let error_type = match block {
+                    3 => Some(ErrorType::Validation),
+                    7 | 11 => Some(ErrorType::Network),
+                    _ => None,
+                };
+                let result = ValidationResult {
+                    block,
+                    execution: Some(stage(None)),
+                    derivation: Some(stage(error_type)),
+                    refs: Default::default(),
+                    duration_ms: 10,
+                    timestamp: chrono::Utc::now(),
+                    known_divergence: None,
+                };
+                serde_json::to_string(&result).unwrap()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_gate_below_threshold_fails() {
+        let gate = SuccessGate { min_success_rate: 90.0, validation_only: false };
+        let outcome = gate.evaluate(&results());
+
+        assert_eq!((outcome.counted, outcome.failed), (20, 3));
+        assert!(!outcome.passed());
+        assert_eq!(outcome.exit_code(), GATE_FAILED_EXIT_CODE);
+        assert_eq!(outcome.summary(), "FAIL: success rate 85.00% (17/20 blocks) < minimum 90.00%");
+    }
+
+    #[test]
+    fn test_gate_ignoring_infrastructure_passes() {
+        let gate = SuccessGate { min_success_rate: 90.0, validation_only: true };
+        let outcome = gate.evaluate(&results());
+
+        assert_eq!((outcome.counted, outcome.failed), (18, 1));
+        assert!(outcome.passed());
+        assert_eq!(outcome.exit_code(), 0);
+        assert_eq!(
+            outcome.summary(),
+            "PASS: success rate 94.44% (17/18 blocks, validation failures only) >= minimum 90.00%"
+        );
+    }
+
+    #[test]
+    fn test_gate_counts_system_failures_like_the_threshold() {
+        let result = |error_type| ValidationResult {
+            block: 1,
+            execution: Some(stage(Some(error_type))),
+            derivation: None,
+            refs: Default::default(),
+            duration_ms: 10,
+            timestamp: chrono::Utc::now(),
+            known_divergence: None,
+        };
+        let gate = SuccessGate { min_success_rate: 90.0, validation_only: true };
+        let cases = [(ErrorType::System, 1), (ErrorType::Unknown, 1), (ErrorType::RateLimit, 0)];
+        for (error_type, counted) in cases {
+            let result = result(error_type);
+            let outcome = gate.evaluate(&serde_json::to_string(&result).unwrap());
+            assert_eq!(outcome.counted, counted, "{error_type:?}");
+            assert_eq!(result.is_infrastructure_failure(), counted == 0);
+        }
+    }
+
+    #[test]
+    fn test_gate_fails_without_results() {
+        let gate = SuccessGate { min_success_rate: 0.0, validation_only: false };
+        assert_eq!(gate.evaluate("").exit_code(), GATE_FAILED_EXIT_CODE);
+    }
+}
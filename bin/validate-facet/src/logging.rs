@@ -0,0 +1,39 @@
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Format of the log lines written to stderr
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per line, with the event fields such as `block` at the top level
+    Json,
+}
+
+/// Install the global subscriber, logging at `debug` level if `verbose`.
+///
+/// Logs go to stderr so that stdout only carries the output of the run, such as the gate summary
+/// or a `diff --json`, and JSON log lines are not interleaved with it.
+pub fn init(format: LogFormat, verbose: bool) {
+    let filter = if verbose { "debug" } else { "info" };
+    match format {
+        LogFormat::Text => {
+            tracing_subscriber::fmt().with_env_filter(filter).with_writer(std::io::stderr).init()
+        }
+        LogFormat::Json => json_subscriber(filter, std::io::stderr).init(),
+    }
+}
+
+/// Subscriber of `--log-format json`, writing to `writer`
+pub fn json_subscriber<W>(filter: &str, writer: W) -> impl Subscriber + Send + Sync + 'static
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .json()
+        .flatten_event(true)
+        .with_writer(writer)
+        .finish()
+}
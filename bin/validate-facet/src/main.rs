@@ -1,45 +1,73 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use eyre::Result;
+use futures::StreamExt;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use std::process::Command;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Semaphore;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
+mod batch;
+mod cache;
+mod checkpoint;
 mod derivation;
+mod determinism;
+mod diff;
+mod divergence;
 mod execution;
+mod gate;
+mod logging;
+mod output;
+mod pipeline;
+mod providers;
+mod ratelimit;
+mod report;
 mod retry;
+mod snapshot;
 mod types;
 
+use cache::{BlockCache, CacheStats};
+use checkpoint::Checkpoint;
+use divergence::KnownDivergences;
+use logging::LogFormat;
+use output::{OutputFormat, PrometheusMetrics};
+use providers::Providers;
+use retry::{BackoffPolicy, BackoffStrategy, RetryBudget, RetryConfig};
+use snapshot::TrustedHashes;
 use types::{ErrorType, TestResult, ValidationResult};
 
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
+    /// Optional subcommand; without one a validation run is started
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Starting block number
-    #[arg(short = 's', long)]
-    start_block: u64,
+    #[arg(short = 's', long, required_unless_present = "rerun_failures")]
+    start_block: Option<u64>,
 
     /// Ending block number (inclusive)
-    #[arg(short = 'e', long)]
-    end_block: u64,
+    #[arg(short = 'e', long, required_unless_present = "rerun_failures")]
+    end_block: Option<u64>,
 
     /// Number of parallel workers
     #[arg(short = 'j', long, default_value = "16")]
     jobs: usize,
 
-    /// L1 RPC endpoint
-    #[arg(long, env = "L1_RPC")]
-    l1_rpc: String,
+    /// L1 RPC endpoint: an http(s):// or ws(s):// URL, or the path of an IPC socket
+    #[arg(long, env = "L1_RPC", global = true)]
+    l1_rpc: Option<String>,
 
-    /// L2 RPC endpoint
-    #[arg(long, env = "L2_RPC")]
-    l2_rpc: String,
+    /// L2 RPC endpoint: an http(s):// or ws(s):// URL, or the path of an IPC socket
+    #[arg(long, env = "L2_RPC", global = true)]
+    l2_rpc: Option<String>,
 
     /// Output directory for results
     #[arg(short = 'o', long)]
@@ -53,6 +81,17 @@ struct Args {
     #[arg(long)]
     skip_derivation: bool,
 
+    /// Send at most this many requests per second to each of the L1 and L2 endpoints, across all
+    /// workers, including the state reads of block execution. Requests over the limit wait for
+    /// their turn instead of failing.
+    #[arg(long, global = true)]
+    rpc_rps: Option<NonZeroU32>,
+
+    /// Share an LRU cache of this many L2 blocks across workers, so blocks fetched as the target of
+    /// one block and the parent of the next are fetched once
+    #[arg(long)]
+    cache_size: Option<NonZeroUsize>,
+
     /// Sample rate for derivation (e.g., 10 means test every 10th block)
     #[arg(long, default_value = "1")]
     derivation_sample_rate: u64,
@@ -61,59 +100,329 @@ struct Args {
     #[arg(short = 'r', long)]
     resume: Option<PathBuf>,
 
-    /// Maximum retries per block
+    /// Maximum retries per block, for the error types whose retries are not set explicitly
     #[arg(long, default_value = "2")]
     max_retries: u32,
 
-    /// Checkpoint interval (blocks)
+    /// Maximum retries across all blocks of the run; once used up, failing blocks are not retried
+    /// and are recorded as system errors
+    #[arg(long)]
+    max_total_retries: Option<usize>,
+
+    /// How the delay between retries grows
+    #[arg(long, value_enum, default_value_t = BackoffStrategy::Exponential)]
+    backoff_strategy: BackoffStrategy,
+
+    /// Base delay between retries, in milliseconds
     #[arg(long, default_value = "1000")]
-    checkpoint_interval: u64,
+    backoff_base_ms: u64,
+
+    /// Upper bound on any single retry delay, in milliseconds
+    #[arg(long, default_value = "60000")]
+    backoff_max_ms: u64,
+
+    /// Randomize each retry delay by up to this fraction in either direction (0 to disable)
+    #[arg(long, default_value = "0.25", value_parser = parse_jitter_frac)]
+    backoff_jitter: f64,
+
+    /// Retries of a block failing with a network error, overriding --max-retries
+    /// [default: 10, capped by --max-retries]
+    #[arg(long)]
+    retries_network: Option<u32>,
+
+    /// Retries of a block failing on the provider's rate limit, overriding --max-retries
+    /// [default: 10, capped by --max-retries]
+    #[arg(long)]
+    retries_ratelimit: Option<u32>,
+
+    /// Retries of a block failing because data is not found, overriding --max-retries
+    /// [default: 5, capped by --max-retries]
+    #[arg(long)]
+    retries_not_found: Option<u32>,
+
+    /// Backoff multiplier after a network error [default: 1.0]
+    #[arg(long, value_parser = parse_backoff_multiplier)]
+    backoff_multiplier_network: Option<f64>,
+
+    /// Backoff multiplier after a rate limit error [default: 2.0]
+    #[arg(long, value_parser = parse_backoff_multiplier)]
+    backoff_multiplier_ratelimit: Option<f64>,
+
+    /// Backoff multiplier after a not-found error [default: 1.5]
+    #[arg(long, value_parser = parse_backoff_multiplier)]
+    backoff_multiplier_not_found: Option<f64>,
+
+    /// Seconds between checkpoints of the processed blocks
+    #[arg(long, alias = "checkpoint-interval", default_value = "30")]
+    checkpoint_interval_secs: u64,
 
     /// Stop if failure rate exceeds this percentage
     #[arg(long, default_value = "10.0")]
     failure_threshold: f64,
 
+    /// Exit non-zero if the final success rate is below this percentage
+    #[arg(long)]
+    min_success_rate: Option<f64>,
+
+    /// Only count validation failures against --min-success-rate, ignoring infrastructure errors
+    #[arg(long, requires = "min_success_rate")]
+    gate_validation_only: bool,
+
     /// Verbose output
     #[arg(short, long)]
     verbose: bool,
 
+    /// Log format; `json` writes one object per line, with fields such as `block` and
+    /// `error_type` structured rather than in the message
+    #[arg(long, value_enum, default_value_t = LogFormat::Text, global = true)]
+    log_format: LogFormat,
+
+    /// Process blocks in strict ascending order, recording results in block order
+    #[arg(long)]
+    ordered: bool,
+
+    /// Validate the range as one chain without workers, deriving each block on top of the state
+    /// carried over from the block before it. The parent is only fetched from the node at gaps in
+    /// the range, after a failed block, or when the node's chain reorged.
+    #[arg(long, conflicts_with = "ordered")]
+    sequential: bool,
+
+    /// Resume after the contiguous watermark of a previous run's checkpoint.
+    ///
+    /// Cheaper than --resume for huge ranges, as the processed set is not rescanned. Blocks
+    /// processed out of order above the watermark are validated again, and gaps below it are
+    /// assumed done.
+    #[arg(long, conflicts_with_all = ["resume", "random_sample"])]
+    resume_from_watermark: Option<PathBuf>,
+
+    /// Validate only the blocks that failed in the `results.jsonl` of a previous results directory.
+    ///
+    /// Results go to a fresh results directory and include the blocks that now pass, so rerunning
+    /// failures from that directory only retries the blocks that still fail.
+    #[arg(long, conflicts_with_all = ["resume", "resume_from_watermark", "random_sample"])]
+    rerun_failures: Option<PathBuf>,
+
+    /// Trusted baseline of block hashes (JSON object or `block,hash` CSV).
+    ///
+    /// Each block of the range in the baseline is derived and built, and only the hash of the
+    /// built block is compared against the trusted value. Blocks missing from the baseline are
+    /// skipped.
+    #[arg(long)]
+    expected_hashes: Option<PathBuf>,
+
+    /// JSON list of `{"block", "reason"}` entries of blocks with an expected, documented
+    /// divergence.
+    ///
+    /// A listed block that fails is recorded with its reason and reported separately, without
+    /// counting towards the failure rate. Listed blocks that pass are recorded as usual.
+    #[arg(long)]
+    known_divergences: Option<PathBuf>,
+
+    /// Additional result format, may be repeated. `results.jsonl` is always written.
+    #[arg(long = "output-format", value_enum)]
+    output_formats: Vec<OutputFormat>,
+
     /// Random sampling mode - test N random blocks from the range
     #[arg(long, conflicts_with = "resume")]
     random_sample: Option<usize>,
 
+    /// Validate only every Nth block of the requested range (positions 0, N, 2N, ... from
+    /// --start-block), for both execution and derivation.
+    ///
+    /// Unlike --derivation-sample-rate the positions do not depend on the absolute block number,
+    /// so the same stride samples the same share of any range. Resuming keeps the positions of the
+    /// original range. Mutually exclusive with --random-sample.
+    #[arg(long, conflicts_with = "random_sample")]
+    sample_stride: Option<NonZeroUsize>,
+
     /// Seed for random sampling (for reproducibility)
     #[arg(long, default_value = "42")]
     random_seed: u64,
+
+    /// Print the blocks the run would process after resume, sampling and rerun filtering, and
+    /// the output directory, then exit without connecting to the RPCs or writing any results
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Args {
+    fn l1_rpc(&self) -> Result<&str> {
+        self.l1_rpc.as_deref().ok_or_else(|| eyre::eyre!("--l1-rpc (or L1_RPC) is required"))
+    }
+
+    fn l2_rpc(&self) -> Result<&str> {
+        self.l2_rpc.as_deref().ok_or_else(|| eyre::eyre!("--l2-rpc (or L2_RPC) is required"))
+    }
+
+    fn backoff_policy(&self) -> BackoffPolicy {
+        BackoffPolicy {
+            base: Duration::from_millis(self.backoff_base_ms),
+            max: Duration::from_millis(self.backoff_max_ms),
+            jitter_frac: self.backoff_jitter,
+            retries: self.retry_config(),
+        }
+    }
+
+    /// Per error type retry policy. Error types whose retries are not set explicitly get the
+    /// defaults of [`RetryConfig`], capped by --max-retries.
+    fn retry_config(&self) -> RetryConfig {
+        let mut config = RetryConfig::default().capped(self.max_retries);
+        let overrides = [
+            (&mut config.network, self.retries_network, self.backoff_multiplier_network),
+            (&mut config.rate_limit, self.retries_ratelimit, self.backoff_multiplier_ratelimit),
+            (&mut config.not_found, self.retries_not_found, self.backoff_multiplier_not_found),
+        ];
+        for (policy, max_retries, backoff_multiplier) in overrides {
+            policy.max_retries = max_retries.unwrap_or(policy.max_retries);
+            policy.backoff_multiplier = backoff_multiplier.unwrap_or(policy.backoff_multiplier);
+        }
+        config
+    }
 }
 
+fn parse_backoff_multiplier(s: &str) -> std::result::Result<f64, String> {
+    let multiplier: f64 = s.parse().map_err(|e| format!("{}", e))?;
+    if !multiplier.is_finite() || multiplier < 0.0 {
+        return Err(format!("backoff multiplier must be a non-negative number, got {}", s));
+    }
+    Ok(multiplier)
+}
+
+fn parse_jitter_frac(s: &str) -> std::result::Result<f64, String> {
+    let frac: f64 = s.parse().map_err(|e| format!("{}", e))?;
+    if !(0.0..=1.0).contains(&frac) {
+        return Err(format!("jitter must be between 0 and 1, got {}", frac));
+    }
+    Ok(frac)
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Inspect the checkpoint of a previous run without resuming it
+    InspectCheckpoint {
+        /// Results directory containing checkpoint.json
+        dir: PathBuf,
+    },
+    /// Derive a block and record the derived transactions for a later replay
+    RecordDerivation {
+        /// L2 block number to derive
+        block: u64,
+        /// File to write the derivation record to
+        #[arg(short = 'o', long)]
+        out: PathBuf,
+    },
+    /// Re-derive a recorded block in this process and compare against the record
+    ReplayDerivation {
+        /// Derivation record written by `record-derivation`
+        file: PathBuf,
+    },
+    /// Merge the results of several runs, e.g. of shards of a range, into one results directory
+    Merge {
+        /// Results directory to write the merged results, checkpoint and final report to
+        #[arg(short = 'o', long)]
+        out: PathBuf,
+        /// Results directories to merge; on conflicting block results the last one wins
+        #[arg(required = true)]
+        dirs: Vec<PathBuf>,
+    },
+    /// Derive and execute a single block and show where Kona diverges from the node
+    Diff {
+        /// L2 block number to diff
+        #[arg(short, long)]
+        block: u64,
+        /// Print the diff as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
 
 struct ValidationState {
     completed: AtomicUsize,
     failed: AtomicUsize,
+    /// Failed blocks whose failures are all infrastructure errors
+    infrastructure_failed: AtomicUsize,
+    /// Failed blocks listed in `--known-divergences`, not counted in `failed`
+    known_divergent: AtomicUsize,
+    /// Set once the failure rate exceeds `--failure-threshold`; no new blocks are started after
+    aborted: AtomicBool,
+    /// Set on the first Ctrl-C; like `aborted`, no new blocks are started after
+    interrupted: AtomicBool,
     total: usize,
+    start_block: u64,
     start_time: Instant,
     results_dir: PathBuf,
-    checkpoint_file: PathBuf,
     results_file: PathBuf,
+    csv_file: Option<PathBuf>,
     results_mutex: tokio::sync::Mutex<()>,
     recent_failures: Arc<tokio::sync::Mutex<Vec<(u64, String)>>>,
+    known_divergences: KnownDivergences,
 }
 
 impl ValidationState {
-    fn new(total: usize, results_dir: PathBuf) -> Self {
-        let checkpoint_file = results_dir.join("checkpoint.json");
+    fn new(total: usize, start_block: u64, results_dir: PathBuf) -> Self {
         let results_file = results_dir.join("results.jsonl");
         
         Self {
             completed: AtomicUsize::new(0),
             failed: AtomicUsize::new(0),
+            infrastructure_failed: AtomicUsize::new(0),
+            known_divergent: AtomicUsize::new(0),
+            aborted: AtomicBool::new(false),
+            interrupted: AtomicBool::new(false),
             total,
+            start_block,
             start_time: Instant::now(),
             results_dir,
-            checkpoint_file,
             results_file,
+            csv_file: None,
             results_mutex: tokio::sync::Mutex::new(()),
             recent_failures: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            known_divergences: KnownDivergences::default(),
+        }
+    }
+
+    fn is_aborted(&self) -> bool {
+        self.aborted.load(Ordering::Relaxed)
+    }
+
+    fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::Relaxed)
+    }
+
+    /// Stop starting new blocks, finishing the ones in flight
+    fn interrupt(&self) {
+        self.interrupted.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether no new blocks should be started, after an abort or an interrupt
+    fn is_stopping(&self) -> bool {
+        self.is_aborted() || self.is_interrupted()
+    }
+
+    /// Also stream every result to `results.csv`
+    fn with_csv_output(mut self) -> Self {
+        self.csv_file = Some(self.results_dir.join(output::CSV_FILE));
+        self
+    }
+
+    /// Treat failures of the blocks in `known_divergences` as expected
+    fn with_known_divergences(mut self, known_divergences: KnownDivergences) -> Self {
+        self.known_divergences = known_divergences;
+        self
+    }
+
+    /// Mark a block listed as a known divergence with the documented reason if it failed
+    /// validation. A listed block failing on anything else, e.g. the RPC, did not show the
+    /// divergence and is counted as usual.
+    fn mark_known_divergence(&self, result: &mut ValidationResult) {
+        let diverged = [&result.execution, &result.derivation]
+            .into_iter()
+            .flatten()
+            .filter(|r| !r.success)
+            .all(|r| r.error_type == Some(ErrorType::Validation));
+        if result.failed() && diverged {
+            result.known_divergence =
+                self.known_divergences.reason(result.block).map(str::to_string);
         }
     }
 
@@ -130,13 +439,21 @@ impl ValidationState {
         serde_json::to_writer(&mut file, &result)?;
         use std::io::Write;
         writeln!(&mut file)?;
+        if let Some(csv_file) = &self.csv_file {
+            output::append_csv(csv_file, &result)?;
+        }
         
         // Update counters
         let is_failed = result.execution.as_ref().map(|r| !r.success).unwrap_or(false) ||
                        result.derivation.as_ref().map(|r| !r.success).unwrap_or(false);
         
-        if is_failed {
+        if is_failed && result.known_divergence.is_some() {
+            self.known_divergent.fetch_add(1, Ordering::Relaxed);
+        } else if is_failed {
             self.failed.fetch_add(1, Ordering::Relaxed);
+            if result.is_infrastructure_failure() {
+                self.infrastructure_failed.fetch_add(1, Ordering::Relaxed);
+            }
             
             // Track recent failures
             let mut failures = self.recent_failures.lock().await;
@@ -166,13 +483,10 @@ impl ValidationState {
     fn save_checkpoint(&self, processed_blocks: &[u64]) -> Result<()> {
         let checkpoint = Checkpoint {
             processed_blocks: processed_blocks.to_vec(),
+            watermark: checkpoint::contiguous_watermark(processed_blocks, self.start_block),
             timestamp: chrono::Utc::now(),
         };
-        
-        let json = serde_json::to_string_pretty(&checkpoint)?;
-        fs::write(&self.checkpoint_file, json)?;
-        
-        Ok(())
+        checkpoint.save(&self.results_dir)
     }
 
     fn get_stats(&self) -> Stats {
@@ -192,6 +506,15 @@ impl ValidationState {
             0.0
         };
         
+        // Blocks that only failed on infrastructure say nothing about Kona's correctness
+        let infrastructure_failed = self.infrastructure_failed.load(Ordering::Relaxed);
+        let counted = completed - infrastructure_failed;
+        let validation_success_rate = if counted > 0 {
+            ((completed - failed) as f64 / counted as f64) * 100.0
+        } else {
+            100.0
+        };
+        
         let eta_seconds = if rate > 0.0 {
             ((self.total - completed) as f64 / rate * 60.0) as u64
         } else {
@@ -203,6 +526,8 @@ impl ValidationState {
             failed,
             total: self.total,
             success_rate,
+            counted,
+            validation_success_rate,
             blocks_per_minute: rate,
             elapsed_seconds: elapsed.as_secs(),
             eta_seconds,
@@ -210,18 +535,16 @@ impl ValidationState {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Checkpoint {
-    processed_blocks: Vec<u64>,
-    timestamp: chrono::DateTime<chrono::Utc>,
-}
-
 #[derive(Debug)]
 struct Stats {
     completed: usize,
     failed: usize,
     total: usize,
     success_rate: f64,
+    /// Blocks that did not fail only on infrastructure errors
+    counted: usize,
+    /// Success rate over the `counted` blocks
+    validation_success_rate: f64,
     blocks_per_minute: f64,
     elapsed_seconds: u64,
     eta_seconds: u64,
@@ -232,78 +555,98 @@ async fn main() -> Result<()> {
     let args = Args::parse();
     
     // Setup logging
-    let filter = if args.verbose { "debug" } else { "info" };
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .init();
+    logging::init(args.log_format, args.verbose);
+    
+    if let Some(command) = args.command.clone() {
+        return run_command(command, &args).await;
+    }
+    let l1_rpc = args.l1_rpc()?.to_string();
+    let l2_rpc = args.l2_rpc()?.to_string();
+    let rerun_blocks = failed_blocks_to_rerun(&args)?;
+    let (start_block, end_block) = match &rerun_blocks {
+        Some(blocks) => (blocks.first().copied().unwrap_or(0), blocks.last().copied().unwrap_or(0)),
+        None => (
+            args.start_block.ok_or_else(|| eyre::eyre!("--start-block is required"))?,
+            args.end_block.ok_or_else(|| eyre::eyre!("--end-block is required"))?,
+        ),
+    };
+    let start_block = resume_start_block(&args, start_block)?;
+    if args.backoff_base_ms > args.backoff_max_ms {
+        return Err(eyre::eyre!(
+            "--backoff-base-ms ({}) must not exceed --backoff-max-ms ({})",
+            args.backoff_base_ms,
+            args.backoff_max_ms
+        ));
+    }
     
     // Setup output directory
     let output_dir = args.output_dir.clone();
     let results_dir = output_dir.unwrap_or_else(|| {
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
         PathBuf::from(format!("validation_{}_{}_{}",
-            args.start_block, args.end_block, timestamp))
+            start_block, end_block, timestamp))
     });
-    fs::create_dir_all(&results_dir)?;
-    fs::create_dir_all(results_dir.join("logs"))?;
+    if args.rerun_failures.as_ref().is_some_and(|dir| dir == &results_dir) {
+        return Err(eyre::eyre!("--rerun-failures must write to a fresh results directory"));
+    }
     
     info!("🚀 Facet Validation Tool");
-    info!("Range: {} - {}", args.start_block, args.end_block);
+    info!("Range: {} - {}", start_block, end_block);
     info!("Workers: {}", args.jobs);
     info!("Output: {}", results_dir.display());
     
-    // Build required binaries
-    if !args.skip_execution {
-        info!("🔨 Building execution-fixture...");
-        build_execution_fixture()?;
-    }
-    
     // Determine blocks to process
-    let mut blocks_to_process: Vec<u64> = (args.start_block..=args.end_block).collect();
-    
-    // Handle random sampling
-    if let Some(sample_size) = args.random_sample {
-        use rand::SeedableRng;
-        use rand::rngs::StdRng;
-        
-        info!("🎲 Random sampling mode: {} blocks from range", sample_size);
-        
-        let total_range = blocks_to_process.len();
-        if sample_size > total_range {
-            return Err(eyre::eyre!("Sample size {} exceeds range size {}", sample_size, total_range));
+    let mut blocks_to_process = match rerun_blocks {
+        Some(blocks) => blocks,
+        None => select_blocks(&args, start_block, end_block)?,
+    };
+    let baseline = match &args.expected_hashes {
+        Some(path) => {
+            let baseline = TrustedHashes::load(path)?;
+            blocks_to_process.retain(|block| baseline.contains(*block));
+            info!(
+                "🔐 Trusted hash mode: {} blocks of the range in the baseline",
+                blocks_to_process.len()
+            );
+            Some(Arc::new(baseline))
         }
-        
-        // Create a seeded RNG for reproducibility
-        let mut rng = StdRng::seed_from_u64(args.random_seed);
-        
-        // Shuffle and take first N blocks
-        use rand::seq::SliceRandom;
-        blocks_to_process.shuffle(&mut rng);
-        blocks_to_process.truncate(sample_size);
-        
-        info!("  Using seed: {}", args.random_seed);
-        info!("  Selected blocks: {} (from {} to {})", 
-            blocks_to_process.len(),
-            blocks_to_process.first().unwrap_or(&0),
-            blocks_to_process.last().unwrap_or(&0)
-        );
+        None => None,
+    };
+    if args.dry_run {
+        info!("📝 Dry run: {}", describe_plan(&blocks_to_process, &results_dir));
+        return Ok(());
     }
-    
-    // Handle resume
-    let resume_dir = args.resume.clone();
-    if let Some(resume_dir) = resume_dir {
-        if resume_dir.exists() {
-            info!("📂 Resuming from checkpoint...");
-            let checkpoint: Checkpoint = serde_json::from_str(&fs::read_to_string(resume_dir.join("checkpoint.json"))?)?;
-            let processed: std::collections::HashSet<_> = checkpoint.processed_blocks.into_iter().collect();
-            blocks_to_process.retain(|b| !processed.contains(b));
-            info!("  Already processed: {}", processed.len());
-            info!("  Remaining: {}", blocks_to_process.len());
+    fs::create_dir_all(&results_dir)?;
+    fs::create_dir_all(results_dir.join("logs"))?;
+    if blocks_to_process.is_empty() {
+        info!("✅ No blocks to process after applying filters");
+        write_final_report(&results_dir, &FinalReport::empty(start_block, end_block))?;
+        if args.output_formats.contains(&OutputFormat::Prometheus) {
+            PrometheusMetrics::default().write(&results_dir)?;
         }
+        // A gated run that validated nothing must not pass silently
+        run_success_gate(&args, &results_dir).await;
+        return Ok(());
     }
     
     let total_blocks = blocks_to_process.len();
-    let state = Arc::new(ValidationState::new(total_blocks, results_dir.clone()));
+    let mut state = ValidationState::new(total_blocks, start_block, results_dir.clone());
+    if args.output_formats.contains(&OutputFormat::Csv) {
+        state = state.with_csv_output();
+    }
+    if let Some(path) = &args.known_divergences {
+        let known_divergences = KnownDivergences::load(path)?;
+        info!("📋 {} known divergences will not count as failures", known_divergences.len());
+        state = state.with_known_divergences(known_divergences);
+    }
+    let state = Arc::new(state);
+    let cache = args.cache_size.map(|size| Arc::new(BlockCache::new(size)));
+    
+    // Every task clones these, so all blocks share the same connection pools
+    let mut providers = Providers::connect(&l1_rpc, &l2_rpc, args.rpc_rps).await?;
+    if let Some(cache) = &cache {
+        providers = providers.with_block_cache(cache.clone());
+    }
     
     // Progress bars
     let multi_progress = MultiProgress::new();
@@ -317,196 +660,560 @@ async fn main() -> Result<()> {
     // Spawn stats thread
     let _stats_handle = spawn_stats_monitor(state.clone(), multi_progress.clone());
     
-    // Create semaphore for concurrency control
-    let semaphore = Arc::new(Semaphore::new(args.jobs));
+    let retry_budget = RetryBudget::new(args.max_total_retries);
     
     // Process blocks
-    let mut tasks = vec![];
     let processed_blocks = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+    let checkpoint_handle = spawn_checkpoint_writer(
+        state.clone(),
+        processed_blocks.clone(),
+        Duration::from_secs(args.checkpoint_interval_secs.max(1)),
+    );
+    let interrupt_handle = spawn_interrupt_handler(state.clone());
     
-    for block in blocks_to_process {
-        let permit = semaphore.clone().acquire_owned().await?;
-        let state = state.clone();
-        let args = args.clone();
-        let main_progress = main_progress.clone();
-        let results_dir = results_dir.clone();
-        let processed_blocks = processed_blocks.clone();
-        
-        let task = tokio::spawn(async move {
-            let _permit = permit;
-            
-            let start = Instant::now();
-            let mut result = ValidationResult {
+    if args.sequential {
+        info!("⛓️  Sequential mode: validating the range as one chain");
+        // Consecutive blocks share one block cache, so each parent is still cached from before
+        let providers = providers.for_block();
+        let mut chain = derivation::SequentialChain::default();
+        blocks_to_process.sort_unstable();
+        for block in blocks_to_process {
+            if state.is_stopping() {
+                break;
+            }
+            let result = validate_block(
                 block,
-                execution: None,
-                derivation: None,
-                duration_ms: 0,
-                timestamp: chrono::Utc::now(),
-            };
-            
-            // Run execution validation
-            if !args.skip_execution {
-                match execution::validate_execution(
-                    block,
-                    &args.l2_rpc,
-                    args.max_retries,
-                    &results_dir,
-                ).await {
-                    Ok(test_result) => result.execution = Some(test_result),
-                    Err(e) => {
-                        error!("Block {} execution error: {}", block, e);
-                        result.execution = Some(TestResult {
-                            success: false,
-                            error: Some(e.to_string()),
-                            error_type: Some(ErrorType::Unknown),
-                            retries: 0,
-                        });
-                    }
+                &args,
+                &providers,
+                &results_dir,
+                baseline.as_deref(),
+                &retry_budget,
+                Some(&mut chain),
+            )
+            .await;
+            finish_block(result, &args, &state, &main_progress, &processed_blocks).await;
+        }
+        info!(
+            "⛓️  {} blocks derived on a parent from the node rather than the chain",
+            chain.rpc_parents
+        );
+    } else if args.ordered {
+        // Results are recorded strictly in ascending block order, with at most `jobs` in flight
+        info!("🔢 Ordered mode: processing blocks in ascending order");
+        let mut results = pipeline::ordered_stream(blocks_to_process, args.jobs, |block| {
+            let args = args.clone();
+            let results_dir = results_dir.clone();
+            let providers = providers.clone();
+            let baseline = baseline.clone();
+            let retry_budget = retry_budget.clone();
+            let state = state.clone();
+            tokio::spawn(async move {
+                // Blocks queued behind an abort are skipped, the ones in flight still finish
+                if state.is_stopping() {
+                    return None;
                 }
-            }
-            
-            // Run derivation validation (with sampling)
-            if !args.skip_derivation && block % args.derivation_sample_rate == 0 {
-                match derivation::validate_derivation(
+                let result = validate_block(
                     block,
-                    &args.l1_rpc,
-                    &args.l2_rpc,
-                    args.max_retries,
-                ).await {
-                    Ok(test_result) => result.derivation = Some(test_result),
-                    Err(e) => {
-                        error!("Block {} derivation error: {}", block, e);
-                        result.derivation = Some(TestResult {
-                            success: false,
-                            error: Some(e.to_string()),
-                            error_type: Some(ErrorType::Unknown),
-                            retries: 0,
-                        });
-                    }
-                }
+                    &args,
+                    &providers,
+                    &results_dir,
+                    baseline.as_deref(),
+                    &retry_budget,
+                    None,
+                )
+                .await;
+                Some(result)
+            })
+        });
+        while let Some(result) = results.next().await {
+            if let Some(result) = result? {
+                finish_block(result, &args, &state, &main_progress, &processed_blocks).await;
             }
-            
-            result.duration_ms = start.elapsed().as_millis() as u64;
-            
-            // Record result
-            if let Err(e) = state.record_result(result.clone()).await {
-                error!("Failed to record result: {}", e);
+        }
+    } else {
+        // Create semaphore for concurrency control
+        let semaphore = Arc::new(Semaphore::new(args.jobs));
+        let mut tasks = vec![];
+        
+        for block in blocks_to_process {
+            if state.is_stopping() {
+                break;
             }
-            
-            // Print failures in real-time
-            let exec_failed = result.execution.as_ref().map(|r| !r.success).unwrap_or(false);
-            let deriv_failed = result.derivation.as_ref().map(|r| !r.success).unwrap_or(false);
-            
-            if exec_failed || deriv_failed {
-                let mut failure_msg = format!("❌ Block {} failed:", block);
-                let mut is_infrastructure_issue = false;
-                
-                if exec_failed {
-                    let exec_result = result.execution.as_ref().unwrap();
-                    if let Some(err) = &exec_result.error {
-                        let error_type_str = exec_result.error_type
-                            .map(|t| format!(" [{:?}]", t))
-                            .unwrap_or_default();
-                        failure_msg.push_str(&format!("\n   Execution: {}{}", err, error_type_str));
-                        
-                        if let Some(error_type) = exec_result.error_type {
-                            if matches!(error_type, ErrorType::Network | ErrorType::RateLimit | ErrorType::NotFound) {
-                                is_infrastructure_issue = true;
-                            }
-                        }
-                    }
-                }
-                
-                if deriv_failed {
-                    let deriv_result = result.derivation.as_ref().unwrap();
-                    if let Some(err) = &deriv_result.error {
-                        let error_type_str = deriv_result.error_type
-                            .map(|t| format!(" [{:?}]", t))
-                            .unwrap_or_default();
-                        failure_msg.push_str(&format!("\n   Derivation: {}{}", err, error_type_str));
-                        
-                        if let Some(error_type) = deriv_result.error_type {
-                            if matches!(error_type, ErrorType::Network | ErrorType::RateLimit | ErrorType::NotFound) {
-                                is_infrastructure_issue = true;
-                            }
-                        }
-                    }
-                }
-                
-                if is_infrastructure_issue {
-                    failure_msg.push_str("\n   ⚠️  This appears to be an infrastructure issue, not a validation failure");
-                }
-                
-                error!("{}", failure_msg);
+            let permit = semaphore.clone().acquire_owned().await?;
+            if state.is_stopping() {
+                break;
             }
+            let state = state.clone();
+            let args = args.clone();
+            let main_progress = main_progress.clone();
+            let results_dir = results_dir.clone();
+            let processed_blocks = processed_blocks.clone();
+            let providers = providers.clone();
+            let baseline = baseline.clone();
+            let retry_budget = retry_budget.clone();
             
-            // Update progress
-            main_progress.inc(1);
-            
-            // Add to processed blocks
-            processed_blocks.lock().await.push(block);
-            
-            // Check if we need to checkpoint
-            let completed = state.completed.load(Ordering::Relaxed);
-            if completed % args.checkpoint_interval as usize == 0 {
-                let blocks = processed_blocks.lock().await.clone();
-                if let Err(e) = state.save_checkpoint(&blocks) {
-                    error!("Failed to save checkpoint: {}", e);
-                }
-            }
+            let task = tokio::spawn(async move {
+                let _permit = permit;
+                let result = validate_block(
+                    block,
+                    &args,
+                    &providers,
+                    &results_dir,
+                    baseline.as_deref(),
+                    &retry_budget,
+                    None,
+                )
+                .await;
+                finish_block(result, &args, &state, &main_progress, &processed_blocks).await;
+            });
             
-            // Check failure threshold
-            let stats = state.get_stats();
-            if stats.success_rate < (100.0 - args.failure_threshold) && completed > 10 {
-                error!("Failure rate ({:.1}%) exceeds threshold", 100.0 - stats.success_rate);
-                std::process::exit(1);
-            }
-        });
+            tasks.push(task);
+        }
         
-        tasks.push(task);
+        // Wait for all tasks, including the ones in flight when the run was aborted
+        for task in tasks {
+            let _ = task.await;
+        }
     }
     
-    // Wait for all tasks
-    for task in tasks {
-        let _ = task.await;
+    main_progress.finish_with_message("Complete!");
+    // A timer save in flight must not land after, and overwrite, the final checkpoint
+    checkpoint_handle.abort();
+    let _ = checkpoint_handle.await;
+    interrupt_handle.abort();
+    if let Err(e) = state.save_checkpoint(&processed_blocks.lock().await) {
+        error!("Failed to save checkpoint: {}", e);
     }
     
-    main_progress.finish_with_message("Complete!");
+    let cache_stats = cache.as_ref().map(|cache| cache.stats());
+    write_run_report(&args, &state, start_block, end_block, cache_stats).await?;
+    if state.is_interrupted() {
+        info!(
+            "🛑 Interrupted after {} blocks; continue with --resume {}",
+            state.completed.load(Ordering::Relaxed),
+            results_dir.display()
+        );
+        // Like the second Ctrl-C, but only once the report is written, so scripts and CI can
+        // tell an interrupted run from a complete one
+        std::process::exit(130);
+    }
+    if state.is_aborted() {
+        return Err(eyre::eyre!(
+            "Stopped after {} blocks: failure rate exceeded --failure-threshold {}%",
+            state.completed.load(Ordering::Relaxed),
+            args.failure_threshold
+        ));
+    }
     
+    run_success_gate(&args, &results_dir).await;
+    Ok(())
+}
+
+/// End-of-run CI gate: exit with the gate's exit code if `--min-success-rate` is not met
+async fn run_success_gate(args: &Args, results_dir: &Path) {
+    let Some(min_success_rate) = args.min_success_rate else {
+        return;
+    };
+    let gate = gate::SuccessGate { min_success_rate, validation_only: args.gate_validation_only };
+    let outcome = gate.evaluate(
+        &tokio::fs::read_to_string(results_dir.join("results.jsonl")).await.unwrap_or_default(),
+    );
+    println!("{}", outcome.summary());
+    if !outcome.passed() {
+        std::process::exit(outcome.exit_code());
+    }
+}
+
+/// Log the final stats of a run and write its final report, also after an aborted run
+async fn write_run_report(
+    args: &Args,
+    state: &ValidationState,
+    start_block: u64,
+    end_block: u64,
+    cache_stats: Option<CacheStats>,
+) -> Result<()> {
     // Final stats
     let stats = state.get_stats();
     info!("");
-    info!("🏁 Validation Complete");
+    if state.is_interrupted() {
+        info!("🛑 Validation Interrupted");
+    } else if state.is_aborted() {
+        info!("🛑 Validation Stopped");
+    } else {
+        info!("🏁 Validation Complete");
+    }
     info!("====================");
     info!("Total blocks: {}", stats.total);
     info!("Completed: {}", stats.completed);
     info!("Failed: {}", stats.failed);
+    let known_divergences = state.known_divergent.load(Ordering::Relaxed);
+    if known_divergences > 0 {
+        info!("Known divergences: {}", known_divergences);
+    }
     info!("Success rate: {:.2}%", stats.success_rate);
+    info!("Validation success rate: {:.2}%", stats.validation_success_rate);
     info!("Duration: {}s", stats.elapsed_seconds);
     info!("Average: {:.2} blocks/min", stats.blocks_per_minute);
+    if let Some(cache_stats) = cache_stats {
+        info!(
+            "Block cache: {} hits, {} misses ({:.2}% hit rate)",
+            cache_stats.hits,
+            cache_stats.misses,
+            cache_stats.hit_rate()
+        );
+    }
     
     // Analyze failure types
-    analyze_failure_types(&results_dir).await?;
+    analyze_failure_types(&state.results_dir).await?;
+    
+    // Per-block timing distribution
+    let results = tokio::fs::read_to_string(state.results_dir.join("results.jsonl"))
+        .await
+        .unwrap_or_default();
+    let timing = report::compute_timing_report(&results);
+    timing.log_summary();
     
     // Generate final report
     let report = FinalReport {
-        start_block: args.start_block,
-        end_block: args.end_block,
+        start_block,
+        end_block,
         total_blocks: stats.total,
         completed: stats.completed,
         failed: stats.failed,
         success_rate: stats.success_rate,
         duration_seconds: stats.elapsed_seconds,
         blocks_per_minute: stats.blocks_per_minute,
+        timing,
+        cache: cache_stats,
+        known_divergences,
+        interrupted: state.is_interrupted(),
         timestamp: chrono::Utc::now(),
     };
     
-    let report_file = results_dir.join("final_report.json");
-    fs::write(report_file, serde_json::to_string_pretty(&report)?)?;
+    write_final_report(&state.results_dir, &report)?;
+    if args.output_formats.contains(&OutputFormat::Prometheus) {
+        PrometheusMetrics::from_results(&results).write(&state.results_dir)?;
+    }
     
     Ok(())
 }
 
+/// Blocks that failed in the previous run given by `--rerun-failures`
+fn failed_blocks_to_rerun(args: &Args) -> Result<Option<Vec<u64>>> {
+    let Some(previous_dir) = &args.rerun_failures else {
+        return Ok(None);
+    };
+    let results_file = previous_dir.join("results.jsonl");
+    let content = fs::read_to_string(&results_file)
+        .map_err(|e| eyre::eyre!("Failed to read {}: {}", results_file.display(), e))?;
+    let blocks = report::failed_blocks(&content);
+    info!("🔁 Rerunning {} failed blocks from {}", blocks.len(), previous_dir.display());
+    Ok(Some(blocks))
+}
+
+/// First block to validate, moved past the watermark of `--resume-from-watermark`
+fn resume_start_block(args: &Args, start_block: u64) -> Result<u64> {
+    let Some(resume_dir) = &args.resume_from_watermark else {
+        return Ok(start_block);
+    };
+    let checkpoint = Checkpoint::load(resume_dir)?;
+    match checkpoint.resume_watermark() {
+        Some(watermark) if watermark >= start_block => {
+            info!(
+                "📂 Resuming after watermark {} (blocks {} - {} assumed done)",
+                watermark, start_block, watermark
+            );
+            Ok(watermark + 1)
+        }
+        _ => {
+            info!("📂 No watermark at or above block {}, starting from the beginning", start_block);
+            Ok(start_block)
+        }
+    }
+}
+
+/// Summary of the blocks a run would process, for `--dry-run`
+fn describe_plan(blocks: &[u64], results_dir: &Path) -> String {
+    match (blocks.iter().min(), blocks.iter().max()) {
+        (Some(first), Some(last)) => format!(
+            "{} blocks from {} to {}, output to {}",
+            blocks.len(),
+            first,
+            last,
+            results_dir.display()
+        ),
+        _ => format!("no blocks to process, output to {}", results_dir.display()),
+    }
+}
+
+/// Blocks of the range left to validate after stride or random sampling and resume filtering
+fn select_blocks(args: &Args, start_block: u64, end_block: u64) -> Result<Vec<u64>> {
+    let mut blocks_to_process: Vec<u64> = (start_block..=end_block).collect();
+    
+    // Stride positions count from the requested start, even if a watermark moved the start
+    if let Some(stride) = args.sample_stride {
+        let origin = args.start_block.unwrap_or(start_block).min(start_block);
+        let stride = stride.get() as u64;
+        blocks_to_process.retain(|block| (block - origin) % stride == 0);
+        info!("🪜 Stride sampling: every {} blocks, {} selected", stride, blocks_to_process.len());
+    }
+    
+    // Handle random sampling
+    if let Some(sample_size) = args.random_sample {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+        
+        info!("🎲 Random sampling mode: {} blocks from range", sample_size);
+        
+        let total_range = blocks_to_process.len();
+        if sample_size > total_range {
+            return Err(eyre::eyre!("Sample size {} exceeds range size {}", sample_size, total_range));
+        }
+        
+        // Create a seeded RNG for reproducibility
+        let mut rng = StdRng::seed_from_u64(args.random_seed);
+        
+        // Shuffle and take first N blocks
+        use rand::seq::SliceRandom;
+        blocks_to_process.shuffle(&mut rng);
+        blocks_to_process.truncate(sample_size);
+        
+        info!("  Using seed: {}", args.random_seed);
+        info!("  Selected blocks: {} (from {} to {})", 
+            blocks_to_process.len(),
+            blocks_to_process.first().unwrap_or(&0),
+            blocks_to_process.last().unwrap_or(&0)
+        );
+    }
+    
+    // Handle resume
+    let resume_dir = args.resume.clone();
+    if let Some(resume_dir) = resume_dir {
+        if resume_dir.exists() {
+            info!("📂 Resuming from checkpoint...");
+            let checkpoint = Checkpoint::load(&resume_dir)?;
+            let processed: std::collections::HashSet<_> = checkpoint.processed_blocks.into_iter().collect();
+            blocks_to_process.retain(|b| !processed.contains(b));
+            info!("  Already processed: {}", processed.len());
+            info!("  Remaining: {}", blocks_to_process.len());
+        }
+    }
+    
+    Ok(blocks_to_process)
+}
+
+/// Run the enabled validation stages for a single block, or only the trusted hash check if a
+/// baseline is given. `chain` carries derivation state between blocks in `--sequential` mode.
+async fn validate_block(
+    block: u64,
+    args: &Args,
+    providers: &Providers,
+    results_dir: &Path,
+    baseline: Option<&TrustedHashes>,
+    retry_budget: &RetryBudget,
+    chain: Option<&mut derivation::SequentialChain>,
+) -> ValidationResult {
+    let start = Instant::now();
+    let mut result = ValidationResult {
+        block,
+        execution: None,
+        derivation: None,
+        refs: Default::default(),
+        duration_ms: 0,
+        timestamp: chrono::Utc::now(),
+        known_divergence: None,
+    };
+    
+    if let Some(baseline) = baseline {
+        let mut derivation =
+            snapshot::validate_trusted_hash(block, baseline, providers).await;
+        derivation.duration_ms = Some(start.elapsed().as_millis() as u64);
+        result.derivation = Some(derivation);
+        result.duration_ms = start.elapsed().as_millis() as u64;
+        return result;
+    }
+    
+    // Run execution validation
+    if !args.skip_execution {
+        let stage_start = Instant::now();
+        match execution::validate_execution(
+            block,
+            providers,
+            args.backoff_strategy,
+            &args.backoff_policy(),
+            retry_budget,
+            results_dir,
+        ).await {
+            Ok((test_result, refs)) => {
+                result.execution = Some(test_result);
+                result.refs.merge(refs);
+            }
+            Err(e) => {
+                error!(block, stage = "execution", error = %e, "Stage errored");
+                result.execution = Some(TestResult {
+                    success: false,
+                    error: Some(e.to_string()),
+                    error_type: Some(ErrorType::Unknown),
+                    retries: 0,
+                    duration_ms: None,
+                });
+            }
+        }
+        if let Some(exec) = result.execution.as_mut() {
+            exec.duration_ms = Some(stage_start.elapsed().as_millis() as u64);
+        }
+    }
+    
+    // Run derivation validation (with sampling)
+    if !args.skip_derivation && block % args.derivation_sample_rate == 0 {
+        let stage_start = Instant::now();
+        match derivation::validate_derivation(
+            block,
+            providers,
+            args.backoff_strategy,
+            &args.backoff_policy(),
+            retry_budget,
+            chain,
+        ).await {
+            Ok((test_result, refs)) => {
+                result.derivation = Some(test_result);
+                result.refs.merge(refs);
+            }
+            Err(e) => {
+                error!(block, stage = "derivation", error = %e, "Stage errored");
+                result.derivation = Some(TestResult {
+                    success: false,
+                    error: Some(e.to_string()),
+                    error_type: Some(ErrorType::Unknown),
+                    retries: 0,
+                    duration_ms: None,
+                });
+            }
+        }
+        if let Some(deriv) = result.derivation.as_mut() {
+            deriv.duration_ms = Some(stage_start.elapsed().as_millis() as u64);
+        }
+    }
+    
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    
+    result
+}
+
+/// Record a block's result, report failures, and update progress and checkpoints
+async fn finish_block(
+    mut result: ValidationResult,
+    args: &Args,
+    state: &ValidationState,
+    main_progress: &ProgressBar,
+    processed_blocks: &tokio::sync::Mutex<Vec<u64>>,
+) {
+    let block = result.block;
+    state.mark_known_divergence(&mut result);
+    
+    // Record result
+    if let Err(e) = state.record_result(result.clone()).await {
+        error!("Failed to record result: {}", e);
+    }
+    
+    // Print failures in real-time, one event per failed stage with its fields for log pipelines
+    if let Some(reason) = &result.known_divergence {
+        info!(block, reason = reason.as_str(), "📋 Block failed as a known divergence");
+    } else {
+        let stages = [("execution", &result.execution), ("derivation", &result.derivation)];
+        for (stage, stage_result) in stages {
+            let Some(stage_result) = stage_result.as_ref().filter(|r| !r.success) else {
+                continue;
+            };
+            let error_type = stage_result.error_type.unwrap_or(ErrorType::Unknown);
+            error!(
+                block,
+                stage,
+                error_type = error_type.as_str(),
+                infrastructure = error_type.is_infrastructure(),
+                error = stage_result.error.as_deref().unwrap_or("unknown"),
+                "❌ Block failed"
+            );
+        }
+    }
+    
+    // Update progress
+    main_progress.inc(1);
+    
+    // Add to processed blocks
+    processed_blocks.lock().await.push(block);
+    
+    // Check failure threshold
+    let stats = state.get_stats();
+    // Flaky RPCs must not abort a run whose derivation and execution are correct, nor count
+    // towards the blocks needed before the threshold applies
+    if stats.validation_success_rate < (100.0 - args.failure_threshold) &&
+        stats.counted > 10 &&
+        !state.aborted.swap(true, Ordering::Relaxed)
+    {
+        error!(
+            "Validation failure rate ({:.1}%) exceeds threshold, stopping after the blocks in flight",
+            100.0 - stats.validation_success_rate
+        );
+    }
+}
+
+async fn run_command(command: Command, args: &Args) -> Result<()> {
+    match command {
+        Command::InspectCheckpoint { dir } => {
+            let checkpoint = Checkpoint::load(&dir)?;
+            checkpoint.summarize().print(&dir);
+            Ok(())
+        }
+        Command::RecordDerivation { block, out } => {
+            let providers = Providers::connect(args.l1_rpc()?, args.l2_rpc()?, args.rpc_rps).await?;
+            let deriver = determinism::RpcDeriver::new(providers);
+            let record = determinism::record_derivation(&deriver, block, &out).await?;
+            info!(
+                "📝 Recorded {} derived transactions for block {} to {} (digest {})",
+                record.transactions.len(),
+                block,
+                out.display(),
+                record.digest
+            );
+            Ok(())
+        }
+        Command::ReplayDerivation { file } => {
+            let providers = Providers::connect(args.l1_rpc()?, args.l2_rpc()?, args.rpc_rps).await?;
+            let deriver = determinism::RpcDeriver::new(providers);
+            let report = determinism::replay_derivation(&deriver, &file).await?;
+            if report.is_deterministic() {
+                info!("✅ Block {} re-derived identically ({} transactions)", report.block, report.recorded);
+                Ok(())
+            } else {
+                error!(
+                    "❌ Block {} derivation differs from record: recorded {} txs, replayed {} txs, first mismatch at index {:?}",
+                    report.block, report.recorded, report.replayed, report.first_mismatch
+                );
+                Err(eyre::eyre!("Process-level derivation nondeterminism detected for block {}", report.block))
+            }
+        }
+        Command::Merge { out, dirs } => {
+            merge_runs(&out, &dirs).await?;
+            Ok(())
+        }
+        Command::Diff { block, json } => {
+            let providers = Providers::connect(args.l1_rpc()?, args.l2_rpc()?, args.rpc_rps).await?;
+            let diff = diff::diff_block(block, &providers).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&diff)?);
+            } else {
+                diff.print();
+            }
+            if diff.is_clean() {
+                Ok(())
+            } else {
+                Err(eyre::eyre!("Block {} differs from the node", block))
+            }
+        }
+    }
+}
+
 async fn analyze_failure_types(results_dir: &PathBuf) -> Result<()> {
     use std::collections::HashMap;
     
@@ -516,9 +1223,15 @@ async fn analyze_failure_types(results_dir: &PathBuf) -> Result<()> {
     let mut error_type_counts: HashMap<String, usize> = HashMap::new();
     let mut validation_failures: Vec<(u64, &str, String)> = Vec::new();
     let mut infrastructure_failures: Vec<(u64, &str, ErrorType)> = Vec::new();
+    let mut known_divergences: Vec<(u64, String)> = Vec::new();
     
     for line in content.lines() {
         if let Ok(result) = serde_json::from_str::<ValidationResult>(line) {
+            if let Some(reason) = result.known_divergence {
+                known_divergences.push((result.block, reason));
+                continue;
+            }
+            
             // Check execution failures
             if let Some(exec) = &result.execution {
                 if !exec.success {
@@ -593,6 +1306,17 @@ async fn analyze_failure_types(results_dir: &PathBuf) -> Result<()> {
         info!("  These are likely transient failures due to RPC issues, not validation problems");
     }
     
+    if !known_divergences.is_empty() {
+        info!("");
+        info!("📋 Known Divergences ({}):", known_divergences.len());
+        for (block, reason) in known_divergences.iter().take(10) {
+            info!("  Block {}: {}", block, reason);
+        }
+        if known_divergences.len() > 10 {
+            info!("  ... and {} more", known_divergences.len() - 10);
+        }
+    }
+    
     Ok(())
 }
 
@@ -610,22 +1334,6 @@ fn format_duration(seconds: u64) -> String {
     }
 }
 
-fn build_execution_fixture() -> Result<()> {
-    let mut cmd = Command::new("cargo");
-    cmd.args(&["build", "-p", "execution-fixture", "--release"]);
-    
-    // Set LIBCLANG_PATH for macOS
-    #[cfg(target_os = "macos")]
-    cmd.env("LIBCLANG_PATH", "/Library/Developer/CommandLineTools/usr/lib");
-    
-    let status = cmd.status()?;
-    if !status.success() {
-        return Err(eyre::eyre!("Failed to build execution-fixture"));
-    }
-    
-    Ok(())
-}
-
 fn spawn_stats_monitor(state: Arc<ValidationState>, multi_progress: MultiProgress) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         let stats_bar = multi_progress.add(ProgressBar::new_spinner());
@@ -641,8 +1349,9 @@ fn spawn_stats_monitor(state: Arc<ValidationState>, multi_progress: MultiProgres
             let stats = state.get_stats();
             let eta_formatted = format_duration(stats.eta_seconds);
             let msg = format!(
-                "Success: {:.1}% | Speed: {:.1} blocks/min | ETA: {}",
+                "Success: {:.1}% (validation {:.1}%) | Speed: {:.1} blocks/min | ETA: {}",
                 stats.success_rate,
+                stats.validation_success_rate,
                 stats.blocks_per_minute,
                 eta_formatted
             );
@@ -651,6 +1360,42 @@ fn spawn_stats_monitor(state: Arc<ValidationState>, multi_progress: MultiProgres
     })
 }
 
+/// Periodically checkpoint the blocks processed so far.
+///
+/// A single task writes every checkpoint, so concurrent workers never race on the file.
+fn spawn_checkpoint_writer(
+    state: Arc<ValidationState>,
+    processed_blocks: Arc<tokio::sync::Mutex<Vec<u64>>>,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            
+            let blocks = processed_blocks.lock().await.clone();
+            if let Err(e) = state.save_checkpoint(&blocks) {
+                error!("Failed to save checkpoint: {}", e);
+            }
+        }
+    })
+}
+
+/// On the first Ctrl-C, stop starting new blocks so the run winds down with a final checkpoint and
+/// report once the blocks in flight finish. A second Ctrl-C exits immediately.
+fn spawn_interrupt_handler(state: Arc<ValidationState>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        state.interrupt();
+        warn!("🛑 Interrupted, finishing the blocks in flight; press Ctrl-C again to exit now");
+        if tokio::signal::ctrl_c().await.is_ok() {
+            error!("Interrupted twice, exiting without a final checkpoint");
+            std::process::exit(130);
+        }
+    })
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct FinalReport {
     start_block: u64,
@@ -661,5 +1406,588 @@ struct FinalReport {
     success_rate: f64,
     duration_seconds: u64,
     blocks_per_minute: f64,
+    #[serde(default)]
+    timing: report::TimingReport,
+    /// Hits of the shared block cache, if `--cache-size` was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cache: Option<CacheStats>,
+    /// Failed blocks listed in `--known-divergences`, not counted in `failed`
+    #[serde(default)]
+    known_divergences: usize,
+    /// Whether the run was stopped by Ctrl-C before validating every block
+    #[serde(default)]
+    interrupted: bool,
     timestamp: chrono::DateTime<chrono::Utc>,
-}
\ No newline at end of file
+}
+
+impl FinalReport {
+    /// Report of a run that had no blocks to validate
+    fn empty(start_block: u64, end_block: u64) -> Self {
+        Self {
+            start_block,
+            end_block,
+            total_blocks: 0,
+            completed: 0,
+            failed: 0,
+            success_rate: 0.0,
+            duration_seconds: 0,
+            blocks_per_minute: 0.0,
+            timing: Default::default(),
+            cache: None,
+            known_divergences: 0,
+            interrupted: false,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Merge the results directories of several runs into `out`.
+///
+/// Results are de-duplicated by block, the last directory to contain a block winning. The final
+/// report is recomputed over the merged results, counting a block covered by several runs once,
+/// with the duration summed over the runs, and a checkpoint of the merged blocks is written so that
+/// `--resume` can continue from `out`.
+async fn merge_runs(out: &Path, dirs: &[PathBuf]) -> Result<FinalReport> {
+    let mut runs = Vec::with_capacity(dirs.len());
+    let mut reports = Vec::new();
+    for dir in dirs {
+        let results_file = dir.join("results.jsonl");
+        let content = fs::read_to_string(&results_file)
+            .map_err(|e| eyre::eyre!("Failed to read {}: {}", results_file.display(), e))?;
+        // A run killed before its end has no final report, only its results
+        match fs::read_to_string(dir.join("final_report.json")) {
+            Ok(report) => reports.push(serde_json::from_str::<FinalReport>(&report)?),
+            Err(_) => {
+                warn!("⚠️  No final report in {}, its duration is not counted", dir.display());
+            }
+        }
+        runs.push(content);
+    }
+
+    let merged = report::merge_results(&runs);
+    for block in &merged.conflicts {
+        warn!("⚠️  Block {} has conflicting results, keeping the result of the last run", block);
+    }
+    info!(
+        "🔀 Merged {} results from {} runs into {} ({} conflicting)",
+        merged.results.len(),
+        dirs.len(),
+        out.display(),
+        merged.conflicts.len()
+    );
+
+    fs::create_dir_all(out)?;
+    let mut results = String::new();
+    for result in &merged.results {
+        results.push_str(&serde_json::to_string(result)?);
+        results.push('\n');
+    }
+    fs::write(out.join("results.jsonl"), &results)?;
+
+    let blocks: Vec<u64> = merged.results.iter().map(|r| r.block).collect();
+    let start_block = reports.iter().map(|r| r.start_block).chain(blocks.first().copied()).min();
+    let end_block = reports.iter().map(|r| r.end_block).chain(blocks.last().copied()).max();
+    let (Some(start_block), Some(end_block)) = (start_block, end_block) else {
+        let report = FinalReport::empty(0, 0);
+        write_final_report(out, &report)?;
+        return Ok(report);
+    };
+    Checkpoint {
+        processed_blocks: blocks.clone(),
+        watermark: checkpoint::contiguous_watermark(&blocks, start_block),
+        timestamp: chrono::Utc::now(),
+    }
+    .save(out)?;
+
+    analyze_failure_types(&out.to_path_buf()).await?;
+    let timing = report::compute_timing_report(&results);
+    timing.log_summary();
+
+    // Overlapping runs share blocks, so the totals count the merged blocks rather than the runs'
+    let total_blocks = blocks.len();
+    let completed = merged.results.len();
+    let failed = merged.results.iter().filter(|r| r.counts_as_failure()).count();
+    let known_divergences =
+        merged.results.iter().filter(|r| r.known_divergence.is_some()).count();
+    let duration_seconds: u64 = reports.iter().map(|r| r.duration_seconds).sum();
+    let cache = reports.iter().filter_map(|r| r.cache).reduce(|a, b| CacheStats {
+        hits: a.hits + b.hits,
+        misses: a.misses + b.misses,
+    });
+    let report = FinalReport {
+        start_block,
+        end_block,
+        total_blocks,
+        completed,
+        failed,
+        success_rate: if completed > 0 {
+            ((completed - failed) as f64 / completed as f64) * 100.0
+        } else {
+            0.0
+        },
+        duration_seconds,
+        blocks_per_minute: if duration_seconds > 0 {
+            completed as f64 / duration_seconds as f64 * 60.0
+        } else {
+            0.0
+        },
+        timing,
+        cache,
+        known_divergences,
+        interrupted: reports.iter().any(|r| r.interrupted),
+        timestamp: chrono::Utc::now(),
+    };
+    write_final_report(out, &report)?;
+    Ok(report)
+}
+
+fn write_final_report(results_dir: &Path, report: &FinalReport) -> Result<()> {
+    let report_file = results_dir.join("final_report.json");
+    fs::write(report_file, serde_json::to_string_pretty(report)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    /// Parse `flags` after the RPC endpoints every run needs
+    fn try_test_args(flags: &[&str]) -> Result<Args, clap::Error> {
+        let rpcs = ["--l1-rpc", "http://localhost:8545", "--l2-rpc", "http://localhost:9545"];
+        let argv = ["validate-facet"].into_iter().chain(rpcs).chain(flags.iter().copied());
+        Args::try_parse_from(argv)
+    }
+
+    fn test_args(flags: &[&str]) -> Args {
+        try_test_args(flags).unwrap()
+    }
+
+    /// Result of `block` whose stages passed, failed validation, or did not run (`None`)
+    fn result(block: u64, exec_ok: Option<bool>, deriv_ok: Option<bool>) -> ValidationResult {
+        let stage = |ok: bool, error: &str| TestResult {
+            success: ok,
+            error: (!ok).then(|| error.to_string()),
+            error_type: (!ok).then_some(ErrorType::Validation),
+            retries: 0,
+            duration_ms: None,
+        };
+        ValidationResult {
+            block,
+            execution: exec_ok.map(|ok| stage(ok, "State root mismatch")),
+            derivation: deriv_ok.map(|ok| stage(ok, "Transaction count mismatch")),
+            refs: Default::default(),
+            duration_ms: 0,
+            timestamp: chrono::Utc::now(),
+            known_divergence: None,
+        }
+    }
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Args::command().debug_assert();
+
+        // Subcommands that do not talk to the RPCs parse without them
+        let args = Args::try_parse_from(["validate-facet", "inspect-checkpoint", "results"]).unwrap();
+        assert!(matches!(args.command, Some(Command::InspectCheckpoint { .. })));
+
+        // --rerun-failures takes its blocks from the previous run
+        let args = Args::try_parse_from(["validate-facet", "--rerun-failures", "previous"]).unwrap();
+        assert_eq!((args.start_block, args.end_block), (None, None));
+        assert!(Args::try_parse_from(["validate-facet", "-s", "1"]).is_err());
+
+        // The interval keeps its old flag name for existing invocations
+        let args =
+            Args::parse_from(["validate-facet", "-s", "1", "-e", "2", "--checkpoint-interval", "5"]);
+        assert_eq!(args.checkpoint_interval_secs, 5);
+    }
+
+    #[test]
+    fn test_fully_resumed_range_is_a_no_op() {
+        let resume_dir = tempfile::tempdir().unwrap();
+        let checkpoint = Checkpoint {
+            processed_blocks: vec![100, 101, 102],
+            watermark: Some(102),
+            timestamp: chrono::Utc::now(),
+        };
+        fs::write(
+            resume_dir.path().join(checkpoint::CHECKPOINT_FILE),
+            serde_json::to_string(&checkpoint).unwrap(),
+        )
+        .unwrap();
+
+        let args = test_args(&[
+            "-s", "100",
+            "-e", "102",
+            "--resume", resume_dir.path().to_str().unwrap(),
+        ]);
+        let blocks = select_blocks(&args, 100, 102).unwrap();
+        assert!(blocks.is_empty());
+
+        let results_dir = tempfile::tempdir().unwrap();
+        write_final_report(results_dir.path(), &FinalReport::empty(100, 102)).unwrap();
+        let report: FinalReport = serde_json::from_str(
+            &fs::read_to_string(results_dir.path().join("final_report.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!((report.total_blocks, report.completed, report.failed), (0, 0, 0));
+        assert!(!results_dir.path().join("results.jsonl").exists());
+    }
+
+    #[test]
+    fn test_empty_range_selects_no_blocks() {
+        let args = test_args(&[
+            "-s", "101",
+            "-e", "100",
+        ]);
+        assert!(select_blocks(&args, 101, 100).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failure_threshold_stops_the_run_with_a_report() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let args = test_args(&[
+            "-s", "1",
+            "-e", "100",
+            "--failure-threshold", "10",
+        ]);
+        let state = ValidationState::new(100, 1, results_dir.path().to_path_buf());
+        let processed_blocks = tokio::sync::Mutex::new(Vec::new());
+        let progress = ProgressBar::hidden();
+
+        for block in 1..=12 {
+            let result = result(block, Some(false), None);
+            finish_block(result, &args, &state, &progress, &processed_blocks).await;
+            // The threshold only applies after the first 10 blocks
+            assert_eq!(state.is_aborted(), block > 10);
+        }
+
+        write_run_report(&args, &state, 1, 100, None).await.unwrap();
+        let report: FinalReport = serde_json::from_str(
+            &fs::read_to_string(results_dir.path().join("final_report.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!((report.total_blocks, report.completed, report.failed), (100, 12, 12));
+    }
+
+    #[tokio::test]
+    async fn test_known_divergences_do_not_trip_the_threshold() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let args = test_args(&[
+            "-s", "1",
+            "-e", "20",
+            "--failure-threshold", "10",
+        ]);
+        let reason = "gas limit migration";
+        let known = (1..=13)
+            .map(|block| format!(r#"{{"block": {}, "reason": "{}"}}"#, block, reason))
+            .collect::<Vec<_>>()
+            .join(",");
+        let state = ValidationState::new(20, 1, results_dir.path().to_path_buf())
+            .with_known_divergences(KnownDivergences::parse(&format!("[{}]", known)).unwrap());
+        let processed_blocks = tokio::sync::Mutex::new(Vec::new());
+        let progress = ProgressBar::hidden();
+
+        // Every allowlisted block fails, which would stop the run without the list. Block 13 is
+        // listed too but fails on the RPC, so it is not a known divergence.
+        for block in 1..=20 {
+            let mut result = result(block, Some(block > 13), None);
+            if block == 13 {
+                result.execution.as_mut().unwrap().error_type = Some(ErrorType::Network);
+            }
+            finish_block(result, &args, &state, &progress, &processed_blocks).await;
+        }
+
+        assert!(!state.is_aborted());
+        let stats = state.get_stats();
+        assert_eq!((stats.completed, stats.failed), (20, 1));
+        assert_eq!(stats.validation_success_rate, 100.0);
+
+        write_run_report(&args, &state, 1, 20, None).await.unwrap();
+        let report: FinalReport = serde_json::from_str(
+            &fs::read_to_string(results_dir.path().join("final_report.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!((report.failed, report.known_divergences), (1, 12));
+        let results = fs::read_to_string(results_dir.path().join("results.jsonl")).unwrap();
+        let marked: Vec<u64> = results
+            .lines()
+            .map(|line| serde_json::from_str::<ValidationResult>(line).unwrap())
+            .filter(|r| r.known_divergence.as_deref() == Some(reason))
+            .map(|r| r.block)
+            .collect();
+        assert_eq!(marked, (1..=12).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_json_logs_carry_failure_fields() {
+        #[derive(Clone, Default)]
+        struct LogBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+        impl std::io::Write for LogBuffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = LogBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = logging::json_subscriber("info", move || writer.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let results_dir = tempfile::tempdir().unwrap();
+        let args = test_args(&[
+            "-s", "7",
+            "-e", "7",
+            "--log-format", "json",
+        ]);
+        assert_eq!(args.log_format, LogFormat::Json);
+        let state = ValidationState::new(1, 7, results_dir.path().to_path_buf());
+        let result = result(7, Some(true), Some(false));
+        let processed_blocks = tokio::sync::Mutex::new(Vec::new());
+        finish_block(result, &args, &state, &ProgressBar::hidden(), &processed_blocks).await;
+
+        let logs = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let failures: Vec<serde_json::Value> = logs
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .filter(|event: &serde_json::Value| event["level"] == "ERROR")
+            .collect();
+        // Only the failed stage is logged, with its fields outside of the message
+        assert_eq!(failures.len(), 1);
+        let failure = &failures[0];
+        assert_eq!(failure["block"], 7);
+        assert_eq!(failure["stage"], "derivation");
+        assert_eq!(failure["error_type"], "validation");
+        assert_eq!(failure["infrastructure"], false);
+        assert_eq!(failure["error"], "Transaction count mismatch");
+        assert!(!failure["message"].as_str().unwrap().contains('7'));
+    }
+
+    #[tokio::test]
+    async fn test_infrastructure_failures_do_not_trip_the_threshold() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let args = test_args(&[
+            "-s", "1",
+            "-e", "20",
+            "--failure-threshold", "10",
+        ]);
+        let state = ValidationState::new(20, 1, results_dir.path().to_path_buf());
+        let processed_blocks = tokio::sync::Mutex::new(Vec::new());
+        let progress = ProgressBar::hidden();
+
+        let error_types = [ErrorType::Network, ErrorType::RateLimit, ErrorType::NotFound];
+        for block in 1..=20 {
+            let mut result = result(block, None, Some(false));
+            let derivation = result.derivation.as_mut().unwrap();
+            derivation.error = Some("connection reset by peer".to_string());
+            derivation.error_type = Some(error_types[block as usize % 3]);
+            finish_block(result, &args, &state, &progress, &processed_blocks).await;
+        }
+
+        assert!(!state.is_aborted());
+        let stats = state.get_stats();
+        assert_eq!(stats.success_rate, 0.0);
+        assert_eq!(stats.validation_success_rate, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_run_checkpoints_completed_blocks_for_resume() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let results_path = results_dir.path().to_str().unwrap();
+        let args = test_args(&[
+            "-s", "1",
+            "-e", "10",
+        ]);
+        let state = ValidationState::new(10, 1, results_dir.path().to_path_buf());
+        let processed_blocks = tokio::sync::Mutex::new(Vec::new());
+        let progress = ProgressBar::hidden();
+
+        // Blocks 1-4 complete, Ctrl-C arrives while block 5 is in flight, which still finishes
+        for block in 1..=5 {
+            if block == 5 {
+                state.interrupt();
+            }
+            let result = result(block, Some(true), None);
+            finish_block(result, &args, &state, &progress, &processed_blocks).await;
+        }
+        assert!(state.is_stopping());
+        assert!(!state.is_aborted());
+
+        state.save_checkpoint(&processed_blocks.lock().await).unwrap();
+        write_run_report(&args, &state, 1, 10, None).await.unwrap();
+        let report: FinalReport = serde_json::from_str(
+            &fs::read_to_string(results_dir.path().join("final_report.json")).unwrap(),
+        )
+        .unwrap();
+        assert!(report.interrupted);
+        assert_eq!(report.completed, 5);
+
+        let resumed = test_args(&[
+            "-s", "1",
+            "-e", "10",
+            "--resume", results_path,
+        ]);
+        assert_eq!(select_blocks(&resumed, 1, 10).unwrap(), vec![6, 7, 8, 9, 10]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_overlapping_runs() {
+        let write_run = |results: Vec<ValidationResult>, start_block, end_block, seconds| {
+            let dir = tempfile::tempdir().unwrap();
+            let jsonl: Vec<_> = results.iter().map(|r| serde_json::to_string(r).unwrap()).collect();
+            fs::write(dir.path().join("results.jsonl"), jsonl.join("\n")).unwrap();
+            let report = FinalReport {
+                total_blocks: (end_block - start_block + 1) as usize,
+                completed: results.len(),
+                duration_seconds: seconds,
+                ..FinalReport::empty(start_block, end_block)
+            };
+            write_final_report(dir.path(), &report).unwrap();
+            dir
+        };
+
+        // Blocks 1-6 with block 3 failing, interrupted before blocks 7-8, then 5-10 where block 5
+        // fails after passing in the first
+        let run = |blocks: std::ops::RangeInclusive<u64>, failing| {
+            blocks.map(|b| result(b, Some(b != failing), None)).collect::<Vec<_>>()
+        };
+        let first = write_run(run(1..=6, 3), 1, 8, 20);
+        let second = write_run(run(5..=10, 5), 5, 10, 40);
+
+        let out = tempfile::tempdir().unwrap();
+        let dirs = [first.path().to_path_buf(), second.path().to_path_buf()];
+        let report = merge_runs(out.path(), &dirs).await.unwrap();
+        assert_eq!((report.start_block, report.end_block), (1, 10));
+        assert_eq!((report.total_blocks, report.completed, report.failed), (10, 10, 2));
+        assert_eq!(report.duration_seconds, 60);
+        assert_eq!(report.success_rate, 80.0);
+
+        let merged = fs::read_to_string(out.path().join("results.jsonl")).unwrap();
+        assert_eq!(merged.lines().count(), 10);
+        assert_eq!(report::failed_blocks(&merged), vec![3, 5]);
+        assert_eq!(Checkpoint::load(out.path()).unwrap().watermark, Some(10));
+    }
+
+    #[test]
+    fn test_retry_flags_override_error_type_defaults() {
+        let args = test_args(&[
+            "-s", "1",
+            "-e", "2",
+            "--retries-ratelimit", "20",
+            "--backoff-multiplier-ratelimit", "4",
+        ]);
+        let policy = args.backoff_policy();
+        assert_eq!(policy.retries.backoff_multiplier(ErrorType::RateLimit), 4.0);
+        assert_eq!(policy.retries.network.backoff_multiplier, 1.0);
+
+        // Count the attempts a block failing with one error type gets: the explicit rate limit
+        // retries override --max-retries, the other types keep their defaults capped by it
+        let attempts = |error_type| {
+            let budget = RetryBudget::default();
+            let mut retries = retry::StageRetries::new(args.backoff_strategy, &policy, &budget);
+            let mut attempts = 1;
+            while retries.next_backoff(error_type).is_ok() {
+                attempts += 1;
+            }
+            attempts
+        };
+        assert_eq!(attempts(ErrorType::RateLimit), 21);
+        assert_eq!(attempts(ErrorType::Network), 3);
+        assert_eq!(attempts(ErrorType::Validation), 2);
+
+        for multiplier in ["-1", "NaN", "inf"] {
+            assert!(Args::try_parse_from([
+                "validate-facet",
+                "-s", "1",
+                "-e", "2",
+                "--backoff-multiplier-network", multiplier,
+            ])
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_dry_run_describes_the_plan() {
+        let args = test_args(&[
+            "-s", "100",
+            "-e", "199",
+            "--random-sample", "10",
+            "--dry-run",
+        ]);
+        assert!(args.dry_run);
+
+        // Random samples are unordered, the plan reports their bounds
+        let blocks = select_blocks(&args, 100, 199).unwrap();
+        let (first, last) = (blocks.iter().min().unwrap(), blocks.iter().max().unwrap());
+        assert_eq!(
+            describe_plan(&blocks, Path::new("out")),
+            format!("10 blocks from {} to {}, output to out", first, last)
+        );
+        assert_eq!(
+            describe_plan(&[], Path::new("out")),
+            "no blocks to process, output to out"
+        );
+    }
+
+    #[test]
+    fn test_sample_stride_counts_from_range_start() {
+        let args = |start: &str| {
+            test_args(&[
+                "-s", start,
+                "-e", "1000",
+                "--sample-stride", "5",
+            ])
+        };
+        assert_eq!(select_blocks(&args("101"), 101, 112).unwrap(), vec![101, 106, 111]);
+        assert_eq!(select_blocks(&args("103"), 103, 112).unwrap(), vec![103, 108]);
+
+        // A start moved by a watermark keeps the positions of the requested range
+        assert_eq!(select_blocks(&args("101"), 104, 112).unwrap(), vec![106, 111]);
+
+        assert!(try_test_args(&[
+            "-s", "100",
+            "-e", "110",
+            "--sample-stride", "5",
+            "--random-sample", "3",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_resume_from_watermark_skips_to_watermark() {
+        let resume_dir = tempfile::tempdir().unwrap();
+        // 105 was not processed before the run stopped; 107 and 109 completed out of order
+        let processed = vec![100, 101, 102, 103, 104, 107, 109];
+        let checkpoint = Checkpoint {
+            watermark: checkpoint::contiguous_watermark(&processed, 100),
+            processed_blocks: processed,
+            timestamp: chrono::Utc::now(),
+        };
+        assert_eq!(checkpoint.watermark, Some(104));
+        fs::write(
+            resume_dir.path().join(checkpoint::CHECKPOINT_FILE),
+            serde_json::to_string(&checkpoint).unwrap(),
+        )
+        .unwrap();
+
+        let args = test_args(&[
+            "-s", "100",
+            "-e", "110",
+            "--resume-from-watermark", resume_dir.path().to_str().unwrap(),
+        ]);
+        let start_block = resume_start_block(&args, 100).unwrap();
+        assert_eq!(start_block, 105);
+
+        // Completions above the watermark are validated again
+        assert_eq!(select_blocks(&args, start_block, 110).unwrap(), (105..=110).collect::<Vec<_>>());
+
+        // A range starting above the watermark keeps its start block
+        assert_eq!(resume_start_block(&args, 200).unwrap(), 200);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::types::{TestResult, ValidationResult};
+use eyre::Result;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+pub const CSV_FILE: &str = "results.csv";
+pub const PROMETHEUS_FILE: &str = "metrics.prom";
+
+const CSV_HEADER: &str = "block,exec_success,deriv_success,duration_ms,error_type";
+
+/// Additional result formats written next to `results.jsonl`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// `results.jsonl`, which is always written
+    Jsonl,
+    /// `results.csv`, one row per block as results arrive
+    Csv,
+    /// `metrics.prom` for the node_exporter textfile collector, written at the end of the run
+    Prometheus,
+}
+
+/// CSV row of a result. Stages that did not run and the error type of a passing block are empty.
+pub fn csv_row(result: &ValidationResult) -> String {
+    let success = |stage: &Option<TestResult>| {
+        stage.as_ref().map_or_else(String::new, |r| r.success.to_string())
+    };
+    format!(
+        "{},{},{},{},{}",
+        result.block,
+        success(&result.execution),
+        success(&result.derivation),
+        result.duration_ms,
+        result.failure_type().map_or("", |t| t.as_str())
+    )
+}
+
+/// Append a result to a CSV file, writing the header first if the file is new or empty
+pub fn append_csv(path: &Path, result: &ValidationResult) -> Result<()> {
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{}", CSV_HEADER)?;
+    }
+    writeln!(file, "{}", csv_row(result))?;
+    Ok(())
+}
+
+/// Run totals exposed in the Prometheus text format
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrometheusMetrics {
+    pub blocks: u64,
+    /// Failed blocks by the error type of their first failed stage
+    pub failures: BTreeMap<&'static str, u64>,
+}
+
+impl PrometheusMetrics {
+    /// Compute the metrics from the contents of a `results.jsonl` file
+    pub fn from_results(results_jsonl: &str) -> Self {
+        let mut metrics = Self::default();
+        for line in results_jsonl.lines() {
+            if let Ok(result) = serde_json::from_str::<ValidationResult>(line) {
+                metrics.blocks += 1;
+                if !result.counts_as_failure() {
+                    continue;
+                }
+                if let Some(error_type) = result.failure_type() {
+                    *metrics.failures.entry(error_type.as_str()).or_default() += 1;
+                }
+            }
+        }
+        metrics
+    }
+
+    /// Ratio of blocks that passed, between 0 and 1
+    pub fn success_rate(&self) -> f64 {
+        if self.blocks == 0 {
+            return 0.0;
+        }
+        let failed: u64 = self.failures.values().sum();
+        (self.blocks - failed) as f64 / self.blocks as f64
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# HELP facet_validation_blocks_total Blocks validated.");
+        let _ = writeln!(out, "# TYPE facet_validation_blocks_total counter");
+        let _ = writeln!(out, "facet_validation_blocks_total {}", self.blocks);
+        let _ =
+            writeln!(out, "# HELP facet_validation_failures_total Failed blocks by error type.");
+        let _ = writeln!(out, "# TYPE facet_validation_failures_total counter");
+        for (error_type, count) in &self.failures {
+            let _ = writeln!(
+                out,
+                "facet_validation_failures_total{{type=\"{}\"}} {}",
+                error_type, count
+            );
+        }
+        let _ = writeln!(out, "# HELP facet_validation_success_rate Ratio of blocks that passed.");
+        let _ = writeln!(out, "# TYPE facet_validation_success_rate gauge");
+        let _ = writeln!(out, "facet_validation_success_rate {}", self.success_rate());
+        out
+    }
+
+    /// Write the textfile through a rename, so the collector never reads a partial file
+    pub fn write(&self, results_dir: &Path) -> Result<()> {
+        let tmp = results_dir.join(format!("{}.tmp", PROMETHEUS_FILE));
+        fs::write(&tmp, self.render())?;
+        fs::rename(tmp, results_dir.join(PROMETHEUS_FILE))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ErrorType;
+
+    fn result(
+        block: u64,
+        execution: Option<ErrorType>,
+        derivation: Option<bool>,
+    ) -> ValidationResult {
+        let stage = |success: bool, error_type: Option<ErrorType>| TestResult {
+            success,
+            error: (!success).then(|| "failed".to_string()),
+            error_type,
+            retries: 0,
+            duration_ms: None,
+        };
+        ValidationResult {
+            block,
+            execution: Some(stage(execution.is_none(), execution)),
+            derivation: derivation.map(|success| stage(success, None)),
+            refs: Default::default(),
+            duration_ms: 25,
+            timestamp: chrono::Utc::now(),
+            known_divergence: None,
+        }
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CSV_FILE);
+        append_csv(&path, &result(100, None, Some(true))).unwrap();
+        append_csv(&path, &result(101, Some(ErrorType::Network), None)).unwrap();
+        append_csv(&path, &result(102, None, Some(false))).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "block,exec_success,deriv_success,duration_ms,error_type\n\
+             100,true,true,25,\n\
+             101,false,,25,network\n\
+             102,true,false,25,unknown\n"
+        );
+    }
+
+    #[test]
+    fn test_prometheus_metrics() {
+        let jsonl = [
+            result(100, None, Some(true)),
+            result(101, Some(ErrorType::Network), Some(true)),
+            result(102, Some(ErrorType::Validation), None),
+            result(103, Some(ErrorType::Network), Some(false)),
+        ]
+        .iter()
+        .map(|r| serde_json::to_string(r).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let metrics = PrometheusMetrics::from_results(&jsonl);
+        assert_eq!(metrics.blocks, 4);
+        assert_eq!(metrics.success_rate(), 0.25);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("facet_validation_blocks_total 4\n"));
+        assert!(rendered.contains("facet_validation_failures_total{type=\"network\"} 2\n"));
+        assert!(rendered.contains("facet_validation_failures_total{type=\"validation\"} 1\n"));
+        assert!(rendered.contains("facet_validation_success_rate 0.25\n"));
+
+        let dir = tempfile::tempdir().unwrap();
+        metrics.write(dir.path()).unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join(PROMETHEUS_FILE)).unwrap(), rendered);
+    }
+}
@@ -0,0 +1,52 @@
+use futures::{Stream, StreamExt};
+use std::future::Future;
+
+/// Run `process` over `blocks` in ascending block order with at most `depth` blocks in flight.
+///
+/// Results are yielded in block order, so once the result for block N is observed every lower
+/// block has already been processed.
+pub fn ordered_stream<F, Fut>(
+    mut blocks: Vec<u64>,
+    depth: usize,
+    process: F,
+) -> impl Stream<Item = Fut::Output>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future,
+{
+    blocks.sort_unstable();
+    futures::stream::iter(blocks).map(process).buffered(depth.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_ordered_results_ascending() {
+        // Shuffled input, and later blocks finish first
+        let blocks = vec![7, 3, 9, 1, 5, 2, 8, 4, 6, 10];
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let recorded = ordered_stream(blocks, 3, |block| {
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20 - block)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                block
+            }
+        })
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(recorded, (1..=10).collect::<Vec<_>>());
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 3);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::batch::RpcBlock;
+use crate::cache::{BlockCache, CachedL2ChainProvider};
+use crate::ratelimit::{RateLimitLayer, RateLimiter};
+use alloy_provider::{Network, RootProvider};
+use alloy_rpc_client::{ClientBuilder, IpcConnect, WsConnect};
+use eyre::Result;
+use kona_genesis::RollupConfig;
+use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider, RpcEndpoint};
+use op_alloy_consensus::OpBlock;
+use op_alloy_network::Optimism;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+/// RPC clients built once per run and cloned into every block's task.
+///
+/// [`RootProvider`] clones share one transport, so blocks reuse the same connection pool instead
+/// of opening new connections. The chain providers built from them are cheap to create per block.
+#[derive(Debug, Clone)]
+pub struct Providers {
+    pub l1: RootProvider,
+    pub l2: RootProvider<Optimism>,
+    /// L2 blocks shared across blocks, if `--cache-size` was set
+    pub l2_blocks: Option<Arc<BlockCache>>,
+}
+
+impl Providers {
+    /// Connect to both endpoints over HTTP, WebSocket, or IPC, depending on their scheme.
+    ///
+    /// With `rps` set, each endpoint is sent at most that many requests per second, shared by all
+    /// clones of the providers.
+    pub async fn connect(l1_rpc: &str, l2_rpc: &str, rps: Option<NonZeroU32>) -> Result<Self> {
+        Ok(Self {
+            l1: connect_endpoint(l1_rpc, rps).await?,
+            l2: connect_endpoint(l2_rpc, rps).await?,
+            l2_blocks: None,
+        })
+    }
+
+    pub fn with_block_cache(mut self, cache: Arc<BlockCache>) -> Self {
+        self.l2_blocks = Some(cache);
+        self
+    }
+
+    /// Providers for the validation of a single block, which share L2 blocks between the stages
+    /// of that block even without a run-wide cache
+    pub fn for_block(&self) -> Self {
+        Self {
+            l2_blocks: Some(self.l2_blocks.clone().unwrap_or_else(BlockCache::per_block)),
+            ..self.clone()
+        }
+    }
+
+    /// Add a block fetched from the node outside the chain providers to the L2 block cache
+    pub fn cache_l2_block(&self, block: &RpcBlock) {
+        if let Some(cache) = &self.l2_blocks {
+            let block: OpBlock =
+                block.clone().into_consensus().map_transactions(|t| t.inner.inner.into_inner());
+            cache.insert(block.header.number, block);
+        }
+    }
+
+    pub fn l1_chain_provider(&self, cache_size: usize) -> AlloyChainProvider {
+        AlloyChainProvider::new(self.l1.clone(), cache_size)
+    }
+
+    /// L2 chain provider backed by the shared block cache, which replaces the provider's own cache
+    pub fn l2_chain_provider(&self) -> CachedL2ChainProvider {
+        let rollup_config = Arc::new(RollupConfig::facet());
+        let inner = AlloyL2ChainProvider::new(self.l2.clone(), rollup_config.clone(), 1);
+        let cache = self.l2_blocks.clone().unwrap_or_else(BlockCache::per_block);
+        CachedL2ChainProvider::new(inner, rollup_config, cache)
+    }
+}
+
+/// Connect to an endpoint, behind its own [`RateLimiter`] if `rps` is set
+async fn connect_endpoint<N: Network>(
+    rpc: &str,
+    rps: Option<NonZeroU32>,
+) -> Result<RootProvider<N>> {
+    let endpoint = rpc.parse::<RpcEndpoint>()?;
+    let Some(rps) = rps else {
+        return Ok(endpoint.connect().await?);
+    };
+    let limiter = Arc::new(RateLimiter::new(rps));
+    let builder = ClientBuilder::default().layer(RateLimitLayer::new(limiter));
+    let client = match endpoint {
+        RpcEndpoint::Http(url) => builder.http(url),
+        RpcEndpoint::Ws(url) => builder.ws(WsConnect::new(url.as_str())).await?,
+        RpcEndpoint::Ipc(path) => builder.ipc(IpcConnect::new(path)).await?,
+    };
+    Ok(RootProvider::new(client))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use kona_executor::{RpcTrieDBProvider, TrieDBProvider};
+    use kona_mpt::TrieProvider;
+    use kona_protocol::BatchValidationProvider;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{Context, Poll};
+    use std::time::{Duration, Instant};
+    use tower::{Layer, Service};
+
+    /// Layer counting the requests sent through a transport
+    #[derive(Clone)]
+    struct CountingLayer(Arc<AtomicUsize>);
+
+    impl<S> Layer<S> for CountingLayer {
+        type Service = CountingService<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            CountingService { inner, requests: self.0.clone() }
+        }
+    }
+
+    #[derive(Clone)]
+    struct CountingService<S> {
+        inner: S,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl<S: Service<Request>, Request> Service<Request> for CountingService<S> {
+        type Response = S::Response;
+        type Error = S::Error;
+        type Future = S::Future;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, request: Request) -> Self::Future {
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            self.inner.call(request)
+        }
+    }
+
+    fn rpc_block(number: u64) -> RpcBlock {
+        RpcBlock {
+            header: alloy_rpc_types_eth::Header::new(alloy_consensus::Header {
+                number,
+                ..Default::default()
+            }),
+            uncles: vec![],
+            transactions: alloy_rpc_types_eth::BlockTransactions::Full(vec![]),
+            withdrawals: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_block_reads_prefetched_blocks_without_requests() {
+        // Nothing listens on the endpoint, so every request that goes out fails
+        let url = "http://127.0.0.1:1";
+        let requests = Arc::new(AtomicUsize::new(0));
+        let l2 = ClientBuilder::default()
+            .layer(CountingLayer(requests.clone()))
+            .http(url.parse().unwrap());
+        let providers = Providers {
+            l1: RootProvider::new(ClientBuilder::default().http(url.parse().unwrap())),
+            l2: RootProvider::new(l2),
+            l2_blocks: None,
+        };
+
+        // The batch fetch of the parent and target is the block's only request. Deriving and the
+        // FCT period check then read both, the parent twice, from the block's cache.
+        let block_providers = providers.for_block();
+        block_providers.cache_l2_block(&rpc_block(99));
+        block_providers.cache_l2_block(&rpc_block(100));
+        let mut l2_chain_provider = block_providers.l2_chain_provider();
+        for number in [99, 100, 99] {
+            l2_chain_provider.block_by_number(number).await.unwrap();
+        }
+        assert_eq!(requests.load(Ordering::Relaxed), 0);
+
+        // Another block's providers send their requests through the same transport
+        let mut other = providers.for_block().l2_chain_provider();
+        assert!(other.block_by_number(99).await.is_err());
+        assert_eq!(requests.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_execution_requests_go_through_the_l2_limiter() {
+        // Nothing listens on the endpoint, so every request that goes out fails
+        let url = "http://127.0.0.1:1";
+        let requests = Arc::new(AtomicUsize::new(0));
+        let limiter = Arc::new(RateLimiter::new(NonZeroU32::new(10).unwrap()));
+        let l2 = ClientBuilder::default()
+            .layer(RateLimitLayer::new(limiter))
+            .layer(CountingLayer(requests.clone()))
+            .http(url.parse().unwrap());
+        let providers = Providers {
+            l1: RootProvider::new(ClientBuilder::default().http(url.parse().unwrap())),
+            l2: RootProvider::new(l2),
+            l2_blocks: None,
+        };
+
+        // Execution fetches the block, then its trie provider reads the parent state, all through
+        // the L2 client
+        let start = Instant::now();
+        assert!(crate::execution::execute_block(100, &providers).await.is_err());
+        let trie_provider = RpcTrieDBProvider::new(providers.l2.clone());
+        assert!(trie_provider.header_by_hash(B256::ZERO).is_err());
+        assert!(trie_provider.trie_node_by_hash(B256::ZERO).is_err());
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+
+        // At 10 requests per second, the second and third request each wait for their turn
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,161 @@
+use std::future::Future;
+use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+use tower::{Layer, Service};
+
+/// Token bucket limiting the requests per second sent to an RPC endpoint, shared by all workers.
+///
+/// The bucket holds a single token, refilled once every `1 / rps` seconds, so requests are spread
+/// evenly instead of bursting at the start of every second. A caller that finds the bucket empty
+/// waits for its turn instead of failing, and callers are served in the order they arrived.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    /// When the next token is available
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(rps: NonZeroU32) -> Self {
+        Self { interval: Duration::from_secs(1) / rps.get(), next: Mutex::new(Instant::now()) }
+    }
+
+    /// Wait until a request may be sent
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next = self.next.lock().unwrap();
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.interval;
+            slot
+        };
+        sleep_until(slot).await;
+    }
+}
+
+/// [`Layer`] making an RPC client acquire from a [`RateLimiter`] before every request it sends.
+///
+/// A JSON-RPC batch is sent as one request, and takes a single token.
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: Arc<RateLimiter>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService { inner, limiter: self.limiter.clone() }
+    }
+}
+
+/// Transport service installed by [`RateLimitLayer`]
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S, Request> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request> + Clone + Send + 'static,
+    S::Future: Send,
+    Request: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Only the instance that was polled ready may be called, so take it and leave the clone
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone();
+        Box::pin(async move {
+            limiter.acquire().await;
+            inner.call(request).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_aggregate_rate_stays_under_limit() {
+        let rps = 100;
+        let limiter = Arc::new(RateLimiter::new(NonZeroU32::new(rps).unwrap()));
+        let (workers, requests_per_worker) = (4, 25);
+
+        let start = Instant::now();
+        let tasks: Vec<_> = (0..workers)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    for _ in 0..requests_per_worker {
+                        limiter.acquire().await;
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        // The first request goes out immediately, every later one a full interval after the last
+        let requests = workers * requests_per_worker;
+        let min_elapsed = Duration::from_secs(1) / rps * (requests - 1);
+        assert_eq!(start.elapsed(), min_elapsed);
+    }
+
+    /// Service that, like a buffer, only accepts a call on the instance that was polled ready
+    struct ReadyOnce {
+        ready: bool,
+    }
+
+    impl Clone for ReadyOnce {
+        fn clone(&self) -> Self {
+            Self { ready: false }
+        }
+    }
+
+    impl Service<u32> for ReadyOnce {
+        type Response = u32;
+        type Error = ();
+        type Future = std::future::Ready<Result<u32, ()>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), ()>> {
+            self.ready = true;
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: u32) -> Self::Future {
+            assert!(std::mem::take(&mut self.ready), "called without poll_ready");
+            std::future::ready(Ok(request))
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_service_calls_the_ready_inner_service() {
+        let limiter = Arc::new(RateLimiter::new(NonZeroU32::new(10).unwrap()));
+        let mut service = RateLimitLayer::new(limiter).layer(ReadyOnce { ready: false });
+        for request in 0..3 {
+            std::future::poll_fn(|cx| service.poll_ready(cx)).await.unwrap();
+            assert_eq!(service.call(request).await, Ok(request));
+        }
+    }
+}
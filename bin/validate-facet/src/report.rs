@@ -0,0 +1,250 @@
+use crate::types::ValidationResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Distribution of per-block durations for a single measurement
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DurationStats {
+    pub count: usize,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+impl DurationStats {
+    /// Compute nearest-rank percentiles over the given samples, or `None` if there are none
+    pub fn from_samples(mut samples: Vec<u64>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+
+        Some(Self {
+            count: samples.len(),
+            p50_ms: percentile(&samples, 50.0),
+            p90_ms: percentile(&samples, 90.0),
+            p99_ms: percentile(&samples, 99.0),
+            max_ms: *samples.last().unwrap(),
+        })
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice
+fn percentile(sorted: &[u64], pct: f64) -> u64 {
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Per-block timing percentiles, split by stage where the stage duration was recorded
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimingReport {
+    pub total: Option<DurationStats>,
+    pub execution: Option<DurationStats>,
+    pub derivation: Option<DurationStats>,
+}
+
+impl TimingReport {
+    pub fn log_summary(&self) {
+        let stages = [
+            ("Total", &self.total),
+            ("Execution", &self.execution),
+            ("Derivation", &self.derivation),
+        ];
+
+        info!("");
+        info!("⏱️  Block Timing");
+        info!("==============");
+        for (name, stats) in stages {
+            if let Some(stats) = stats {
+                info!(
+                    "  {}: p50 {}ms | p90 {}ms | p99 {}ms | max {}ms ({} blocks)",
+                    name, stats.p50_ms, stats.p90_ms, stats.p99_ms, stats.max_ms, stats.count
+                );
+            }
+        }
+    }
+}
+
+/// Compute timing percentiles from the contents of a `results.jsonl` file
+pub fn compute_timing_report(results_jsonl: &str) -> TimingReport {
+    let mut total = Vec::new();
+    let mut execution = Vec::new();
+    let mut derivation = Vec::new();
+
+    for line in results_jsonl.lines() {
+        if let Ok(result) = serde_json::from_str::<ValidationResult>(line) {
+            total.push(result.duration_ms);
+            if let Some(ms) = result.execution.as_ref().and_then(|r| r.duration_ms) {
+                execution.push(ms);
+            }
+            if let Some(ms) = result.derivation.as_ref().and_then(|r| r.duration_ms) {
+                derivation.push(ms);
+            }
+        }
+    }
+
+    TimingReport {
+        total: DurationStats::from_samples(total),
+        execution: DurationStats::from_samples(execution),
+        derivation: DurationStats::from_samples(derivation),
+    }
+}
+
+/// Blocks whose latest result in a `results.jsonl` file failed, in ascending order.
+///
+/// Only the last result of a block counts, so a block that passed on a later attempt in the same
+/// file is no longer reported.
+pub fn failed_blocks(results_jsonl: &str) -> Vec<u64> {
+    let mut latest = BTreeMap::new();
+    for line in results_jsonl.lines() {
+        if let Ok(result) = serde_json::from_str::<ValidationResult>(line) {
+            latest.insert(result.block, result.failed());
+        }
+    }
+    latest.into_iter().filter_map(|(block, failed)| failed.then_some(block)).collect()
+}
+
+/// Results of several runs merged into a single set
+#[derive(Debug, Clone, Default)]
+pub struct MergedResults {
+    /// One result per block, in ascending block order
+    pub results: Vec<ValidationResult>,
+    /// Blocks whose outcome differs between the merged runs, in ascending order
+    pub conflicts: Vec<u64>,
+}
+
+/// Merge the contents of several `results.jsonl` files, de-duplicating results by block.
+///
+/// The last writer wins: a block's result is taken from the last file that contains it, and from
+/// the last line within that file, the same as for [`failed_blocks`]. A block that passed in one
+/// run and failed in another is reported as a conflict.
+pub fn merge_results(runs: &[String]) -> MergedResults {
+    let mut latest: BTreeMap<u64, ValidationResult> = BTreeMap::new();
+    let mut conflicts = Vec::new();
+    for run in runs {
+        let mut results: BTreeMap<u64, ValidationResult> = BTreeMap::new();
+        for line in run.lines() {
+            if let Ok(result) = serde_json::from_str::<ValidationResult>(line) {
+                results.insert(result.block, result);
+            }
+        }
+        for (block, result) in results {
+            let failed = result.failed();
+            if let Some(previous) = latest.insert(block, result) {
+                if previous.failed() != failed {
+                    conflicts.push(block);
+                }
+            }
+        }
+    }
+    conflicts.sort_unstable();
+    conflicts.dedup();
+    MergedResults { results: latest.into_values().collect(), conflicts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TestResult;
+
+    fn result(block: u64, duration_ms: u64, derivation_ms: Option<u64>) -> ValidationResult {
+        ValidationResult {
+            block,
+            execution: None,
+            derivation: derivation_ms.map(|ms| TestResult {
+                success: true,
+                error: None,
+                error_type: None,
+                retries: 0,
+                duration_ms: Some(ms),
+            }),
+            refs: Default::default(),
+            duration_ms,
+            timestamp: chrono::Utc::now(),
+            known_divergence: None,
+        }
+    }
+
+    #[test]
+    fn test_timing_percentiles() {
+        // Durations 1..=100ms, derivation stage recorded on every other block
+        let jsonl = (1..=100u64)
+            .map(|i| {
+                let deriv = (i % 2 == 0).then_some(i * 10);
+                serde_json::to_string(&result(i, i, deriv)).unwrap()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let report = compute_timing_report(&jsonl);
+
+        let total = report.total.unwrap();
+        assert_eq!(total.count, 100);
+        assert_eq!(total.p50_ms, 50);
+        assert_eq!(total.p90_ms, 90);
+        assert_eq!(total.p99_ms, 99);
+        assert_eq!(total.max_ms, 100);
+
+        let derivation = report.derivation.unwrap();
+        assert_eq!(derivation.count, 50);
+        assert_eq!(derivation.p50_ms, 500);
+        assert_eq!(derivation.p90_ms, 900);
+        assert_eq!(derivation.p99_ms, 1000);
+        assert_eq!(derivation.max_ms, 1000);
+
+        assert!(report.execution.is_none());
+    }
+
+    #[test]
+    fn test_failed_blocks_uses_latest_result() {
+        let failed = |block| {
+            let mut result = result(block, 10, Some(10));
+            result.derivation.as_mut().unwrap().success = false;
+            result
+        };
+        let jsonl = [
+            failed(102),
+            result(100, 10, Some(10)),
+            failed(101),
+            result(103, 10, None),
+            result(101, 10, Some(10)),
+        ]
+        .iter()
+        .map(|r| serde_json::to_string(r).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        // 101 failed first but passed when rerun later in the same file
+        assert_eq!(failed_blocks(&jsonl), vec![102]);
+        assert!(failed_blocks("").is_empty());
+    }
+
+    #[test]
+    fn test_merge_results_last_writer_wins() {
+        let failed = |block| {
+            let mut result = result(block, 10, Some(10));
+            result.derivation.as_mut().unwrap().success = false;
+            result
+        };
+        let jsonl = |results: &[ValidationResult]| {
+            results.iter().map(|r| serde_json::to_string(r).unwrap()).collect::<Vec<_>>().join("\n")
+        };
+        let first = jsonl(&[result(1, 10, None), failed(2), result(3, 10, None)]);
+        let second = jsonl(&[result(2, 20, None), result(3, 20, None), result(4, 20, None)]);
+
+        let merged = merge_results(&[first, second]);
+        let blocks: Vec<_> = merged.results.iter().map(|r| (r.block, r.duration_ms)).collect();
+        assert_eq!(blocks, vec![(1, 10), (2, 20), (3, 20), (4, 20)]);
+        // Block 3 passed in both runs, only block 2 changed outcome
+        assert_eq!(merged.conflicts, vec![2]);
+    }
+
+    #[test]
+    fn test_timing_single_sample() {
+        let stats = DurationStats::from_samples(vec![7]).unwrap();
+        assert_eq!((stats.p50_ms, stats.p90_ms, stats.p99_ms, stats.max_ms), (7, 7, 7, 7));
+        assert!(DurationStats::from_samples(vec![]).is_none());
+    }
+}
@@ -1,9 +1,15 @@
-use crate::types::ErrorType;
+use crate::types::{is_not_found_message, ErrorType, ValidationError};
 use rand::Rng;
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// Classify an error based on its message
+/// Classify an error, by variant for a [`ValidationError`] and otherwise based on its message
 pub fn classify_error(error: &eyre::Error) -> ErrorType {
+    if let Some(error) = error.downcast_ref::<ValidationError>() {
+        return error.error_type();
+    }
+    
     let error_str = error.to_string().to_lowercase();
     
     // Network-related errors
@@ -28,28 +34,10 @@ pub fn classify_error(error: &eyre::Error) -> ErrorType {
     }
     
     // Block/data not found
-    if error_str.contains("not found") ||
-       error_str.contains("does not exist") ||
-       error_str.contains("unknown block") ||
-       error_str.contains("missing") && error_str.contains("block") ||
-       error_str.contains("failed to fetch block") {
+    if is_not_found_message(&error_str) {
         return ErrorType::NotFound;
     }
     
-    // Execution fixture errors - classify based on the specific error
-    if error_str.contains("execution-fixture failed") {
-        if error_str.contains("network error") || 
-           error_str.contains("500 internal server error") ||
-           error_str.contains("httperror") {
-            return ErrorType::Network;
-        }
-        if error_str.contains("missing preimage") {
-            return ErrorType::NotFound;  // Missing data, might be retryable
-        }
-        // Other execution failures are likely validation errors
-        return ErrorType::Validation;
-    }
-    
     // System errors
     if error_str.contains("out of memory") ||
        error_str.contains("disk full") ||
@@ -70,44 +58,311 @@ pub fn classify_error(error: &eyre::Error) -> ErrorType {
     ErrorType::Unknown
 }
 
+/// How often and how patiently a failure of one [`ErrorType`] is retried
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorRetryPolicy {
+    /// Retries of a block failing with this error type
+    pub max_retries: u32,
+    /// Scale applied to the backoff delay before it is capped
+    pub backoff_multiplier: f64,
+}
+
+impl ErrorRetryPolicy {
+    pub const fn new(max_retries: u32, backoff_multiplier: f64) -> Self {
+        Self { max_retries, backoff_multiplier }
+    }
+}
+
+/// [`ErrorRetryPolicy`] of every [`ErrorType`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryConfig {
+    pub network: ErrorRetryPolicy,
+    pub rate_limit: ErrorRetryPolicy,
+    pub not_found: ErrorRetryPolicy,
+    pub validation: ErrorRetryPolicy,
+    pub system: ErrorRetryPolicy,
+    pub unknown: ErrorRetryPolicy,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            // Network errors get more retries
+            network: ErrorRetryPolicy::new(10, 1.0),
+            // Rate limits need backing off aggressively
+            rate_limit: ErrorRetryPolicy::new(10, 2.0),
+            // The block might appear soon
+            not_found: ErrorRetryPolicy::new(5, 1.5),
+            // Validation errors are unlikely to change
+            validation: ErrorRetryPolicy::new(1, 1.0),
+            // System errors rarely resolve
+            system: ErrorRetryPolicy::new(1, 1.0),
+            unknown: ErrorRetryPolicy::new(1, 1.0),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Cap the retries of every error type at `max_retries`
+    pub fn capped(mut self, max_retries: u32) -> Self {
+        for policy in [
+            &mut self.network,
+            &mut self.rate_limit,
+            &mut self.not_found,
+            &mut self.validation,
+            &mut self.system,
+            &mut self.unknown,
+        ] {
+            policy.max_retries = policy.max_retries.min(max_retries);
+        }
+        self
+    }
+
+    pub fn get(&self, error_type: ErrorType) -> ErrorRetryPolicy {
+        match error_type {
+            ErrorType::Network => self.network,
+            ErrorType::RateLimit => self.rate_limit,
+            ErrorType::NotFound => self.not_found,
+            ErrorType::Validation => self.validation,
+            ErrorType::System => self.system,
+            ErrorType::Unknown => self.unknown,
+        }
+    }
+
+    pub fn max_retries(&self, error_type: ErrorType) -> u32 {
+        self.get(error_type).max_retries
+    }
+
+    pub fn backoff_multiplier(&self, error_type: ErrorType) -> f64 {
+        self.get(error_type).backoff_multiplier
+    }
+}
+
+/// Scale, cap, and jitter applied to the delays of a [`BackoffStrategy`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackoffPolicy {
+    /// Base delay shared by all backoff strategies
+    pub base: Duration,
+    /// Upper bound on any single backoff delay
+    pub max: Duration,
+    /// Delays are scaled by a random factor in `[1 - jitter_frac, 1 + jitter_frac]`
+    pub jitter_frac: f64,
+    /// Retries and backoff multiplier per error type
+    pub retries: RetryConfig,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            base: Duration::from_millis(1000),
+            max: Duration::from_secs(60),
+            jitter_frac: 0.25,
+            retries: RetryConfig::default(),
+        }
+    }
+}
+
+impl BackoffPolicy {
+    fn base_ms(&self) -> u64 {
+        self.base.as_millis() as u64
+    }
+
+    fn max_ms(&self) -> u64 {
+        self.max.as_millis() as u64
+    }
+}
+
+/// How the delay between retries grows with the retry count
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BackoffStrategy {
+    /// `base * 2^retry`
+    #[default]
+    Exponential,
+    /// `base * (retry + 1)`
+    Linear,
+    /// Always `base`
+    Constant,
+    /// AWS-style decorrelated jitter: each delay is drawn from `[base, previous * 3]`
+    DecorrelatedJitter,
+}
+
+impl BackoffStrategy {
+    /// Delay before the retry following `retry_count` failed attempts, capped at `policy.max`
+    pub fn next_delay(&self, retry_count: u32, policy: &BackoffPolicy) -> Duration {
+        self.next_delay_with_rng(retry_count, policy, &mut rand::thread_rng())
+    }
+
+    fn next_delay_with_rng<R: Rng>(
+        &self,
+        retry_count: u32,
+        policy: &BackoffPolicy,
+        rng: &mut R,
+    ) -> Duration {
+        let (base, max) = (policy.base_ms(), policy.max_ms());
+        let delay_ms = match self {
+            Self::Exponential => base.saturating_mul(2u64.saturating_pow(retry_count)),
+            Self::Linear => base.saturating_mul(retry_count as u64 + 1),
+            Self::Constant => base,
+            Self::DecorrelatedJitter => {
+                // The strategy is defined over the previous delay, so replay the chain of draws
+                let mut delay = base;
+                for _ in 0..=retry_count {
+                    delay = rng.gen_range(base..=delay.saturating_mul(3)).min(max);
+                }
+                delay
+            }
+        };
+        Duration::from_millis(delay_ms.min(max))
+    }
+
+    /// Whether the strategy already randomizes its delays
+    fn is_jittered(&self) -> bool {
+        matches!(self, Self::DecorrelatedJitter)
+    }
+}
+
 /// Calculate backoff duration with jitter
-pub fn calculate_backoff(retry_count: u32, error_type: ErrorType) -> Duration {
-    let base_delay_ms = 1000u64;
-    let max_delay_ms = 60_000u64; // Cap at 60 seconds
-    
-    // Exponential backoff: 2^retry * base_delay
-    let exponential_delay = base_delay_ms.saturating_mul(2u64.saturating_pow(retry_count));
+pub fn calculate_backoff(
+    retry_count: u32,
+    error_type: ErrorType,
+    strategy: BackoffStrategy,
+    policy: &BackoffPolicy,
+) -> Duration {
+    let delay = strategy.next_delay(retry_count, policy).as_millis() as u64;
     
     // Apply error-specific multiplier
-    let multiplier = error_type.backoff_multiplier();
-    let delay_with_multiplier = (exponential_delay as f64 * multiplier) as u64;
+    let multiplier = policy.retries.backoff_multiplier(error_type);
+    let delay_with_multiplier = (delay as f64 * multiplier) as u64;
     
     // Cap the delay
-    let capped_delay = delay_with_multiplier.min(max_delay_ms);
+    let capped_delay = delay_with_multiplier.min(policy.max_ms());
+    if strategy.is_jittered() || policy.jitter_frac <= 0.0 {
+        return Duration::from_millis(capped_delay);
+    }
     
-    // Add jitter (±25%)
+    // Add jitter (±jitter_frac)
     let mut rng = rand::thread_rng();
-    let jitter_factor = 0.75 + (rng.gen::<f64>() * 0.5); // 0.75 to 1.25
+    let jitter_factor = 1.0 - policy.jitter_frac + rng.gen::<f64>() * 2.0 * policy.jitter_frac;
     let final_delay = (capped_delay as f64 * jitter_factor) as u64;
     
     Duration::from_millis(final_delay)
 }
 
+/// Why a failed validation stage is not retried again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryStop {
+    /// The error type is never retried
+    NotRetryable,
+    /// The retries of the error type are used up
+    Exhausted { max_retries: u32 },
+    /// The run-wide `--max-total-retries` budget is used up
+    BudgetExhausted,
+}
+
+/// Retry bookkeeping of one validation stage of a block, shared by execution and derivation
+#[derive(Debug)]
+pub struct StageRetries<'a> {
+    strategy: BackoffStrategy,
+    policy: &'a BackoffPolicy,
+    budget: &'a RetryBudget,
+    retries: u32,
+}
+
+impl<'a> StageRetries<'a> {
+    pub fn new(
+        strategy: BackoffStrategy,
+        policy: &'a BackoffPolicy,
+        budget: &'a RetryBudget,
+    ) -> Self {
+        Self { strategy, policy, budget, retries: 0 }
+    }
+
+    /// Retries made so far
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Decide whether to retry after a failure of `error_type`, returning the delay before the
+    /// retry. The limit is that of the error type of the latest failure.
+    pub fn next_backoff(&mut self, error_type: ErrorType) -> Result<Duration, RetryStop> {
+        if !error_type.should_retry() {
+            return Err(RetryStop::NotRetryable);
+        }
+        let max_retries = self.policy.retries.max_retries(error_type);
+        if self.retries >= max_retries {
+            return Err(RetryStop::Exhausted { max_retries });
+        }
+        if !self.budget.try_take() {
+            return Err(RetryStop::BudgetExhausted);
+        }
+        self.retries += 1;
+        Ok(calculate_backoff(self.retries - 1, error_type, self.strategy, self.policy))
+    }
+}
+
+/// Retries shared by all blocks of a run, so a wide outage cannot multiply the per-block retries
+/// into an unbounded number of requests
+#[derive(Debug, Clone, Default)]
+pub struct RetryBudget {
+    /// Retries left, or `None` if unlimited
+    remaining: Option<Arc<AtomicUsize>>,
+}
+
+impl RetryBudget {
+    pub fn new(max_total_retries: Option<usize>) -> Self {
+        Self { remaining: max_total_retries.map(|n| Arc::new(AtomicUsize::new(n))) }
+    }
+
+    /// Take one retry from the budget, returning `false` once it is exhausted
+    pub fn try_take(&self) -> bool {
+        match &self.remaining {
+            Some(remaining) => remaining
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .is_ok(),
+            None => true,
+        }
+    }
+}
+
+/// Source of the current time, injectable so that time-dependent logic can be tested without
+/// sleeping
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// [`Clock`] backed by the system's monotonic clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
 /// Circuit breaker state
-pub struct CircuitBreaker {
+pub struct CircuitBreaker<C: Clock = SystemClock> {
     consecutive_failures: u32,
-    last_failure_time: Option<std::time::Instant>,
+    last_failure_time: Option<Instant>,
     threshold: u32,
     reset_duration: Duration,
+    clock: C,
 }
 
 impl CircuitBreaker {
     pub fn new(threshold: u32, reset_duration: Duration) -> Self {
+        Self::with_clock(threshold, reset_duration, SystemClock)
+    }
+}
+
+impl<C: Clock> CircuitBreaker<C> {
+    pub fn with_clock(threshold: u32, reset_duration: Duration, clock: C) -> Self {
         Self {
             consecutive_failures: 0,
             last_failure_time: None,
             threshold,
             reset_duration,
+            clock,
         }
     }
     
@@ -118,14 +373,18 @@ impl CircuitBreaker {
     
     pub fn record_failure(&mut self) {
         self.consecutive_failures += 1;
-        self.last_failure_time = Some(std::time::Instant::now());
+        self.last_failure_time = Some(self.clock.now());
     }
     
+    /// Whether calls should be skipped.
+    ///
+    /// Once `reset_duration` has passed since the last failure the breaker is half-open: calls are
+    /// let through again, and a single further failure re-opens it.
     pub fn is_open(&self) -> bool {
         if self.consecutive_failures >= self.threshold {
             if let Some(last_failure) = self.last_failure_time {
                 // Check if we should reset
-                if last_failure.elapsed() > self.reset_duration {
+                if self.clock.now().saturating_duration_since(last_failure) > self.reset_duration {
                     return false;
                 }
             }
@@ -139,4 +398,251 @@ impl CircuitBreaker {
         self.consecutive_failures = 0;
         self.last_failure_time = None;
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{ErrorPayload, RpcError};
+    use alloy_transport::TransportErrorKind;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::sync::{Arc, Mutex};
+
+    /// [`Clock`] that only moves when advanced; clones share the same time
+    #[derive(Debug, Clone)]
+    struct MockClock {
+        now: Arc<Mutex<Instant>>,
+    }
+
+    impl MockClock {
+        fn new() -> Self {
+            Self { now: Arc::new(Mutex::new(Instant::now())) }
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.now.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            *self.now.lock().unwrap()
+        }
+    }
+
+    fn delays_ms(strategy: BackoffStrategy, retries: u32) -> Vec<u64> {
+        let policy = BackoffPolicy::default();
+        (0..retries).map(|r| strategy.next_delay(r, &policy).as_millis() as u64).collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_retry_budget_caps_retries_across_tasks() {
+        let budget = RetryBudget::new(Some(25));
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let budget = budget.clone();
+                // Each task would retry 10 times on its own
+                tokio::spawn(async move { (0..10).filter(|_| budget.try_take()).count() })
+            })
+            .collect();
+
+        let mut taken = 0;
+        for task in tasks {
+            taken += task.await.unwrap();
+        }
+        assert_eq!(taken, 25);
+        assert!(!budget.try_take());
+
+        let unlimited = RetryBudget::default();
+        assert!((0..1_000).all(|_| unlimited.try_take()));
+    }
+
+    #[test]
+    fn test_exponential_sequence() {
+        assert_eq!(
+            delays_ms(BackoffStrategy::Exponential, 8),
+            vec![1000, 2000, 4000, 8000, 16000, 32000, 60000, 60000]
+        );
+        assert_eq!(BackoffStrategy::default(), BackoffStrategy::Exponential);
+    }
+
+    #[test]
+    fn test_linear_sequence() {
+        assert_eq!(delays_ms(BackoffStrategy::Linear, 5), vec![1000, 2000, 3000, 4000, 5000]);
+        assert_eq!(
+            BackoffStrategy::Linear.next_delay(100, &BackoffPolicy::default()).as_millis(),
+            60000
+        );
+    }
+
+    #[test]
+    fn test_constant_sequence() {
+        assert_eq!(delays_ms(BackoffStrategy::Constant, 5), vec![1000; 5]);
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_bounds() {
+        let policy = BackoffPolicy::default();
+        let (base, max) = (policy.base_ms(), policy.max_ms());
+        let mut rng = StdRng::seed_from_u64(42);
+        for retry in 0..10u32 {
+            for _ in 0..100 {
+                let delay = BackoffStrategy::DecorrelatedJitter
+                    .next_delay_with_rng(retry, &policy, &mut rng)
+                    .as_millis() as u64;
+                let upper = base.saturating_mul(3u64.pow(retry + 1)).min(max);
+                assert!((base..=upper).contains(&delay), "retry {retry}: {delay}ms");
+            }
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_is_seeded() {
+        let draw = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..6)
+                .map(|r| {
+                    BackoffStrategy::DecorrelatedJitter.next_delay_with_rng(
+                        r,
+                        &BackoffPolicy::default(),
+                        &mut rng,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(draw(7), draw(7));
+    }
+
+    #[test]
+    fn test_zero_jitter_policy_is_exact() {
+        let policy = BackoffPolicy {
+            base: Duration::from_millis(100),
+            max: Duration::from_millis(1500),
+            jitter_frac: 0.0,
+            ..Default::default()
+        };
+        let delays: Vec<u64> = (0..6)
+            .map(|r| {
+                calculate_backoff(r, ErrorType::Network, BackoffStrategy::Exponential, &policy)
+                    .as_millis() as u64
+            })
+            .collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 1500, 1500]);
+    }
+
+    #[test]
+    fn test_custom_retry_config_is_respected() {
+        let mut policy = BackoffPolicy {
+            base: Duration::from_millis(100),
+            max: Duration::from_secs(60),
+            jitter_frac: 0.0,
+            ..Default::default()
+        };
+        let delay = |policy: &BackoffPolicy| {
+            calculate_backoff(0, ErrorType::RateLimit, BackoffStrategy::Constant, policy)
+        };
+        assert_eq!(policy.retries.max_retries(ErrorType::RateLimit), 10);
+        assert_eq!(delay(&policy), Duration::from_millis(200));
+
+        policy.retries.rate_limit = ErrorRetryPolicy::new(20, 4.0);
+        assert_eq!(policy.retries.max_retries(ErrorType::RateLimit), 20);
+        assert_eq!(delay(&policy), Duration::from_millis(400));
+
+        // Other error types keep their defaults
+        assert_eq!(policy.retries.network, RetryConfig::default().network);
+    }
+
+    #[test]
+    fn test_circuit_breaker_open_reset_closed() {
+        let clock = MockClock::new();
+        let mut breaker = CircuitBreaker::with_clock(3, Duration::from_secs(60), clock.clone());
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(!breaker.is_open());
+        breaker.record_failure();
+        assert!(breaker.is_open());
+
+        // Stays open for the full reset duration
+        clock.advance(Duration::from_secs(60));
+        assert!(breaker.is_open());
+
+        // Half-open: a trial call is let through, and a success closes the breaker
+        clock.advance(Duration::from_secs(1));
+        assert!(!breaker.is_open());
+        breaker.record_success();
+        breaker.record_failure();
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn test_circuit_breaker_half_open_failure_reopens() {
+        let clock = MockClock::new();
+        let mut breaker = CircuitBreaker::with_clock(2, Duration::from_secs(30), clock.clone());
+        breaker.record_failure();
+        breaker.record_failure();
+
+        clock.advance(Duration::from_secs(31));
+        assert!(!breaker.is_open());
+
+        // The trial call fails, restarting the reset timer
+        breaker.record_failure();
+        assert!(breaker.is_open());
+        clock.advance(Duration::from_secs(30));
+        assert!(breaker.is_open());
+        clock.advance(Duration::from_secs(1));
+        assert!(!breaker.is_open());
+
+        breaker.reset();
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn test_classify_typed_errors() {
+        let classify = |e: ValidationError| classify_error(&eyre::Report::new(e));
+
+        // RPC errors keep the classification of their kind
+        let server_error = |message: &'static str| {
+            RpcError::ErrorResp(ErrorPayload { code: -32000, message: message.into(), data: None })
+        };
+        let timed_out = TransportErrorKind::custom_str("request timed out");
+        assert_eq!(classify(ValidationError::rpc(timed_out)), ErrorType::Network);
+        for message in ["header not found", "unknown block", "block 7 does not exist"] {
+            assert_eq!(classify(ValidationError::rpc(server_error(message))), ErrorType::NotFound, "{message}");
+        }
+        // Errors that would be misclassified by the string heuristics
+        let too_many_requests = TransportErrorKind::http_error(429, String::new());
+        assert_eq!(classify(ValidationError::rpc(too_many_requests)), ErrorType::RateLimit);
+        assert_eq!(classify(ValidationError::Invalid("unexpected sequence number".into())), ErrorType::Validation);
+        assert_eq!(classify(ValidationError::NotFound("Block 7 not found".into())), ErrorType::NotFound);
+
+        let mismatch = ValidationError::Mismatch { block: 7, index: 2, geth_len: 120, kona_len: 118 };
+        assert_eq!(mismatch.to_string(), "Transaction 2 differs at block 7: Geth 120 bytes vs Kona 118 bytes");
+        assert_eq!(classify(mismatch), ErrorType::Validation);
+        let count = ValidationError::TxCountMismatch { geth: 3, kona: 2 };
+        assert_eq!(count.to_string(), "Transaction count mismatch: Geth 3 vs Kona 2");
+        assert_eq!(classify(count), ErrorType::Validation);
+
+        // Context added on top of a typed error does not hide it
+        let refused = TransportErrorKind::custom_str("connection refused");
+        let wrapped = eyre::Report::new(ValidationError::rpc(refused)).wrap_err("block 7");
+        assert_eq!(classify_error(&wrapped), ErrorType::Network);
+    }
+
+    #[test]
+    fn test_classify_foreign_errors_by_message() {
+        let cases = [
+            ("error sending request for url", ErrorType::Network),
+            ("operation timeout", ErrorType::Network),
+            ("429 Too Many Requests", ErrorType::RateLimit),
+            ("header not found", ErrorType::NotFound),
+            ("missing block 12", ErrorType::NotFound),
+            ("receipts root hash mismatch", ErrorType::Validation),
+            ("out of memory", ErrorType::System),
+            ("Validation test failed", ErrorType::Unknown),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(classify_error(&eyre::eyre!("{}", message)), expected, "{message}");
+        }
+    }
+}
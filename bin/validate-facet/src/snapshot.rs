@@ -0,0 +1,171 @@
+use crate::derivation::derive_attributes;
+use crate::execution::{build_block, fetch_header};
+use crate::providers::Providers;
+use crate::retry::classify_error;
+use crate::types::{TestResult, ValidationError};
+use alloy_primitives::B256;
+use eyre::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Trusted baseline of `block -> hash` to check derived blocks against
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedHashes {
+    hashes: BTreeMap<u64, B256>,
+}
+
+impl TrustedHashes {
+    /// Load a baseline from a JSON object of block to hash, or from `block,hash` CSV lines
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&content)
+            .map_err(|e| eyre::eyre!("Invalid baseline {}: {}", path.display(), e))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        if content.trim_start().starts_with('{') {
+            let raw: BTreeMap<String, B256> = serde_json::from_str(content)?;
+            let hashes = raw
+                .into_iter()
+                .map(|(block, hash)| Ok((block.trim().parse::<u64>()?, hash)))
+                .collect::<Result<_>>()?;
+            return Ok(Self { hashes });
+        }
+
+        let mut hashes = BTreeMap::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (block, hash) = line
+                .split_once(',')
+                .ok_or_else(|| eyre::eyre!("line {}: expected `block,hash`", i + 1))?;
+            // Allow a `block,hash` header row
+            let Ok(block) = block.trim().parse::<u64>() else {
+                if i == 0 {
+                    continue;
+                }
+                return Err(eyre::eyre!("line {}: invalid block number {:?}", i + 1, block));
+            };
+            let hash = hash
+                .trim()
+                .parse()
+                .map_err(|e| eyre::eyre!("line {}: invalid hash: {}", i + 1, e))?;
+            hashes.insert(block, hash);
+        }
+        Ok(Self { hashes })
+    }
+
+    pub fn contains(&self, block: u64) -> bool {
+        self.hashes.contains_key(&block)
+    }
+
+    /// Check a derived block hash against the trusted value
+    pub fn check(&self, block: u64, derived: B256) -> Result<()> {
+        let expected = self.hashes.get(&block).ok_or_else(|| {
+            ValidationError::NotFound(format!("Block {} is not in the trusted baseline", block))
+        })?;
+        if *expected != derived {
+            return Err(ValidationError::Invalid(format!(
+                "Trusted hash validation failed at block {}: expected {} vs derived {}",
+                block, expected, derived
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Derive the attributes of `block` and build it on top of the node's parent block, returning the
+/// hash of the built block
+pub async fn derived_block_hash(block: u64, providers: &Providers) -> Result<B256> {
+    let (attributes, _) = derive_attributes(block, providers, None).await?;
+    let parent_header = fetch_header(&providers.l2, block.saturating_sub(1)).await?;
+    Ok(build_block(block, parent_header, attributes, providers)?.hash_slow())
+}
+
+/// Derive and build `block` and compare only its hash against the trusted baseline
+pub async fn validate_trusted_hash(
+    block: u64,
+    baseline: &TrustedHashes,
+    providers: &Providers,
+) -> TestResult {
+    let result = match derived_block_hash(block, providers).await {
+        Ok(derived) => baseline.check(block, derived),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(()) => TestResult {
+            success: true,
+            error: None,
+            error_type: None,
+            retries: 0,
+            duration_ms: None,
+        },
+        Err(e) => TestResult {
+            success: false,
+            error: Some(e.to_string()),
+            error_type: Some(classify_error(&e)),
+            retries: 0,
+            duration_ms: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ErrorType;
+
+    const BASELINE_CSV: &str = "block,hash
+100,0x1111111111111111111111111111111111111111111111111111111111111111
+101,0x2222222222222222222222222222222222222222222222222222222222222222
+102,0x3333333333333333333333333333333333333333333333333333333333333333
+";
+
+    #[test]
+    fn test_baseline_with_one_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.csv");
+        fs::write(&path, BASELINE_CSV).unwrap();
+        let baseline = TrustedHashes::load(&path).unwrap();
+        assert!((100..=102).all(|block| baseline.contains(block)));
+
+        // Block 101 derives to a different hash than the trusted one
+        let derived = [
+            (100, B256::repeat_byte(0x11)),
+            (101, B256::repeat_byte(0xee)),
+            (102, B256::repeat_byte(0x33)),
+        ];
+        let failures: Vec<u64> = derived
+            .iter()
+            .filter_map(|(block, hash)| {
+                let err = baseline.check(*block, *hash).err()?;
+                assert_eq!(classify_error(&err), ErrorType::Validation);
+                Some(*block)
+            })
+            .collect();
+        assert_eq!(failures, vec![101]);
+
+        let err = baseline.check(103, B256::ZERO).unwrap_err();
+        assert_eq!(classify_error(&err), ErrorType::NotFound);
+    }
+
+    #[test]
+    fn test_baseline_json_matches_csv() {
+        let json = r#"{
+            "100": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "101": "0x2222222222222222222222222222222222222222222222222222222222222222",
+            "102": "0x3333333333333333333333333333333333333333333333333333333333333333"
+        }"#;
+        assert_eq!(
+            TrustedHashes::parse(json).unwrap(),
+            TrustedHashes::parse(BASELINE_CSV).unwrap()
+        );
+        assert!(TrustedHashes::parse("100,not-a-hash").is_err());
+    }
+}
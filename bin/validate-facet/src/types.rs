@@ -1,12 +1,78 @@
+use alloy_json_rpc::RpcError;
+use alloy_primitives::B256;
+use alloy_transport::{TransportError, TransportErrorKind};
 use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResult {
     pub block: u64,
     pub execution: Option<TestResult>,
     pub derivation: Option<TestResult>,
+    /// Chain state the block was validated against
+    #[serde(flatten)]
+    pub refs: BlockRefs,
     pub duration_ms: u64,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Documented reason of the divergence, if the block failed and is a known divergence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_divergence: Option<String>,
+}
+
+impl ValidationResult {
+    /// Whether any stage that ran failed
+    pub fn failed(&self) -> bool {
+        [&self.execution, &self.derivation].into_iter().flatten().any(|r| !r.success)
+    }
+
+    /// Whether the block failed other than by a known divergence, counting towards failure rates
+    pub fn counts_as_failure(&self) -> bool {
+        self.failed() && self.known_divergence.is_none()
+    }
+
+    /// Whether every failed stage failed on an infrastructure error.
+    ///
+    /// Such a block says nothing about Kona's correctness, so it is left out of both the
+    /// `--failure-threshold` rate and the `--gate-validation-only` success rate.
+    pub fn is_infrastructure_failure(&self) -> bool {
+        let mut failures = [&self.execution, &self.derivation]
+            .into_iter()
+            .flatten()
+            .filter(|r| !r.success)
+            .peekable();
+        failures.peek().is_some() &&
+            failures.all(|r| r.error_type.is_some_and(|error_type| error_type.is_infrastructure()))
+    }
+
+    /// Error type of the first failed stage, execution before derivation
+    pub fn failure_type(&self) -> Option<ErrorType> {
+        [&self.execution, &self.derivation]
+            .into_iter()
+            .flatten()
+            .find(|r| !r.success)
+            .map(|r| r.error_type.unwrap_or(ErrorType::Unknown))
+    }
+}
+
+/// L1 and L2 block hashes a validation ran against, so a result can be tied to specific chain
+/// state after a reorg
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BlockRefs {
+    #[serde(default)]
+    pub l1_origin_hash: Option<B256>,
+    #[serde(default)]
+    pub l1_origin_number: Option<u64>,
+    #[serde(default)]
+    pub l2_block_hash: Option<B256>,
+}
+
+impl BlockRefs {
+    /// Fill in the refs captured by another stage, preferring the other stage's values
+    pub fn merge(&mut self, other: BlockRefs) {
+        self.l1_origin_hash = other.l1_origin_hash.or(self.l1_origin_hash);
+        self.l1_origin_number = other.l1_origin_number.or(self.l1_origin_number);
+        self.l2_block_hash = other.l2_block_hash.or(self.l2_block_hash);
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +81,9 @@ pub struct TestResult {
     pub error: Option<String>,
     pub error_type: Option<ErrorType>,
     pub retries: u32,
+    /// Wall-clock time spent in this stage, including retries
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -34,23 +103,201 @@ impl ErrorType {
         true
     }
     
-    pub fn max_retries(&self) -> u32 {
+    /// Whether the error lies with the RPC providers rather than with Kona
+    pub fn is_infrastructure(&self) -> bool {
+        matches!(self, ErrorType::Network | ErrorType::RateLimit | ErrorType::NotFound)
+    }
+    
+    /// Name of the error type as it is serialized
+    pub fn as_str(&self) -> &'static str {
         match self {
-            ErrorType::Network => 10,      // Network errors get more retries
-            ErrorType::RateLimit => 10,    // Rate limits need backing off
-            ErrorType::NotFound => 5,     // Data might appear
-            ErrorType::System => 1,      // System errors rarely resolve
-            ErrorType::Validation => 1,   // Validation errors unlikely to change
-            ErrorType::Unknown => 1,     // Unknown errors get one retry
+            ErrorType::Network => "network",
+            ErrorType::RateLimit => "rate_limit",
+            ErrorType::NotFound => "not_found",
+            ErrorType::Validation => "validation",
+            ErrorType::System => "system",
+            ErrorType::Unknown => "unknown",
         }
     }
-    
-    pub fn backoff_multiplier(&self) -> f64 {
+}
+
+/// Failure of a validation stage whose [`ErrorType`] is known where it is raised, so it does not
+/// have to be recovered from the message
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// An RPC request failed before returning a result
+    Network(String),
+    /// An RPC request was rejected for exceeding the provider's rate limit
+    RateLimit(String),
+    /// A block needed for the check is not available
+    NotFound(String),
+    /// A derived transaction differs from the node's
+    Mismatch { block: u64, index: usize, geth_len: usize, kona_len: usize },
+    /// The derived and the node's block contain a different number of transactions
+    TxCountMismatch { geth: usize, kona: usize },
+    /// A field of the derived payload attributes differs from the node's block header
+    AttributeMismatch { block: u64, field: &'static str, geth: String, kona: String },
+    /// Any other check the node's block failed
+    Invalid(String),
+}
+
+impl ValidationError {
+    /// Wrap the error of an RPC request, classified by its kind.
+    ///
+    /// JSON-RPC error responses are classified by their EIP-1474 code, and HTTP errors by their
+    /// status. Only the generic server error, which nodes also return for missing blocks and
+    /// state, falls back to the node's message.
+    pub fn rpc(error: impl Borrow<TransportError>) -> Self {
+        let error = error.borrow();
+        let message = error.to_string();
+        match error {
+            RpcError::ErrorResp(payload) => match payload.code {
+                // Limit exceeded
+                -32005 | 429 => Self::RateLimit(message),
+                // Resource not found
+                -32001 => Self::NotFound(message),
+                // Server error
+                -32000 if is_not_found_message(&payload.message.to_lowercase()) => {
+                    Self::NotFound(message)
+                }
+                _ => Self::Network(message),
+            },
+            RpcError::Transport(TransportErrorKind::HttpError(http)) if http.status == 429 => {
+                Self::RateLimit(message)
+            }
+            // A required result the node returned as null is missing on the node
+            RpcError::NullResp => Self::NotFound(message),
+            _ => Self::Network(message),
+        }
+    }
+
+    pub fn error_type(&self) -> ErrorType {
+        match self {
+            Self::Network(_) => ErrorType::Network,
+            Self::RateLimit(_) => ErrorType::RateLimit,
+            Self::NotFound(_) => ErrorType::NotFound,
+            Self::Mismatch { .. }
+            | Self::TxCountMismatch { .. }
+            | Self::AttributeMismatch { .. }
+            | Self::Invalid(_) => ErrorType::Validation,
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ErrorType::RateLimit => 2.0,    // Back off more aggressively
-            ErrorType::NotFound => 1.5,     // Block might appear soon
-            ErrorType::Network => 1.0,      // Standard backoff
-            _ => 1.0,
+            Self::Network(message)
+            | Self::RateLimit(message)
+            | Self::NotFound(message)
+            | Self::Invalid(message) => f.write_str(message),
+            Self::Mismatch { block, index, geth_len, kona_len } => write!(
+                f,
+                "Transaction {} differs at block {}: Geth {} bytes vs Kona {} bytes",
+                index, block, geth_len, kona_len
+            ),
+            Self::TxCountMismatch { geth, kona } => {
+                write!(f, "Transaction count mismatch: Geth {} vs Kona {}", geth, kona)
+            }
+            Self::AttributeMismatch { block, field, geth, kona } => write!(
+                f,
+                "Payload attribute {} differs at block {}: Geth {} vs Kona {}",
+                field, block, geth, kona
+            ),
         }
     }
-}
\ No newline at end of file
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Whether a lowercased error message reports a block or state the node does not have.
+///
+/// Only a fallback for errors without a code to classify them by, so it matches the messages nodes
+/// send for missing blocks and state rather than any "not found".
+pub fn is_not_found_message(lower: &str) -> bool {
+    const MISSING: [&str; 4] =
+        ["header not found", "unknown block", "missing trie node", "failed to fetch block"];
+    MISSING.iter().any(|pattern| lower.contains(pattern)) ||
+        lower.contains("block") &&
+            (lower.contains("not found") ||
+                lower.contains("does not exist") ||
+                lower.contains("missing")) ||
+        // Key value store misses of `debug_dbGet`, e.g. `leveldb: not found`
+        lower == "not found" ||
+        lower.ends_with(": not found")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::ErrorPayload;
+
+    fn error_resp(code: i64, message: &'static str) -> TransportError {
+        RpcError::ErrorResp(ErrorPayload { code, message: message.into(), data: None })
+    }
+
+    #[test]
+    fn test_rpc_errors_are_classified_by_kind() {
+        let error_type = |error: TransportError| ValidationError::rpc(error).error_type();
+
+        assert_eq!(error_type(error_resp(-32005, "limit exceeded")), ErrorType::RateLimit);
+        assert_eq!(error_type(error_resp(429, "too many requests")), ErrorType::RateLimit);
+        let too_many_requests = TransportErrorKind::http_error(429, String::new());
+        assert_eq!(error_type(too_many_requests), ErrorType::RateLimit);
+        assert_eq!(error_type(error_resp(-32001, "resource not found")), ErrorType::NotFound);
+        assert_eq!(error_type(RpcError::NullResp), ErrorType::NotFound);
+        assert_eq!(error_type(TransportErrorKind::backend_gone()), ErrorType::Network);
+        let bad_gateway = TransportErrorKind::http_error(502, String::new());
+        assert_eq!(error_type(bad_gateway), ErrorType::Network);
+
+        // The generic server error falls back to the node's message
+        for message in ["header not found", "missing trie node 0xab", "leveldb: not found"] {
+            assert_eq!(error_type(error_resp(-32000, message)), ErrorType::NotFound, "{message}");
+        }
+        assert_eq!(error_type(error_resp(-32000, "execution reverted")), ErrorType::Network);
+
+        // A miss of some other cache is not a missing block, whatever carries it
+        let cache_miss = "request not found in cache";
+        assert!(!is_not_found_message(cache_miss));
+        assert_eq!(error_type(error_resp(-32000, cache_miss)), ErrorType::Network);
+        assert_eq!(error_type(error_resp(-32603, cache_miss)), ErrorType::Network);
+        assert_eq!(error_type(TransportErrorKind::custom_str(cache_miss)), ErrorType::Network);
+    }
+
+    #[test]
+    fn test_block_refs_serialized_into_jsonl() {
+        let mut result = ValidationResult {
+            block: 100,
+            execution: None,
+            derivation: None,
+            refs: BlockRefs::default(),
+            duration_ms: 5,
+            timestamp: chrono::Utc::now(),
+            known_divergence: None,
+        };
+
+        // Execution only knows the L2 block; derivation also knows the L1 origin
+        result.refs.merge(BlockRefs { l2_block_hash: Some(B256::repeat_byte(0x22)), ..Default::default() });
+        result.refs.merge(BlockRefs {
+            l1_origin_hash: Some(B256::repeat_byte(0x11)),
+            l1_origin_number: Some(19_000_000),
+            l2_block_hash: None,
+        });
+
+        let line = serde_json::to_string(&result).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["l1_origin_hash"], format!("{}", B256::repeat_byte(0x11)));
+        assert_eq!(json["l1_origin_number"], 19_000_000);
+        assert_eq!(json["l2_block_hash"], format!("{}", B256::repeat_byte(0x22)));
+
+        let decoded: ValidationResult = serde_json::from_str(&line).unwrap();
+        assert_eq!(decoded.refs, result.refs);
+    }
+
+    #[test]
+    fn test_results_without_refs_still_parse() {
+        let line = r#"{"block":1,"execution":null,"derivation":null,"duration_ms":3,"timestamp":"2024-01-01T00:00:00Z"}"#;
+        let result: ValidationResult = serde_json::from_str(line).unwrap();
+        assert_eq!(result.refs, BlockRefs::default());
+    }
+}
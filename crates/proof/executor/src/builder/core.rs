@@ -8,9 +8,10 @@ use alloy_evm::{
     block::{BlockExecutionResult, BlockExecutor, BlockExecutorFactory},
 };
 use alloy_op_evm::{OpBlockExecutionCtx, OpBlockExecutorFactory, block::OpAlloyReceiptBuilder};
-use alloy_primitives::SignatureError;
+use alloy_primitives::{B256, SignatureError};
 use kona_genesis::RollupConfig;
 use kona_mpt::TrieHinter;
+use kona_protocol::OutputRoot;
 use op_alloy_consensus::{OpReceiptEnvelope, OpTxEnvelope};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use op_revm::OpSpecId;
@@ -178,6 +179,17 @@ where
         self.trie_db.set_parent_block_header(header.clone());
         Ok((header, ex_result).into())
     }
+
+    /// Returns the [`TrieAccount`](alloy_trie::TrieAccount) of `address` in the state produced by
+    /// the last built block, or in the parent state if no block has been built yet.
+    #[cfg(feature = "test-utils")]
+    pub fn account(
+        &mut self,
+        address: &alloy_primitives::Address,
+    ) -> ExecutorResult<Option<alloy_trie::TrieAccount>> {
+        let block_number = self.trie_db.parent_block_header().number;
+        Ok(self.trie_db.get_trie_account(address, block_number)?)
+    }
 }
 
 /// The outcome of a block building operation, returning the sealed block [`Header`] and the
@@ -190,6 +202,26 @@ pub struct BlockBuildingOutcome {
     pub execution_result: BlockExecutionResult<OpReceiptEnvelope>,
 }
 
+impl BlockBuildingOutcome {
+    /// Computes the output root of the built block.
+    ///
+    /// **CONSTRUCTION:**
+    /// ```text
+    /// output_root = keccak256(version_byte .. payload)
+    /// payload = state_root .. withdrawals_root .. block_hash
+    /// ```
+    ///
+    /// The `withdrawals_root` is the storage root of the L2 to L1 message passer after the block.
+    /// Before Isthmus it is not committed to in the header, so it must be supplied by the caller,
+    /// e.g. via [`StatelessL2Builder::compute_output_root`] or an `eth_getProof` of the
+    /// [`Predeploys::L2_TO_L1_MESSAGE_PASSER`].
+    ///
+    /// [`Predeploys::L2_TO_L1_MESSAGE_PASSER`]: kona_protocol::Predeploys::L2_TO_L1_MESSAGE_PASSER
+    pub fn output_root(&self, withdrawals_root: B256) -> B256 {
+        OutputRoot::from_parts(self.header.state_root, withdrawals_root, self.header.hash()).hash()
+    }
+}
+
 impl From<(Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>)> for BlockBuildingOutcome {
     fn from(
         (header, execution_result): (Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>),
@@ -200,10 +232,39 @@ impl From<(Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>)> for BlockBu
 
 #[cfg(test)]
 mod test {
-    use crate::test_utils::run_test_fixture;
+    use super::*;
+    use crate::{NoopTrieDBProvider, test_utils::run_test_fixture};
+    use alloc::vec;
+    use alloy_consensus::{Eip658Value, Receipt, Signed, TxEnvelope, TxLegacy, TxReceipt};
+    use alloy_eips::Decodable2718;
+    use alloy_op_evm::OpEvmFactory;
+    use alloy_primitives::{Sealable, Signature, TxKind, U256, b256, keccak256};
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use alloy_trie::EMPTY_ROOT_HASH;
+    use kona_derive::derive_facet_deposits;
+    use kona_mpt::NoopTrieHinter;
+    use kona_protocol::{FACET_INBOX_ADDRESS, encode_facet_payload};
+    use op_alloy_consensus::TxDeposit;
     use rstest::rstest;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_outcome_output_root() {
+        let header = Header {
+            state_root: B256::left_padding_from(&[0xbe, 0xef]),
+            ..Default::default()
+        };
+        let outcome = BlockBuildingOutcome {
+            header: Sealed::new_unchecked(header, B256::left_padding_from(&[0xc0, 0xde])),
+            execution_result: Default::default(),
+        };
+
+        assert_eq!(
+            outcome.output_root(B256::left_padding_from(&[0xba, 0xbe])),
+            b256!("0c39fb6b07cf6694b13e63e59f7b15255be1c93a4d6d3e0da6c99729647c0d11")
+        );
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_statelessly_execute_block(
@@ -213,4 +274,80 @@ mod test {
     ) {
         run_test_fixture(path).await;
     }
+
+    #[test]
+    fn test_execute_facet_creation_deposit() {
+        let config = RollupConfig::facet();
+
+        // Init code deploying the single-byte runtime code `STOP`:
+        // PUSH1 0, PUSH1 0, MSTORE8, PUSH1 1, PUSH1 0, RETURN
+        let init_code = [0x60, 0x00, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xf3];
+        let payload =
+            encode_facet_payload(config.l2_chain_id, None, U256::ZERO, 1_000_000, &init_code, &[]);
+
+        // Derive the creation deposit from an L1 tx to the facet inbox.
+        let l1_tx = TxEnvelope::Legacy(Signed::new_unchecked(
+            TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: TxKind::Call(FACET_INBOX_ADDRESS),
+                input: payload,
+                ..Default::default()
+            },
+            Signature::test_signature(),
+            Default::default(),
+        ));
+        let receipt = Receipt { status: Eip658Value::Eip658(true), ..Default::default() };
+        let (deposits, _, _) =
+            derive_facet_deposits(&[l1_tx], &[receipt], config.l2_chain_id, 1, 0, 0).unwrap();
+        assert_eq!(deposits.len(), 1);
+
+        let deposit = TxDeposit::decode_2718(&mut deposits[0].as_ref()).unwrap();
+        assert_eq!(deposit.to, TxKind::Create);
+        let created = deposit.from.create(0);
+
+        // Execute it on top of an empty state.
+        let parent = Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            state_root: EMPTY_ROOT_HASH,
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        let mut builder = StatelessL2Builder::new(
+            &config,
+            OpEvmFactory::default(),
+            NoopTrieDBProvider,
+            NoopTrieHinter,
+            parent.seal_slow(),
+        );
+        let outcome = builder
+            .build_block(OpPayloadAttributes {
+                payload_attributes: PayloadAttributes {
+                    timestamp: config.block_time,
+                    prev_randao: B256::ZERO,
+                    suggested_fee_recipient: Default::default(),
+                    withdrawals: Some(vec![]),
+                    parent_beacon_block_root: Some(B256::ZERO),
+                },
+                transactions: Some(deposits),
+                no_tx_pool: Some(true),
+                gas_limit: Some(30_000_000),
+                eip_1559_params: None,
+            })
+            .unwrap();
+
+        let receipts = &outcome.execution_result.receipts;
+        assert_eq!(receipts.len(), 1);
+        assert!(matches!(receipts[0], OpReceiptEnvelope::Deposit(_)));
+        assert!(receipts[0].status());
+
+        let account = builder.trie_db.get_trie_account(&created, 1).unwrap().unwrap();
+        assert_eq!(account.code_hash, keccak256([0x00]));
+        let depositor = builder.trie_db.get_trie_account(&deposit.from, 1).unwrap().unwrap();
+        assert_eq!(depositor.nonce, 1);
+    }
 }
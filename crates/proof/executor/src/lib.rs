@@ -5,7 +5,7 @@
     issue_tracker_base_url = "https://github.com/op-rs/kona/issues/"
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
-#![cfg_attr(not(feature = "test-utils"), no_std)]
+#![cfg_attr(not(any(feature = "rpc", feature = "test-utils")), no_std)]
 
 extern crate alloc;
 
@@ -25,5 +25,10 @@ pub(crate) mod util;
 
 pub(crate) mod constants;
 
+#[cfg(feature = "rpc")]
+mod rpc;
+#[cfg(feature = "rpc")]
+pub use rpc::{RpcTrieDBProvider, RpcTrieDBProviderError};
+
 #[cfg(feature = "test-utils")]
 pub mod test_utils;
@@ -0,0 +1,148 @@
+//! A [TrieDBProvider] that serves the state of an L2 node over RPC.
+
+use crate::TrieDBProvider;
+use alloy_consensus::Header;
+use alloy_primitives::{B256, Bytes};
+use alloy_provider::{Network, Provider, RootProvider};
+use alloy_rlp::Decodable;
+use alloy_transport::TransportError;
+use kona_mpt::{TrieNode, TrieProvider};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+use tokio::runtime::Handle;
+
+/// geth's hashdb scheme key prefix of contract code.
+const CODE_PREFIX: u8 = b'c';
+
+/// An error type for the [RpcTrieDBProvider].
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RpcTrieDBProviderError {
+    /// The node failed to serve the preimage of the hash.
+    #[error("Failed to fetch the preimage of {0}: {1}")]
+    Rpc(B256, Arc<TransportError>),
+    /// Failed to decode the RLP-encoded preimage.
+    #[error("Failed to decode RLP: {0}")]
+    Rlp(alloy_rlp::Error),
+}
+
+/// A [TrieDBProvider] that fetches trie nodes, bytecode and headers from an L2 node through
+/// `debug_dbGet` and `debug_getRawHeader`.
+///
+/// The [TrieDBProvider] interface is synchronous, so every request blocks the current thread
+/// until it completes. The provider must be used on a multi-threaded tokio runtime.
+///
+/// The block builder reports provider failures as strings, so clones of the provider share the
+/// first failure they hit, see [RpcTrieDBProvider::take_error].
+#[derive(Debug, Clone)]
+pub struct RpcTrieDBProvider<N: Network> {
+    provider: RootProvider<N>,
+    error: Arc<Mutex<Option<RpcTrieDBProviderError>>>,
+}
+
+impl<N: Network> RpcTrieDBProvider<N> {
+    /// Creates a new [RpcTrieDBProvider] fetching preimages from the given node.
+    pub fn new(provider: RootProvider<N>) -> Self {
+        Self { provider, error: Default::default() }
+    }
+
+    /// Takes the first failure hit by the provider or any of its clones.
+    pub fn take_error(&self) -> Option<RpcTrieDBProviderError> {
+        self.error.lock().expect("Error lock poisoned").take()
+    }
+
+    /// Blocks on `request`, recording its failure.
+    fn block_on<T>(
+        &self,
+        request: impl Future<Output = Result<T, RpcTrieDBProviderError>>,
+    ) -> Result<T, RpcTrieDBProviderError> {
+        let result = tokio::task::block_in_place(|| Handle::current().block_on(request));
+        if let Err(e) = &result {
+            let mut error = self.error.lock().expect("Error lock poisoned");
+            if error.is_none() {
+                *error = Some(e.clone());
+            }
+        }
+        result
+    }
+
+    /// Fetches the preimage of `hash` from the node's database.
+    async fn db_get(&self, hash: B256) -> Result<Bytes, RpcTrieDBProviderError> {
+        self.provider
+            .client()
+            .request("debug_dbGet", &[hash])
+            .await
+            .map_err(|e| RpcTrieDBProviderError::Rpc(hash, Arc::new(e)))
+    }
+}
+
+impl<N: Network> TrieProvider for RpcTrieDBProvider<N> {
+    type Error = RpcTrieDBProviderError;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        let preimage = self.block_on(self.db_get(key))?;
+        TrieNode::decode(&mut preimage.as_ref()).map_err(RpcTrieDBProviderError::Rlp)
+    }
+}
+
+impl<N: Network> TrieDBProvider for RpcTrieDBProvider<N> {
+    fn bytecode_by_hash(&self, hash: B256) -> Result<Bytes, Self::Error> {
+        self.block_on(async {
+            // Code is stored under the hashdb scheme prefix, or without it by older nodes.
+            let code_hash = Bytes::from([&[CODE_PREFIX], hash.as_slice()].concat());
+            match self.provider.client().request::<_, Bytes>("debug_dbGet", &[code_hash]).await {
+                Ok(code) => Ok(code),
+                Err(_) => self.db_get(hash).await,
+            }
+        })
+    }
+
+    fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+        let encoded_header = self.block_on(async {
+            self.provider
+                .client()
+                .request::<_, Bytes>("debug_getRawHeader", &[hash])
+                .await
+                .map_err(|e| RpcTrieDBProviderError::Rpc(hash, Arc::new(e)))
+        })?;
+        Header::decode(&mut encoded_header.as_ref()).map_err(RpcTrieDBProviderError::Rlp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_provider::network::Ethereum;
+    use alloy_rpc_client::RpcClient;
+    use alloy_transport::mock::Asserter;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rpc_provider_serves_and_records_failures() {
+        let asserter = Asserter::new();
+        let provider = RpcTrieDBProvider::<Ethereum>::new(RootProvider::new(RpcClient::mocked(
+            asserter.clone(),
+        )));
+
+        let header = Header { number: 7, ..Default::default() };
+        asserter.push_success(&Bytes::from(alloy_rlp::encode(&header)));
+        assert_eq!(provider.header_by_hash(header.hash_slow()).unwrap(), header);
+        assert!(provider.take_error().is_none());
+
+        // The prefixed code lookup falls back to the bare hash
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        asserter.push_failure_msg("not found");
+        asserter.push_success(&code);
+        assert_eq!(provider.bytecode_by_hash(B256::ZERO).unwrap(), code);
+
+        // A clone records the failure for the original
+        asserter.push_failure_msg("missing trie node");
+        let clone = provider.clone();
+        assert!(matches!(
+            clone.trie_node_by_hash(B256::ZERO),
+            Err(RpcTrieDBProviderError::Rpc(hash, _)) if hash == B256::ZERO
+        ));
+        assert!(matches!(provider.take_error(), Some(RpcTrieDBProviderError::Rpc(..))));
+        assert!(provider.take_error().is_none());
+    }
+}
@@ -1,46 +1,48 @@
 //! Test utilities for the executor.
 
-use crate::{StatelessL2Builder, TrieDBProvider};
+use crate::{StatelessL2Builder, TrieDBProvider, compute_receipts_root};
 use alloy_consensus::Header;
+use alloy_eips::eip2718::Encodable2718;
 use alloy_op_evm::OpEvmFactory;
-use alloy_primitives::{B256, Bytes, Sealable};
+use alloy_primitives::{Address, B256, Bytes, Sealable, Sealed, U256, keccak256};
 use alloy_provider::{Provider, RootProvider, network::primitives::BlockTransactions};
-use alloy_rlp::Decodable;
+use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_client::RpcClient;
 use alloy_rpc_types_engine::PayloadAttributes;
 use alloy_transport_http::{Client, Http};
-use kona_genesis::RollupConfig;
-use kona_mpt::{NoopTrieHinter, TrieNode, TrieProvider};
+use kona_genesis::{FacetRollupConfigBuilder, RollupConfig};
+use kona_mpt::{NoopTrieHinter, TrieHinter, TrieNode, TrieProvider};
+use kona_preimage::PreimageKey;
+use kona_protocol::{OutputRoot, Predeploys};
 use kona_registry::ROLLUP_CONFIGS;
-use kona_genesis::{BaseFeeConfig, ChainGenesis, HardForkConfig, SystemConfig};
-use alloy_eips::BlockNumHash;
-use alloy_primitives::{address, b256, U256};
+use op_alloy_consensus::OpReceiptEnvelope;
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use rocksdb::{DB, Options};
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::{fs, runtime::Handle, sync::Mutex};
+use tracing::{debug, warn};
 
 /// Executes a [ExecutorTestFixture] stored at the passed `fixture_path` and asserts that the
 /// produced block hash matches the expected block hash.
 pub async fn run_test_fixture(fixture_path: PathBuf) {
-    // First, untar the fixture.
-    let fixture_dir = tempfile::tempdir().expect("Failed to create temporary directory");
-    tokio::process::Command::new("tar")
-        .arg("-xvf")
-        .arg(fixture_path.as_path())
-        .arg("-C")
-        .arg(fixture_dir.path())
-        .arg("--strip-components=1")
-        .output()
-        .await
-        .expect("Failed to untar fixture");
+    run_test_fixture_with_hinter(fixture_path, &NoopTrieHinter).await
+}
 
-    let mut options = Options::default();
-    options.set_compression_type(rocksdb::DBCompressionType::Snappy);
-    options.create_if_missing(true);
-    let kv_store = DB::open(&options, fixture_dir.path().join("kv"))
-        .unwrap_or_else(|e| panic!("Failed to open database at {fixture_dir:?}: {e}"));
+/// Executes a [ExecutorTestFixture] like [run_test_fixture], sending all trie hints issued during
+/// the block build to `hinter`.
+///
+/// Use a [RecordingTrieHinter] to inspect which preimages the build requests, or a custom hinter
+/// that pre-warms the provider.
+pub async fn run_test_fixture_with_hinter(
+    fixture_path: PathBuf,
+    hinter: &dyn TrieHinter<Error = String>,
+) {
+    let (fixture_dir, kv_store) = open_fixture(&fixture_path).await;
     let provider = DiskTrieNodeProvider::new(kv_store);
     let fixture: ExecutorTestFixture =
         serde_json::from_slice(&fs::read(fixture_dir.path().join("fixture.json")).await.unwrap())
@@ -50,7 +52,7 @@ pub async fn run_test_fixture(fixture_path: PathBuf) {
         &fixture.rollup_config,
         OpEvmFactory::default(),
         provider,
-        NoopTrieHinter,
+        hinter,
         fixture.parent_header.seal_slow(),
     );
 
@@ -61,6 +63,237 @@ pub async fn run_test_fixture(fixture_path: PathBuf) {
         fixture.expected_block_hash,
         "Produced header does not match the expected header"
     );
+
+    if let Some(expected_output_root) = fixture.expected_output_root {
+        assert_eq!(
+            executor.compute_output_root().unwrap(),
+            expected_output_root,
+            "Produced output root does not match the expected output root"
+        );
+    }
+}
+
+/// The result of executing a single fixture with [run_fixtures_in_dir].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureOutcome {
+    /// The path of the fixture tarball.
+    pub path: PathBuf,
+    /// The failure message of the fixture, `None` if it passed.
+    pub error: Option<String>,
+}
+
+impl FixtureOutcome {
+    /// Returns `true` if the fixture passed.
+    pub const fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// The per-fixture results of [run_fixtures_in_dir], ordered by fixture path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixtureRunSummary {
+    /// The outcome of every fixture found in the directory.
+    pub outcomes: Vec<FixtureOutcome>,
+}
+
+impl FixtureRunSummary {
+    /// Returns `true` if every fixture passed.
+    pub fn all_passed(&self) -> bool {
+        self.outcomes.iter().all(FixtureOutcome::passed)
+    }
+
+    /// Returns the outcomes of the fixtures that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &FixtureOutcome> {
+        self.outcomes.iter().filter(|outcome| !outcome.passed())
+    }
+}
+
+/// Executes every `*.tar.gz` fixture in `dir` with [run_test_fixture], running at most
+/// `concurrency` fixtures at a time, and returns the outcome of each.
+///
+/// Fixtures are executed on the blocking thread pool, so this must be called from within a
+/// multi-threaded runtime. A fixture that panics is reported as failed with the panic message.
+pub async fn run_fixtures_in_dir(dir: PathBuf, concurrency: usize) -> FixtureRunSummary {
+    let mut entries = fs::read_dir(&dir)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to read fixture directory {dir:?}: {e}"));
+    let mut fixtures = Vec::new();
+    while let Some(entry) = entries.next_entry().await.expect("Failed to read directory entry") {
+        let path = entry.path();
+        if path.is_file() && path.to_string_lossy().ends_with(".tar.gz") {
+            fixtures.push(path);
+        }
+    }
+
+    let permits = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+    let mut tasks = tokio::task::JoinSet::new();
+    for path in fixtures {
+        let permit = permits.clone().acquire_owned().await.expect("Semaphore closed");
+        tasks.spawn(async move {
+            let fixture = path.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                Handle::current().block_on(run_test_fixture(fixture))
+            })
+            .await;
+            drop(permit);
+
+            let error = result.err().map(|e| match e.try_into_panic() {
+                Ok(panic) => panic
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_else(|| "Fixture panicked".to_string()),
+                Err(e) => e.to_string(),
+            });
+            FixtureOutcome { path, error }
+        });
+    }
+
+    let mut outcomes = tasks.join_all().await;
+    outcomes.sort_by(|a, b| a.path.cmp(&b.path));
+    FixtureRunSummary { outcomes }
+}
+
+/// Executes an [ExecutorRangeFixture] stored at the passed `fixture_path`, building every block
+/// on top of the header and state produced by the previous one, and asserts that each produced
+/// block hash matches the expected block hash.
+pub async fn run_range_fixture(fixture_path: PathBuf) {
+    let (fixture_dir, kv_store) = open_fixture(&fixture_path).await;
+    let provider = DiskTrieNodeProvider::new(kv_store);
+    let fixture: ExecutorRangeFixture = serde_json::from_slice(
+        &fs::read(fixture_dir.path().join(RANGE_FIXTURE_FILE)).await.unwrap(),
+    )
+    .expect("Failed to deserialize range fixture");
+    let first = fixture.blocks.first().expect("Range fixture has no blocks");
+
+    let mut executor = StatelessL2Builder::new(
+        &fixture.rollup_config,
+        OpEvmFactory::default(),
+        provider,
+        NoopTrieHinter,
+        first.parent_header.clone().seal_slow(),
+    );
+
+    let mut parent_hash = None;
+    for block in &fixture.blocks {
+        // The recorded parent must be the block produced just before
+        if let Some(parent_hash) = parent_hash {
+            assert_eq!(
+                block.parent_header.hash_slow(),
+                parent_hash,
+                "Range fixture is not contiguous at block {}",
+                block.parent_header.number + 1
+            );
+        }
+
+        let outcome = executor.build_block(block.executing_payload.clone()).unwrap();
+        assert_eq!(
+            outcome.header.hash(),
+            block.expected_block_hash,
+            "Produced header of block {} does not match the expected header",
+            outcome.header.number
+        );
+        parent_hash = Some(outcome.header.hash());
+    }
+}
+
+/// Untars the fixture at `fixture_path` into a temporary directory and opens its key value store.
+async fn open_fixture(fixture_path: &Path) -> (tempfile::TempDir, DB) {
+    let fixture_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    tokio::process::Command::new("tar")
+        .arg("-xvf")
+        .arg(fixture_path)
+        .arg("-C")
+        .arg(fixture_dir.path())
+        .arg("--strip-components=1")
+        .output()
+        .await
+        .expect("Failed to untar fixture");
+
+    let mut options = Options::default();
+    options.set_compression_type(rocksdb::DBCompressionType::Snappy);
+    options.create_if_missing(true);
+    let kv_store = DB::open(&options, fixture_dir.path().join("kv"))
+        .unwrap_or_else(|e| panic!("Failed to open database at {fixture_dir:?}: {e}"));
+    (fixture_dir, kv_store)
+}
+
+/// Returns the key a fixture's K/V store holds the preimage of `hash` under: its keccak256
+/// [PreimageKey], so the store can be served to the proof program as is, see
+/// [DiskPreimageFetcher](kona_preimage::DiskPreimageFetcher).
+fn fixture_key(hash: B256) -> [u8; 32] {
+    PreimageKey::new_keccak256(*hash).into()
+}
+
+/// A hint received by a [RecordingTrieHinter].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordedHint {
+    /// A trie node preimage, by hash.
+    TrieNode(B256),
+    /// The account proof of an address at a block number.
+    AccountProof(Address, u64),
+    /// The storage proof of a slot of an address at a block number.
+    StorageProof(Address, U256, u64),
+    /// The execution witness of a payload built on top of the parent block hash.
+    ExecutionWitness(B256),
+}
+
+/// A [TrieHinter] that records every hint it receives, in order.
+#[derive(Debug, Default)]
+pub struct RecordingTrieHinter {
+    hints: std::sync::Mutex<Vec<RecordedHint>>,
+}
+
+impl RecordingTrieHinter {
+    /// Returns all hints received so far.
+    pub fn hints(&self) -> Vec<RecordedHint> {
+        self.hints.lock().expect("Poisoned lock").clone()
+    }
+
+    /// Returns the hashes of all trie nodes hinted so far.
+    pub fn trie_node_hashes(&self) -> Vec<B256> {
+        self.hints()
+            .into_iter()
+            .filter_map(|hint| match hint {
+                RecordedHint::TrieNode(hash) => Some(hash),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn record(&self, hint: RecordedHint) -> Result<(), String> {
+        self.hints.lock().map_err(|e| e.to_string())?.push(hint);
+        Ok(())
+    }
+}
+
+impl TrieHinter for RecordingTrieHinter {
+    type Error = String;
+
+    fn hint_trie_node(&self, hash: B256) -> Result<(), Self::Error> {
+        self.record(RecordedHint::TrieNode(hash))
+    }
+
+    fn hint_account_proof(&self, address: Address, block_number: u64) -> Result<(), Self::Error> {
+        self.record(RecordedHint::AccountProof(address, block_number))
+    }
+
+    fn hint_storage_proof(
+        &self,
+        address: Address,
+        slot: U256,
+        block_number: u64,
+    ) -> Result<(), Self::Error> {
+        self.record(RecordedHint::StorageProof(address, slot, block_number))
+    }
+
+    fn hint_execution_witness(
+        &self,
+        parent_hash: B256,
+        _op_payload_attributes: &OpPayloadAttributes,
+    ) -> Result<(), Self::Error> {
+        self.record(RecordedHint::ExecutionWitness(parent_hash))
+    }
 }
 
 /// The test fixture format for the [`StatelessL2Builder`].
@@ -74,6 +307,102 @@ pub struct ExecutorTestFixture {
     pub executing_payload: OpPayloadAttributes,
     /// The expected block hash
     pub expected_block_hash: B256,
+    /// The expected output root of the executed block, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_output_root: Option<B256>,
+}
+
+impl ExecutorTestFixture {
+    /// Serializes the fixture into its canonical byte representation.
+    ///
+    /// The canonical form is compact JSON with the keys of every object sorted lexicographically,
+    /// so two fixtures with equal contents always serialize to identical bytes regardless of
+    /// struct field order or map iteration order.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let value = serde_json::to_value(self).expect("Failed to serialize fixture");
+        serde_json::to_vec(&canonicalize_json(value)).expect("Failed to serialize fixture")
+    }
+
+    /// Returns the content address of the fixture, defined as the keccak256 hash of its
+    /// [canonical bytes](Self::canonical_bytes).
+    pub fn fixture_id(&self) -> B256 {
+        keccak256(self.canonical_bytes())
+    }
+}
+
+/// Name of the file holding an [ExecutorRangeFixture] inside its tarball.
+const RANGE_FIXTURE_FILE: &str = "range_fixture.json";
+
+/// The test fixture format for a contiguous range of blocks executed by one
+/// [`StatelessL2Builder`], so the state each block leaves behind is the state the next executes on.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExecutorRangeFixture {
+    /// The rollup configuration for the executing chain.
+    pub rollup_config: RollupConfig,
+    /// The blocks of the range, in ascending order.
+    pub blocks: Vec<RangeFixtureBlock>,
+}
+
+/// A single block of an [ExecutorRangeFixture].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RangeFixtureBlock {
+    /// The parent block header, the header of the previous block in the range.
+    pub parent_header: Header,
+    /// The executing payload attributes.
+    pub executing_payload: OpPayloadAttributes,
+    /// The expected block hash
+    pub expected_block_hash: B256,
+}
+
+/// A receipts root computed from a block's execution receipts that differs from the root committed
+/// to in its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Receipts root mismatch: computed {computed}, header {expected}")]
+pub struct ReceiptsRootMismatch {
+    /// The receipts root computed from the execution receipts.
+    pub computed: B256,
+    /// The receipts root in the expected header.
+    pub expected: B256,
+}
+
+/// Checks that the receipts root computed from `receipts` matches the `expected` header root.
+///
+/// A mismatch here, independent of the state root, points at a receipt encoding bug rather than
+/// an execution bug.
+pub fn check_receipts_root(
+    receipts: &[OpReceiptEnvelope],
+    config: &RollupConfig,
+    timestamp: u64,
+    expected: B256,
+) -> Result<(), ReceiptsRootMismatch> {
+    let computed = compute_receipts_root(receipts, config, timestamp);
+    if computed != expected {
+        return Err(ReceiptsRootMismatch { computed, expected });
+    }
+    Ok(())
+}
+
+/// Encodes the full transactions of a block in the raw EIP-2718 form `debug_getRawTransaction`
+/// returns.
+fn encode_transactions<T: Encodable2718>(transactions: &[T]) -> Vec<Bytes> {
+    transactions.iter().map(|tx| tx.encoded_2718().into()).collect()
+}
+
+/// Recursively rebuilds every JSON object in `value` with its keys in sorted order.
+fn canonicalize_json(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries.into_iter().map(|(k, v)| (k, canonicalize_json(v))).collect(),
+            )
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(canonicalize_json).collect())
+        }
+        other => other,
+    }
 }
 
 /// A test fixture creator for the [`StatelessL2Builder`].
@@ -109,33 +438,46 @@ impl ExecutorTestFixtureCreator {
 }
 
 impl ExecutorTestFixtureCreator {
-    /// Create a static test fixture with the configuration provided.
-    pub async fn create_static_fixture(self) {
+    /// Returns the rollup config of the provider's chain, falling back to the Facet config for
+    /// chains that are not in the registry.
+    async fn rollup_config(&self) -> RollupConfig {
         let chain_id = self.provider.get_chain_id().await.expect("Failed to get chain ID");
-        let rollup_config = ROLLUP_CONFIGS.get(&chain_id)
-            .cloned()
-            .unwrap_or_else(|| {
-                println!("⚠️  Chain ID {} not found in registry, using custom facet config", chain_id);
-                create_custom_facet_config(chain_id)
-            });
+        ROLLUP_CONFIGS.get(&chain_id).cloned().unwrap_or_else(|| {
+            warn!(
+                target: "executor_fixture",
+                chain_id,
+                "Chain ID not found in registry, using custom facet config"
+            );
+            create_custom_facet_config(chain_id)
+        })
+    }
 
+    /// Fetches `block_number` and its parent, returning the sealed parent header, the expected
+    /// header and the payload attributes that rebuild the block.
+    async fn fetch_payload(
+        &self,
+        rollup_config: &RollupConfig,
+        block_number: u64,
+    ) -> (Sealed<Header>, Header, OpPayloadAttributes) {
         let executing_block = self
             .provider
-            .get_block_by_number(self.block_number.into())
+            .get_block_by_number(block_number.into())
             .await
             .expect("Failed to get parent block")
             .expect("Block not found");
         let parent_block = self
             .provider
-            .get_block_by_number((self.block_number - 1).into())
+            .get_block_by_number((block_number - 1).into())
             .await
             .expect("Failed to get parent block")
             .expect("Block not found");
 
-        let executing_header = executing_block.header;
+        let executing_header = executing_block.header.inner;
         let parent_header = parent_block.header.inner.seal_slow();
 
         let encoded_executing_transactions = match executing_block.transactions {
+            // Full bodies are already at hand, so no raw transaction has to be fetched
+            BlockTransactions::Full(transactions) => encode_transactions(&transactions),
             BlockTransactions::Hashes(transactions) => {
                 let mut encoded_transactions = Vec::with_capacity(transactions.len());
                 for (i, tx_hash) in transactions.iter().enumerate() {
@@ -145,40 +487,41 @@ impl ExecutorTestFixtureCreator {
                         .request::<&[B256; 1], Bytes>("debug_getRawTransaction", &[*tx_hash])
                         .await
                         .expect("Block not found");
-                    
-                    // Debug logging
-                    println!("=== GETH Transaction {} (hash: {}) ===", i, tx_hash);
-                    println!("  Raw length: {} bytes", tx.len());
-                    println!("  First 40 bytes: {:02x?}", &tx[..40.min(tx.len())]);
-                    
-                    // Try to identify transaction type
-                    if tx.len() > 0 {
-                        match tx[0] {
-                            0x7e => {
-                                println!("  Type: DEPOSIT (0x7e)");
-                                // Also fetch the transaction details to see mint value
-                                let tx_details: serde_json::Value = self
-                                    .provider
-                                    .client()
-                                    .request("eth_getTransactionByHash", &[*tx_hash])
-                                    .await
-                                    .expect("Failed to get transaction details");
-                                if let Some(mint) = tx_details.get("mint") {
-                                    println!("  Mint value from Geth: {}", mint);
-                                }
-                            },
-                            0x02 => println!("  Type: EIP-1559 (0x02)"),
-                            0x01 => println!("  Type: EIP-2930 (0x01)"),
-                            _ => println!("  Type: Unknown or Legacy (0x{:02x})", tx[0]),
+
+                    let tx_type = tx.first().copied().unwrap_or_default();
+                    debug!(
+                        target: "executor_fixture",
+                        index = i,
+                        %tx_hash,
+                        len = tx.len(),
+                        tx_type,
+                        "Fetched raw transaction"
+                    );
+                    // The mint of a deposit is only in the node's RPC representation
+                    let debug_enabled =
+                        tracing::enabled!(target: "executor_fixture", tracing::Level::DEBUG);
+                    if tx_type == 0x7E && debug_enabled {
+                        let tx_details: serde_json::Value = self
+                            .provider
+                            .client()
+                            .request("eth_getTransactionByHash", &[*tx_hash])
+                            .await
+                            .expect("Failed to get transaction details");
+                        if let Some(mint) = tx_details.get("mint") {
+                            debug!(target: "executor_fixture", %tx_hash, %mint, "Deposit mint");
                         }
                     }
-                    
+
                     encoded_transactions.push(tx);
                 }
-                println!("=== Total transactions from Geth: {} ===\n", encoded_transactions.len());
+                debug!(
+                    target: "executor_fixture",
+                    count = encoded_transactions.len(),
+                    "Fetched raw transactions"
+                );
                 encoded_transactions
             }
-            _ => panic!("Only BlockTransactions::Hashes are supported."),
+            BlockTransactions::Uncle => panic!("Uncle blocks have no transactions to execute."),
         };
 
         let payload_attrs = OpPayloadAttributes {
@@ -201,12 +544,38 @@ impl ExecutorTestFixtureCreator {
             ),
         };
 
+        (parent_header, executing_header, payload_attrs)
+    }
+
+    /// Create a static test fixture with the configuration provided.
+    pub async fn create_static_fixture(self) {
+        let rollup_config = self.rollup_config().await;
+        let (parent_header, executing_header, payload_attrs) =
+            self.fetch_payload(&rollup_config, self.block_number).await;
+
+        // The message passer storage root is not committed to in the header before Isthmus.
+        let message_passer = self
+            .provider
+            .get_proof(Predeploys::L2_TO_L1_MESSAGE_PASSER, Vec::new())
+            .block_id(self.block_number.into())
+            .await
+            .expect("Failed to get the message passer proof");
+        let expected_output_root = Some(
+            OutputRoot::from_parts(
+                executing_header.state_root,
+                message_passer.storage_hash,
+                executing_header.hash_slow(),
+            )
+            .hash(),
+        );
+
         let fixture_path = self.data_dir.join("fixture.json");
         let fixture = ExecutorTestFixture {
             rollup_config: rollup_config.clone(),
             parent_header: parent_header.inner().clone(),
             executing_payload: payload_attrs.clone(),
             expected_block_hash: executing_header.hash_slow(),
+            expected_output_root,
         };
 
         let mut executor = StatelessL2Builder::new(
@@ -218,44 +587,122 @@ impl ExecutorTestFixtureCreator {
         );
         let outcome = executor.build_block(payload_attrs).expect("Failed to execute block");
 
-        // Debug: Print execution details
-        println!("\n=== Execution Results ===");
-        println!("Gas used: {} (expected: {})", outcome.execution_result.gas_used, executing_header.gas_used);
-        println!("Receipts count: {}", outcome.execution_result.receipts.len());
-        
-        // Print receipt details to see gas usage per transaction
+        debug!(
+            target: "executor_fixture",
+            gas_used = outcome.execution_result.gas_used,
+            expected_gas_used = executing_header.gas_used,
+            receipts = outcome.execution_result.receipts.len(),
+            state_root = %outcome.header.state_root,
+            expected_state_root = %executing_header.state_root,
+            "Executed block"
+        );
         for (i, receipt) in outcome.execution_result.receipts.iter().enumerate() {
-            println!("\nReceipt {}: {:?}", i, receipt);
+            debug!(target: "executor_fixture", index = i, ?receipt, "Receipt");
         }
-        
-        // Print state root comparison
-        println!("\n=== State Root Comparison ===");
-        println!("Kona state root:  {:?}", outcome.header.state_root);
-        println!("Geth state root:  {:?}", executing_header.state_root);
-        
+
+        // Report a receipts root discrepancy on its own, before the full header comparison.
+        if let Err(mismatch) = check_receipts_root(
+            &outcome.execution_result.receipts,
+            &rollup_config,
+            executing_header.timestamp,
+            executing_header.receipts_root,
+        ) {
+            panic!("{mismatch}");
+        }
+
         assert_eq!(
             outcome.header.inner(),
-            &executing_header.inner,
+            &executing_header,
             "Produced header does not match the expected header"
         );
-        fs::write(fixture_path.as_path(), serde_json::to_vec(&fixture).unwrap()).await.unwrap();
-
-        // Tar the fixture.
-        let data_dir = fixture_path.parent().unwrap();
-        tokio::process::Command::new("tar")
-            .arg("-czf")
-            .arg(data_dir.with_extension("tar.gz").file_name().unwrap())
-            .arg(data_dir.file_name().unwrap())
-            .current_dir(data_dir.parent().unwrap())
-            .output()
-            .await
-            .expect("Failed to tar fixture");
+        fs::write(fixture_path.as_path(), fixture.canonical_bytes()).await.unwrap();
+        archive_fixture_dir(fixture_path.parent().unwrap()).await;
+    }
+
+    /// Executes the block on top of its parent without recording a fixture, returning the node's
+    /// header and the header produced by the executor.
+    pub async fn execute_block(self) -> (Header, Header) {
+        let rollup_config = self.rollup_config().await;
+        let (parent_header, executing_header, payload_attrs) =
+            self.fetch_payload(&rollup_config, self.block_number).await;
+
+        let mut executor = StatelessL2Builder::new(
+            &rollup_config,
+            OpEvmFactory::default(),
+            self,
+            NoopTrieHinter,
+            parent_header,
+        );
+        let outcome = executor.build_block(payload_attrs).expect("Failed to execute block");
+        (executing_header, outcome.header.into_inner())
+    }
+
+    /// Create a test fixture for the blocks `start..=end`, executed on top of each other starting
+    /// from the parent of `start`. The fixture is written to the creator's data directory and is
+    /// run with [run_range_fixture].
+    pub async fn create_range_fixture(self, start: u64, end: u64) {
+        assert!(start > 0 && start <= end, "Invalid block range {start}..={end}");
+        let rollup_config = self.rollup_config().await;
+
+        let mut blocks = Vec::with_capacity((end - start + 1) as usize);
+        let mut expected_headers = Vec::with_capacity(blocks.capacity());
+        for block_number in start..=end {
+            let (parent_header, executing_header, payload_attrs) =
+                self.fetch_payload(&rollup_config, block_number).await;
+            blocks.push(RangeFixtureBlock {
+                parent_header: parent_header.into_inner(),
+                executing_payload: payload_attrs,
+                expected_block_hash: executing_header.hash_slow(),
+            });
+            expected_headers.push(executing_header);
+        }
+
+        // Build every block on the state the previous one left behind, fetching the preimages of
+        // the parent state of `start` and of anything the range touches along the way.
+        let fixture = ExecutorRangeFixture { rollup_config, blocks };
+        let data_dir = self.data_dir.clone();
+        let mut executor = StatelessL2Builder::new(
+            &fixture.rollup_config,
+            OpEvmFactory::default(),
+            self,
+            NoopTrieHinter,
+            fixture.blocks[0].parent_header.clone().seal_slow(),
+        );
+        for (block, expected_header) in fixture.blocks.iter().zip(&expected_headers) {
+            let outcome = executor
+                .build_block(block.executing_payload.clone())
+                .expect("Failed to execute block");
+            assert_eq!(
+                outcome.header.inner(),
+                expected_header,
+                "Produced header of block {} does not match the expected header",
+                expected_header.number
+            );
+        }
 
-        // Remove the leftover directory.
-        fs::remove_dir_all(data_dir).await.expect("Failed to remove temporary directory");
+        let fixture_path = data_dir.join(RANGE_FIXTURE_FILE);
+        let fixture = serde_json::to_vec(&fixture).expect("Failed to serialize fixture");
+        fs::write(fixture_path.as_path(), fixture).await.unwrap();
+        archive_fixture_dir(&data_dir).await;
     }
 }
 
+/// Tars the fixture directory `data_dir` into `<data_dir>.tar.gz` next to it and removes the
+/// directory.
+async fn archive_fixture_dir(data_dir: &Path) {
+    tokio::process::Command::new("tar")
+        .arg("-czf")
+        .arg(data_dir.with_extension("tar.gz").file_name().unwrap())
+        .arg(data_dir.file_name().unwrap())
+        .current_dir(data_dir.parent().unwrap())
+        .output()
+        .await
+        .expect("Failed to tar fixture");
+
+    // Remove the leftover directory.
+    fs::remove_dir_all(data_dir).await.expect("Failed to remove temporary directory");
+}
+
 impl TrieProvider for ExecutorTestFixtureCreator {
     type Error = TestTrieNodeProviderError;
 
@@ -273,7 +720,7 @@ impl TrieProvider for ExecutorTestFixtureCreator {
                 self.kv_store
                     .lock()
                     .await
-                    .put(key, preimage.clone())
+                    .put(fixture_key(key), preimage.clone())
                     .map_err(|_| TestTrieNodeProviderError::KVStore)?;
 
                 Ok(preimage)
@@ -316,7 +763,7 @@ impl TrieDBProvider for ExecutorTestFixtureCreator {
                 self.kv_store
                     .lock()
                     .await
-                    .put(hash, code.clone())
+                    .put(fixture_key(hash), code.clone())
                     .map_err(|_| TestTrieNodeProviderError::KVStore)?;
 
                 Ok(code)
@@ -339,7 +786,7 @@ impl TrieDBProvider for ExecutorTestFixtureCreator {
                 self.kv_store
                     .lock()
                     .await
-                    .put(hash, preimage.clone())
+                    .put(fixture_key(hash), preimage.clone())
                     .map_err(|_| TestTrieNodeProviderError::KVStore)?;
 
                 Ok(preimage)
@@ -371,7 +818,7 @@ impl TrieProvider for DiskTrieNodeProvider {
         TrieNode::decode(
             &mut self
                 .kv_store
-                .get(key)
+                .get(fixture_key(key))
                 .map_err(|_| TestTrieNodeProviderError::PreimageNotFound)?
                 .ok_or(TestTrieNodeProviderError::PreimageNotFound)?
                 .as_slice(),
@@ -383,7 +830,7 @@ impl TrieProvider for DiskTrieNodeProvider {
 impl TrieDBProvider for DiskTrieNodeProvider {
     fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
         self.kv_store
-            .get(code_hash)
+            .get(fixture_key(code_hash))
             .map_err(|_| TestTrieNodeProviderError::PreimageNotFound)?
             .map(Bytes::from)
             .ok_or(TestTrieNodeProviderError::PreimageNotFound)
@@ -393,7 +840,7 @@ impl TrieDBProvider for DiskTrieNodeProvider {
         Header::decode(
             &mut self
                 .kv_store
-                .get(hash)
+                .get(fixture_key(hash))
                 .map_err(|_| TestTrieNodeProviderError::PreimageNotFound)?
                 .ok_or(TestTrieNodeProviderError::PreimageNotFound)?
                 .as_slice(),
@@ -402,6 +849,77 @@ impl TrieDBProvider for DiskTrieNodeProvider {
     }
 }
 
+/// A [`TrieDBProvider`] that serves preimages from an in-memory map keyed by their hash.
+///
+/// Unlike the [`DiskTrieNodeProvider`], it needs no database on disk, and unlike the
+/// [`NoopTrieDBProvider`](crate::NoopTrieDBProvider), it can serve the state a block executes on.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTrieNodeProvider {
+    preimages: HashMap<B256, Bytes>,
+}
+
+impl MemoryTrieNodeProvider {
+    /// Creates a new [`MemoryTrieNodeProvider`] serving the given preimages.
+    pub const fn new(preimages: HashMap<B256, Bytes>) -> Self {
+        Self { preimages }
+    }
+
+    /// Creates a new [`MemoryTrieNodeProvider`] holding every entry of the given [`rocksdb`] K/V
+    /// store, keyed by the hash of its preimage.
+    pub fn from_kv_store(kv_store: &DB) -> Self {
+        let preimages = kv_store
+            .iterator(rocksdb::IteratorMode::Start)
+            .map(|entry| {
+                let (_, value) = entry.expect("Failed to read key value store");
+                (keccak256(&value), Bytes::from(value.into_vec()))
+            })
+            .collect();
+        Self::new(preimages)
+    }
+
+    /// Creates a new [`MemoryTrieNodeProvider`] holding the key value store of the fixture at
+    /// `fixture_path`.
+    pub async fn from_fixture(fixture_path: &Path) -> Self {
+        let (_fixture_dir, kv_store) = open_fixture(fixture_path).await;
+        Self::from_kv_store(&kv_store)
+    }
+
+    /// Inserts the preimage of `hash`.
+    pub fn insert(&mut self, hash: B256, preimage: Bytes) {
+        self.preimages.insert(hash, preimage);
+    }
+
+    /// Inserts an RLP encoded preimage keyed by its hash.
+    pub fn insert_rlp<T: Encodable>(&mut self, value: &T) -> B256 {
+        let preimage = Bytes::from(alloy_rlp::encode(value));
+        let hash = keccak256(&preimage);
+        self.insert(hash, preimage);
+        hash
+    }
+
+    fn preimage(&self, hash: B256) -> Result<&Bytes, TestTrieNodeProviderError> {
+        self.preimages.get(&hash).ok_or(TestTrieNodeProviderError::PreimageNotFound)
+    }
+}
+
+impl TrieProvider for MemoryTrieNodeProvider {
+    type Error = TestTrieNodeProviderError;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        TrieNode::decode(&mut self.preimage(key)?.as_ref()).map_err(TestTrieNodeProviderError::Rlp)
+    }
+}
+
+impl TrieDBProvider for MemoryTrieNodeProvider {
+    fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+        self.preimage(code_hash).cloned()
+    }
+
+    fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+        Header::decode(&mut self.preimage(hash)?.as_ref()).map_err(TestTrieNodeProviderError::Rlp)
+    }
+}
+
 /// An error type for the [`DiskTrieNodeProvider`] and [`ExecutorTestFixtureCreator`].
 #[derive(Debug, thiserror::Error)]
 pub enum TestTrieNodeProviderError {
@@ -418,56 +936,431 @@ pub enum TestTrieNodeProviderError {
 
 /// Creates a custom rollup config for the facet chain when not found in registry
 fn create_custom_facet_config(chain_id: u64) -> RollupConfig {
-    RollupConfig {
-        genesis: ChainGenesis {
-            l1: BlockNumHash {
-                hash: b256!("0x481724ee99b1f4cb71d826e2ec5a37265f460e9b112315665c977f4050b0af54"),
-                number: 10,
+    let facet = RollupConfig::facet();
+    FacetRollupConfigBuilder::new(facet.genesis, facet.hardforks)
+        .l2_chain_id(chain_id)
+        .build()
+        .expect("The canonical Facet config is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoopTrieDBProvider, TrieDB};
+    use alloy_consensus::{Receipt, ReceiptWithBloom, SignableTransaction, TxLegacy};
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_primitives::Signature;
+    use alloy_transport::mock::Asserter;
+    use alloy_trie::EMPTY_ROOT_HASH;
+    use op_alloy_consensus::{OpTxEnvelope, TxDeposit};
+    use revm::Database;
+
+    fn sample_fixture() -> ExecutorTestFixture {
+        ExecutorTestFixture {
+            rollup_config: create_custom_facet_config(0xface7),
+            parent_header: Header { number: 1, gas_limit: 30_000_000, ..Default::default() },
+            executing_payload: OpPayloadAttributes {
+                payload_attributes: PayloadAttributes {
+                    timestamp: 12,
+                    prev_randao: B256::ZERO,
+                    suggested_fee_recipient: Address::ZERO,
+                    withdrawals: Some(vec![]),
+                    parent_beacon_block_root: Some(B256::ZERO),
+                },
+                transactions: Some(vec![Bytes::from_static(&[0x7e, 0x01, 0x02])]),
+                no_tx_pool: Some(true),
+                gas_limit: Some(30_000_000),
+                eip_1559_params: None,
             },
-            l2: BlockNumHash {
-                hash: b256!("0x88aedfbf7dea6bfa2c4ff315784ad1a7f145d8f650969359c003bbed68c87631"),
-                number: 0,
+            expected_block_hash: B256::repeat_byte(0xab),
+            expected_output_root: None,
+        }
+    }
+
+    #[test]
+    fn test_fixture_id_deterministic() {
+        let a = sample_fixture();
+        let b = sample_fixture();
+        assert_eq!(a.canonical_bytes(), b.canonical_bytes());
+        assert_eq!(a.fixture_id(), b.fixture_id());
+
+        // Round-tripping through the canonical encoding preserves the id.
+        let decoded: ExecutorTestFixture = serde_json::from_slice(&a.canonical_bytes()).unwrap();
+        assert_eq!(decoded.fixture_id(), a.fixture_id());
+
+        let mut c = sample_fixture();
+        c.expected_block_hash = B256::ZERO;
+        assert_ne!(c.fixture_id(), a.fixture_id());
+    }
+
+    #[test]
+    fn test_custom_facet_config_only_overrides_chain_id() {
+        let config = create_custom_facet_config(0xface7);
+        assert_eq!(config.l2_chain_id, 0xface7);
+        assert_eq!(config.hardforks, RollupConfig::facet().hardforks);
+        assert_eq!(config.genesis, RollupConfig::facet().genesis);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_run_fixtures_in_dir_reports_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("block-1.tar.gz"), b"not a tarball").await.unwrap();
+        fs::write(dir.path().join("block-2.tar.gz"), b"not a tarball").await.unwrap();
+        fs::write(dir.path().join("notes.txt"), b"ignored").await.unwrap();
+
+        let summary = run_fixtures_in_dir(dir.path().to_path_buf(), 2).await;
+        assert_eq!(
+            summary.outcomes.iter().map(|o| o.path.clone()).collect::<Vec<_>>(),
+            vec![dir.path().join("block-1.tar.gz"), dir.path().join("block-2.tar.gz")]
+        );
+        assert!(!summary.all_passed());
+        assert_eq!(summary.failures().count(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_disk_preimage_fetcher_serves_fixture_preimages() {
+        use kona_preimage::{
+            BidirectionalChannel, DiskPreimageFetcher, OracleReader, OracleServer, PreimageFetcher,
+            PreimageOracleClient, PreimageOracleServer, errors::PreimageOracleError,
+        };
+
+        // Store a header preimage the way the fixture creator does, keyed by its preimage key.
+        let dir = tempfile::tempdir().unwrap();
+        let creator =
+            ExecutorTestFixtureCreator::new("http://localhost:8545", 7, dir.path().to_path_buf());
+        let header = Header { number: 7, ..Default::default() };
+        let encoded = alloy_rlp::encode(&header);
+        let hash = keccak256(&encoded);
+        creator.kv_store.lock().await.put(fixture_key(hash), &encoded).unwrap();
+        let kv_path = creator.data_dir.join("kv");
+        drop(creator);
+
+        // The executor reads the store back by hash
+        let provider = DiskTrieNodeProvider::new(DB::open_default(&kv_path).unwrap());
+        assert_eq!(provider.header_by_hash(hash).unwrap(), header);
+        drop(provider);
+
+        let fetcher = DiskPreimageFetcher::new(DB::open_default(kv_path).unwrap());
+        let missing = PreimageKey::new_keccak256([0x11; 32]);
+        assert!(matches!(
+            fetcher.get_preimage(missing).await,
+            Err(PreimageOracleError::KeyNotFound)
+        ));
+
+        let channel = BidirectionalChannel::new().unwrap();
+        tokio::task::spawn(async move {
+            let server = OracleServer::new(channel.host);
+            while server.next_preimage_request(&fetcher).await.is_ok() {}
+        });
+        let reader = OracleReader::new(channel.client);
+        assert_eq!(reader.get(PreimageKey::new_keccak256(*hash)).await.unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_memory_provider_serves_preimages() {
+        let mut provider = MemoryTrieNodeProvider::default();
+        let header = Header { number: 9, ..Default::default() };
+        let header_hash = provider.insert_rlp(&header);
+        assert_eq!(header_hash, header.hash_slow());
+        assert_eq!(provider.header_by_hash(header_hash).unwrap(), header);
+
+        let code = Bytes::from_static(&[0x60, 0x00, 0x00]);
+        provider.insert(keccak256(&code), code.clone());
+        assert_eq!(provider.bytecode_by_hash(keccak256(&code)).unwrap(), code);
+
+        assert!(matches!(
+            provider.trie_node_by_hash(B256::repeat_byte(0x11)),
+            Err(TestTrieNodeProviderError::PreimageNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_range_fixture_roundtrip() {
+        let block = sample_fixture();
+        let mut next = sample_fixture();
+        next.parent_header.number = 2;
+        let fixture = ExecutorRangeFixture {
+            rollup_config: block.rollup_config,
+            blocks: [block, next]
+                .into_iter()
+                .map(|f| RangeFixtureBlock {
+                    parent_header: f.parent_header,
+                    executing_payload: f.executing_payload,
+                    expected_block_hash: f.expected_block_hash,
+                })
+                .collect(),
+        };
+
+        let decoded: ExecutorRangeFixture =
+            serde_json::from_slice(&serde_json::to_vec(&fixture).unwrap()).unwrap();
+        assert_eq!(decoded.rollup_config, fixture.rollup_config);
+        assert_eq!(
+            decoded.blocks.iter().map(|b| b.parent_header.number).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(decoded.blocks[1].executing_payload, fixture.blocks[1].executing_payload);
+    }
+
+    #[test]
+    fn test_recording_hinter_captures_trie_hints() {
+        let hinter = RecordingTrieHinter::default();
+        let parent =
+            Header { number: 7, state_root: EMPTY_ROOT_HASH, ..Default::default() }.seal_slow();
+        let mut db = TrieDB::new(parent, NoopTrieDBProvider, &hinter);
+
+        assert!(db.get_trie_account(&Predeploys::L1_BLOCK_INFO, 7).unwrap().is_none());
+        db.storage(Predeploys::L1_BLOCK_INFO, U256::from(3)).unwrap();
+
+        assert_eq!(
+            hinter.hints(),
+            vec![
+                RecordedHint::AccountProof(Predeploys::L1_BLOCK_INFO, 7),
+                RecordedHint::StorageProof(Predeploys::L1_BLOCK_INFO, U256::from(3), 7),
+            ]
+        );
+        assert!(hinter.trie_node_hashes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_fixture_with_recording_hinter() {
+        let config = create_custom_facet_config(0xfacade);
+        let parent = Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            state_root: EMPTY_ROOT_HASH,
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        let (from, to) = (Address::repeat_byte(0x22), Address::repeat_byte(0x33));
+        let deposit = OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+            from,
+            to: alloy_primitives::TxKind::Call(to),
+            gas_limit: 100_000,
+            ..Default::default()
+        }));
+        let payload = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: config.block_time,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: Some(vec![]),
+                parent_beacon_block_root: Some(B256::ZERO),
             },
-            l2_time: 1725557164,
-            system_config: Some(SystemConfig {
-                batcher_address: address!("c81f87a644b41e49b3221f41251f15c6cb00ce03"),
-                overhead: U256::ZERO,
-                scalar: U256::from(1_000_000u64),
+            transactions: Some(vec![deposit.encoded_2718().into()]),
+            no_tx_pool: Some(true),
+            gas_limit: Some(30_000_000),
+            eip_1559_params: None,
+        };
+        let expected_block_hash = StatelessL2Builder::new(
+            &config,
+            OpEvmFactory::default(),
+            NoopTrieDBProvider,
+            NoopTrieHinter,
+            parent.clone().seal_slow(),
+        )
+        .build_block(payload.clone())
+        .unwrap()
+        .header
+        .hash();
+
+        // The parent state is empty, so the fixture's key value store holds no preimages.
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("block-1");
+        drop(DB::open_default(data_dir.join("kv")).unwrap());
+        let fixture = ExecutorTestFixture {
+            rollup_config: config,
+            parent_header: parent.clone(),
+            executing_payload: payload,
+            expected_block_hash,
+            expected_output_root: None,
+        };
+        fs::write(data_dir.join("fixture.json"), fixture.canonical_bytes()).await.unwrap();
+        archive_fixture_dir(&data_dir).await;
+
+        let hinter = RecordingTrieHinter::default();
+        run_test_fixture_with_hinter(dir.path().join("block-1.tar.gz"), &hinter).await;
+
+        let hints = hinter.hints();
+        assert_eq!(hints[0], RecordedHint::ExecutionWitness(parent.hash_slow()));
+        assert!(hints.contains(&RecordedHint::AccountProof(from, 0)));
+        assert!(hints.contains(&RecordedHint::AccountProof(to, 0)));
+    }
+
+    #[test]
+    fn test_receipts_root_mismatch_is_reported() {
+        let fixture = sample_fixture();
+        let timestamp = fixture.executing_payload.payload_attributes.timestamp;
+        let receipts = vec![OpReceiptEnvelope::Eip1559(ReceiptWithBloom {
+            receipt: Receipt { status: true.into(), cumulative_gas_used: 21_000, logs: vec![] },
+            ..Default::default()
+        })];
+        let root = compute_receipts_root(&receipts, &fixture.rollup_config, timestamp);
+
+        assert_eq!(check_receipts_root(&receipts, &fixture.rollup_config, timestamp, root), Ok(()));
+
+        // A header committing to the empty receipts root disagrees with the executed receipts.
+        let header = Header { receipts_root: EMPTY_ROOT_HASH, ..Default::default() };
+        assert_eq!(
+            check_receipts_root(&receipts, &fixture.rollup_config, timestamp, header.receipts_root),
+            Err(ReceiptsRootMismatch { computed: root, expected: EMPTY_ROOT_HASH })
+        );
+    }
+
+    #[test]
+    fn test_full_block_transactions_are_encoded_directly() {
+        let transactions = vec![
+            OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+                input: Bytes::from_static(&[0x01, 0x02]),
+                ..Default::default()
+            })),
+            OpTxEnvelope::Legacy(
+                TxLegacy { nonce: 3, gas_limit: 21_000, ..Default::default() }
+                    .into_signed(Signature::test_signature()),
+            ),
+        ];
+
+        let encoded = encode_transactions(&transactions);
+        assert_eq!(encoded.len(), 2);
+        assert_eq!(encoded[0][0], 0x7e);
+        for (raw, tx) in encoded.iter().zip(&transactions) {
+            assert_eq!(&OpTxEnvelope::decode_2718(&mut raw.as_ref()).unwrap(), tx);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_range_fixture_from_full_blocks() {
+        let config = create_custom_facet_config(0xfacade);
+
+        // Build a short chain on top of an empty state, each block carrying a zero fee transfer.
+        let genesis = Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(0),
+            state_root: EMPTY_ROOT_HASH,
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        let mut builder = StatelessL2Builder::new(
+            &config,
+            OpEvmFactory::default(),
+            NoopTrieDBProvider,
+            NoopTrieHinter,
+            genesis.clone().seal_slow(),
+        );
+        let mut blocks = vec![(genesis, Vec::new())];
+        for number in 1..=2u8 {
+            let transactions = vec![alloy_consensus::TxEnvelope::Legacy(
+                TxLegacy {
+                    gas_limit: 21_000,
+                    to: alloy_primitives::TxKind::Call(Address::with_last_byte(number)),
+                    ..Default::default()
+                }
+                .into_signed(Signature::test_signature()),
+            )];
+            // The attributes `fetch_payload` rebuilds from the header and the full bodies
+            let outcome = builder
+                .build_block(OpPayloadAttributes {
+                    payload_attributes: PayloadAttributes {
+                        timestamp: u64::from(number) * config.block_time,
+                        prev_randao: B256::ZERO,
+                        suggested_fee_recipient: Address::ZERO,
+                        withdrawals: None,
+                        parent_beacon_block_root: Some(B256::ZERO),
+                    },
+                    transactions: Some(encode_transactions(&transactions)),
+                    no_tx_pool: None,
+                    gas_limit: Some(30_000_000),
+                    eip_1559_params: None,
+                })
+                .unwrap();
+            blocks.push((outcome.header.into_inner(), transactions));
+        }
+        let rpc_block = |number: usize| {
+            let (header, transactions) = blocks[number].clone();
+            let transactions = transactions
+                .into_iter()
+                .map(|tx| alloy_rpc_types_eth::Transaction {
+                    inner: alloy_consensus::transaction::Recovered::new_unchecked(
+                        tx,
+                        Address::ZERO,
+                    ),
+                    block_hash: None,
+                    block_number: Some(number as u64),
+                    transaction_index: Some(0),
+                    effective_gas_price: Some(0),
+                })
+                .collect();
+            alloy_rpc_types_eth::Block {
+                header: alloy_rpc_types_eth::Header::new(header),
+                uncles: Vec::new(),
+                transactions: BlockTransactions::Full(transactions),
+                withdrawals: None,
+            }
+        };
+
+        // Serve the chain with full bodies only, so no raw transaction can be fetched.
+        let asserter = Asserter::new();
+        asserter.push_success(&alloy_primitives::U64::from(0xfacade));
+        for number in 1..=2 {
+            asserter.push_success(&rpc_block(number));
+            asserter.push_success(&rpc_block(number - 1));
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let mut creator =
+            ExecutorTestFixtureCreator::new("http://127.0.0.1:1", 1, dir.path().to_path_buf());
+        creator.provider = RootProvider::new(RpcClient::mocked(asserter));
+        creator.create_range_fixture(1, 2).await;
+
+        run_range_fixture(dir.path().join("block-1.tar.gz")).await;
+    }
+
+    #[tokio::test]
+    async fn test_fetch_payload_encodes_full_block_transactions() {
+        let tx = alloy_consensus::TxEnvelope::Legacy(
+            TxLegacy { nonce: 3, gas_limit: 21_000, ..Default::default() }
+                .into_signed(Signature::test_signature()),
+        );
+        let transaction = alloy_rpc_types_eth::Transaction {
+            inner: alloy_consensus::transaction::Recovered::new_unchecked(
+                tx.clone(),
+                Address::ZERO,
+            ),
+            block_hash: None,
+            block_number: Some(8),
+            transaction_index: Some(0),
+            effective_gas_price: Some(0),
+        };
+        let block = |number: u64, transactions| alloy_rpc_types_eth::Block {
+            header: alloy_rpc_types_eth::Header::new(Header {
+                number,
+                timestamp: number * 12,
                 gas_limit: 30_000_000,
-                base_fee_scalar: Some(1368),
-                blob_base_fee_scalar: Some(810949),
                 ..Default::default()
             }),
-        },
-        l1_chain_id: 1, // Ethereum mainnet
-        l2_chain_id: chain_id,
-        block_time: 12,
-        max_sequencer_drift: 600,
-        seq_window_size: 3600,
-        channel_timeout: 300,
-        hardforks: HardForkConfig {
-            regolith_time: Some(0),
-            canyon_time: Some(0),
-            delta_time: Some(0),
-            ecotone_time: Some(0),
-            fjord_time: Some(0),
-            isthmus_time: None,
-            ..Default::default()
-        },
-        batch_inbox_address: address!("ff00000000000000000000000000000000042069"),
-        deposit_contract_address: address!("08073dc48dde578137b8af042bcbc1c2491f1eb2"),
-        l1_system_config_address: address!("94ee52a9d8edd72a85dea7fae3ba6d75e4bf1710"),
-        protocol_versions_address: address!("0000000000000000000000000000000000000000"),
-        superchain_config_address: Some(address!("0000000000000000000000000000000000000000")),
-        da_challenge_address: Some(address!("0000000000000000000000000000000000000000")),
-        blobs_enabled_l1_timestamp: None,
-        granite_channel_timeout: 50,
-        interop_message_expiry_window: 3600,
-        alt_da_config: None,
-        chain_op_config: BaseFeeConfig {
-            eip1559_elasticity: 2,
-            eip1559_denominator: 8,
-            eip1559_denominator_canyon: 8,
-        },
+            uncles: Vec::new(),
+            transactions: BlockTransactions::Full(transactions),
+            withdrawals: None,
+        };
+
+        // Only the block and its parent are served, a raw transaction request would fail.
+        let asserter = Asserter::new();
+        asserter.push_success(&block(8, vec![transaction]));
+        asserter.push_success(&block(7, Vec::new()));
+        let dir = tempfile::tempdir().unwrap();
+        let mut creator =
+            ExecutorTestFixtureCreator::new("http://127.0.0.1:1", 8, dir.path().to_path_buf());
+        creator.provider = RootProvider::new(RpcClient::mocked(asserter));
+
+        let config = create_custom_facet_config(0xfacade);
+        let (parent, header, payload) = creator.fetch_payload(&config, 8).await;
+        assert_eq!(parent.number, 7);
+        assert_eq!(header.number, 8);
+        assert_eq!(payload.payload_attributes.timestamp, 96);
+        assert_eq!(payload.transactions, Some(vec![tx.encoded_2718().into()]));
     }
 }
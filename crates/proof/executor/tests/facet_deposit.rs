@@ -3,18 +3,20 @@
 //! Tests the facet deposit pipeline using existing infrastructure without heavy dependencies
 
 use alloy_primitives::{address, b256, Address, B256, Bytes, U256, hex};
-use alloy_consensus::{TxLegacy, Signed, TxEnvelope, Receipt, Eip658Value, Header, Sealable};
-use alloy_eips::eip2718::Encodable2718;
+use alloy_consensus::{TxLegacy, Signed, TxEnvelope, Receipt, Eip658Value, Header, Sealable, TxReceipt};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
 use alloy_op_evm::OpEvmFactory;
 use kona_protocol::{
-    DEPOSIT_TX_TYPE, FACET_INBOX_ADDRESS, decode_facet_payload,
+    DEPOSIT_TX_TYPE, FACET_INBOX_ADDRESS, FctMintCalculator, decode_facet_payload,
 };
 use kona_derive::derive_facet_deposits;
-use kona_executor::{StatelessL2Builder, NoopTrieDBProvider};
+use kona_executor::{StatelessL2Builder, test_utils::MemoryTrieNodeProvider};
 use kona_genesis::RollupConfig;
 use kona_mpt::NoopTrieHinter;
+use op_alloy_consensus::{OpReceiptEnvelope, TxDeposit};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use alloy_rpc_types_engine::PayloadAttributes;
+use alloy_trie::EMPTY_ROOT_HASH;
 
 #[test]
 fn facet_deposit_derivation_and_execution() {
@@ -39,9 +41,10 @@ fn facet_deposit_derivation_and_execution() {
     let envelope = TxEnvelope::Legacy(signed);
     let receipt = Receipt { status: Eip658Value::Eip658(true), ..Default::default() };
 
-    // 2. Derive deposit transactions using the facet deposits function
+    // 2. Derive deposit transactions using the facet deposits function, at the initial mint rate
     let l2_chain_id = 16436858;
-    let (deposits, _, _) = derive_facet_deposits(&[envelope], &[receipt], l2_chain_id, 1, 0u128, 0u128)
+    let mint_rate = FctMintCalculator::INITIAL_RATE;
+    let (deposits, _, _) = derive_facet_deposits(&[envelope], &[receipt], l2_chain_id, 1, mint_rate, 0u128)
         .expect("derive failed");
     
     assert_eq!(deposits.len(), 1, "Should derive exactly one deposit");
@@ -51,16 +54,35 @@ fn facet_deposit_derivation_and_execution() {
     
     // 3. Verify the deposit transaction format
     assert_eq!(deposit_tx[0], DEPOSIT_TX_TYPE, "Should be deposit transaction type");
-    assert_eq!(deposit_tx.len(), 89, "Should be correct length");
+    // 89 bytes with a zero mint, plus the 8 bytes of the mint amount
+    assert_eq!(deposit_tx.len(), 97, "Should be correct length");
+    
+    // The deposit mints FCT for the 576 L1 data gas of the payload
+    let deposit = TxDeposit::decode_2718(&mut deposit_tx.as_ref()).expect("decode failed");
+    let expected_mint = FctMintCalculator::calculate_mint_amount(
+        FctMintCalculator::calculate_data_gas_used(&input, false),
+        mint_rate,
+    );
+    assert!(expected_mint > 0, "Should mint a nonzero amount");
+    assert_eq!(deposit.mint, Some(expected_mint), "Should mint at the initial rate");
     
-    // 4. Create execution environment
-    let rollup_config = RollupConfig::default();
+    // 4. Create execution environment on an empty parent state, served from memory
+    let rollup_config = RollupConfig::facet();
+    let mut provider = MemoryTrieNodeProvider::default();
     let parent_header = Header {
         number: 1000,
         timestamp: 1_000_000,
         gas_limit: 30_000_000,
+        base_fee_per_gas: Some(1_000_000_000),
+        state_root: EMPTY_ROOT_HASH,
+        withdrawals_root: Some(EMPTY_ROOT_HASH),
+        blob_gas_used: Some(0),
+        excess_blob_gas: Some(0),
+        parent_beacon_block_root: Some(B256::ZERO),
         ..Default::default()
-    }.seal_slow();
+    };
+    provider.insert_rlp(&parent_header);
+    let parent_header = parent_header.seal_slow();
     
     // 5. Create payload attributes with our deposit transaction
     let payload_attrs = OpPayloadAttributes {
@@ -69,7 +91,7 @@ fn facet_deposit_derivation_and_execution() {
             prev_randao: B256::ZERO,
             suggested_fee_recipient: Address::ZERO,
             withdrawals: Some(vec![]),
-            parent_beacon_block_root: None,
+            parent_beacon_block_root: Some(B256::ZERO),
         },
         transactions: Some(deposits),
         no_tx_pool: Some(true),
@@ -77,25 +99,32 @@ fn facet_deposit_derivation_and_execution() {
         eip_1559_params: None,
     };
     
-    // 6. Create executor and test block building (structure validation)
+    // 6. Execute the block with the derived deposit
     let mut executor = StatelessL2Builder::new(
         &rollup_config,
         OpEvmFactory::default(),
-        NoopTrieDBProvider,
+        provider,
         NoopTrieHinter,
-        parent_header,
+        parent_header.clone(),
     );
-    
-    // 7. Test that we can create the execution structure
-    // Note: We can't execute with NoopTrieDBProvider but we can validate the pipeline
-    
-    println!("✅ Facet deposit pipeline validation successful!");
-    println!("✅ L1 transaction → derive → deposit transaction format validated");
-    println!("✅ Executor infrastructure created successfully");
-    println!("✅ Payload attributes structure validated");
-    
-    // The fact that we can create all these structures indicates 
-    // the facet deposits are properly formatted for execution
+    assert!(executor.account(&deposit.from).unwrap().is_none(), "Depositor should start empty");
+    let outcome = executor.build_block(payload_attrs).expect("execution failed");
+    
+    // 7. The deposit is the only transaction and succeeded
+    let receipts = &outcome.execution_result.receipts;
+    assert_eq!(receipts.len(), 1, "Should produce exactly one receipt");
+    assert!(matches!(receipts[0], OpReceiptEnvelope::Deposit(_)), "Should be a deposit receipt");
+    assert!(receipts[0].status(), "Deposit should succeed");
+    assert_eq!(outcome.header.number, parent_header.number + 1);
+    assert_eq!(outcome.header.parent_hash, parent_header.hash());
+    
+    // 8. The minted FCT is credited to the depositor
+    let depositor = executor.account(&deposit.from).unwrap().expect("Depositor should exist");
+    assert_eq!(depositor.balance, U256::from(expected_mint), "Balance should increase by the mint");
+    assert_eq!(depositor.nonce, 1);
+    
+    println!("✅ Facet deposit pipeline execution successful!");
+    println!("✅ L1 transaction → derive → deposit transaction → block {}", outcome.header.number);
 }
 
 #[test]
@@ -0,0 +1,154 @@
+//! Offline end-to-end test of the facet pipeline: synthetic L1 inbox tx ➜ `derive_facet_deposits`
+//! ➜ `StatelessL2Builder`, on top of a small in-memory pre-state.
+
+use alloy_consensus::{
+    EMPTY_ROOT_HASH, Eip658Value, Header, Receipt, Signed, TxEnvelope, TxLegacy, TxReceipt,
+};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_op_evm::OpEvmFactory;
+use alloy_primitives::{
+    Address, B256, Bytes, Sealable, Signature, TxKind, U256, address, keccak256,
+};
+use alloy_rlp::Encodable;
+use alloy_rpc_types_engine::PayloadAttributes;
+use alloy_trie::{HashBuilder, KECCAK_EMPTY, Nibbles, TrieAccount, proof::ProofRetainer};
+use kona_derive::derive_facet_deposits;
+use kona_executor::{StatelessL2Builder, test_utils::MemoryTrieNodeProvider};
+use kona_genesis::RollupConfig;
+use kona_mpt::NoopTrieHinter;
+use kona_protocol::{FACET_INBOX_ADDRESS, FctMintCalculator, encode_facet_payload};
+use op_alloy_consensus::TxDeposit;
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+/// Facet payload target, which stores the first calldata word in slot 0
+const TARGET: Address = address!("0x1111111111111111111111111111111111111111");
+
+/// `PUSH1 0, CALLDATALOAD, PUSH1 0, SSTORE, STOP`
+const TARGET_CODE: [u8; 7] = [0x60, 0x00, 0x35, 0x60, 0x00, 0x55, 0x00];
+
+/// Root of a secure trie over `(unhashed key, RLP value)` pairs, and its nodes.
+fn secure_trie(entries: &[(&[u8], Vec<u8>)]) -> (B256, Vec<Bytes>) {
+    let mut leaves = entries
+        .iter()
+        .map(|(key, value)| (Nibbles::unpack(keccak256(key)), value.clone()))
+        .collect::<Vec<_>>();
+    leaves.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut hb = HashBuilder::default()
+        .with_proof_retainer(ProofRetainer::new(leaves.iter().map(|(k, _)| k.clone()).collect()));
+    for (key, value) in &leaves {
+        hb.add_leaf(key.clone(), value);
+    }
+    let root = hb.root();
+    (root, hb.take_proof_nodes().values().cloned().collect())
+}
+
+fn rlp<T: Encodable>(value: &T) -> Vec<u8> {
+    let mut out = Vec::with_capacity(value.length());
+    value.encode(&mut out);
+    out
+}
+
+#[test]
+fn test_facet_derive_then_execute_offline() {
+    let config = RollupConfig::facet();
+
+    // 1. Derive the deposit of an L1 tx calling the target through the facet inbox.
+    let payload = encode_facet_payload(
+        config.l2_chain_id,
+        Some(TARGET),
+        U256::ZERO,
+        1_000_000,
+        &[0x12, 0x34],
+        &[],
+    );
+    let l1_tx = TxEnvelope::Legacy(Signed::new_unchecked(
+        TxLegacy {
+            chain_id: Some(1),
+            gas_limit: 100_000,
+            to: TxKind::Call(FACET_INBOX_ADDRESS),
+            input: payload,
+            ..Default::default()
+        },
+        Signature::test_signature(),
+        Default::default(),
+    ));
+    let receipt = Receipt { status: Eip658Value::Eip658(true), ..Default::default() };
+    let (deposits, _, _) = derive_facet_deposits(
+        &[l1_tx],
+        &[receipt],
+        config.l2_chain_id,
+        1,
+        FctMintCalculator::INITIAL_RATE,
+        0,
+    )
+    .unwrap();
+    assert_eq!(deposits.len(), 1);
+    let deposit = TxDeposit::decode_2718(&mut deposits[0].as_ref()).unwrap();
+    let mint = deposit.mint.unwrap();
+    assert!(mint > 0);
+
+    // 2. Seed the pre-state with the target contract only.
+    let mut provider = MemoryTrieNodeProvider::default();
+    provider.insert(keccak256(TARGET_CODE), Bytes::from_static(&TARGET_CODE));
+    let target_pre = TrieAccount {
+        storage_root: EMPTY_ROOT_HASH,
+        code_hash: keccak256(TARGET_CODE),
+        ..Default::default()
+    };
+    let (pre_state_root, nodes) = secure_trie(&[(TARGET.as_slice(), rlp(&target_pre))]);
+    nodes.into_iter().for_each(|node| provider.insert(keccak256(&node), node));
+
+    // 3. Execute the derived deposits on top of it.
+    let parent = Header {
+        gas_limit: 30_000_000,
+        base_fee_per_gas: Some(1_000_000_000),
+        state_root: pre_state_root,
+        withdrawals_root: Some(EMPTY_ROOT_HASH),
+        blob_gas_used: Some(0),
+        excess_blob_gas: Some(0),
+        parent_beacon_block_root: Some(B256::ZERO),
+        ..Default::default()
+    };
+    let mut builder = StatelessL2Builder::new(
+        &config,
+        OpEvmFactory::default(),
+        provider,
+        NoopTrieHinter,
+        parent.seal_slow(),
+    );
+    let outcome = builder
+        .build_block(OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: config.block_time,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: Some(vec![]),
+                parent_beacon_block_root: Some(B256::ZERO),
+            },
+            transactions: Some(deposits),
+            no_tx_pool: Some(true),
+            gas_limit: Some(30_000_000),
+            eip_1559_params: None,
+        })
+        .unwrap();
+    assert!(outcome.execution_result.receipts.iter().all(|r| r.status()));
+
+    // 4. The post-state holds exactly the written target slot and the minted depositor balance.
+    let slot_value = U256::from(0x1234) << 240;
+    let (target_storage_root, _) = secure_trie(&[(B256::ZERO.as_slice(), rlp(&slot_value))]);
+    let target_post = TrieAccount { storage_root: target_storage_root, ..target_pre };
+    let depositor_post = TrieAccount {
+        nonce: 1,
+        balance: U256::from(mint),
+        storage_root: EMPTY_ROOT_HASH,
+        code_hash: KECCAK_EMPTY,
+    };
+    let (expected_state_root, _) = secure_trie(&[
+        (TARGET.as_slice(), rlp(&target_post)),
+        (deposit.from.as_slice(), rlp(&depositor_post)),
+    ]);
+
+    assert_ne!(outcome.header.state_root, pre_state_root);
+    assert_eq!(outcome.header.state_root, expected_state_root);
+}
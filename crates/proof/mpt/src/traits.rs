@@ -82,3 +82,32 @@ pub trait TrieHinter {
         op_payload_attributes: &OpPayloadAttributes,
     ) -> Result<(), Self::Error>;
 }
+
+impl<T: TrieHinter + ?Sized> TrieHinter for &T {
+    type Error = T::Error;
+
+    fn hint_trie_node(&self, hash: B256) -> Result<(), Self::Error> {
+        (**self).hint_trie_node(hash)
+    }
+
+    fn hint_account_proof(&self, address: Address, block_number: u64) -> Result<(), Self::Error> {
+        (**self).hint_account_proof(address, block_number)
+    }
+
+    fn hint_storage_proof(
+        &self,
+        address: Address,
+        slot: U256,
+        block_number: u64,
+    ) -> Result<(), Self::Error> {
+        (**self).hint_storage_proof(address, slot, block_number)
+    }
+
+    fn hint_execution_witness(
+        &self,
+        parent_hash: B256,
+        op_payload_attributes: &OpPayloadAttributes,
+    ) -> Result<(), Self::Error> {
+        (**self).hint_execution_witness(parent_hash, op_payload_attributes)
+    }
+}
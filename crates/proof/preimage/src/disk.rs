@@ -0,0 +1,63 @@
+//! A [PreimageFetcher] backed by a [rocksdb] key-value store.
+
+use crate::{
+    PreimageFetcher, PreimageKey,
+    errors::{PreimageOracleError, PreimageOracleResult},
+};
+use async_trait::async_trait;
+use rocksdb::DB;
+
+/// A [PreimageFetcher] that serves the preimages of a [rocksdb] K/V store keyed by their
+/// [PreimageKey], such as the K/V store of an executor test fixture. Every request is a single
+/// point read of its key.
+#[derive(Debug)]
+pub struct DiskPreimageFetcher {
+    kv_store: DB,
+}
+
+impl DiskPreimageFetcher {
+    /// Creates a new [DiskPreimageFetcher] serving the preimages of the given [rocksdb] K/V store.
+    pub const fn new(kv_store: DB) -> Self {
+        Self { kv_store }
+    }
+}
+
+#[async_trait]
+impl PreimageFetcher for DiskPreimageFetcher {
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        self.kv_store
+            .get(<[u8; 32]>::from(key))
+            .map_err(|e| PreimageOracleError::Other(e.to_string()))?
+            .ok_or(PreimageOracleError::KeyNotFound)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PreimageKeyType;
+    use alloy_primitives::keccak256;
+
+    #[tokio::test]
+    async fn test_disk_fetcher_reads_typed_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let kv_store = DB::open_default(dir.path()).unwrap();
+
+        let preimage = b"facet".to_vec();
+        let hash = keccak256(&preimage);
+        let key = PreimageKey::new_keccak256(*hash);
+        kv_store.put(<[u8; 32]>::from(key), &preimage).unwrap();
+        // An entry under the raw hash is not a preimage key
+        kv_store.put(hash, b"raw").unwrap();
+
+        let fetcher = DiskPreimageFetcher::new(kv_store);
+        assert_eq!(fetcher.get_preimage(key).await.unwrap(), preimage);
+
+        // The key type is part of the key
+        let sha256 = PreimageKey::new(*hash, PreimageKeyType::Sha256);
+        assert!(matches!(
+            fetcher.get_preimage(sha256).await,
+            Err(PreimageOracleError::KeyNotFound)
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 //! Errors for the `kona-preimage` crate.
 
 use alloc::string::String;
+use core::time::Duration;
 use thiserror::Error;
 
 /// A [PreimageOracleError] is an enum that differentiates pipe-related errors from other errors
@@ -47,6 +48,9 @@ pub enum ChannelError {
     /// Buffer length mismatch.
     #[error("Buffer length mismatch. Expected {0}, got {1}.")]
     BufferLengthMismatch(usize, usize),
+    /// No data arrived from the peer within the read timeout.
+    #[error("Timed out after {0:?} waiting for the channel.")]
+    Timeout(Duration),
 }
 
 /// A [Result] type for the [ChannelError] enum.
@@ -18,7 +18,7 @@ mod key;
 pub use key::{PreimageKey, PreimageKeyType};
 
 mod oracle;
-pub use oracle::{OracleReader, OracleServer};
+pub use oracle::{OracleReader, OracleServer, PreimageStream};
 
 mod hint;
 pub use hint::{HintReader, HintWriter};
@@ -33,3 +33,8 @@ pub use traits::{
 mod native_channel;
 #[cfg(feature = "std")]
 pub use native_channel::{BidirectionalChannel, NativeChannel};
+
+#[cfg(feature = "rocksdb")]
+mod disk;
+#[cfg(feature = "rocksdb")]
+pub use disk::DiskPreimageFetcher;
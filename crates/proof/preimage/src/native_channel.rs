@@ -1,16 +1,17 @@
-//! Native implementation of the [Channel] trait, backed by [async_channel]'s unbounded
-//! channel primitives.
+//! Native implementation of the [Channel] trait, backed by [async_channel]'s unbounded or
+//! bounded channel primitives.
 
 use crate::{
     Channel,
     errors::{ChannelError, ChannelResult},
 };
-use async_channel::{Receiver, Sender, unbounded};
+use async_channel::{Receiver, Sender, bounded, unbounded};
 use async_trait::async_trait;
 use std::{
     collections::VecDeque,
     io::Result,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 /// A bidirectional channel, allowing for synchronized communication between two parties.
@@ -28,10 +29,36 @@ impl BidirectionalChannel {
         let (bw, ar) = unbounded();
         let (aw, br) = unbounded();
 
-        Ok(Self {
-            client: NativeChannel { read: ar, write: aw, read_buffer: Arc::new(Mutex::new(VecDeque::new())) },
-            host: NativeChannel { read: br, write: bw, read_buffer: Arc::new(Mutex::new(VecDeque::new())) },
-        })
+        Ok(Self { client: NativeChannel::new(ar, aw), host: NativeChannel::new(br, bw) })
+    }
+
+    /// Creates a [BidirectionalChannel] instance that holds at most `capacity` unread writes in
+    /// each direction. See [NativeChannel::write] for the backpressure this applies.
+    ///
+    /// # Errors
+    ///
+    /// Returns [std::io::ErrorKind::InvalidInput] if `capacity` is zero, as a channel that holds
+    /// no writes could never be written to.
+    pub fn bounded(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "bounded channel capacity must be at least 1",
+            ));
+        }
+        let (bw, ar) = bounded(capacity);
+        let (aw, br) = bounded(capacity);
+
+        Ok(Self { client: NativeChannel::new(ar, aw), host: NativeChannel::new(br, bw) })
+    }
+
+    /// Sets the default read timeout of both ends, see [NativeChannel::with_read_timeout].
+    #[cfg(feature = "timeout")]
+    pub const fn with_read_timeout(self, timeout: Duration) -> Self {
+        Self {
+            client: self.client.with_read_timeout(timeout),
+            host: self.host.with_read_timeout(timeout),
+        }
     }
 }
 
@@ -44,11 +71,68 @@ pub struct NativeChannel {
     pub(crate) write: Sender<Vec<u8>>,
     /// Buffer for accumulating partial reads
     read_buffer: Arc<Mutex<VecDeque<u8>>>,
+    /// How long [Channel::read] and [Channel::read_exact] wait for the peer, if bounded
+    #[cfg(feature = "timeout")]
+    read_timeout: Option<Duration>,
 }
 
-#[async_trait]
-impl Channel for NativeChannel {
-    async fn read(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+impl NativeChannel {
+    fn new(read: Receiver<Vec<u8>>, write: Sender<Vec<u8>>) -> Self {
+        Self {
+            read,
+            write,
+            read_buffer: Arc::new(Mutex::new(VecDeque::new())),
+            #[cfg(feature = "timeout")]
+            read_timeout: None,
+        }
+    }
+
+    /// The default read timeout, see [NativeChannel::with_read_timeout].
+    #[cfg(feature = "timeout")]
+    const fn default_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// Without the `timeout` feature, reads wait for the peer indefinitely.
+    #[cfg(not(feature = "timeout"))]
+    const fn default_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Sets the default timeout of [Channel::read] and [Channel::read_exact], after which they
+    /// return [ChannelError::Timeout] instead of waiting for a peer that may have died.
+    ///
+    /// The timer is tokio's, so reads must be polled within a tokio runtime.
+    #[cfg(feature = "timeout")]
+    pub const fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Like [Channel::read], but returns [ChannelError::Timeout] if no data arrives within
+    /// `timeout`. Data already buffered from an earlier message is returned immediately.
+    #[cfg(feature = "timeout")]
+    pub async fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> ChannelResult<usize> {
+        self.read_with_timeout(buf, Some(timeout)).await
+    }
+
+    /// Receives the next message from the peer, waiting at most `timeout` if one is given.
+    async fn recv(&self, timeout: Option<Duration>) -> ChannelResult<Vec<u8>> {
+        let data = match timeout {
+            #[cfg(feature = "timeout")]
+            Some(timeout) => tokio::time::timeout(timeout, self.read.recv())
+                .await
+                .map_err(|_| ChannelError::Timeout(timeout))?,
+            _ => self.read.recv().await,
+        };
+        data.map_err(|_| ChannelError::Closed)
+    }
+
+    async fn read_with_timeout(
+        &self,
+        buf: &mut [u8],
+        timeout: Option<Duration>,
+    ) -> ChannelResult<usize> {
         // Check if we have buffered data first
         {
             let mut read_buffer = self.read_buffer.lock().unwrap();
@@ -62,7 +146,7 @@ impl Channel for NativeChannel {
         } // Drop lock before await
         
         // Otherwise, receive new data
-        let data = self.read.recv().await.map_err(|_| ChannelError::Closed)?;
+        let data = self.recv(timeout).await?;
         let len = data.len().min(buf.len());
         buf[..len].copy_from_slice(&data[..len]);
         
@@ -74,6 +158,13 @@ impl Channel for NativeChannel {
         
         Ok(len)
     }
+}
+
+#[async_trait]
+impl Channel for NativeChannel {
+    async fn read(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        self.read_with_timeout(buf, self.default_timeout()).await
+    }
 
     async fn read_exact(&self, buf: &mut [u8]) -> ChannelResult<usize> {
         let mut total_read = 0;
@@ -90,7 +181,7 @@ impl Channel for NativeChannel {
             
             // If we still need more data, receive from channel
             if total_read < buf.len() {
-                let data = self.read.recv().await.map_err(|_| ChannelError::Closed)?;
+                let data = self.recv(self.default_timeout()).await?;
                 let remaining = buf.len() - total_read;
                 let to_copy = data.len().min(remaining);
                 
@@ -108,8 +199,101 @@ impl Channel for NativeChannel {
         Ok(total_read)
     }
 
+    /// Sends `buf` to the other end as a single message, which is all-or-nothing: either all of
+    /// `buf` is queued for the reader, or nothing is and the channel is closed.
+    ///
+    /// On a channel created with [BidirectionalChannel::bounded], each write takes one slot until
+    /// the reader receives it. Once `capacity` writes are unread, `write` waits for the reader to
+    /// drain one, so a slow reader throttles the writer instead of growing the queue.
     async fn write(&self, buf: &[u8]) -> ChannelResult<usize> {
         self.write.send(buf.to_vec()).await.map_err(|_| ChannelError::Closed)?;
         Ok(buf.len())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_bounded_channel_blocks_writer_until_drained() {
+        let err = BidirectionalChannel::bounded(0).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        let chan = BidirectionalChannel::bounded(2).unwrap();
+
+        chan.host.write(&[1]).await.unwrap();
+        chan.host.write(&[2]).await.unwrap();
+
+        // The third write waits for a free slot
+        let writer = tokio::spawn({
+            let host = chan.host.clone();
+            async move { host.write(&[3]).await.unwrap() }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!writer.is_finished());
+
+        let mut buf = [0u8; 1];
+        chan.client.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [1]);
+        assert_eq!(tokio::time::timeout(Duration::from_secs(1), writer).await.unwrap().unwrap(), 1);
+
+        let mut buf = [0u8; 2];
+        chan.client.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [2, 3]);
+    }
+
+    #[cfg(feature = "timeout")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_read_times_out_on_silent_writer() {
+        let chan = BidirectionalChannel::new().unwrap();
+        let timeout = Duration::from_millis(20);
+
+        let mut buf = [0u8; 4];
+        let err = chan.client.read_timeout(&mut buf, timeout).await.unwrap_err();
+        assert!(matches!(err, ChannelError::Timeout(t) if t == timeout));
+
+        // The default timeout also bounds the trait reads
+        let chan = chan.with_read_timeout(timeout);
+        assert!(matches!(chan.client.read(&mut buf).await, Err(ChannelError::Timeout(_))));
+        assert!(matches!(chan.client.read_exact(&mut buf).await, Err(ChannelError::Timeout(_))));
+
+        // Data left over from an earlier message is returned without waiting
+        chan.host.write(&[1, 2, 3, 4, 5, 6]).await.unwrap();
+        chan.client.read_exact(&mut buf).await.unwrap();
+        assert_eq!(chan.client.read_timeout(&mut buf, timeout).await.unwrap(), 2);
+        assert_eq!(buf[..2], [5, 6]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reassembles_writes_across_reads() {
+        let chan = BidirectionalChannel::new().unwrap();
+        let payload = (0..=255u8).cycle().take(1000).collect::<Vec<_>>();
+
+        // Several small writes of uneven sizes, read back with one large read
+        let mut written = 0;
+        for size in [1, 7, 64, 128, 300, 500] {
+            let n = chan.host.write(&payload[written..written + size]).await.unwrap();
+            assert_eq!(n, size);
+            written += n;
+        }
+        assert_eq!(written, payload.len());
+
+        let mut buf = vec![0u8; payload.len()];
+        assert_eq!(chan.client.read_exact(&mut buf).await.unwrap(), payload.len());
+        assert_eq!(buf, payload);
+
+        // One large write, read back in small chunks that straddle the buffered remainder
+        chan.host.write(&payload).await.unwrap();
+        chan.host.write(&[0xff; 3]).await.unwrap();
+        let mut read = Vec::new();
+        let mut chunk = [0u8; 33];
+        while read.len() < payload.len() + 3 {
+            let want = chunk.len().min(payload.len() + 3 - read.len());
+            chan.client.read_exact(&mut chunk[..want]).await.unwrap();
+            read.extend_from_slice(&chunk[..want]);
+        }
+        assert_eq!(read[..payload.len()], payload[..]);
+        assert_eq!(read[payload.len()..], [0xff; 3]);
+    }
+}
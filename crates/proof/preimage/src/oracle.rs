@@ -37,6 +37,85 @@ where
         trace!(target: "oracle_client", "Received length prefix: {length} bytes for key: {key}");
         Ok(length)
     }
+
+    /// Get the data corresponding to the key from the host, writing it into the front of the
+    /// provided buffer. Returns the length of the preimage.
+    ///
+    /// If the buffer is shorter than the preimage, the preimage is discarded and a
+    /// [PreimageOracleError::BufferLengthMismatch] is returned.
+    pub async fn get_into(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<usize> {
+        let mut stream = self.get_streaming(key).await?;
+        let length = stream.len();
+        if buf.len() < length {
+            stream.discard().await?;
+            return Err(PreimageOracleError::BufferLengthMismatch(length, buf.len()));
+        }
+
+        self.channel.read_exact(&mut buf[..length]).await?;
+        Ok(length)
+    }
+
+    /// Request the data corresponding to the key from the host, returning a [PreimageStream] that
+    /// reads it in caller-sized chunks rather than allocating the whole preimage.
+    ///
+    /// The stream must be read to the end before the next request is made on this reader.
+    pub async fn get_streaming(
+        &self,
+        key: PreimageKey,
+    ) -> PreimageOracleResult<PreimageStream<'_, C>> {
+        let length = self.write_key(key).await?;
+        Ok(PreimageStream { channel: &self.channel, length, remaining: length })
+    }
+}
+
+/// A preimage being read from the host in chunks, returned by [OracleReader::get_streaming].
+#[derive(Debug)]
+pub struct PreimageStream<'a, C> {
+    channel: &'a C,
+    length: usize,
+    remaining: usize,
+}
+
+impl<C> PreimageStream<'_, C>
+where
+    C: Channel,
+{
+    /// Returns the length of the preimage.
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the preimage is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the number of bytes of the preimage that have not been read yet.
+    pub const fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Reads the next chunk of the preimage into the front of `buf`, returning its length.
+    ///
+    /// Every chunk fills `buf` except for the last one. Returns `0` once the whole preimage has
+    /// been read.
+    pub async fn read_chunk(&mut self, buf: &mut [u8]) -> PreimageOracleResult<usize> {
+        let chunk = buf.len().min(self.remaining);
+        if chunk == 0 {
+            return Ok(0);
+        }
+
+        self.channel.read_exact(&mut buf[..chunk]).await?;
+        self.remaining -= chunk;
+        Ok(chunk)
+    }
+
+    /// Reads and drops the rest of the preimage.
+    async fn discard(&mut self) -> PreimageOracleResult<()> {
+        let mut scratch = [0u8; 256];
+        while self.read_chunk(&mut scratch).await? != 0 {}
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -212,6 +291,47 @@ mod test {
         assert_eq!(contents_b, MOCK_DATA_B);
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_oracle_reader_streams_large_preimage() {
+        let data = (0..10_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let key = PreimageKey::new(*keccak256(&data), PreimageKeyType::Keccak256);
+        let preimages = Arc::new(Mutex::new(HashMap::from([(key, data.clone())])));
+
+        let preimage_channel = BidirectionalChannel::new().unwrap();
+        tokio::task::spawn(async move {
+            let oracle_server = OracleServer::new(preimage_channel.host);
+            let test_fetcher = TestFetcher { preimages };
+            while oracle_server.next_preimage_request(&test_fetcher).await.is_ok() {}
+        });
+
+        let oracle_reader = OracleReader::new(preimage_channel.client);
+        let whole = oracle_reader.get(key).await.unwrap();
+
+        // Reassemble the preimage from fixed-size chunks.
+        let mut stream = oracle_reader.get_streaming(key).await.unwrap();
+        assert_eq!(stream.len(), data.len());
+        let (mut chunk, mut streamed) = ([0u8; 1024], Vec::new());
+        loop {
+            let read = stream.read_chunk(&mut chunk).await.unwrap();
+            if read == 0 {
+                break;
+            }
+            assert!(read == chunk.len() || stream.remaining() == 0);
+            streamed.extend_from_slice(&chunk[..read]);
+        }
+        assert_eq!(streamed, whole);
+
+        // A short buffer is rejected without desynchronizing the channel.
+        let mut short = [0u8; 16];
+        assert!(matches!(
+            oracle_reader.get_into(key, &mut short).await,
+            Err(PreimageOracleError::BufferLengthMismatch(10_000, 16))
+        ));
+        let mut buf = alloc::vec![0u8; 12_000];
+        assert_eq!(oracle_reader.get_into(key, &mut buf).await.unwrap(), data.len());
+        assert_eq!(&buf[..data.len()], whole.as_slice());
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_oracle_client_and_host() {
         const MOCK_DATA_A: &[u8] = b"1234567890";
@@ -134,11 +134,15 @@ pub trait Channel {
 
     /// Asynchronously write the provided buffer to the channel.
     ///
+    /// Writes are all-or-nothing: `write` only returns once all of `buf` was written, so callers
+    /// must not loop over it like `std::io::Write::write`. The reader is not told where one write
+    /// ends and the next begins, and reassembles them with [Channel::read_exact].
+    ///
     /// # Arguments
     /// - `buf`: The buffer to write to the host.
     ///
     /// # Returns
-    /// - `Ok(usize)`: The number of bytes written.
+    /// - `Ok(usize)`: The number of bytes written, always `buf.len()`.
     /// - `Err(_)` if the data could not be written.
     async fn write(&self, buf: &[u8]) -> ChannelResult<usize>;
 }
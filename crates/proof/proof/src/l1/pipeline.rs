@@ -9,8 +9,8 @@ use kona_derive::{
     pipeline::{DerivationPipeline, PipelineBuilder},
     prelude::{AttributesQueueStage, StatefulAttributesBuilder},
     traits::{
-        ChainProvider, DataAvailabilityProvider, L2ChainProvider, OriginProvider, Pipeline,
-        SignalReceiver,
+        BlobProvider, ChainProvider, DataAvailabilityProvider, L2ChainProvider, OriginProvider,
+        Pipeline, SignalReceiver,
     },
     types::{PipelineResult, ResetSignal, Signal, StepResult},
 };
@@ -21,34 +21,36 @@ use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use spin::RwLock;
 
 /// An oracle-backed derivation pipeline.
-pub type ProviderDerivationPipeline<L1, L2, DA> =
-    DerivationPipeline<AttributesQueueStage<DA, L1, L2, ProviderAttributesBuilder<L1, L2>>, L2>;
+pub type ProviderDerivationPipeline<L1, L2, DA, B> =
+    DerivationPipeline<AttributesQueueStage<DA, L1, L2, ProviderAttributesBuilder<L1, L2, B>>, L2>;
 
 /// An oracle-backed payload attributes builder for the `AttributesQueue` stage of the derivation
 /// pipeline.
-pub type ProviderAttributesBuilder<L1, L2> = StatefulAttributesBuilder<L1, L2>;
+pub type ProviderAttributesBuilder<L1, L2, B> = StatefulAttributesBuilder<L1, L2, B>;
 
 /// An oracle-backed derivation pipeline.
 #[derive(Debug)]
-pub struct OraclePipeline<O, L1, L2, DA>
+pub struct OraclePipeline<O, L1, L2, DA, B>
 where
     O: CommsClient + FlushableCache + Send + Sync + Debug,
     L1: ChainProvider + Send + Sync + Debug + Clone,
     L2: L2ChainProvider + Send + Sync + Debug + Clone,
     DA: DataAvailabilityProvider + Send + Sync + Debug + Clone,
+    B: BlobProvider + Send + Sync + Debug + Clone,
 {
     /// The internal derivation pipeline.
-    pub pipeline: ProviderDerivationPipeline<L1, L2, DA>,
+    pub pipeline: ProviderDerivationPipeline<L1, L2, DA, B>,
     /// The caching oracle.
     pub caching_oracle: Arc<O>,
 }
 
-impl<O, L1, L2, DA> OraclePipeline<O, L1, L2, DA>
+impl<O, L1, L2, DA, B> OraclePipeline<O, L1, L2, DA, B>
 where
     O: CommsClient + FlushableCache + FlushableCache + Send + Sync + Debug,
     L1: ChainProvider + Send + Sync + Debug + Clone,
     L2: L2ChainProvider + Send + Sync + Debug + Clone,
     DA: DataAvailabilityProvider + Send + Sync + Debug + Clone,
+    B: BlobProvider + Send + Sync + Debug + Clone,
 {
     /// Constructs a new oracle-backed derivation pipeline.
     pub async fn new(
@@ -56,6 +58,7 @@ where
         sync_start: Arc<RwLock<PipelineCursor>>,
         caching_oracle: Arc<O>,
         da_provider: DA,
+        blob_provider: B,
         chain_provider: L1,
         mut l2_chain_provider: L2,
     ) -> PipelineResult<Self> {
@@ -63,7 +66,8 @@ where
             cfg.clone(),
             l2_chain_provider.clone(),
             chain_provider.clone(),
-        );
+        )
+        .with_blob_provider(blob_provider);
 
         let mut pipeline = PipelineBuilder::new()
             .rollup_config(cfg.clone())
@@ -94,13 +98,14 @@ where
     }
 }
 
-impl<O, L1, L2, DA> DriverPipeline<ProviderDerivationPipeline<L1, L2, DA>>
-    for OraclePipeline<O, L1, L2, DA>
+impl<O, L1, L2, DA, B> DriverPipeline<ProviderDerivationPipeline<L1, L2, DA, B>>
+    for OraclePipeline<O, L1, L2, DA, B>
 where
     O: CommsClient + FlushableCache + Send + Sync + Debug,
     L1: ChainProvider + Send + Sync + Debug + Clone,
     L2: L2ChainProvider + Send + Sync + Debug + Clone,
     DA: DataAvailabilityProvider + Send + Sync + Debug + Clone,
+    B: BlobProvider + Send + Sync + Debug + Clone,
 {
     /// Flushes the cache on re-org.
     fn flush(&mut self) {
@@ -109,12 +114,13 @@ where
 }
 
 #[async_trait]
-impl<O, L1, L2, DA> SignalReceiver for OraclePipeline<O, L1, L2, DA>
+impl<O, L1, L2, DA, B> SignalReceiver for OraclePipeline<O, L1, L2, DA, B>
 where
     O: CommsClient + FlushableCache + Send + Sync + Debug,
     L1: ChainProvider + Send + Sync + Debug + Clone,
     L2: L2ChainProvider + Send + Sync + Debug + Clone,
     DA: DataAvailabilityProvider + Send + Sync + Debug + Clone,
+    B: BlobProvider + Send + Sync + Debug + Clone,
 {
     /// Receives a signal from the driver.
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
@@ -122,12 +128,13 @@ where
     }
 }
 
-impl<O, L1, L2, DA> OriginProvider for OraclePipeline<O, L1, L2, DA>
+impl<O, L1, L2, DA, B> OriginProvider for OraclePipeline<O, L1, L2, DA, B>
 where
     O: CommsClient + FlushableCache + Send + Sync + Debug,
     L1: ChainProvider + Send + Sync + Debug + Clone,
     L2: L2ChainProvider + Send + Sync + Debug + Clone,
     DA: DataAvailabilityProvider + Send + Sync + Debug + Clone,
+    B: BlobProvider + Send + Sync + Debug + Clone,
 {
     /// Returns the optional L1 [BlockInfo] origin.
     fn origin(&self) -> Option<BlockInfo> {
@@ -135,12 +142,13 @@ where
     }
 }
 
-impl<O, L1, L2, DA> Iterator for OraclePipeline<O, L1, L2, DA>
+impl<O, L1, L2, DA, B> Iterator for OraclePipeline<O, L1, L2, DA, B>
 where
     O: CommsClient + FlushableCache + Send + Sync + Debug,
     L1: ChainProvider + Send + Sync + Debug + Clone,
     L2: L2ChainProvider + Send + Sync + Debug + Clone,
     DA: DataAvailabilityProvider + Send + Sync + Debug + Clone,
+    B: BlobProvider + Send + Sync + Debug + Clone,
 {
     type Item = OpAttributesWithParent;
 
@@ -150,12 +158,13 @@ where
 }
 
 #[async_trait]
-impl<O, L1, L2, DA> Pipeline for OraclePipeline<O, L1, L2, DA>
+impl<O, L1, L2, DA, B> Pipeline for OraclePipeline<O, L1, L2, DA, B>
 where
     O: CommsClient + FlushableCache + Send + Sync + Debug,
     L1: ChainProvider + Send + Sync + Debug + Clone,
     L2: L2ChainProvider + Send + Sync + Debug + Clone,
     DA: DataAvailabilityProvider + Send + Sync + Debug + Clone,
+    B: BlobProvider + Send + Sync + Debug + Clone,
 {
     /// Peeks at the next [OpAttributesWithParent] from the pipeline.
     fn peek(&self) -> Option<&OpAttributesWithParent> {
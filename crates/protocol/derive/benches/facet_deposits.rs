@@ -0,0 +1,60 @@
+#![allow(missing_docs)]
+//! Contains benchmarks for facet deposit derivation.
+//!
+//! Run with `--features parallel` to compare against the rayon signer recovery.
+
+use alloy_consensus::{Eip658Value, Receipt, Signed, TxEnvelope, TxLegacy};
+use alloy_primitives::{Address, Signature, TxKind, U256};
+use criterion::{Criterion, criterion_group, criterion_main};
+use kona_derive::derive_facet_deposits;
+use kona_protocol::{FACET_INBOX_ADDRESS, encode_facet_payload};
+
+const L2_CHAIN_ID: u64 = 16436858;
+
+/// A synthetic L1 block of `count` successful inbox calldata txs.
+///
+/// The txs share a test signature, so each recovers to an arbitrary signer, at the full cost of an
+/// ECDSA recovery.
+fn inbox_block(count: u64) -> (Vec<TxEnvelope>, Vec<Receipt>) {
+    let txs = (0..count)
+        .map(|nonce| {
+            let input = encode_facet_payload(
+                L2_CHAIN_ID,
+                Some(Address::repeat_byte(0x11)),
+                U256::ZERO,
+                1_000_000,
+                &nonce.to_be_bytes(),
+                &[],
+            );
+            let tx = TxLegacy {
+                nonce,
+                gas_limit: 100_000,
+                to: TxKind::Call(FACET_INBOX_ADDRESS),
+                input,
+                ..Default::default()
+            };
+            TxEnvelope::Legacy(Signed::new_unchecked(
+                tx,
+                Signature::test_signature(),
+                Default::default(),
+            ))
+        })
+        .collect();
+    let receipts = (0..count)
+        .map(|_| Receipt { status: Eip658Value::Eip658(true), ..Default::default() })
+        .collect();
+    (txs, receipts)
+}
+
+fn facet_deposits(c: &mut Criterion) {
+    let mut g = c.benchmark_group("facet_deposits");
+    g.sample_size(10);
+
+    let (txs, receipts) = inbox_block(200);
+    g.bench_function("Derive - 200 inbox txs", |b| {
+        b.iter(|| derive_facet_deposits(&txs, &receipts, L2_CHAIN_ID, 1, 0, 0).unwrap());
+    });
+}
+
+criterion_group!(benches, facet_deposits);
+criterion_main!(benches);
@@ -1,18 +1,20 @@
 //! Derivation test that inspects structured transaction fields before RLP encoding
 //! This helps isolate differences between Kona and Geth implementations
 
-use alloy_primitives::{hex, BlockNumber};
+use alloy_primitives::{hex, BlockNumber, Bytes};
 use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_types_eth::BlockNumberOrTag;
-use alloy_eips::eip2718::Decodable2718;
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
 use clap::Parser;
 use eyre::Result;
 use kona_derive::{
     attributes::StatefulAttributesBuilder,
+    compare_transactions,
     traits::AttributesBuilder,
+    TxMatchStatus,
 };
 use kona_genesis::RollupConfig;
-use kona_protocol::{BatchValidationProvider, L1BlockInfoTx, FctMintCalculator};
+use kona_protocol::{BatchValidationProvider, L1BlockInfoFacet, L1BlockInfoTx, FctMintCalculator};
 use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider};
 use op_alloy_network::Optimism;
 use op_alloy_consensus::TxDeposit;
@@ -38,34 +40,17 @@ struct Args {
     verbose: bool,
 }
 
-async fn create_facet_rollup_config() -> Result<RollupConfig> {
-    let mut config = RollupConfig::default();
-    
-    // Set Facet-specific values
-    config.l2_chain_id = 0xface7;
-    config.block_time = 12;
-    config.max_sequencer_drift = 600;
-    config.seq_window_size = 3600;
-    config.channel_timeout = 300;
-    config.granite_channel_timeout = 50;
-    
-    // Set addresses
-    config.batch_inbox_address = "0xFACEC003e8e0cF7152467C26D37634925A9ce65B".parse()?;
-    config.deposit_contract_address = "0x00000000000000000000000000000000000face7".parse()?;
-    
-    // Enable all hardforks from genesis
-    config.hardforks.regolith_time = Some(0);
-    config.hardforks.canyon_time = Some(0);
-    config.hardforks.delta_time = Some(0);
-    config.hardforks.ecotone_time = Some(0);
-    config.hardforks.fjord_time = Some(0);
-    config.hardforks.granite_time = Some(0);
-    
-    Ok(config)
+/// Decode the Facet L1 block info from an encoded L1BlockInfoTx deposit
+fn decode_facet_l1_info(tx_bytes: &[u8]) -> Result<Option<L1BlockInfoFacet>> {
+    let deposit_tx = TxDeposit::decode_2718(&mut &tx_bytes[1..])?;
+    match L1BlockInfoTx::decode_calldata(&deposit_tx.input)? {
+        L1BlockInfoTx::Facet(facet) => Ok(Some(facet)),
+        _ => Ok(None),
+    }
 }
 
 /// Inspect FCT values from L1BlockInfoTx
-fn inspect_l1_block_info_tx(tx_bytes: &[u8]) -> Result<()> {
+fn inspect_l1_block_info_tx(tx_bytes: &[u8], block_time: u64) -> Result<()> {
     // Skip the 0x7e prefix
     let deposit_data = &tx_bytes[1..];
     let deposit_tx = TxDeposit::decode_2718(&mut &deposit_data[..])?;
@@ -80,56 +65,46 @@ fn inspect_l1_block_info_tx(tx_bytes: &[u8]) -> Result<()> {
     
     // Decode the L1BlockInfoTx from calldata
     let l1_info = L1BlockInfoTx::decode_calldata(&deposit_tx.input)?;
-    
-    match l1_info {
-        L1BlockInfoTx::Facet(facet) => {
-            println!("\n📊 L1BlockInfoTx::Facet Fields:");
-            println!("   Number: {}", facet.number);
-            println!("   Time: {}", facet.time);
-            println!("   Base fee: {}", facet.base_fee);
-            println!("   Block hash: 0x{}", hex::encode(facet.block_hash));
-            println!("   Sequence number: {}", facet.sequence_number);
-            println!("   Batcher address: {}", facet.batcher_address);
-            println!("   Blob base fee: {}", facet.blob_base_fee);
-            println!("   Blob base fee scalar: {}", facet.blob_base_fee_scalar);
-            println!("   Base fee scalar: {}", facet.base_fee_scalar);
-            println!("   Empty scalars: {}", facet.empty_scalars);
-            println!("   L1 fee overhead: {}", facet.l1_fee_overhead);
-            println!("   ⭐ FCT mint rate: {}", facet.fct_mint_rate);
-            println!("   ⭐ FCT mint period L1 data gas: {}", facet.fct_mint_period_l1_data_gas);
-            
-            // Show what these values would be at block 6
-            let expected_mint_rate = FctMintCalculator::compute_new_rate(6, 0, 0);
-            println!("\n   📐 Expected FCT values for block 6:");
-            println!("      - Mint rate: {} (vs actual: {})", expected_mint_rate, facet.fct_mint_rate);
-            println!("      - Period L1 data gas: Should match cumulative from deposits");
-            
-            // Decode the raw bytes to see the layout
-            println!("\n   🔍 Raw calldata (last 32 bytes):");
-            let calldata_len = deposit_tx.input.len();
-            if calldata_len >= 32 {
-                let last_32 = &deposit_tx.input[calldata_len - 32..];
-                println!("      0x{}", hex::encode(last_32));
-                
-                // Try to parse as two u128 values
-                if last_32.len() == 32 {
-                    let mint_rate_bytes = &last_32[0..16];
-                    let gas_bytes = &last_32[16..32];
-                    
-                    let mint_rate = u128::from_be_bytes(mint_rate_bytes.try_into()?);
-                    let gas = u128::from_be_bytes(gas_bytes.try_into()?);
-                    
-                    println!("      Parsed as u128s:");
-                    println!("        - First 16 bytes (mint rate?): {}", mint_rate);
-                    println!("        - Last 16 bytes (gas?): {}", gas);
-                }
-            }
-        }
-        _ => {
-            println!("\n⚠️  Not a Facet L1BlockInfoTx variant");
-        }
+    let (Some(fct_mint_rate), Some(fct_mint_period_l1_data_gas)) =
+        (l1_info.fct_mint_rate(), l1_info.fct_mint_period_l1_data_gas())
+    else {
+        println!("\n⚠️  Not a Facet L1BlockInfoTx variant");
+        return Ok(());
+    };
+
+    println!("\n📊 L1BlockInfoTx::Facet Fields:");
+    println!("   Number: {}", l1_info.id().number);
+    println!("   Base fee: {}", l1_info.l1_base_fee());
+    println!("   Block hash: 0x{}", hex::encode(l1_info.block_hash()));
+    println!("   Sequence number: {}", l1_info.sequence_number());
+    println!("   Batcher address: {}", l1_info.batcher_address());
+    println!("   Blob base fee: {}", l1_info.blob_base_fee());
+    println!("   Blob base fee scalar: {}", l1_info.blob_base_fee_scalar());
+    println!("   Base fee scalar: {}", l1_info.l1_fee_scalar());
+    println!("   Empty scalars: {}", l1_info.empty_scalars());
+    println!("   L1 fee overhead: {}", l1_info.l1_fee_overhead());
+    println!("   ⭐ FCT mint rate: {}", fct_mint_rate);
+    println!("   ⭐ FCT mint period L1 data gas: {}", fct_mint_period_l1_data_gas);
+
+    // Show what these values would be at block 6
+    let expected_mint_rate = FctMintCalculator::new(block_time).compute_new_rate(6, 0, 0);
+    println!("\n   📐 Expected FCT values for block 6:");
+    println!("      - Mint rate: {} (vs actual: {})", expected_mint_rate, fct_mint_rate);
+    println!("      - Period L1 data gas: Should match cumulative from deposits");
+
+    // The FCT fields are the two trailing u128 words, period data gas before mint rate
+    println!("\n   🔍 Raw calldata (last 32 bytes):");
+    let calldata_len = deposit_tx.input.len();
+    if calldata_len >= 32 {
+        let last_32 = &deposit_tx.input[calldata_len - 32..];
+        println!("      0x{}", hex::encode(last_32));
+        println!("        - Period L1 data gas: 0x{}", hex::encode(&last_32[..16]));
+        println!("        - Mint rate: 0x{}", hex::encode(&last_32[16..]));
     }
-    
+    if l1_info.encode_calldata() != deposit_tx.input {
+        println!("   ⚠️  Calldata does not re-encode to the same bytes");
+    }
+
     Ok(())
 }
 
@@ -146,7 +121,7 @@ async fn main() -> Result<()> {
     let l2_provider: RootProvider<Optimism> = RootProvider::new_http(args.l2_rpc.parse()?);
     
     // Get rollup config
-    let rollup_config = Arc::new(create_facet_rollup_config().await?);
+    let rollup_config = Arc::new(RollupConfig::facet());
     
     // Create chain providers
     let l1_chain_provider = AlloyChainProvider::new(l1_provider.clone(), 100);
@@ -219,7 +194,7 @@ async fn main() -> Result<()> {
     // Inspect the L1BlockInfoTx from Kona
     if let Some(first_tx) = kona_txs.first() {
         println!("\n================== KONA L1BlockInfoTx ==================");
-        inspect_l1_block_info_tx(first_tx)?;
+        inspect_l1_block_info_tx(first_tx, rollup_config.block_time)?;
     }
     
     // Compare with actual block from RPC
@@ -237,44 +212,59 @@ async fn main() -> Result<()> {
     
     // Inspect the L1BlockInfoTx from Geth
     if let Some(geth_tx) = actual_txs.first() {
-        use alloy_eips::eip2718::Encodable2718;
         let geth_bytes = geth_tx.inner.inner.encoded_2718();
         
         println!("\n================== GETH L1BlockInfoTx ==================");
-        inspect_l1_block_info_tx(&geth_bytes)?;
+        inspect_l1_block_info_tx(&geth_bytes, rollup_config.block_time)?;
     }
     
     // Compare byte-by-byte
     println!("\n5️⃣ Comparing transactions byte-by-byte");
-    for (i, (geth_tx, kona_tx_bytes)) in actual_txs.iter().zip(kona_txs.iter()).enumerate() {
-        use alloy_eips::eip2718::Encodable2718;
-        let geth_bytes = geth_tx.inner.inner.encoded_2718();
+    let geth_txs: Vec<Bytes> = actual_txs
+        .iter()
+        .map(|tx| Bytes::from(tx.inner.inner.encoded_2718()))
+        .collect();
+    let comparison = compare_transactions(kona_txs, &geth_txs);
+    for tx in &comparison.transactions {
+        let i = tx.index;
+        let (Some(geth_bytes), Some(kona_tx_bytes)) = (geth_txs.get(i), kona_txs.get(i)) else {
+            println!("\n   ❌ Transaction {} only exists on one side", i);
+            continue;
+        };
         
         println!("\n   Transaction {}: {} bytes (Geth) vs {} bytes (Kona)", 
             i, geth_bytes.len(), kona_tx_bytes.len());
         
-        if &geth_bytes != kona_tx_bytes {
+        if let TxMatchStatus::Mismatch { first_byte_offset } = tx.status {
             println!("   ❌ Transaction {} differs", i);
             
             // For L1BlockInfoTx, show where the differences are
             if i == 0 {
-                println!("\n   📍 Byte-level differences:");
-                let min_len = geth_bytes.len().min(kona_tx_bytes.len());
-                let mut first_diff = None;
-                
-                for j in 0..min_len {
-                    if geth_bytes[j] != kona_tx_bytes[j] {
-                        if first_diff.is_none() {
-                            first_diff = Some(j);
-                            println!("      First difference at byte {}", j);
+                match (decode_facet_l1_info(geth_bytes), decode_facet_l1_info(kona_tx_bytes)) {
+                    (Ok(Some(geth_info)), Ok(Some(kona_info))) => {
+                        println!("\n   🧾 Field-level differences (Geth != Kona):");
+                        let diffs = geth_info.diff(&kona_info);
+                        if diffs.is_empty() {
+                            println!("      None - the decoded fields match");
                         }
+                        for diff in diffs {
+                            println!("      {}", diff);
+                        }
+                    }
+                    (geth_info, kona_info) => {
+                        println!("\n   ⚠️  Could not decode both L1BlockInfoTx as Facet:");
+                        println!("      Geth: {:?}", geth_info);
+                        println!("      Kona: {:?}", kona_info);
                     }
                 }
-                
-                if let Some(diff_pos) = first_diff {
+
+                println!("\n   📍 Byte-level differences:");
+                let min_len = geth_bytes.len().min(kona_tx_bytes.len());
+                if let Some(diff_pos) = first_byte_offset {
                     let start = diff_pos.saturating_sub(16);
                     let end = (diff_pos + 16).min(min_len);
                     
+                    println!("      First difference at byte {}", diff_pos);
                     println!("      Around byte {} (showing bytes {}-{}):", diff_pos, start, end);
                     println!("      Geth: 0x{}", hex::encode(&geth_bytes[start..end]));
                     println!("      Kona: 0x{}", hex::encode(&kona_tx_bytes[start..end]));
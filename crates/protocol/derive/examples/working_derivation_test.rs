@@ -1,17 +1,20 @@
 //! Working derivation test based on patterns from the Kona codebase
 //! This properly handles the Ethereum vs Optimism network types
 
-use alloy_primitives::{hex, BlockNumber};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{hex, BlockNumber, Bytes};
 use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_types_eth::BlockNumberOrTag;
 use clap::Parser;
 use eyre::Result;
 use kona_derive::{
     attributes::StatefulAttributesBuilder,
+    compare_transactions,
     traits::AttributesBuilder,
+    TxMatchStatus,
 };
 use kona_genesis::RollupConfig;
-use kona_protocol::BatchValidationProvider;
+use kona_protocol::{BatchValidationProvider, L1BlockInfoTx};
 use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider};
 use op_alloy_network::Optimism;
 use std::sync::Arc;
@@ -36,32 +39,6 @@ struct Args {
     verbose: bool,
 }
 
-async fn create_facet_rollup_config() -> Result<RollupConfig> {
-    let mut config = RollupConfig::default();
-    
-    // Set Facet-specific values
-    config.l2_chain_id = 0xface7;
-    config.block_time = 12;
-    config.max_sequencer_drift = 600;
-    config.seq_window_size = 3600;
-    config.channel_timeout = 300;
-    config.granite_channel_timeout = 50;
-    
-    // Set addresses
-    config.batch_inbox_address = "0xFACEC003e8e0cF7152467C26D37634925A9ce65B".parse()?;
-    config.deposit_contract_address = "0x00000000000000000000000000000000000face7".parse()?;
-    
-    // Enable all hardforks from genesis
-    config.hardforks.regolith_time = Some(0);
-    config.hardforks.canyon_time = Some(0);
-    config.hardforks.delta_time = Some(0);
-    config.hardforks.ecotone_time = Some(0);
-    config.hardforks.fjord_time = Some(0);
-    config.hardforks.granite_time = Some(0);
-    
-    Ok(config)
-}
-
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
@@ -75,7 +52,7 @@ async fn main() -> Result<()> {
     let l2_provider: RootProvider<Optimism> = RootProvider::new_http(args.l2_rpc.parse()?);
     
     // Get rollup config
-    let rollup_config = Arc::new(create_facet_rollup_config().await?);
+    let rollup_config = Arc::new(RollupConfig::facet());
     
     // Create chain providers
     let l1_chain_provider = AlloyChainProvider::new(l1_provider.clone(), 100);
@@ -182,41 +159,41 @@ async fn main() -> Result<()> {
     println!("   Geth transactions: {}", actual_txs.len());
     println!("   Kona transactions: {}", kona_txs.len());
     
-    if actual_txs.len() != kona_txs.len() {
+    // Compare each transaction
+    println!("\n5️⃣ Comparing transactions byte-by-byte");
+    let geth_txs: Vec<Bytes> = actual_txs
+        .iter()
+        .map(|tx| Bytes::from(tx.inner.inner.encoded_2718()))
+        .collect();
+    let comparison = compare_transactions(kona_txs, &geth_txs);
+    if comparison.count_mismatch() {
         return Err(eyre::eyre!(
             "Transaction count mismatch: Geth {} vs Kona {}",
-            actual_txs.len(),
-            kona_txs.len()
+            comparison.actual_count,
+            comparison.derived_count
         ));
     }
     
-    // Compare each transaction
-    println!("\n5️⃣ Comparing transactions byte-by-byte");
-    for (i, (geth_tx, kona_tx_bytes)) in actual_txs.iter().zip(kona_txs.iter()).enumerate() {
-        // Get transaction bytes using eip2718 encoding
-        use alloy_eips::eip2718::Encodable2718;
-        let geth_bytes = geth_tx.inner.inner.encoded_2718();
-        
-        if &geth_bytes != kona_tx_bytes {
-            println!("   ❌ Transaction {} mismatch:", i);
+    for tx in &comparison.transactions {
+        let i = tx.index;
+        if let TxMatchStatus::Mismatch { first_byte_offset } = tx.status {
+            // Continue to see all transactions instead of failing immediately
+            let offset = first_byte_offset.unwrap_or_default();
+            println!("   ❌ Transaction {} mismatch at byte {}:", i, offset);
             if args.verbose {
-                println!("      Geth: 0x{}", hex::encode(&geth_bytes));
-                println!("      Kona: 0x{}", hex::encode(kona_tx_bytes));
+                println!("      Geth: 0x{}", hex::encode(&geth_txs[i]));
+                println!("      Kona: 0x{}", hex::encode(&kona_txs[i]));
             }
-            // Continue to see all transactions instead of failing immediately
-            // return Err(eyre::eyre!("Transaction {} encoding mismatch", i));
-        }
-        
-        if args.verbose {
-            println!("   ✅ Transaction {} matches ({} bytes)", i, geth_bytes.len());
+        } else if args.verbose {
+            println!("   ✅ Transaction {} matches ({} bytes)", i, geth_txs[i].len());
         }
     }
     
     // Check L1BlockInfoTx
     if let Some(first_tx) = kona_txs.first() {
-        if first_tx.get(0) == Some(&0x7e) {
+        if first_tx.first() == Some(&0x7e) {
             println!("\n✅ First transaction is L1BlockInfoTx (deposit)");
-            if first_tx.len() > 190 && first_tx.len() < 200 {
+            if L1BlockInfoTx::is_facet_info_tx(first_tx) {
                 println!("✅ Facet L1BlockInfoTx format detected ({} bytes)", first_tx.len());
             }
         }
@@ -3,4 +3,7 @@
 //! [AttributesBuilder]: crate::traits::AttributesBuilder
 
 mod stateful;
-pub use stateful::StatefulAttributesBuilder;
+pub use stateful::{NoopBlobProvider, StatefulAttributesBuilder};
+
+mod prefetching;
+pub use prefetching::PrefetchingAttributesBuilder;
@@ -0,0 +1,287 @@
+//! An [AttributesBuilder] that warms the chain provider caches ahead of the builder's lookups.
+
+use crate::{
+    attributes::{NoopBlobProvider, StatefulAttributesBuilder},
+    errors::PipelineError,
+    traits::{AttributesBuilder, BlobProvider, ChainProvider, L2ChainProvider},
+    types::PipelineResult,
+};
+use alloc::{boxed::Box, fmt::Debug, string::ToString};
+use alloy_eips::BlockNumHash;
+use async_trait::async_trait;
+use core::ops::RangeInclusive;
+use kona_protocol::{BatchValidationProvider, L2BlockInfo};
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+/// A [StatefulAttributesBuilder] that can prefetch the L1 epochs and L2 parents of a range of
+/// existing L2 blocks, e.g. when re-deriving thousands of blocks for validation.
+///
+/// [Self::prefetch] issues every lookup [StatefulAttributesBuilder::prepare_payload_attributes]
+/// makes for the range up front, so that the builder itself is served from the providers' caches.
+/// Prefetching only helps with caching providers, such as the alloy providers.
+///
+/// ## Cache capacity
+///
+/// The alloy providers are built with a cache size of `100`: each L1 epoch takes one entry in each
+/// of the L1 provider's caches, and each L2 block one entry in the L2 provider's block cache. A
+/// range of more than `99` blocks (plus its parent) evicts its first blocks before the builder
+/// reads them, so those lookups are fetched twice. Prefetch a window no larger than the cache,
+/// and prefetch the next window once the builder has consumed it.
+#[derive(Debug)]
+pub struct PrefetchingAttributesBuilder<L1P, L2P, BP = NoopBlobProvider>
+where
+    L1P: ChainProvider + Debug,
+    L2P: L2ChainProvider + Debug,
+{
+    /// The wrapped attributes builder.
+    inner: StatefulAttributesBuilder<L1P, L2P, BP>,
+}
+
+impl<L1P, L2P, BP> PrefetchingAttributesBuilder<L1P, L2P, BP>
+where
+    L1P: ChainProvider + Debug + Send,
+    L2P: L2ChainProvider + Debug + Send,
+{
+    /// Creates a new [PrefetchingAttributesBuilder] wrapping the given builder.
+    pub const fn new(inner: StatefulAttributesBuilder<L1P, L2P, BP>) -> Self {
+        Self { inner }
+    }
+
+    /// Consumes the wrapper, returning the inner [StatefulAttributesBuilder].
+    pub fn into_inner(self) -> StatefulAttributesBuilder<L1P, L2P, BP> {
+        self.inner
+    }
+
+    /// Prefetches everything the builder looks up to build the L2 blocks in `blocks`: each block's
+    /// parent, and the header of each block's L1 origin, along with the receipts and transactions
+    /// of every origin that starts a new epoch.
+    ///
+    /// The blocks must already exist on the L2 chain, as their L1 origins are read from it.
+    pub async fn prefetch(&mut self, blocks: RangeInclusive<u64>) -> PipelineResult<()> {
+        let (l1_provider, l2_provider) = self.inner.providers_mut();
+        let l2_error = |e: <L2P as BatchValidationProvider>::Error| {
+            PipelineError::Provider(e.to_string()).temp()
+        };
+
+        let mut parent_origin = None;
+        for number in blocks.start().saturating_sub(1)..=*blocks.end() {
+            l2_provider.block_by_number(number).await.map_err(l2_error)?;
+            let info = l2_provider.l2_block_info_by_number(number).await.map_err(l2_error)?;
+            let origin = info.l1_origin.hash;
+
+            if let Some(parent_origin) = parent_origin {
+                l1_provider.header_by_hash(origin).await.map_err(Into::into)?;
+                if parent_origin != origin {
+                    l1_provider.receipts_by_hash(origin).await.map_err(Into::into)?;
+                    l1_provider
+                        .block_info_and_transactions_by_hash(origin)
+                        .await
+                        .map_err(Into::into)?;
+                }
+            }
+            parent_origin = Some(origin);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<L1P, L2P, BP> AttributesBuilder for PrefetchingAttributesBuilder<L1P, L2P, BP>
+where
+    L1P: ChainProvider + Debug + Send,
+    L2P: L2ChainProvider + Debug + Send,
+    BP: BlobProvider + Debug + Send,
+{
+    async fn prepare_payload_attributes(
+        &mut self,
+        l2_parent: L2BlockInfo,
+        epoch: BlockNumHash,
+    ) -> PipelineResult<OpPayloadAttributes> {
+        self.inner.prepare_payload_attributes(l2_parent, epoch).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestChainProvider, TestProviderError};
+    use alloc::{sync::Arc, vec, vec::Vec};
+    use alloy_consensus::{BlockBody, Header, Receipt, TxEnvelope};
+    use alloy_primitives::{B256, map::HashMap};
+    use kona_genesis::{HardForkConfig, RollupConfig, SystemConfig};
+    use kona_protocol::{BlockInfo, FctMintCalculator, L1BlockInfoTx};
+    use op_alloy_consensus::{OpBlock, OpTxEnvelope};
+
+    /// A caching L1 provider counting the lookups that miss its cache.
+    #[derive(Debug, Default)]
+    struct CachingL1 {
+        inner: TestChainProvider,
+        headers: HashMap<B256, Header>,
+        receipts: HashMap<B256, Vec<Receipt>>,
+        transactions: HashMap<B256, (BlockInfo, Vec<TxEnvelope>)>,
+        fetches: usize,
+    }
+
+    #[async_trait]
+    impl ChainProvider for CachingL1 {
+        type Error = TestProviderError;
+
+        async fn header_by_hash(&mut self, hash: B256) -> Result<Header, Self::Error> {
+            if let Some(header) = self.headers.get(&hash) {
+                return Ok(header.clone());
+            }
+            self.fetches += 1;
+            let header = self.inner.header_by_hash(hash).await?;
+            self.headers.insert(hash, header.clone());
+            Ok(header)
+        }
+
+        async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+            self.fetches += 1;
+            self.inner.block_info_by_number(number).await
+        }
+
+        async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+            if let Some(receipts) = self.receipts.get(&hash) {
+                return Ok(receipts.clone());
+            }
+            self.fetches += 1;
+            let receipts = self.inner.receipts_by_hash(hash).await?;
+            self.receipts.insert(hash, receipts.clone());
+            Ok(receipts)
+        }
+
+        async fn block_info_and_transactions_by_hash(
+            &mut self,
+            hash: B256,
+        ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+            if let Some(block) = self.transactions.get(&hash) {
+                return Ok(block.clone());
+            }
+            self.fetches += 1;
+            let block = self.inner.block_info_and_transactions_by_hash(hash).await?;
+            self.transactions.insert(hash, block.clone());
+            Ok(block)
+        }
+    }
+
+    /// A caching L2 provider counting the blocks that miss its cache.
+    #[derive(Debug, Default)]
+    struct CachingL2 {
+        blocks: Vec<(L2BlockInfo, OpBlock)>,
+        cache: HashMap<u64, OpBlock>,
+        fetches: usize,
+    }
+
+    #[async_trait]
+    impl BatchValidationProvider for CachingL2 {
+        type Error = TestProviderError;
+
+        async fn l2_block_info_by_number(
+            &mut self,
+            number: u64,
+        ) -> Result<L2BlockInfo, Self::Error> {
+            self.block_by_number(number).await?;
+            Ok(self.blocks.iter().find(|(info, _)| info.block_info.number == number).unwrap().0)
+        }
+
+        async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+            if let Some(block) = self.cache.get(&number) {
+                return Ok(block.clone());
+            }
+            self.fetches += 1;
+            let (_, block) = self
+                .blocks
+                .iter()
+                .find(|(info, _)| info.block_info.number == number)
+                .ok_or(TestProviderError::L2BlockNotFound)?;
+            self.cache.insert(number, block.clone());
+            Ok(block.clone())
+        }
+    }
+
+    #[async_trait]
+    impl L2ChainProvider for CachingL2 {
+        type Error = TestProviderError;
+
+        async fn system_config_by_number(
+            &mut self,
+            number: u64,
+            _: Arc<RollupConfig>,
+        ) -> Result<SystemConfig, <Self as L2ChainProvider>::Error> {
+            self.block_by_number(number).await?;
+            Ok(SystemConfig::default())
+        }
+    }
+
+    /// A builder over L2 blocks `0..=4`, all in the epoch of a single L1 block.
+    fn builder() -> (StatefulAttributesBuilder<CachingL1, CachingL2>, Vec<L2BlockInfo>) {
+        let block_time = 2;
+        let cfg = RollupConfig {
+            block_time,
+            hardforks: HardForkConfig { ecotone_time: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+        let header = Header { number: 1, ..Default::default() };
+        let epoch = BlockNumHash { hash: header.hash_slow(), number: 1 };
+
+        let mut l1 = CachingL1::default();
+        l1.inner.insert_header(epoch.hash, header.clone());
+        let mut l2 = CachingL2::default();
+        for number in 0..=4 {
+            let timestamp = number * block_time;
+            let (_, deposit) = L1BlockInfoTx::try_new_with_deposit_tx_and_fct_values(
+                &cfg,
+                &SystemConfig::default(),
+                number,
+                &header,
+                timestamp,
+                FctMintCalculator::INITIAL_RATE,
+                0,
+            )
+            .unwrap();
+            let info = L2BlockInfo {
+                block_info: BlockInfo { number, timestamp, ..Default::default() },
+                l1_origin: epoch,
+                seq_num: number,
+            };
+            let block = OpBlock {
+                header: Header { number, timestamp, ..Default::default() },
+                body: BlockBody {
+                    transactions: vec![OpTxEnvelope::Deposit(deposit)],
+                    ..Default::default()
+                },
+            };
+            l2.blocks.push((info, block));
+        }
+        let parents = l2.blocks.iter().map(|(info, _)| *info).collect();
+        (StatefulAttributesBuilder::new(Arc::new(cfg), l2, l1), parents)
+    }
+
+    /// Builds L2 blocks `2..=4`, returning the number of provider lookups that missed the caches.
+    async fn build(
+        builder: &mut PrefetchingAttributesBuilder<CachingL1, CachingL2>,
+        parents: &[L2BlockInfo],
+    ) -> usize {
+        let (l1, l2) = builder.inner.providers_mut();
+        let before = l1.fetches + l2.fetches;
+        for parent in &parents[1..4] {
+            builder.prepare_payload_attributes(*parent, parent.l1_origin).await.unwrap();
+        }
+        let (l1, l2) = builder.inner.providers_mut();
+        l1.fetches + l2.fetches - before
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_warms_provider_caches() {
+        // Without prefetching, the builder fetches each parent, and the epoch's header once.
+        let (inner, parents) = builder();
+        let mut cold = PrefetchingAttributesBuilder::new(inner);
+        assert_eq!(build(&mut cold, &parents).await, 4);
+
+        let (inner, parents) = builder();
+        let mut warm = PrefetchingAttributesBuilder::new(inner);
+        warm.prefetch(2..=4).await.unwrap();
+        assert_eq!(build(&mut warm, &parents).await, 0);
+    }
+}
@@ -1,13 +1,23 @@
 //! The [`AttributesBuilder`] and it's default implementation.
 
 use crate::{
-    errors::{BuilderError, PipelineEncodingError, PipelineError, PipelineErrorKind},
-    traits::{AttributesBuilder, ChainProvider, L2ChainProvider},
+    FacetDepositConfig, FacetDerivationOutcome, check_facet_deposit_count,
+    derive_facet_deposits_with_validation,
+    errors::{
+        BlobProviderError, BuilderError, PipelineEncodingError, PipelineError, PipelineErrorKind,
+    },
+    facet_inbox_blob_hashes,
+    helpers::facet_deposits::derive_facet_deposits_with_verified_blobs,
+    traits::{AttributesBuilder, AttributesMetrics, BlobProvider, ChainProvider, L2ChainProvider},
     types::PipelineResult,
 };
-use alloc::{boxed::Box, fmt::Debug, format, string::ToString, sync::Arc, vec, vec::Vec};
-use alloy_consensus::{Eip658Value, Receipt, Transaction};
-use alloy_eips::{BlockNumHash, eip2718::Encodable2718};
+use alloc::{boxed::Box, fmt::Debug, string::ToString, sync::Arc, vec, vec::Vec};
+use alloy_consensus::{Eip658Value, Receipt};
+use alloy_eips::{
+    BlockNumHash,
+    eip2718::Encodable2718,
+    eip4844::{Blob, IndexedBlobHash},
+};
 use alloy_primitives::{Address, B256, Bytes};
 use alloy_rlp::Encodable;
 use alloy_rpc_types_engine::PayloadAttributes;
@@ -15,14 +25,14 @@ use async_trait::async_trait;
 use kona_genesis::RollupConfig;
 use kona_hardforks::{Hardfork, Hardforks};
 use kona_protocol::{
-    decode_deposit, FctMintCalculator, L1BlockInfoTx, L2BlockInfo, Predeploys, DEPOSIT_EVENT_ABI_HASH
+    decode_deposit, BlockInfo, FctMintCalculator, L1BlockInfoFacet, L1BlockInfoTx, L2BlockInfo,
+    Predeploys, DEPOSIT_EVENT_ABI_HASH, FACET_EIP7623_L1_TIME
 };
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
-use crate::derive_facet_deposits;
 
 /// A stateful implementation of the [AttributesBuilder].
 #[derive(Debug, Default)]
-pub struct StatefulAttributesBuilder<L1P, L2P>
+pub struct StatefulAttributesBuilder<L1P, L2P, BP = NoopBlobProvider>
 where
     L1P: ChainProvider + Debug,
     L2P: L2ChainProvider + Debug,
@@ -33,6 +43,36 @@ where
     config_fetcher: L2P,
     /// The L1 receipts fetcher.
     receipts_fetcher: L1P,
+    /// The optional receiver of build measurements.
+    metrics: Option<Arc<dyn AttributesMetrics>>,
+    /// The optional `(fct_mint_rate, fct_mint_period_l1_data_gas)` of the L2 block with the given
+    /// number, used instead of the values in its L1 info transaction.
+    parent_fct: Option<(u64, (u128, u128))>,
+    /// The optional inbox that Facet deposits are derived from, instead of the mainnet inbox.
+    deposit_cfg: Option<FacetDepositConfig>,
+    /// The L1 timestamp from which Facet payloads are charged the EIP-7623 calldata floor.
+    eip7623_time: Option<u64>,
+    /// The optional fetcher of the blobs sent to the Facet inbox.
+    blob_provider: Option<BP>,
+    /// Whether derived Facet deposits are counted against an independent inbox scan.
+    check_deposit_count: bool,
+}
+
+/// The [BlobProvider] of a [StatefulAttributesBuilder] without one, which never fetches blobs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopBlobProvider;
+
+#[async_trait]
+impl BlobProvider for NoopBlobProvider {
+    type Error = BlobProviderError;
+
+    async fn get_blobs(
+        &mut self,
+        _block_ref: &BlockInfo,
+        _blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<Box<Blob>>, Self::Error> {
+        Err(BlobProviderError::Backend("no blob provider".to_string()))
+    }
 }
 
 impl<L1P, L2P> StatefulAttributesBuilder<L1P, L2P>
@@ -41,22 +81,118 @@ where
     L2P: L2ChainProvider + Debug,
 {
     /// Create a new [StatefulAttributesBuilder] with the given epoch.
+    ///
+    /// Facet deposits are derived from the mainnet inbox for the rollup config's L2 chain id,
+    /// see [Self::with_deposit_config].
     pub const fn new(rcfg: Arc<RollupConfig>, sys_cfg_fetcher: L2P, receipts: L1P) -> Self {
-        Self { rollup_cfg: rcfg, config_fetcher: sys_cfg_fetcher, receipts_fetcher: receipts }
+        Self {
+            rollup_cfg: rcfg,
+            config_fetcher: sys_cfg_fetcher,
+            receipts_fetcher: receipts,
+            metrics: None,
+            parent_fct: None,
+            deposit_cfg: None,
+            eip7623_time: FACET_EIP7623_L1_TIME,
+            blob_provider: None,
+            check_deposit_count: false,
+        }
+    }
+}
+
+impl<L1P, L2P, BP> StatefulAttributesBuilder<L1P, L2P, BP>
+where
+    L1P: ChainProvider + Debug,
+    L2P: L2ChainProvider + Debug,
+{
+    /// Derives Facet deposits from the blobs of EIP-4844 transactions sent to the inbox, fetched
+    /// from `blob_provider`, which must verify them against their versioned hashes.
+    ///
+    /// Without a blob provider, inbox blobs are skipped.
+    pub const fn with_blob_provider<B>(
+        self,
+        blob_provider: B,
+    ) -> StatefulAttributesBuilder<L1P, L2P, B> {
+        StatefulAttributesBuilder {
+            rollup_cfg: self.rollup_cfg,
+            config_fetcher: self.config_fetcher,
+            receipts_fetcher: self.receipts_fetcher,
+            metrics: self.metrics,
+            parent_fct: self.parent_fct,
+            deposit_cfg: self.deposit_cfg,
+            eip7623_time: self.eip7623_time,
+            blob_provider: Some(blob_provider),
+            check_deposit_count: self.check_deposit_count,
+        }
+    }
+
+    /// Reports the duration, transaction count and Facet deposit count of every built payload
+    /// attributes to `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<dyn AttributesMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Builds the child of the L2 block `parent` from the given FCT values instead of reading them
+    /// from the parent's L1 info transaction, for a parent that was derived locally and is not
+    /// served by the L2 provider.
+    pub const fn with_parent_fct(
+        mut self,
+        parent: u64,
+        fct_mint_rate: u128,
+        fct_mint_period_l1_data_gas: u128,
+    ) -> Self {
+        self.parent_fct = Some((parent, (fct_mint_rate, fct_mint_period_l1_data_gas)));
+        self
+    }
+
+    /// Derives Facet deposits from the inbox of `deposit_cfg`, such as the inbox of a testnet or
+    /// local devnet.
+    pub const fn with_deposit_config(mut self, deposit_cfg: FacetDepositConfig) -> Self {
+        self.deposit_cfg = Some(deposit_cfg);
+        self
+    }
+
+    /// Charges Facet payloads of L1 blocks from the timestamp `time` on the EIP-7623 calldata
+    /// floor, or never with `None`, instead of following [FACET_EIP7623_L1_TIME].
+    pub const fn with_eip7623_time(mut self, time: Option<u64>) -> Self {
+        self.eip7623_time = time;
+        self
+    }
+
+    /// Fails the build of an epoch's first block if its Facet deposit count disagrees with an
+    /// independent [scan_facet_inbox](crate::scan_facet_inbox) of the L1 origin.
+    ///
+    /// The scan decodes every inbox payload a second time, so it is meant for validation tooling
+    /// rather than for a node.
+    pub const fn with_deposit_count_check(mut self) -> Self {
+        self.check_deposit_count = true;
+        self
+    }
+
+    /// Returns the inbox that Facet deposits are derived from.
+    fn deposit_config(&self) -> FacetDepositConfig {
+        self.deposit_cfg.unwrap_or_else(|| FacetDepositConfig::mainnet(self.rollup_cfg.l2_chain_id))
+    }
+
+    /// Returns the L1 receipts fetcher and the L2 system config fetcher.
+    pub(crate) const fn providers_mut(&mut self) -> (&mut L1P, &mut L2P) {
+        (&mut self.receipts_fetcher, &mut self.config_fetcher)
     }
 }
 
 #[async_trait]
-impl<L1P, L2P> AttributesBuilder for StatefulAttributesBuilder<L1P, L2P>
+impl<L1P, L2P, BP> AttributesBuilder for StatefulAttributesBuilder<L1P, L2P, BP>
 where
     L1P: ChainProvider + Debug + Send,
     L2P: L2ChainProvider + Debug + Send,
+    BP: BlobProvider + Debug + Send,
 {
     async fn prepare_payload_attributes(
         &mut self,
         l2_parent: L2BlockInfo,
         epoch: BlockNumHash,
     ) -> PipelineResult<OpPayloadAttributes> {
+        let start = self.metrics.as_ref().map(|metrics| metrics.now());
         tracing::info!(
             target: "attributes_builder",
             "prepare_payload_attributes called for L2 block {} (parent: {}), L1 origin: {} -> epoch: {}",
@@ -79,31 +215,16 @@ where
         let mut new_fct_mint_period_l1_data_gas: u128;
         
         // Read facet parameters from parent block (needed for both new and continuing epochs)
-        let (parent_fct_mint_rate, parent_fct_mint_period_l1_data_gas) = if l2_parent.block_info.number > 0 {
-            // Fetch parent block to get facet parameters
-            let parent_block = self
-                .config_fetcher
-                .block_by_number(l2_parent.block_info.number)
-                .await
-                .map_err(|e| PipelineError::AttributesBuilder(BuilderError::Custom(e.to_string())).crit())?;
-            
-            let first_tx = parent_block.body.transactions.first()
-                .ok_or_else(|| PipelineError::AttributesBuilder(BuilderError::Custom("Parent block has no transactions".to_string())).crit())?;
-            
-            let deposit_tx = first_tx.as_deposit()
-                .ok_or_else(|| PipelineError::AttributesBuilder(BuilderError::Custom("First transaction is not a deposit".to_string())).crit())?;
-            
-            let l1_info = L1BlockInfoTx::decode_calldata(deposit_tx.input().as_ref())
-                .map_err(|e| PipelineError::AttributesBuilder(BuilderError::Custom(format!("Failed to decode L1 info: {}", e))).crit())?;
-            
-            match l1_info {
-                L1BlockInfoTx::Facet(facet_info) => {
-                    (facet_info.fct_mint_rate, facet_info.fct_mint_period_l1_data_gas)
-                }
-                _ => return Err(PipelineError::AttributesBuilder(BuilderError::Custom("Parent block is not using Facet L1 info variant".to_string())).crit()),
-            }
-        } else {
-            (FctMintCalculator::INITIAL_RATE, 0u128)
+        let (parent_fct_mint_rate, parent_fct_mint_period_l1_data_gas) = match self.parent_fct {
+            Some((number, fct)) if number == l2_parent.block_info.number => fct,
+            _ => L1BlockInfoFacet::from_l2_parent(
+                &mut self.config_fetcher,
+                l2_parent.block_info.number,
+            )
+            .await
+            .map_err(|e| {
+                PipelineError::AttributesBuilder(BuilderError::Custom(e.to_string())).crit()
+            })?,
         };
 
         // If the L1 origin changed in this block, then we are in the first block of the epoch.
@@ -131,7 +252,7 @@ where
             }
             let receipts =
                 self.receipts_fetcher.receipts_by_hash(epoch.hash).await.map_err(Into::into)?;
-            let (_, txs) = self
+            let (block_info, txs) = self
                 .receipts_fetcher
                 .block_info_and_transactions_by_hash(epoch.hash)
                 .await
@@ -145,15 +266,60 @@ where
                 receipts.len()
             );
             
-            let (deposits, rate, cumulative_gas) = derive_facet_deposits(
-                &txs,
-                &receipts,
-                self.rollup_cfg.l2_chain_id,
-                l2_parent.block_info.number + 1, // Next L2 block number
-                parent_fct_mint_rate,
-                parent_fct_mint_period_l1_data_gas,
-            )
-            .map_err(|e| PipelineError::BadEncoding(e).crit())?;
+            let deposit_cfg = self.deposit_config();
+            let l2_block_number = l2_parent.block_info.number + 1;
+            let mint_calculator = FctMintCalculator::new(self.rollup_cfg.block_time);
+            let eip7623 = self.eip7623_time.is_some_and(|time| header.timestamp >= time);
+            let blob_hashes = facet_inbox_blob_hashes(&txs, &deposit_cfg);
+            let blobs = match self.blob_provider.as_mut() {
+                Some(blob_provider) if !blob_hashes.is_empty() => Some(
+                    blob_provider.get_blobs(&block_info, &blob_hashes).await.map_err(Into::into)?,
+                ),
+                _ => {
+                    if !blob_hashes.is_empty() {
+                        tracing::warn!(
+                            target: "attributes_builder",
+                            "L1 block {}: skipping {} Facet inbox blobs without a blob provider",
+                            epoch.number,
+                            blob_hashes.len()
+                        );
+                    }
+                    None
+                }
+            };
+            let derived = match &blobs {
+                Some(blobs) => derive_facet_deposits_with_verified_blobs(
+                    &txs,
+                    &receipts,
+                    blobs,
+                    &deposit_cfg,
+                    l2_block_number,
+                    parent_fct_mint_rate,
+                    parent_fct_mint_period_l1_data_gas,
+                    mint_calculator,
+                    eip7623,
+                )
+                .map(FacetDerivationOutcome::into_parts),
+                None => derive_facet_deposits_with_validation(
+                    &txs,
+                    &receipts,
+                    &deposit_cfg,
+                    l2_block_number,
+                    parent_fct_mint_rate,
+                    parent_fct_mint_period_l1_data_gas,
+                    mint_calculator,
+                    eip7623,
+                    false,
+                ),
+            };
+            let (deposits, rate, cumulative_gas) =
+                derived.map_err(|e| PipelineError::BadEncoding(e).crit())?;
+            if self.check_deposit_count {
+                // Skipped blobs are neither derived nor scanned
+                let blobs: Vec<&Blob> = blobs.iter().flatten().map(|blob| &**blob).collect();
+                check_facet_deposit_count(&txs, &receipts, &blobs, &deposit_cfg, deposits.len())
+                    .map_err(|e| PipelineError::BadEncoding(e).crit())?;
+            }
             
             tracing::info!(
                 target: "attributes_builder",
@@ -288,6 +454,11 @@ where
             l1_header.mix_hash
         );
         
+        if let (Some(metrics), Some(start)) = (&self.metrics, start) {
+            let duration = metrics.now().saturating_sub(start);
+            metrics.on_attributes_built(duration, txs.len(), deposit_transactions.len());
+        }
+
         Ok(OpPayloadAttributes {
             payload_attributes: PayloadAttributes {
                 timestamp: next_l2_time,
@@ -315,13 +486,14 @@ mod tests {
     use super::*;
     use crate::{
         errors::ResetError,
-        test_utils::{TestChainProvider, TestSystemConfigL2Fetcher},
+        test_utils::{TestAttributesMetrics, TestChainProvider, TestSystemConfigL2Fetcher},
     };
+    use core::time::Duration;
     use alloc::vec;
-    use alloy_consensus::Header;
-    use alloy_primitives::{B256, Log, LogData, U64, U256, address};
+    use alloy_consensus::{Header, Signed, TxEnvelope, TxLegacy};
+    use alloy_primitives::{B256, Log, LogData, Signature, TxKind, U64, U256, address, hex};
     use kona_genesis::{HardForkConfig, SystemConfig};
-    use kona_protocol::{BlockInfo, DepositError};
+    use kona_protocol::{BlockInfo, DepositError, FACET_INBOX_ADDRESS};
 
     fn generate_valid_log() -> Log {
         let deposit_contract = address!("1111111111111111111111111111111111111111");
@@ -649,4 +821,212 @@ mod tests {
         assert_eq!(payload.transactions.as_ref().unwrap().len(), 10);
         assert_eq!(payload, expected);
     }
+
+    #[tokio::test]
+    async fn test_prepare_payload_with_parent_fct() {
+        use alloy_eips::eip2718::Decodable2718;
+        use op_alloy_consensus::OpTxEnvelope;
+
+        let block_time = 2;
+        let timestamp = 100;
+        let cfg = Arc::new(RollupConfig {
+            block_time,
+            hardforks: HardForkConfig { ecotone_time: Some(0), ..Default::default() },
+            ..Default::default()
+        });
+        let l2_number = 1;
+        let mut fetcher = TestSystemConfigL2Fetcher::default();
+        fetcher.insert(l2_number, SystemConfig::default());
+        let mut provider = TestChainProvider::default();
+        let header = Header { timestamp, ..Default::default() };
+        let hash = header.hash_slow();
+        provider.insert_header(hash, header);
+        // The fetcher serves no blocks, so the parent's FCT values can only come from the builder
+        let mut builder = StatefulAttributesBuilder::new(cfg, fetcher, provider)
+            .with_parent_fct(l2_number, 7, 576);
+        let epoch = BlockNumHash { hash, number: l2_number };
+        let l2_parent = L2BlockInfo {
+            block_info: BlockInfo {
+                hash: B256::ZERO,
+                number: l2_number,
+                timestamp,
+                parent_hash: hash,
+            },
+            l1_origin: epoch,
+            seq_num: 0,
+        };
+        let payload = builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+
+        // A block continuing the epoch keeps the parent's values
+        let transactions = payload.transactions.unwrap();
+        let l1_info_tx = OpTxEnvelope::decode_2718(&mut transactions[0].as_ref()).unwrap();
+        let calldata = &l1_info_tx.as_deposit().unwrap().input;
+        let L1BlockInfoTx::Facet(info) = L1BlockInfoTx::decode_calldata(calldata).unwrap() else {
+            panic!("expected a Facet L1 info transaction");
+        };
+        assert_eq!((info.fct_mint_rate, info.fct_mint_period_l1_data_gas), (7, 576));
+    }
+
+    /// A Facet payload calling `0x1111…1111`.
+    const FACET_PAYLOAD: [u8; 36] =
+        hex!("46e283face7a94111111111111111111111111111111111111111180830f424082123480");
+
+    /// Returns a legacy transaction carrying [FACET_PAYLOAD] as calldata to `inbox`.
+    fn inbox_calldata_tx(inbox: Address) -> TxEnvelope {
+        let tx = TxLegacy {
+            to: TxKind::Call(inbox),
+            input: Bytes::from_static(&FACET_PAYLOAD),
+            ..Default::default()
+        };
+        TxEnvelope::Legacy(Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO))
+    }
+
+    /// Returns a provider serving a new epoch whose L1 block holds the successful `tx`, along with
+    /// the epoch and the L2 parent, which is served by no provider.
+    fn facet_epoch(tx: TxEnvelope) -> (TestChainProvider, BlockNumHash, L2BlockInfo) {
+        let receipt = Receipt { status: Eip658Value::Eip658(true), ..Default::default() };
+        let parent_hash = B256::repeat_byte(0x01);
+        let header = Header { number: 2, parent_hash, timestamp: 100, ..Default::default() };
+        let hash = header.hash_slow();
+        let mut provider = TestChainProvider::default();
+        provider.insert_header(hash, header);
+        provider.insert_receipts(hash, vec![receipt]);
+        provider.insert_block_with_transactions(
+            2,
+            BlockInfo { hash, number: 2, ..Default::default() },
+            vec![tx],
+        );
+
+        let epoch = BlockNumHash { hash, number: 2 };
+        let l2_parent = L2BlockInfo {
+            block_info: BlockInfo { number: 1, timestamp: 100, ..Default::default() },
+            l1_origin: BlockNumHash { hash: parent_hash, number: 1 },
+            seq_num: 0,
+        };
+        (provider, epoch, l2_parent)
+    }
+
+    /// Returns a builder for the child of the L2 parent of [facet_epoch].
+    fn facet_builder(
+        provider: TestChainProvider,
+    ) -> StatefulAttributesBuilder<TestChainProvider, TestSystemConfigL2Fetcher> {
+        let cfg =
+            Arc::new(RollupConfig { block_time: 2, l2_chain_id: 0xface7a, ..Default::default() });
+        let mut fetcher = TestSystemConfigL2Fetcher::default();
+        fetcher.insert(1, SystemConfig::default());
+        StatefulAttributesBuilder::new(cfg, fetcher, provider).with_parent_fct(
+            1,
+            FctMintCalculator::INITIAL_RATE,
+            0,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_with_deposit_config() {
+        // An L1 block carrying a payload to the inbox of a devnet deployment
+        let deposit_cfg = FacetDepositConfig {
+            inbox_address: Address::repeat_byte(0xde),
+            ..FacetDepositConfig::mainnet(0xface7a)
+        };
+        let (provider, epoch, l2_parent) =
+            facet_epoch(inbox_calldata_tx(deposit_cfg.inbox_address));
+
+        // The mainnet inbox receives nothing, so only the L1 info transaction is built
+        let mut mainnet = facet_builder(provider.clone());
+        let payload = mainnet.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        assert_eq!(payload.transactions.unwrap().len(), 1);
+
+        let mut devnet = facet_builder(provider).with_deposit_config(deposit_cfg);
+        let payload = devnet.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        let transactions = payload.transactions.unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[1][0], 0x7e);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_with_eip7623_time() {
+        use alloy_eips::eip2718::Decodable2718;
+        use op_alloy_consensus::TxDeposit;
+
+        // The L1 block of the epoch has timestamp 100
+        let (provider, epoch, l2_parent) = facet_epoch(inbox_calldata_tx(FACET_INBOX_ADDRESS));
+        let mint = |time| {
+            let mut builder = facet_builder(provider.clone()).with_eip7623_time(time);
+            async move {
+                let payload = builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+                let deposit = &payload.transactions.unwrap()[1];
+                TxDeposit::decode_2718(&mut deposit.as_ref()).unwrap().mint
+            }
+        };
+
+        // 36 non-zero bytes: 576 gas at 16 per byte, 1440 at the floor of 10 per token
+        let rate = FctMintCalculator::INITIAL_RATE;
+        assert_eq!(mint(None).await, Some(576 * rate));
+        assert_eq!(mint(Some(101)).await, Some(576 * rate));
+        assert_eq!(mint(Some(100)).await, Some(1440 * rate));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_with_blob_provider() {
+        use crate::test_utils::{TestBlobProvider, encode_blob};
+        use alloy_consensus::{TxEip4844, TxEip4844Variant};
+        use alloy_eips::eip2718::Decodable2718;
+        use op_alloy_consensus::TxDeposit;
+
+        // An L1 block carrying a payload in a blob to the inbox
+        let blob_hash = B256::repeat_byte(0x42);
+        let tx = TxEip4844 {
+            to: FACET_INBOX_ADDRESS,
+            blob_versioned_hashes: vec![blob_hash],
+            ..Default::default()
+        };
+        let tx = TxEnvelope::Eip4844(Signed::new_unchecked(
+            TxEip4844Variant::TxEip4844(tx),
+            Signature::test_signature(),
+            B256::ZERO,
+        ));
+        let (provider, epoch, l2_parent) = facet_epoch(tx);
+
+        // Without a blob provider the blob is skipped
+        let mut builder = facet_builder(provider.clone());
+        let payload = builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        assert_eq!(payload.transactions.unwrap().len(), 1);
+
+        let mut blob_provider = TestBlobProvider::default();
+        blob_provider.insert_blob(blob_hash, encode_blob(&FACET_PAYLOAD));
+        let mut builder = facet_builder(provider).with_blob_provider(blob_provider);
+        let payload = builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        let transactions = payload.transactions.unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        // Blob bytes are not calldata, so the deposit mints no FCT
+        let deposit = TxDeposit::decode_2718(&mut transactions[1].as_ref()).unwrap();
+        assert_eq!(deposit.to, TxKind::Call(Address::repeat_byte(0x11)));
+        assert_eq!(deposit.mint, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_payload_reports_metrics() {
+        let cfg = Arc::new(RollupConfig { block_time: 2, ..Default::default() });
+        let mut fetcher = TestSystemConfigL2Fetcher::default();
+        fetcher.insert(0, SystemConfig::default());
+        let mut provider = TestChainProvider::default();
+        let header = Header::default();
+        let hash = header.hash_slow();
+        provider.insert_header(hash, header);
+        let metrics = Arc::new(TestAttributesMetrics::default());
+        let mut builder =
+            StatefulAttributesBuilder::new(cfg, fetcher, provider).with_metrics(metrics.clone());
+        let epoch = BlockNumHash { hash, number: 0 };
+        let l2_parent = L2BlockInfo { l1_origin: epoch, ..Default::default() };
+
+        builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        assert_eq!(*metrics.builds.lock(), vec![(Duration::from_millis(1), 1, 0)]);
+
+        // A failed build is not reported.
+        let other_epoch = BlockNumHash { hash: B256::ZERO, number: 0 };
+        builder.prepare_payload_attributes(l2_parent, other_epoch).await.unwrap_err();
+        assert_eq!(metrics.build_count(), 1);
+        assert_eq!(metrics.facet_deposit_count(), 0);
+    }
 }
@@ -166,6 +166,22 @@ pub enum PipelineEncodingError {
     /// Facet decode error.
     #[error("Facet decode error: {0}")]
     FacetDecodeError(#[from] kona_protocol::FacetDecodeError),
+    /// A derived facet deposit did not round-trip decode as the [TxDeposit] it was encoded from.
+    ///
+    /// [TxDeposit]: op_alloy_consensus::TxDeposit
+    #[error("Derived facet deposit {0} does not decode as a valid deposit transaction")]
+    InvalidFacetDeposit(usize),
+    /// Derived facet deposit count disagrees with an independent scan of the L1 block.
+    #[error("Facet deposit count mismatch: expected {expected} from inbox scan, derived {actual}")]
+    FacetDepositCountMismatch {
+        /// Deposits expected from the inbox scan.
+        expected: usize,
+        /// Deposits produced by derivation.
+        actual: usize,
+    },
+    /// The blob of a Facet inbox transaction is missing or does not match its versioned hash.
+    #[error("Facet inbox blob {0} is missing or does not match its versioned hash")]
+    InvalidFacetBlob(B256),
 }
 
 #[cfg(test)]
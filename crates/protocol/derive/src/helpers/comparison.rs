@@ -0,0 +1,210 @@
+use alloc::vec::Vec;
+use alloy_eips::Encodable2718;
+use alloy_network_primitives::BlockTransactions;
+use alloy_primitives::{Bytes, keccak256};
+
+/// How a derived transaction compares to the node's transaction at the same index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxMatchStatus {
+    /// Both encodings are identical.
+    Match,
+    /// The encodings differ.
+    Mismatch {
+        /// Offset of the first differing byte, or the shorter length if one encoding is a prefix
+        /// of the other. `None` if the node only returned the transaction hash.
+        first_byte_offset: Option<usize>,
+    },
+    /// Only the node's block has a transaction at this index.
+    MissingDerived,
+    /// Only the derived block has a transaction at this index.
+    MissingActual,
+}
+
+/// The comparison of a single transaction of a derived block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionComparison {
+    /// Index of the transaction in the block.
+    pub index: usize,
+    /// How the derived transaction compares to the node's.
+    pub status: TxMatchStatus,
+    /// Length of the derived EIP-2718 encoding, if derived.
+    pub derived_len: Option<usize>,
+    /// Length of the node's EIP-2718 encoding, if known.
+    pub actual_len: Option<usize>,
+}
+
+impl TransactionComparison {
+    /// Returns `true` if the derived transaction is identical to the node's.
+    pub const fn is_match(&self) -> bool {
+        matches!(self.status, TxMatchStatus::Match)
+    }
+}
+
+/// The transaction-by-transaction comparison of a derived block against the node's block,
+/// returned by [compare_derived_block].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DerivationComparison {
+    /// Number of derived transactions.
+    pub derived_count: usize,
+    /// Number of transactions in the node's block.
+    pub actual_count: usize,
+    /// The comparison of every index present in either block, in order.
+    pub transactions: Vec<TransactionComparison>,
+}
+
+impl DerivationComparison {
+    /// Returns `true` if both blocks contain the same transactions.
+    pub fn is_match(&self) -> bool {
+        self.transactions.iter().all(TransactionComparison::is_match)
+    }
+
+    /// Returns `true` if the blocks contain a different number of transactions.
+    pub const fn count_mismatch(&self) -> bool {
+        self.derived_count != self.actual_count
+    }
+
+    /// Returns the first transaction that does not match, if any.
+    pub fn first_mismatch(&self) -> Option<&TransactionComparison> {
+        self.transactions.iter().find(|tx| !tx.is_match())
+    }
+}
+
+/// Compares the EIP-2718 encoded `derived` transactions against the transactions of the node's
+/// block.
+///
+/// Full transactions are compared byte by byte. If the node only returned hashes, the derived
+/// transactions are compared by hash and no byte offset is reported. Uncle blocks have no
+/// transactions.
+pub fn compare_derived_block<T: Encodable2718>(
+    derived: &[Bytes],
+    actual: &BlockTransactions<T>,
+) -> DerivationComparison {
+    match actual {
+        BlockTransactions::Full(txs) => {
+            let actual = txs.iter().map(|tx| Bytes::from(tx.encoded_2718())).collect::<Vec<_>>();
+            compare_transactions(derived, &actual)
+        }
+        BlockTransactions::Hashes(hashes) => {
+            let transactions = (0..derived.len().max(hashes.len()))
+                .map(|index| {
+                    let (d, a) = (derived.get(index), hashes.get(index));
+                    let status = match (d, a) {
+                        (Some(d), Some(a)) if keccak256(d) == *a => TxMatchStatus::Match,
+                        (Some(_), Some(_)) => TxMatchStatus::Mismatch { first_byte_offset: None },
+                        (None, _) => TxMatchStatus::MissingDerived,
+                        (_, None) => TxMatchStatus::MissingActual,
+                    };
+                    TransactionComparison {
+                        index,
+                        status,
+                        derived_len: d.map(|d| d.len()),
+                        actual_len: None,
+                    }
+                })
+                .collect();
+            DerivationComparison {
+                derived_count: derived.len(),
+                actual_count: hashes.len(),
+                transactions,
+            }
+        }
+        BlockTransactions::Uncle => compare_transactions(derived, &[]),
+    }
+}
+
+/// Compares the EIP-2718 encoded `derived` transactions against the node's encoded `actual`
+/// transactions byte by byte.
+pub fn compare_transactions(derived: &[Bytes], actual: &[Bytes]) -> DerivationComparison {
+    let transactions = (0..derived.len().max(actual.len()))
+        .map(|index| {
+            let (d, a) = (derived.get(index), actual.get(index));
+            let status = match (d, a) {
+                (Some(d), Some(a)) => first_byte_difference(d, a).map_or(
+                    TxMatchStatus::Match,
+                    |offset| TxMatchStatus::Mismatch { first_byte_offset: Some(offset) },
+                ),
+                (None, _) => TxMatchStatus::MissingDerived,
+                (_, None) => TxMatchStatus::MissingActual,
+            };
+            TransactionComparison {
+                index,
+                status,
+                derived_len: d.map(|d| d.len()),
+                actual_len: a.map(|a| a.len()),
+            }
+        })
+        .collect();
+    DerivationComparison { derived_count: derived.len(), actual_count: actual.len(), transactions }
+}
+
+/// Offset of the first differing byte, or the shorter length if one slice is a prefix of the other.
+fn first_byte_difference(a: &[u8], b: &[u8]) -> Option<usize> {
+    a.iter()
+        .zip(b)
+        .position(|(a, b)| a != b)
+        .or_else(|| (a.len() != b.len()).then(|| a.len().min(b.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{Signed, TxEnvelope, TxLegacy};
+    use alloy_primitives::Signature;
+
+    fn tx(nonce: u64) -> TxEnvelope {
+        TxEnvelope::Legacy(Signed::new_unchecked(
+            TxLegacy { nonce, ..Default::default() },
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    }
+
+    #[test]
+    fn test_compare_full_transactions() {
+        let actual = BlockTransactions::Full(vec![tx(0), tx(1), tx(2)]);
+        let mut derived: Vec<Bytes> = [tx(0), tx(1), tx(2)]
+            .iter()
+            .map(|tx| tx.encoded_2718().into())
+            .collect();
+
+        let comparison = compare_derived_block(&derived, &actual);
+        assert!(comparison.is_match());
+        assert!(!comparison.count_mismatch());
+
+        // Corrupt the last byte of the second transaction and drop the third
+        let mut corrupted = derived[1].to_vec();
+        *corrupted.last_mut().unwrap() ^= 0xff;
+        derived[1] = corrupted.into();
+        derived.pop();
+
+        let comparison = compare_derived_block(&derived, &actual);
+        assert!(!comparison.is_match());
+        assert!(comparison.count_mismatch());
+        let first = comparison.first_mismatch().unwrap();
+        assert_eq!(first.index, 1);
+        assert_eq!(
+            first.status,
+            TxMatchStatus::Mismatch { first_byte_offset: Some(derived[1].len() - 1) }
+        );
+        assert_eq!(comparison.transactions[2].status, TxMatchStatus::MissingDerived);
+        assert_eq!(comparison.transactions[2].derived_len, None);
+    }
+
+    #[test]
+    fn test_compare_hashes_and_prefixes() {
+        let derived: Vec<Bytes> = vec![tx(0).encoded_2718().into()];
+        let hashes = BlockTransactions::<TxEnvelope>::Hashes(vec![keccak256(&derived[0])]);
+        assert!(compare_derived_block(&derived, &hashes).is_match());
+
+        let comparison = compare_transactions(&derived, &[derived[0].slice(..3)]);
+        assert_eq!(
+            comparison.transactions[0].status,
+            TxMatchStatus::Mismatch { first_byte_offset: Some(3) }
+        );
+        assert_eq!(
+            compare_transactions(&derived, &[]).transactions[0].status,
+            TxMatchStatus::MissingActual
+        );
+    }
+}
@@ -1,12 +1,182 @@
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use alloy_consensus::{Receipt, TxEnvelope, Eip658Value, Transaction};
-use alloy_eips::Encodable2718;
+use alloy_eips::{Decodable2718, Encodable2718};
+use alloy_eips::eip4844::{Blob, IndexedBlobHash};
 use alloy_primitives::{Address, B256, Bytes, Log};
-use kona_protocol::{decode_facet_payload, alias_l1_to_l2, FACET_INBOX_ADDRESS, FACET_LOG_INBOX_EVENT_SIG, FctMintCalculator};
+use kona_protocol::{decode_facet_payload_with_eip7623, facet_source_hash, alias_l1_to_l2, FacetDecodeError, FACET_INBOX_ADDRESS, FACET_LOG_INBOX_EVENT_SIG, FACET_TX_TYPE, FctMintCalculator, MAX_FACET_PAYLOAD_BYTES, FACET_BLOB_SOURCE_DOMAIN, FACET_CALLDATA_SOURCE_DOMAIN, FACET_LOG_SOURCE_DOMAIN};
 use crate::errors::PipelineEncodingError;
+use op_alloy_consensus::TxDeposit;
+
+/// Where a Facet deployment receives its L1 payloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FacetDepositConfig {
+    /// Address whose calldata carries facet payloads.
+    pub inbox_address: Address,
+    /// `topic0` of the event whose data carries a facet payload.
+    pub log_event_sig: B256,
+    /// Chain id expected inside the facet RLP.
+    pub l2_chain_id: u64,
+}
+
+impl FacetDepositConfig {
+    /// The mainnet inbox ([FACET_INBOX_ADDRESS] and [FACET_LOG_INBOX_EVENT_SIG]) for the given
+    /// L2 chain id.
+    pub const fn mainnet(l2_chain_id: u64) -> Self {
+        Self {
+            inbox_address: FACET_INBOX_ADDRESS,
+            log_event_sig: FACET_LOG_INBOX_EVENT_SIG,
+            l2_chain_id,
+        }
+    }
+}
+
+/// Everything [derive_facet_deposits_detailed] learned while deriving an L1 block's deposits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetDerivationOutcome {
+    /// EIP-2718 encoded deposit transactions, in L1 order.
+    pub deposits: Vec<Bytes>,
+    /// FCT mint rate for the L2 block.
+    pub new_mint_rate: u128,
+    /// Cumulative L1 data gas of the current mint period, including this block.
+    pub new_cumulative_l1_data_gas: u128,
+    /// Inbox calldata and logs that did not decode as a facet payload, in L1 order.
+    pub skipped: Vec<SkippedPayload>,
+    /// The number of skipped payloads per rejection reason.
+    pub rejections: FacetRejectionCounts,
+}
+
+/// Number of inbox payloads rejected per [FacetDecodeError] kind, for metrics exporters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FacetRejectionCounts {
+    /// [FacetDecodeError::Short] rejections.
+    pub short: u64,
+    /// [FacetDecodeError::TooLong] rejections.
+    pub too_long: u64,
+    /// [FacetDecodeError::WrongPrefix] rejections.
+    pub wrong_prefix: u64,
+    /// [FacetDecodeError::Rlp] rejections.
+    pub rlp: u64,
+    /// [FacetDecodeError::BadChainId] rejections.
+    pub bad_chain_id: u64,
+    /// [FacetDecodeError::GasLimitTooHigh] rejections.
+    pub gas_limit_too_high: u64,
+    /// [FacetDecodeError::InvalidToLength] rejections.
+    pub invalid_to_length: u64,
+    /// [FacetDecodeError::Decompress] rejections.
+    pub decompress: u64,
+    /// [FacetDecodeError::DecompressedTooLong] rejections.
+    pub decompressed_too_long: u64,
+}
+
+impl FacetRejectionCounts {
+    /// Counts one rejection for `reason`.
+    pub const fn record(&mut self, reason: &FacetDecodeError) {
+        let count = match reason {
+            FacetDecodeError::Short => &mut self.short,
+            FacetDecodeError::TooLong(..) => &mut self.too_long,
+            FacetDecodeError::WrongPrefix(_) => &mut self.wrong_prefix,
+            FacetDecodeError::Rlp(_) => &mut self.rlp,
+            FacetDecodeError::BadChainId(..) => &mut self.bad_chain_id,
+            FacetDecodeError::GasLimitTooHigh(..) => &mut self.gas_limit_too_high,
+            FacetDecodeError::InvalidToLength(_) => &mut self.invalid_to_length,
+            FacetDecodeError::Decompress => &mut self.decompress,
+            FacetDecodeError::DecompressedTooLong(_) => &mut self.decompressed_too_long,
+        };
+        *count += 1;
+    }
+
+    /// The total number of rejections.
+    pub const fn total(&self) -> u64 {
+        self.short +
+            self.too_long +
+            self.wrong_prefix +
+            self.rlp +
+            self.bad_chain_id +
+            self.gas_limit_too_high +
+            self.invalid_to_length +
+            self.decompress +
+            self.decompressed_too_long
+    }
+
+    /// The count of every rejection reason, labelled with [FacetDecodeError::reason].
+    pub const fn by_reason(&self) -> [(&'static str, u64); 9] {
+        [
+            ("short", self.short),
+            ("too_long", self.too_long),
+            ("wrong_prefix", self.wrong_prefix),
+            ("rlp", self.rlp),
+            ("bad_chain_id", self.bad_chain_id),
+            ("gas_limit_too_high", self.gas_limit_too_high),
+            ("invalid_to_length", self.invalid_to_length),
+            ("decompress", self.decompress),
+            ("decompressed_too_long", self.decompressed_too_long),
+        ]
+    }
+}
+
+/// An inbox payload that was dropped because it failed to decode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedPayload {
+    /// Hash of the L1 transaction carrying the payload.
+    pub tx_hash: B256,
+    /// Why the payload was rejected.
+    pub reason: FacetDecodeError,
+}
+
+/// A single decision made by facet deposit derivation, recorded by [derive_facet_deposits_traced].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeriveTraceEvent {
+    /// An L1 transaction was considered, with its recipient (`None` for contract creations).
+    ConsideredTx {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+        /// Recipient of the L1 transaction.
+        to: Option<Address>,
+    },
+    /// The transaction's receipt reports failure, so none of its payloads are derived.
+    SkippedFailedTx {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+    },
+    /// The transaction's calldata was sent to the inbox address.
+    MatchedInboxCalldata {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+    },
+    /// The transaction's receipt contains an inbox log.
+    MatchedInboxLog {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+        /// Index of the log within the receipt.
+        log_index: usize,
+    },
+    /// The matched calldata or log decoded as a facet payload sent by `from`.
+    DecodedPayload {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+        /// The L2 sender of the payload.
+        from: Address,
+    },
+    /// The matched calldata or log failed to decode and was skipped.
+    SkippedPayload {
+        /// Hash of the L1 transaction.
+        tx_hash: B256,
+        /// Why the payload was rejected.
+        reason: FacetDecodeError,
+    },
+    /// A deposit was produced from a decoded payload.
+    ProducedDeposit {
+        /// Hash of the L1 transaction the deposit was derived from.
+        tx_hash: B256,
+        /// Index of the deposit in the derived deposits.
+        index: usize,
+    },
+}
 
 /// Derive Optimism `0x7e` deposit transactions from facet inbox calldata + event logs.
 ///
+/// Uses the mainnet inbox, see [derive_facet_deposits_with_config] for other deployments.
+///
 /// * `txs`         – list of L1 transactions in canonical order (index already implied)
 /// * `receipts`    – receipts matching `txs` by index
 /// * `l2_chain_id` – Optimism chain id we expect inside the facet RLP
@@ -23,6 +193,300 @@ pub fn derive_facet_deposits(
     fct_mint_rate: u128,
     fct_mint_period_l1_data_gas: u128,
 ) -> Result<(Vec<Bytes>, u128, u128), PipelineEncodingError> {
+    derive_facet_deposits_with_config(
+        txs,
+        receipts,
+        &FacetDepositConfig::mainnet(l2_chain_id),
+        l2_block_number,
+        fct_mint_rate,
+        fct_mint_period_l1_data_gas,
+    )
+}
+
+/// [derive_facet_deposits], additionally reporting every inbox payload that was skipped because
+/// it failed to decode.
+pub fn derive_facet_deposits_detailed(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    l2_chain_id: u64,
+    l2_block_number: u64,
+    fct_mint_rate: u128,
+    fct_mint_period_l1_data_gas: u128,
+) -> Result<FacetDerivationOutcome, PipelineEncodingError> {
+    derive_facet_deposits_inner(
+        txs,
+        receipts,
+        &[],
+        &FacetDepositConfig::mainnet(l2_chain_id),
+        l2_block_number,
+        fct_mint_rate,
+        fct_mint_period_l1_data_gas,
+        FctMintCalculator::default(),
+        false,
+        false,
+        None,
+    )
+}
+
+/// [derive_facet_deposits_detailed], additionally recording every derivation decision to `trace`
+/// in order.
+///
+/// The trace is independent of any `tracing` subscriber, which makes derivation decisions
+/// assertable in tests and inspectable by tooling.
+pub fn derive_facet_deposits_traced(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    l2_chain_id: u64,
+    l2_block_number: u64,
+    fct_mint_rate: u128,
+    fct_mint_period_l1_data_gas: u128,
+    trace: &mut Vec<DeriveTraceEvent>,
+) -> Result<FacetDerivationOutcome, PipelineEncodingError> {
+    derive_facet_deposits_inner(
+        txs,
+        receipts,
+        &[],
+        &FacetDepositConfig::mainnet(l2_chain_id),
+        l2_block_number,
+        fct_mint_rate,
+        fct_mint_period_l1_data_gas,
+        FctMintCalculator::default(),
+        false,
+        false,
+        Some(trace),
+    )
+}
+
+/// [derive_facet_deposits] against the inbox of an arbitrary Facet deployment, such as a testnet
+/// or local devnet.
+pub fn derive_facet_deposits_with_config(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    cfg: &FacetDepositConfig,
+    l2_block_number: u64,
+    fct_mint_rate: u128,
+    fct_mint_period_l1_data_gas: u128,
+) -> Result<(Vec<Bytes>, u128, u128), PipelineEncodingError> {
+    derive_facet_deposits_inner(
+        txs,
+        receipts,
+        &[],
+        cfg,
+        l2_block_number,
+        fct_mint_rate,
+        fct_mint_period_l1_data_gas,
+        FctMintCalculator::default(),
+        false,
+        false,
+        None,
+    )
+    .map(FacetDerivationOutcome::into_parts)
+}
+
+/// [derive_facet_deposits_with_config] with an explicit [FctMintCalculator] and data gas
+/// schedule, optionally round-trip decoding every produced deposit.
+///
+/// The `mint_calculator` should be built from the rollup config's block time, which determines
+/// the halving schedule in blocks.
+///
+/// With `eip7623` set, payloads are charged the EIP-7623 calldata floor. Select it from the L1
+/// block's timestamp with [kona_protocol::is_facet_eip7623_active].
+///
+/// With `validate_encoding` set, each encoded deposit is decoded back into a [TxDeposit] and
+/// compared against the deposit it was encoded from, so encoding bugs surface here rather than
+/// downstream in the engine. This costs a decode per deposit and is meant for validation runs.
+#[allow(clippy::too_many_arguments)]
+pub fn derive_facet_deposits_with_validation(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    cfg: &FacetDepositConfig,
+    l2_block_number: u64,
+    fct_mint_rate: u128,
+    fct_mint_period_l1_data_gas: u128,
+    mint_calculator: FctMintCalculator,
+    eip7623: bool,
+    validate_encoding: bool,
+) -> Result<(Vec<Bytes>, u128, u128), PipelineEncodingError> {
+    derive_facet_deposits_inner(
+        txs,
+        receipts,
+        &[],
+        cfg,
+        l2_block_number,
+        fct_mint_rate,
+        fct_mint_period_l1_data_gas,
+        mint_calculator,
+        eip7623,
+        validate_encoding,
+        None,
+    )
+    .map(FacetDerivationOutcome::into_parts)
+}
+
+/// [derive_facet_deposits_with_validation], additionally decoding Facet payloads carried in the
+/// blobs of EIP-4844 transactions sent to the inbox.
+///
+/// `blobs` are the blobs of every EIP-4844 transaction sent to the inbox, in L1 order, see
+/// [facet_inbox_blob_hashes]. Each blob is KZG-verified against its versioned hash before it is
+/// decoded. A blob that is missing or does not match its versioned hash fails derivation with
+/// [PipelineEncodingError::InvalidFacetBlob], while a blob that does not decode as a facet payload
+/// is skipped like malformed calldata. A transaction's blob payloads are derived after its
+/// calldata and before its logs.
+///
+/// A blob payload is charged the L1 data gas of its bytes as inbox calldata, so a payload mints
+/// the same FCT, and counts the same toward the mint period's L1 data gas, whether it reaches the
+/// inbox as calldata or in a blob.
+#[cfg(feature = "kzg")]
+#[allow(clippy::too_many_arguments)]
+pub fn derive_facet_deposits_with_blobs(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    blobs: &[Blob],
+    cfg: &FacetDepositConfig,
+    l2_block_number: u64,
+    fct_mint_rate: u128,
+    fct_mint_period_l1_data_gas: u128,
+    mint_calculator: FctMintCalculator,
+    eip7623: bool,
+) -> Result<FacetDerivationOutcome, PipelineEncodingError> {
+    let blob_payloads = decode_inbox_blobs(txs, blobs, cfg.inbox_address, verify_blob)?;
+    derive_facet_deposits_inner(
+        txs,
+        receipts,
+        &blob_payloads,
+        cfg,
+        l2_block_number,
+        fct_mint_rate,
+        fct_mint_period_l1_data_gas,
+        mint_calculator,
+        eip7623,
+        false,
+        None,
+    )
+}
+
+/// [derive_facet_deposits_with_blobs] for blobs that were already verified against their
+/// versioned hashes, such as those served by a [BlobProvider](crate::traits::BlobProvider).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn derive_facet_deposits_with_verified_blobs(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    blobs: &[Box<Blob>],
+    cfg: &FacetDepositConfig,
+    l2_block_number: u64,
+    fct_mint_rate: u128,
+    fct_mint_period_l1_data_gas: u128,
+    mint_calculator: FctMintCalculator,
+    eip7623: bool,
+) -> Result<FacetDerivationOutcome, PipelineEncodingError> {
+    let blobs = blobs.iter().map(|blob| &**blob);
+    let blob_payloads = decode_inbox_blobs(txs, blobs, cfg.inbox_address, |_, _| Ok(()))?;
+    derive_facet_deposits_inner(
+        txs,
+        receipts,
+        &blob_payloads,
+        cfg,
+        l2_block_number,
+        fct_mint_rate,
+        fct_mint_period_l1_data_gas,
+        mint_calculator,
+        eip7623,
+        false,
+        None,
+    )
+}
+
+/// Returns the versioned hashes of the blobs of every EIP-4844 transaction sent to the inbox of
+/// `cfg`, each indexed by its position among all blobs of the L1 block.
+pub fn facet_inbox_blob_hashes(
+    txs: &[TxEnvelope],
+    cfg: &FacetDepositConfig,
+) -> Vec<IndexedBlobHash> {
+    let mut index = 0;
+    let mut inbox_hashes = Vec::new();
+    for tx in txs {
+        let Some(hashes) = tx.blob_versioned_hashes() else { continue };
+        if tx.to() == Some(cfg.inbox_address) {
+            for (hash, index) in hashes.iter().zip(index..) {
+                inbox_hashes.push(IndexedBlobHash { index, hash: *hash });
+            }
+        }
+        index += hashes.len() as u64;
+    }
+    inbox_hashes
+}
+
+/// Checks `blob` against its versioned `hash` with KZG.
+#[cfg(feature = "kzg")]
+fn verify_blob(blob: &Blob, hash: &B256) -> Result<(), PipelineEncodingError> {
+    use alloy_eips::eip4844::{env_settings::EnvKzgSettings, kzg_to_versioned_hash};
+
+    let commitment = c_kzg::Blob::from_bytes(blob.as_slice())
+        .and_then(|blob| EnvKzgSettings::Default.get().blob_to_kzg_commitment(&blob))
+        .map_err(|_| PipelineEncodingError::InvalidFacetBlob(*hash))?;
+    if kzg_to_versioned_hash(&commitment.to_bytes().into_inner()) != *hash {
+        return Err(PipelineEncodingError::InvalidFacetBlob(*hash));
+    }
+    Ok(())
+}
+
+/// Checks `blobs` against the versioned hashes of the EIP-4844 transactions sent to the inbox with
+/// `verify`, returning the decoded data of each transaction's blobs by transaction index.
+///
+/// Blobs that do not decode are returned as empty data, which then fails to decode as a payload.
+fn decode_inbox_blobs<'a>(
+    txs: &[TxEnvelope],
+    blobs: impl IntoIterator<Item = &'a Blob>,
+    inbox_address: Address,
+    verify: impl Fn(&Blob, &B256) -> Result<(), PipelineEncodingError>,
+) -> Result<Vec<Vec<Bytes>>, PipelineEncodingError> {
+    let mut blobs = blobs.into_iter();
+    txs.iter()
+        .map(|tx| {
+            let hashes = match tx.blob_versioned_hashes() {
+                Some(hashes) if tx.to() == Some(inbox_address) => hashes,
+                _ => return Ok(Vec::new()),
+            };
+            hashes
+                .iter()
+                .map(|hash| {
+                    let blob =
+                        blobs.next().ok_or(PipelineEncodingError::InvalidFacetBlob(*hash))?;
+                    verify(blob, hash)?;
+                    Ok(blob_payload(blob))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the data `blob` carries, or empty data if it does not decode.
+fn blob_payload(blob: &Blob) -> Bytes {
+    use crate::sources::BlobData;
+
+    let data = Some(Bytes::copy_from_slice(blob.as_slice()));
+    BlobData { data, calldata: None }.decode().unwrap_or_default()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn derive_facet_deposits_inner(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    blob_payloads: &[Vec<Bytes>],
+    cfg: &FacetDepositConfig,
+    l2_block_number: u64,
+    fct_mint_rate: u128,
+    fct_mint_period_l1_data_gas: u128,
+    mint_calculator: FctMintCalculator,
+    eip7623: bool,
+    validate_encoding: bool,
+    mut trace: Option<&mut Vec<DeriveTraceEvent>>,
+) -> Result<FacetDerivationOutcome, PipelineEncodingError> {
+    let mut record = |event| {
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.push(event);
+        }
+    };
     debug_assert_eq!(txs.len(), receipts.len(), "txs/receipts length mismatch");
     
     tracing::info!(
@@ -33,35 +497,42 @@ pub fn derive_facet_deposits(
     );
 
     // Step 1: Collect all facet payloads with their metadata
+    let signers = recover_inbox_signers(txs, receipts, cfg.inbox_address);
     let mut facet_payloads = Vec::new();
+    let mut skipped = Vec::new();
+    let mut rejections = FacetRejectionCounts::default();
     let mut facet_inbox_count = 0;
     let mut total_calldata_txs = 0;
     let mut sample_addresses = Vec::new();
 
-    for (tx, receipt) in txs.iter().zip(receipts) {
-        if receipt.status != Eip658Value::Eip658(true) {
-            continue; // failed L1 txs do not produce deposits
-        }
-
+    for (tx_index, ((tx, receipt), signer)) in txs.iter().zip(receipts).zip(signers).enumerate() {
         let tx_hash = *match tx {
             TxEnvelope::Legacy(tx) => tx.hash(),
             TxEnvelope::Eip2930(tx) => tx.hash(),
             TxEnvelope::Eip1559(tx) => tx.hash(),
             TxEnvelope::Eip4844(tx) => tx.hash(),
-            _ => &B256::ZERO,
+            TxEnvelope::Eip7702(tx) => tx.hash(),
         };
-
-        // ------------------------------------------------------
-        // path #1 – calldata to FACET_INBOX_ADDRESS
-        // ------------------------------------------------------
-        total_calldata_txs += 1;
+        // Matched exhaustively so that a new envelope type fails to compile here rather than
+        // having its inbox calldata silently dropped.
         let (maybe_to, input): (Option<Address>, &Bytes) = match tx {
             TxEnvelope::Legacy(tx) => (Option::<Address>::from(tx.tx().to), &tx.tx().input),
             TxEnvelope::Eip2930(tx) => (Option::<Address>::from(tx.tx().to), &tx.tx().input),
             TxEnvelope::Eip1559(tx) => (Option::<Address>::from(tx.tx().to), &tx.tx().input),
             TxEnvelope::Eip4844(tx) => (Option::<Address>::from(tx.tx().to()), tx.tx().input()),
-            _ => (None, &Bytes::new()),
+            TxEnvelope::Eip7702(tx) => (Some(tx.tx().to), &tx.tx().input),
         };
+        record(DeriveTraceEvent::ConsideredTx { tx_hash, to: maybe_to });
+
+        if receipt.status != Eip658Value::Eip658(true) {
+            record(DeriveTraceEvent::SkippedFailedTx { tx_hash });
+            continue; // failed L1 txs do not produce deposits
+        }
+
+        // ------------------------------------------------------
+        // path #1 – calldata to the inbox address
+        // ------------------------------------------------------
+        total_calldata_txs += 1;
         
         // Collect sample addresses for debugging
         if sample_addresses.len() < 5 {
@@ -70,23 +541,27 @@ pub fn derive_facet_deposits(
             }
         }
 
-        if maybe_to == Some(FACET_INBOX_ADDRESS) && !input.is_empty() {
+        if maybe_to == Some(cfg.inbox_address) && !input.is_empty() {
             facet_inbox_count += 1;
+            record(DeriveTraceEvent::MatchedInboxCalldata { tx_hash });
             tracing::debug!(
                 target: "facet_deposits",
-                "Found calldata to FACET_INBOX_ADDRESS in tx {}",
+                "Found calldata to inbox {} in tx {}",
+                cfg.inbox_address,
                 tx_hash
             );
             // Try to decode the facet payload, skip if invalid
-            match decode_facet_payload(input, l2_chain_id, false) {
+            match decode_facet_payload_with_eip7623(input, cfg.l2_chain_id, false, eip7623) {
                 Ok(payload) => {
-                    let from = tx.recover_signer().unwrap_or_default();
+                    let from = signer.unwrap_or_default();
+                    record(DeriveTraceEvent::DecodedPayload { tx_hash, from });
                     tracing::info!(
                         target: "facet_deposits",
                         "Successfully decoded facet payload from calldata in tx {}",
                         tx_hash
                     );
-                    facet_payloads.push((payload, from, tx_hash));
+                    let source_hash = facet_source_hash(FACET_CALLDATA_SOURCE_DOMAIN, tx_hash, 0);
+                    facet_payloads.push((payload, from, tx_hash, source_hash));
                 },
                 Err(e) => {
                     tracing::debug!(
@@ -96,38 +571,73 @@ pub fn derive_facet_deposits(
                         e
                     );
                     // Skip invalid facet transactions (wrong prefix, invalid RLP, etc.)
-                    // This handles cases like gzipped data or other malformed inputs
+                    // This handles cases like gzipped data or other malformed inputs; compressed
+                    // payloads are only accepted by `decode_facet_payload_with_compression`
+                    record(DeriveTraceEvent::SkippedPayload { tx_hash, reason: e.clone() });
+                    rejections.record(&e);
+                    skipped.push(SkippedPayload { tx_hash, reason: e });
                 }
             }
-            continue; // one deposit per tx
+            // Fall through: inbox logs emitted by the same tx are derived after its calldata
         }
 
         // ------------------------------------------------------
-        // path #2 – first log with inbox topic0
+        // path #1b – blobs of a 4844 tx to the inbox, if provided
         // ------------------------------------------------------
-        let mut first_log: Option<&Log> = None;
-        for l in &receipt.logs {
-            if l.data.topics().first().is_some_and(|t| *t == FACET_LOG_INBOX_EVENT_SIG) {
-                first_log = Some(l);
-                break;
+        let blob_data = blob_payloads.get(tx_index).map(Vec::as_slice).unwrap_or_default();
+        for (blob_index, data) in blob_data.iter().enumerate() {
+            // Charged as if the blob's payload had been sent as calldata
+            match decode_facet_payload_with_eip7623(data, cfg.l2_chain_id, false, eip7623) {
+                Ok(payload) => {
+                    let from = signer.unwrap_or_default();
+                    record(DeriveTraceEvent::DecodedPayload { tx_hash, from });
+                    let source_hash =
+                        facet_source_hash(FACET_BLOB_SOURCE_DOMAIN, tx_hash, blob_index as u64);
+                    facet_payloads.push((payload, from, tx_hash, source_hash));
+                }
+                Err(e) => {
+                    tracing::debug!(
+                        target: "facet_deposits",
+                        "Failed to decode facet payload from blob {} of tx {}: {:?}",
+                        blob_index,
+                        tx_hash,
+                        e
+                    );
+                    record(DeriveTraceEvent::SkippedPayload { tx_hash, reason: e.clone() });
+                    rejections.record(&e);
+                    skipped.push(SkippedPayload { tx_hash, reason: e });
+                }
             }
         }
-        if let Some(log) = first_log {
+
+        // ------------------------------------------------------
+        // path #2 – every log with inbox topic0, in log order
+        // ------------------------------------------------------
+        let inbox_logs = receipt
+            .logs
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.data.topics().first().is_some_and(|t| *t == cfg.log_event_sig));
+        for (log_index, log) in inbox_logs {
+            record(DeriveTraceEvent::MatchedInboxLog { tx_hash, log_index });
             tracing::debug!(
                 target: "facet_deposits",
                 "Found facet log event in tx {}",
                 tx_hash
             );
             // Try to decode the facet payload from log, skip if invalid
-            match decode_facet_payload(&log.data.data, l2_chain_id, true) {
+            match decode_facet_payload_with_eip7623(&log.data.data, cfg.l2_chain_id, true, eip7623) {
                 Ok(payload) => {
                     let from = alias_l1_to_l2(log.address);
+                    record(DeriveTraceEvent::DecodedPayload { tx_hash, from });
                     tracing::info!(
                         target: "facet_deposits",
                         "Successfully decoded facet payload from log in tx {}",
                         tx_hash
                     );
-                    facet_payloads.push((payload, from, tx_hash));
+                    let source_hash =
+                        facet_source_hash(FACET_LOG_SOURCE_DOMAIN, tx_hash, log_index as u64);
+                    facet_payloads.push((payload, from, tx_hash, source_hash));
                 },
                 Err(e) => {
                     tracing::debug!(
@@ -137,30 +647,30 @@ pub fn derive_facet_deposits(
                         e
                     );
                     // Skip invalid facet log data (wrong prefix, invalid RLP, etc.)
+                    record(DeriveTraceEvent::SkippedPayload { tx_hash, reason: e.clone() });
+                    rejections.record(&e);
+                    skipped.push(SkippedPayload { tx_hash, reason: e });
                 }
             }
         }
     }
 
     // Step 2: Calculate new mint rate based on FCT mint calculation
-    let new_mint_rate = FctMintCalculator::compute_new_rate(
+    let new_mint_rate = mint_calculator.compute_new_rate(
         l2_block_number,
         fct_mint_rate,
         fct_mint_period_l1_data_gas,
     );
 
     // Step 3: Assign mint amounts to each facet transaction
-    for (payload, _, _) in &mut facet_payloads {
-        let mint_amount = FctMintCalculator::calculate_mint_amount(
-            payload.l1_data_gas_used,
-            new_mint_rate,
-        );
+    for (payload, _, _, _) in &mut facet_payloads {
+        let mint_amount = payload.estimated_mint(new_mint_rate);
         payload.set_mint(mint_amount);
     }
 
     // Step 4: Calculate new cumulative L1 data gas
     let batch_l1_data_gas: u64 = facet_payloads.iter()
-        .map(|(payload, _, _)| payload.l1_data_gas_used)
+        .map(|(payload, _, _, _)| payload.l1_data_gas_used)
         .sum();
 
     let new_cumulative_l1_data_gas = if FctMintCalculator::is_first_block_in_period(l2_block_number) {
@@ -170,17 +680,18 @@ pub fn derive_facet_deposits(
     };
 
     // Step 5: Convert payloads to deposit transactions
-    let mut out = Vec::with_capacity(facet_payloads.len());
-    for (payload, from, source_hash) in facet_payloads {
-        let dep = payload.into_deposit(from, source_hash);
-        let mut buf = Vec::with_capacity(dep.eip2718_encoded_length());
-        dep.encode_2718(&mut buf);
-        out.push(buf.into());
+    let source_txs: Vec<B256> = facet_payloads.iter().map(|(_, _, tx_hash, _)| *tx_hash).collect();
+    let deposits = facet_payloads
+        .into_iter()
+        .map(|(payload, from, _, source_hash)| payload.into_deposit(from, source_hash));
+    let out = encode_deposits(deposits, validate_encoding, encode_deposit)?;
+    for (index, tx_hash) in source_txs.into_iter().enumerate() {
+        record(DeriveTraceEvent::ProducedDeposit { tx_hash, index });
     }
     
     tracing::info!(
         target: "facet_deposits",
-        "derive_facet_deposits: Produced {} deposit transactions for L2 block {} (checked {} txs, {} to FACET_INBOX_ADDRESS)",
+        "derive_facet_deposits: Produced {} deposit transactions for L2 block {} (checked {} txs, {} to the inbox)",
         out.len(),
         l2_block_number,
         total_calldata_txs,
@@ -191,10 +702,468 @@ pub fn derive_facet_deposits(
         tracing::debug!(
             target: "facet_deposits",
             "Sample L1 transaction destinations (looking for {:?}): {:?}",
-            FACET_INBOX_ADDRESS,
+            cfg.inbox_address,
             sample_addresses
         );
     }
 
-    Ok((out, new_mint_rate, new_cumulative_l1_data_gas))
-} 
\ No newline at end of file
+    Ok(FacetDerivationOutcome {
+        deposits: out,
+        new_mint_rate,
+        new_cumulative_l1_data_gas,
+        skipped,
+        rejections,
+    })
+}
+
+impl FacetDerivationOutcome {
+    /// Discards the diagnostics, returning `(deposits, new_mint_rate, new_cumulative_l1_data_gas)`.
+    pub fn into_parts(self) -> (Vec<Bytes>, u128, u128) {
+        (self.deposits, self.new_mint_rate, self.new_cumulative_l1_data_gas)
+    }
+}
+
+/// EIP-2718 encodes a deposit transaction.
+fn encode_deposit(deposit: &TxDeposit) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(deposit.eip2718_encoded_length());
+    deposit.encode_2718(&mut buf);
+    buf
+}
+
+/// Encodes deposits with `encode`, round-trip checking each one if `validate` is set.
+fn encode_deposits(
+    deposits: impl IntoIterator<Item = TxDeposit>,
+    validate: bool,
+    encode: fn(&TxDeposit) -> Vec<u8>,
+) -> Result<Vec<Bytes>, PipelineEncodingError> {
+    let mut out = Vec::new();
+    for (index, deposit) in deposits.into_iter().enumerate() {
+        let buf = encode(&deposit);
+        if validate {
+            match TxDeposit::decode_2718(&mut buf.as_slice()) {
+                Ok(decoded) if decoded == deposit => {}
+                _ => return Err(PipelineEncodingError::InvalidFacetDeposit(index)),
+            }
+        }
+        out.push(buf.into());
+    }
+    Ok(out)
+}
+
+/// Facet inbox activity in an L1 block, counted independently of [derive_facet_deposits].
+///
+/// Used to cross-check the number of deposits produced by derivation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FacetInboxScan {
+    /// Successful txs whose calldata to the inbox address decodes as a facet payload.
+    pub inbox_calldata_txs: usize,
+    /// Blobs of successful EIP-4844 txs to the inbox address that decode as a facet payload.
+    pub inbox_blobs: usize,
+    /// Decodable inbox event logs emitted by successful txs.
+    pub inbox_logs: usize,
+    /// Txs that target the inbox or emit an inbox log, but whose receipt reports failure.
+    pub failed_txs: usize,
+}
+
+impl FacetInboxScan {
+    /// The number of deposits derivation is expected to produce for the scanned block.
+    pub const fn expected_deposits(&self) -> usize {
+        self.inbox_calldata_txs + self.inbox_blobs + self.inbox_logs
+    }
+}
+
+/// Recovers the signer of every successful tx carrying inbox calldata, indexed like `txs`.
+///
+/// ECDSA recovery dominates the derivation of blocks with many inbox txs, so with the `parallel`
+/// feature it runs on the rayon thread pool. A signature that fails to recover yields the zero
+/// address.
+fn recover_inbox_signers(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    inbox_address: Address,
+) -> Vec<Option<Address>> {
+    let recover = |(tx, receipt): (&TxEnvelope, &Receipt)| {
+        let inbox_data = !tx.input().is_empty() ||
+            tx.blob_versioned_hashes().is_some_and(|hashes| !hashes.is_empty());
+        let inbox_tx = receipt.status == Eip658Value::Eip658(true) &&
+            tx.to() == Some(inbox_address) &&
+            inbox_data;
+        inbox_tx.then(|| tx.recover_signer().unwrap_or_default())
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+        txs.par_iter().zip(receipts).map(recover).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        txs.iter().zip(receipts).map(recover).collect()
+    }
+}
+
+/// Scans an L1 block for calldata and blobs sent to the inbox of `cfg` and for its inbox event
+/// logs.
+///
+/// Valid inbox calldata contributes one deposit per tx, every valid blob of an EIP-4844 tx to the
+/// inbox one more, and every valid inbox log of the receipt one more, whether or not the tx also
+/// called the inbox. `blobs` are the blobs of every EIP-4844 tx sent to the inbox, in L1 order, see
+/// [facet_inbox_blob_hashes]; the scan does not verify them.
+///
+/// Payloads are checked with [is_facet_payload] rather than the decoder derivation uses, so a bug
+/// in either shows up as a count mismatch.
+pub fn scan_facet_inbox(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    blobs: &[&Blob],
+    cfg: &FacetDepositConfig,
+) -> FacetInboxScan {
+    let mut blobs = blobs.iter().copied();
+    let mut scan = FacetInboxScan::default();
+    for (tx, receipt) in txs.iter().zip(receipts) {
+        let to_inbox = tx.to() == Some(cfg.inbox_address);
+        let calldata = (to_inbox && !tx.input().is_empty()).then(|| tx.input());
+        // The blobs of a failed tx are handed in too, so they are consumed either way
+        let blob_count = tx.blob_versioned_hashes().filter(|_| to_inbox).map_or(0, <[B256]>::len);
+        let tx_blobs: Vec<&Blob> = blobs.by_ref().take(blob_count).collect();
+        let mut logs = receipt
+            .logs
+            .iter()
+            .filter(|l| l.data.topics().first() == Some(&cfg.log_event_sig))
+            .map(|l| &l.data.data)
+            .peekable();
+
+        if calldata.is_none() && blob_count == 0 && logs.peek().is_none() {
+            continue;
+        }
+        if receipt.status != Eip658Value::Eip658(true) {
+            scan.failed_txs += 1;
+            continue;
+        }
+
+        if let Some(input) = calldata {
+            if is_facet_payload(input, cfg.l2_chain_id) {
+                scan.inbox_calldata_txs += 1;
+            }
+        }
+        scan.inbox_blobs += tx_blobs
+            .into_iter()
+            .filter(|blob| is_facet_payload(&blob_payload(blob), cfg.l2_chain_id))
+            .count();
+        scan.inbox_logs += logs.filter(|data| is_facet_payload(data, cfg.l2_chain_id)).count();
+    }
+    scan
+}
+
+/// Returns `true` if `bytes` is a facet payload for `l2_chain_id`.
+///
+/// Checks the `0x46 || RLP([chain_id, to, value, gas, data, mine_boost])` layout field by field,
+/// independently of [decode_facet_payload](kona_protocol::decode_facet_payload).
+fn is_facet_payload(bytes: &[u8], l2_chain_id: u64) -> bool {
+    let Some((&FACET_TX_TYPE, mut rlp)) = bytes.split_first() else {
+        return false;
+    };
+    if bytes.len() > MAX_FACET_PAYLOAD_BYTES {
+        return false;
+    }
+    let Ok(header) = alloy_rlp::Header::decode(&mut rlp) else {
+        return false;
+    };
+    // Bytes after the list are ignored
+    match rlp.get(..header.payload_length) {
+        Some(fields) if header.list => {
+            facet_payload_fields(fields).is_ok_and(|(chain_id, to_len)| {
+                chain_id == l2_chain_id && (to_len == 0 || to_len == 20)
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Decodes the fields of a facet payload list, returning its chain id and the length of its `to`.
+fn facet_payload_fields(mut fields: &[u8]) -> alloy_rlp::Result<(u64, usize)> {
+    use alloy_primitives::U256;
+    use alloy_rlp::Decodable;
+
+    let chain_id = u64::decode(&mut fields)?;
+    let to = Bytes::decode(&mut fields)?;
+    let _value = U256::decode(&mut fields)?;
+    let _gas_limit = u64::decode(&mut fields)?;
+    let _data = Bytes::decode(&mut fields)?;
+    let _mine_boost = Bytes::decode(&mut fields)?;
+    if !fields.is_empty() {
+        return Err(alloy_rlp::Error::ListLengthMismatch { expected: 0, got: fields.len() });
+    }
+    Ok((chain_id, to.len()))
+}
+
+/// Checks that `deposit_count` matches an independent [scan_facet_inbox] of the same L1 block.
+///
+/// A mismatch indicates a bug in [derive_facet_deposits].
+pub fn check_facet_deposit_count(
+    txs: &[TxEnvelope],
+    receipts: &[Receipt],
+    blobs: &[&Blob],
+    cfg: &FacetDepositConfig,
+    deposit_count: usize,
+) -> Result<FacetInboxScan, PipelineEncodingError> {
+    let scan = scan_facet_inbox(txs, receipts, blobs, cfg);
+    if scan.expected_deposits() != deposit_count {
+        return Err(PipelineEncodingError::FacetDepositCountMismatch {
+            expected: scan.expected_deposits(),
+            actual: deposit_count,
+        });
+    }
+    Ok(scan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{TxKind, U256};
+
+    fn deposit() -> TxDeposit {
+        TxDeposit {
+            source_hash: B256::repeat_byte(0x01),
+            from: Address::repeat_byte(0x02),
+            to: TxKind::Call(Address::repeat_byte(0x03)),
+            mint: Some(576),
+            value: U256::ZERO,
+            gas_limit: 1_000_000,
+            is_system_transaction: false,
+            input: Bytes::from_static(&[0x12, 0x34]),
+        }
+    }
+
+    /// Encoder that drops the last byte of the RLP payload.
+    fn truncating_encode(deposit: &TxDeposit) -> Vec<u8> {
+        let mut buf = encode_deposit(deposit);
+        buf.pop();
+        buf
+    }
+
+    #[test]
+    fn test_encode_deposits_roundtrip() {
+        let out = encode_deposits([deposit(), deposit()], true, encode_deposit).unwrap();
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0][0], 0x7e);
+    }
+
+    #[test]
+    fn test_validation_catches_malformed_deposit() {
+        assert_eq!(
+            encode_deposits([deposit()], true, truncating_encode),
+            Err(PipelineEncodingError::InvalidFacetDeposit(0))
+        );
+        // Without validation the malformed bytes pass through unchecked
+        assert!(encode_deposits([deposit()], false, truncating_encode).is_ok());
+    }
+
+    #[cfg(feature = "kzg")]
+    fn versioned_hash(blob: &Blob) -> B256 {
+        use alloy_eips::eip4844::{env_settings::EnvKzgSettings, kzg_to_versioned_hash};
+
+        let blob = c_kzg::Blob::from_bytes(blob.as_slice()).unwrap();
+        let commitment = EnvKzgSettings::Default.get().blob_to_kzg_commitment(&blob).unwrap();
+        kzg_to_versioned_hash(&commitment.to_bytes().into_inner())
+    }
+
+    #[cfg(feature = "kzg")]
+    #[test]
+    fn test_derive_facet_deposit_from_blob() {
+        use crate::test_utils::encode_blob;
+        use alloc::vec;
+        use alloy_consensus::{Signed, TxEip4844, TxEip4844Variant};
+        use alloy_primitives::{Signature, hex};
+        use kona_protocol::FACET_BLOB_SOURCE_DOMAIN;
+
+        let payload =
+            hex::decode("46e283face7a94111111111111111111111111111111111111111180830f424082123480")
+                .unwrap();
+        let blob = encode_blob(&payload);
+        let blob_tx = |hash: B256| {
+            let tx = TxEip4844 {
+                to: FACET_INBOX_ADDRESS,
+                input: Bytes::from(payload.clone()),
+                blob_versioned_hashes: vec![hash],
+                ..Default::default()
+            };
+            TxEnvelope::Eip4844(Signed::new_unchecked(
+                TxEip4844Variant::TxEip4844(tx),
+                Signature::test_signature(),
+                Default::default(),
+            ))
+        };
+        let receipts = [Receipt { status: Eip658Value::Eip658(true), ..Default::default() }];
+
+        let txs = [blob_tx(versioned_hash(&blob))];
+        let cfg = FacetDepositConfig::mainnet(16436858);
+        let rate = FctMintCalculator::INITIAL_RATE;
+        let derive = |txs: &[TxEnvelope], blobs: &[Blob]| {
+            let calculator = FctMintCalculator::default();
+            derive_facet_deposits_with_blobs(
+                txs, &receipts, blobs, &cfg, 1, rate, 0, calculator, false,
+            )
+        };
+        let outcome = derive(&txs, &[blob]).unwrap();
+        assert_eq!(outcome.deposits.len(), 2);
+        let deposits: Vec<TxDeposit> = outcome
+            .deposits
+            .iter()
+            .map(|deposit| TxDeposit::decode_2718(&mut deposit.as_ref()).unwrap())
+            .collect();
+        assert_eq!(deposits[1].to, TxKind::Call(Address::repeat_byte(0x11)));
+        assert_eq!(deposits[1].from, txs[0].recover_signer().unwrap());
+        assert_eq!(
+            deposits[1].source_hash,
+            facet_source_hash(FACET_BLOB_SOURCE_DOMAIN, *txs[0].tx_hash(), 0)
+        );
+
+        // The blob copy of the payload is charged the same L1 data gas as the calldata copy
+        assert_eq!(deposits[0].mint, Some(576 * rate));
+        assert_eq!(deposits[1].mint, Some(576 * rate));
+        assert_eq!(outcome.new_cumulative_l1_data_gas, 2 * 576);
+
+        // A missing blob, or a blob that does not match its versioned hash, fails derivation
+        let missing = versioned_hash(&blob);
+        assert_eq!(derive(&txs, &[]), Err(PipelineEncodingError::InvalidFacetBlob(missing)));
+        let mismatched = B256::repeat_byte(0x01);
+        assert_eq!(
+            derive(&[blob_tx(mismatched)], &[blob]),
+            Err(PipelineEncodingError::InvalidFacetBlob(mismatched))
+        );
+    }
+
+    #[test]
+    fn test_scan_counts_inbox_blobs() {
+        use crate::test_utils::encode_blob;
+        use alloc::vec;
+        use alloy_consensus::{Signed, TxEip4844, TxEip4844Variant};
+        use alloy_primitives::{Signature, hex};
+
+        let payload =
+            hex::decode("46e283face7a94111111111111111111111111111111111111111180830f424082123480")
+                .unwrap();
+        let blob_tx = |hashes: &[B256]| {
+            let tx = TxEip4844 {
+                to: FACET_INBOX_ADDRESS,
+                blob_versioned_hashes: hashes.to_vec(),
+                ..Default::default()
+            };
+            TxEnvelope::Eip4844(Signed::new_unchecked(
+                TxEip4844Variant::TxEip4844(tx),
+                Signature::test_signature(),
+                Default::default(),
+            ))
+        };
+        let receipt =
+            |success| Receipt { status: Eip658Value::Eip658(success), ..Default::default() };
+
+        // A payload and a blob that is not one, then a failed tx whose blob is a payload
+        let txs = [blob_tx(&[B256::repeat_byte(1), B256::repeat_byte(2)]), blob_tx(&[B256::ZERO])];
+        let receipts = [receipt(true), receipt(false)];
+        let blobs = vec![
+            Box::new(encode_blob(&payload)),
+            Box::new(encode_blob(&[0xff; 8])),
+            Box::new(encode_blob(&payload)),
+        ];
+        let cfg = FacetDepositConfig::mainnet(16436858);
+
+        let outcome = derive_facet_deposits_with_verified_blobs(
+            &txs,
+            &receipts,
+            &blobs,
+            &cfg,
+            1,
+            FctMintCalculator::INITIAL_RATE,
+            0,
+            FctMintCalculator::default(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(outcome.deposits.len(), 1);
+
+        let blob_refs: Vec<&Blob> = blobs.iter().map(|blob| &**blob).collect();
+        let scan = check_facet_deposit_count(&txs, &receipts, &blob_refs, &cfg, 1).unwrap();
+        assert_eq!(
+            scan,
+            FacetInboxScan { inbox_calldata_txs: 0, inbox_blobs: 1, inbox_logs: 0, failed_txs: 1 }
+        );
+
+        // Without its blobs, the scan cannot account for the blob deposit
+        assert_eq!(
+            check_facet_deposit_count(&txs, &receipts, &[], &cfg, 1),
+            Err(PipelineEncodingError::FacetDepositCountMismatch { expected: 0, actual: 1 })
+        );
+    }
+
+    #[test]
+    fn test_is_facet_payload_matches_decoder() {
+        use alloc::vec;
+        use alloy_primitives::{U256, hex};
+        use kona_protocol::{decode_facet_payload, encode_facet_payload};
+
+        let chain_id = 16436858;
+        let payload =
+            hex::decode("46e283face7a94111111111111111111111111111111111111111180830f424082123480")
+                .unwrap();
+        let mut trailing = payload.clone();
+        trailing.push(0x00);
+        let mut bad_to =
+            encode_facet_payload(chain_id, None, U256::ZERO, 21_000, &[], &[]).to_vec();
+        // Turn the empty `to` into a single byte string
+        let to = bad_to.iter().position(|b| *b == 0x80).unwrap();
+        bad_to[to] = 0x01;
+
+        let inputs = [
+            payload.clone(),
+            trailing,
+            payload[..payload.len() - 1].to_vec(),
+            payload[1..].to_vec(),
+            vec![FACET_TX_TYPE],
+            vec![],
+            bad_to,
+            encode_facet_payload(chain_id + 1, None, U256::ZERO, 21_000, &[], &[]).to_vec(),
+            encode_facet_payload(chain_id, None, U256::from(7), 21_000, &[0xab], &[1]).to_vec(),
+        ];
+        for input in inputs {
+            assert_eq!(
+                is_facet_payload(&input, chain_id),
+                decode_facet_payload(&input, chain_id, false).is_ok(),
+                "{input:02x?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_facet_inbox_blob_hashes() {
+        use alloc::vec;
+        use alloy_consensus::{Signed, TxEip4844, TxEip4844Variant};
+        use alloy_primitives::Signature;
+
+        let blob_tx = |to: Address, hashes: &[B256]| {
+            let tx = TxEip4844 { to, blob_versioned_hashes: hashes.to_vec(), ..Default::default() };
+            TxEnvelope::Eip4844(Signed::new_unchecked(
+                TxEip4844Variant::TxEip4844(tx),
+                Signature::test_signature(),
+                Default::default(),
+            ))
+        };
+        let hash = B256::repeat_byte;
+        let txs = [
+            blob_tx(FACET_INBOX_ADDRESS, &[hash(0x01)]),
+            blob_tx(Address::repeat_byte(0x22), &[hash(0x02), hash(0x03)]),
+            blob_tx(FACET_INBOX_ADDRESS, &[hash(0x04), hash(0x05)]),
+        ];
+
+        // Blobs sent elsewhere still count toward the index within the block
+        assert_eq!(
+            facet_inbox_blob_hashes(&txs, &FacetDepositConfig::mainnet(16436858)),
+            vec![
+                IndexedBlobHash { index: 0, hash: hash(0x01) },
+                IndexedBlobHash { index: 3, hash: hash(0x04) },
+                IndexedBlobHash { index: 4, hash: hash(0x05) },
+            ]
+        );
+    }
+}
@@ -1 +1,2 @@
-pub mod facet_deposits; 
\ No newline at end of file
+pub mod comparison;
+pub mod facet_deposits;
@@ -31,4 +31,16 @@ pub mod types;
 pub mod test_utils;
 
 mod helpers;
-pub use helpers::facet_deposits::derive_facet_deposits;
+pub use helpers::comparison::{
+    DerivationComparison, TransactionComparison, TxMatchStatus, compare_derived_block,
+    compare_transactions,
+};
+pub use helpers::facet_deposits::{
+    DeriveTraceEvent, FacetDepositConfig, FacetDerivationOutcome, FacetInboxScan,
+    FacetRejectionCounts, SkippedPayload, check_facet_deposit_count, derive_facet_deposits,
+    derive_facet_deposits_detailed, derive_facet_deposits_traced,
+    derive_facet_deposits_with_config, derive_facet_deposits_with_validation,
+    facet_inbox_blob_hashes, scan_facet_inbox,
+};
+#[cfg(feature = "kzg")]
+pub use helpers::facet_deposits::derive_facet_deposits_with_blobs;
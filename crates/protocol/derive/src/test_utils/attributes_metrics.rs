@@ -0,0 +1,43 @@
+//! A counting implementation of [AttributesMetrics] for testing.
+
+use crate::traits::AttributesMetrics;
+use alloc::vec::Vec;
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use spin::Mutex;
+
+/// An [AttributesMetrics] that records every built payload attributes.
+///
+/// Its clock advances by one millisecond every time it is read, so a build reports a duration of
+/// one millisecond.
+#[derive(Debug, Default)]
+pub struct TestAttributesMetrics {
+    /// The milliseconds elapsed on the clock.
+    clock: AtomicU64,
+    /// The `(duration, num_txs, num_facet_deposits)` of every build, in order.
+    pub builds: Mutex<Vec<(Duration, usize, usize)>>,
+}
+
+impl TestAttributesMetrics {
+    /// Returns the number of recorded builds.
+    pub fn build_count(&self) -> usize {
+        self.builds.lock().len()
+    }
+
+    /// Returns the total number of Facet deposits over all recorded builds.
+    pub fn facet_deposit_count(&self) -> usize {
+        self.builds.lock().iter().map(|(_, _, deposits)| deposits).sum()
+    }
+}
+
+impl AttributesMetrics for TestAttributesMetrics {
+    fn now(&self) -> Duration {
+        Duration::from_millis(self.clock.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn on_attributes_built(&self, duration: Duration, num_txs: usize, num_facet_deposits: usize) {
+        self.builds.lock().push((duration, num_txs, num_facet_deposits));
+    }
+}
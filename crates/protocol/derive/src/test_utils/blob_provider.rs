@@ -49,3 +49,18 @@ impl BlobProvider for TestBlobProvider {
         Ok(blobs)
     }
 }
+
+/// Blob encodes `data` of at most 59 bytes, which fits the first round of field elements.
+pub fn encode_blob(data: &[u8]) -> Blob {
+    let mut blob = Blob::ZERO;
+    blob[2..5].copy_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+    let (head, tail) = data.split_at(data.len().min(27));
+    blob[5..5 + head.len()].copy_from_slice(head);
+    if let Some((&x, rest)) = tail.split_first() {
+        // The 28th byte is spread over the high bits of the first two field elements
+        blob[0] = x & 0b0011_1111;
+        blob[32] = (x >> 6) << 4;
+        blob[33..33 + rest.len()].copy_from_slice(rest);
+    }
+    blob
+}
@@ -8,7 +8,7 @@ pub use pipeline::{
 };
 
 mod blob_provider;
-pub use blob_provider::TestBlobProvider;
+pub use blob_provider::{TestBlobProvider, encode_blob};
 
 mod chain_providers;
 pub use chain_providers::{TestChainProvider, TestL2ChainProvider, TestProviderError};
@@ -25,6 +25,9 @@ pub use attributes_queue::{
     new_test_attributes_provider,
 };
 
+mod attributes_metrics;
+pub use attributes_metrics::TestAttributesMetrics;
+
 mod batch_stream;
 pub use batch_stream::TestBatchStreamProvider;
 
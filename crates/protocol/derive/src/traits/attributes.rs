@@ -1,9 +1,10 @@
 //! Contains traits for working with payload attributes and their providers.
 
 use crate::types::PipelineResult;
-use alloc::boxed::Box;
+use alloc::{boxed::Box, fmt::Debug};
 use alloy_eips::BlockNumHash;
 use async_trait::async_trait;
+use core::time::Duration;
 use kona_protocol::{L2BlockInfo, OpAttributesWithParent, SingleBatch};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 
@@ -47,3 +48,29 @@ pub trait AttributesBuilder {
         epoch: BlockNumHash,
     ) -> PipelineResult<OpPayloadAttributes>;
 }
+
+/// Receives measurements from the [StatefulAttributesBuilder], e.g. to export them as metrics.
+///
+/// Every method defaults to a no-op.
+///
+/// [StatefulAttributesBuilder]: crate::attributes::StatefulAttributesBuilder
+pub trait AttributesMetrics: Debug + Send + Sync {
+    /// Returns the current time of a monotonic clock, used to measure how long building the
+    /// attributes takes.
+    ///
+    /// This crate is `no_std`, so the clock is provided by the implementation. The default
+    /// reports every build as taking no time.
+    fn now(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    /// Called after payload attributes were built, with the time taken to build them, their
+    /// number of transactions, and how many of those are Facet deposits.
+    fn on_attributes_built(
+        &self,
+        _duration: Duration,
+        _num_txs: usize,
+        _num_facet_deposits: usize,
+    ) {
+    }
+}
@@ -8,7 +8,7 @@ mod providers;
 pub use providers::{BatchValidationProviderDerive, ChainProvider, L2ChainProvider};
 
 mod attributes;
-pub use attributes::{AttributesBuilder, AttributesProvider, NextAttributes};
+pub use attributes::{AttributesBuilder, AttributesMetrics, AttributesProvider, NextAttributes};
 
 mod data_sources;
 pub use data_sources::{BlobProvider, DataAvailabilityProvider};
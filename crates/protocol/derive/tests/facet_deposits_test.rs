@@ -1,14 +1,27 @@
-use alloy_consensus::{Signed, TxLegacy, TxEnvelope, Receipt, Eip658Value};
-use alloy_primitives::{hex, Bytes, Signature, TxKind, U256, Address, Log, LogData};
-use kona_protocol::{FACET_INBOX_ADDRESS, FACET_LOG_INBOX_EVENT_SIG, alias_l1_to_l2};
-use kona_derive::derive_facet_deposits;
+use alloy_consensus::{Eip658Value, Receipt, Signed, TxEip7702, TxEnvelope, TxLegacy};
+use alloy_eips::eip2718::Decodable2718 as _;
+use alloy_primitives::{Address, Bytes, Log, LogData, Signature, TxKind, U256, b256, hex};
+use kona_derive::{
+    DeriveTraceEvent, FacetDepositConfig, FacetRejectionCounts, SkippedPayload,
+    check_facet_deposit_count, derive_facet_deposits, derive_facet_deposits_detailed,
+    derive_facet_deposits_traced, derive_facet_deposits_with_config,
+    derive_facet_deposits_with_validation, scan_facet_inbox,
+};
+use kona_protocol::{
+    FACET_CALLDATA_SOURCE_DOMAIN, FACET_INBOX_ADDRESS, FACET_LOG_INBOX_EVENT_SIG,
+    FACET_LOG_SOURCE_DOMAIN, FacetDecodeError, FctMintCalculator, alias_l1_to_l2,
+    encode_facet_payload, facet_source_hash,
+};
+use op_alloy_consensus::TxDeposit;
+
+/// A facet payload for chain 16436858 calling `0x11..11` with 1_000_000 gas, data `0x1234`.
+const KNOWN_VALID_PAYLOAD: [u8; 36] =
+    hex!("46e283face7a94111111111111111111111111111111111111111180830f424082123480");
 
 #[test]
 fn test_derive_facet_deposits_from_calldata() {
     // Use the known valid payload
-    let known_valid_payload = "46e283face7a94111111111111111111111111111111111111111180830f424082123480";
-    let facet_data = hex::decode(known_valid_payload).expect("invalid hex");
-    let input = Bytes::from(facet_data);
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
 
     // Build a dummy legacy tx to FACET_INBOX_ADDRESS
     let legacy = TxLegacy {
@@ -27,25 +40,99 @@ fn test_derive_facet_deposits_from_calldata() {
     // Build matching receipt with success and no logs
     let receipt = Receipt { status: Eip658Value::Eip658(true), ..Default::default() };
 
-    let (deposits, _, _) = derive_facet_deposits(&[envelope], &[receipt], 16436858, 1, 0u128, 0u128).expect("derive failed");
+    let (deposits, _, _) =
+        derive_facet_deposits(&[envelope], &[receipt], 16436858, 1, 0u128, 0u128)
+            .expect("derive failed");
 
     // Verify we got exactly one deposit
     assert_eq!(deposits.len(), 1);
-    
+
     // Verify it's a deposit transaction (type 0x7e)
     assert!(!deposits[0].is_empty());
     assert_eq!(deposits[0][0], 0x7e);
-    
+
     // Verify the length is reasonable
     assert_eq!(deposits[0].len(), 89);
 }
 
+#[test]
+fn test_derive_facet_deposits_with_eip7623_floor() {
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
+    let legacy = TxLegacy { to: TxKind::Call(FACET_INBOX_ADDRESS), input, ..Default::default() };
+    let envelope = TxEnvelope::Legacy(Signed::new_unchecked(
+        legacy,
+        Signature::test_signature(),
+        Default::default(),
+    ));
+    let receipt = Receipt { status: Eip658Value::Eip658(true), ..Default::default() };
+
+    let derive = |eip7623| {
+        derive_facet_deposits_with_validation(
+            &[envelope.clone()],
+            &[receipt.clone()],
+            &FacetDepositConfig::mainnet(16436858),
+            1,
+            FctMintCalculator::INITIAL_RATE,
+            0u128,
+            FctMintCalculator::default(),
+            eip7623,
+            true,
+        )
+        .expect("derive failed")
+    };
+    let (pre_deposits, _, pre_gas) = derive(false);
+    let (post_deposits, _, post_gas) = derive(true);
+
+    // 36 non-zero bytes: 576 gas at 16 per byte, 1440 at the floor of 10 per token
+    assert_eq!(pre_gas, 576);
+    assert_eq!(post_gas, 1440);
+
+    let mint = |deposit: &Bytes| TxDeposit::decode_2718(&mut deposit.as_ref()).unwrap().mint;
+    assert_eq!(mint(&pre_deposits[0]), Some(576 * FctMintCalculator::INITIAL_RATE));
+    assert_eq!(mint(&post_deposits[0]), Some(1440 * FctMintCalculator::INITIAL_RATE));
+}
+
+#[test]
+fn test_derive_facet_deposits_from_eip7702_calldata() {
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
+
+    // Submit the inbox calldata through a set-code tx
+    let eip7702 = TxEip7702 {
+        chain_id: 1,
+        gas_limit: 100_000,
+        to: FACET_INBOX_ADDRESS,
+        input: input.clone(),
+        ..Default::default()
+    };
+    let envelope = TxEnvelope::Eip7702(Signed::new_unchecked(
+        eip7702,
+        Signature::test_signature(),
+        Default::default(),
+    ));
+    let receipt = Receipt { status: Eip658Value::Eip658(true), ..Default::default() };
+
+    let (deposits, _, _) =
+        derive_facet_deposits(&[envelope.clone()], &[receipt.clone()], 16436858, 1, 0u128, 0u128)
+            .expect("derive failed");
+
+    assert_eq!(deposits.len(), 1);
+    assert_eq!(deposits[0][0], 0x7e);
+    assert!(
+        check_facet_deposit_count(
+            &[envelope],
+            &[receipt],
+            &[],
+            &FacetDepositConfig::mainnet(16436858),
+            deposits.len()
+        )
+        .is_ok()
+    );
+}
+
 #[test]
 fn test_derive_facet_deposits_from_log() {
     // Use the known valid payload
-    let known_valid_payload = "46e283face7a94111111111111111111111111111111111111111180830f424082123480";
-    let facet_data = hex::decode(known_valid_payload).expect("invalid hex");
-    let input = Bytes::from(facet_data);
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
 
     // Build a dummy transaction that does NOT go to FACET_INBOX_ADDRESS
     let dummy_contract = Address::from_slice(&[0x22; 20]);
@@ -63,45 +150,258 @@ fn test_derive_facet_deposits_from_log() {
     let envelope_log = TxEnvelope::Legacy(signed_log);
 
     // Build receipt with a log containing the facet payload
-    let emitting_contract = Address::from_slice(&hex::decode("db8dc4ac38c094746529a14be18d99c18ecaedac").expect("valid hex"));
+    let emitting_contract = Address::from_slice(
+        &hex::decode("db8dc4ac38c094746529a14be18d99c18ecaedac").expect("valid hex"),
+    );
     let log = Log {
         address: emitting_contract,
         data: LogData::new(
             vec![FACET_LOG_INBOX_EVENT_SIG], // topic0 is the facet inbox event signature
-            input.clone(), // log data is the known valid payload
-        ).expect("valid log data"),
-    };
-    
-    let receipt_log = Receipt {
-        status: Eip658Value::Eip658(true),
-        logs: vec![log],
-        ..Default::default()
+            input.clone(),                   // log data is the known valid payload
+        )
+        .expect("valid log data"),
     };
 
-    let (deposits_log, _, _) = derive_facet_deposits(&[envelope_log], &[receipt_log], 16436858, 1, 0u128, 0u128).expect("derive failed");
+    let receipt_log =
+        Receipt { status: Eip658Value::Eip658(true), logs: vec![log], ..Default::default() };
+
+    let (deposits_log, _, _) =
+        derive_facet_deposits(&[envelope_log], &[receipt_log], 16436858, 1, 0u128, 0u128)
+            .expect("derive failed");
 
     // Verify we got exactly one deposit
     assert_eq!(deposits_log.len(), 1);
-    
+
     // Verify it's a deposit transaction (type 0x7e)
     assert!(!deposits_log[0].is_empty());
     assert_eq!(deposits_log[0][0], 0x7e);
-    
+
     // Verify the length is reasonable
     assert_eq!(deposits_log[0].len(), 89);
 }
 
+#[test]
+fn test_derive_facet_deposits_with_custom_inbox() {
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
+
+    // A devnet deployment with its own inbox address and event signature
+    let cfg = FacetDepositConfig {
+        inbox_address: Address::repeat_byte(0xde),
+        log_event_sig: b256!("1111111111111111111111111111111111111111111111111111111111111111"),
+        l2_chain_id: 16436858,
+    };
+    let to_inbox = |to: Address| {
+        let legacy = TxLegacy {
+            chain_id: Some(1u64),
+            gas_limit: 21000,
+            to: TxKind::Call(to),
+            input: input.clone(),
+            ..Default::default()
+        };
+        TxEnvelope::Legacy(Signed::new_unchecked(
+            legacy,
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    };
+    let devnet_log = Log {
+        address: Address::repeat_byte(0x22),
+        data: LogData::new(vec![cfg.log_event_sig], input.clone()).expect("valid log data"),
+    };
+    let txs = [to_inbox(cfg.inbox_address), to_inbox(FACET_INBOX_ADDRESS)];
+    let receipts = [
+        Receipt { status: Eip658Value::Eip658(true), logs: vec![devnet_log], ..Default::default() },
+        Receipt { status: Eip658Value::Eip658(true), ..Default::default() },
+    ];
+
+    // Devnet calldata and log are derived, the call to the mainnet inbox is not
+    let (deposits, _, _) =
+        derive_facet_deposits_with_config(&txs, &receipts, &cfg, 1, 0u128, 0u128)
+            .expect("derive failed");
+    assert_eq!(deposits.len(), 2);
+    let from: Vec<Address> = deposits
+        .iter()
+        .map(|d| TxDeposit::decode_2718(&mut d.as_ref()).expect("valid deposit").from)
+        .collect();
+    assert_eq!(
+        from,
+        vec![
+            txs[0].recover_signer().expect("recoverable signer"),
+            alias_l1_to_l2(Address::repeat_byte(0x22))
+        ]
+    );
+    let scan = check_facet_deposit_count(&txs, &receipts, &[], &cfg, deposits.len())
+        .expect("count mismatch");
+    assert_eq!((scan.inbox_calldata_txs, scan.inbox_logs), (1, 1));
+
+    // The mainnet wrapper only sees the mainnet inbox
+    let (mainnet_deposits, _, _) =
+        derive_facet_deposits(&txs, &receipts, 16436858, 1, 0u128, 0u128).expect("derive failed");
+    assert_eq!(mainnet_deposits.len(), 1);
+}
+
+#[test]
+fn test_derive_facet_deposits_from_multiple_logs() {
+    let chain_id = 16436858;
+    let first_payload = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
+    let second_payload = encode_facet_payload(
+        chain_id,
+        Some(Address::repeat_byte(0x44)),
+        U256::from(1),
+        50_000,
+        &[0xab, 0xcd],
+        &[],
+    );
+
+    let legacy = TxLegacy {
+        chain_id: Some(1u64),
+        nonce: 0,
+        gas_price: 1,
+        gas_limit: 21000,
+        to: TxKind::Call(Address::from_slice(&[0x22; 20])),
+        value: U256::ZERO,
+        input: Bytes::new(),
+    };
+    let envelope = TxEnvelope::Legacy(Signed::new_unchecked(
+        legacy,
+        Signature::test_signature(),
+        Default::default(),
+    ));
+
+    // Two inbox events from different contracts in the same receipt
+    let first_emitter = Address::from_slice(&[0x33; 20]);
+    let second_emitter = Address::from_slice(&[0x55; 20]);
+    let inbox_log = |address: Address, data: Bytes| Log {
+        address,
+        data: LogData::new(vec![FACET_LOG_INBOX_EVENT_SIG], data).expect("valid log data"),
+    };
+    let receipt = Receipt {
+        status: Eip658Value::Eip658(true),
+        logs: vec![
+            inbox_log(first_emitter, first_payload),
+            inbox_log(second_emitter, second_payload),
+        ],
+        ..Default::default()
+    };
+
+    let (deposits, _, _) =
+        derive_facet_deposits(&[envelope.clone()], &[receipt.clone()], chain_id, 1, 0u128, 0u128)
+            .expect("derive failed");
+    assert_eq!(deposits.len(), 2);
+
+    // Deposits come out in log order, each sent by its emitter's alias
+    let decoded: Vec<TxDeposit> = deposits
+        .iter()
+        .map(|d| TxDeposit::decode_2718(&mut d.as_ref()).expect("valid deposit"))
+        .collect();
+    assert_eq!(decoded[0].from, alias_l1_to_l2(first_emitter));
+    assert_eq!(decoded[0].to, TxKind::Call(Address::repeat_byte(0x11)));
+    assert_eq!(decoded[1].from, alias_l1_to_l2(second_emitter));
+    assert_eq!(decoded[1].to, TxKind::Call(Address::repeat_byte(0x44)));
+    assert_eq!(decoded[1].input.as_ref(), &[0xab, 0xcd]);
+    assert_ne!(decoded[0].source_hash, decoded[1].source_hash);
+
+    let scan = check_facet_deposit_count(
+        &[envelope],
+        &[receipt],
+        &[],
+        &FacetDepositConfig::mainnet(chain_id),
+        deposits.len(),
+    )
+    .expect("count mismatch");
+    assert_eq!(scan.inbox_logs, 2);
+}
+
+#[test]
+fn test_derive_facet_deposits_from_calldata_and_log_in_same_tx() {
+    let chain_id = 16436858;
+    let calldata = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
+    let log_payload = encode_facet_payload(
+        chain_id,
+        Some(Address::repeat_byte(0x44)),
+        U256::ZERO,
+        50_000,
+        &[0xab],
+        &[],
+    );
+
+    // One tx that calls the inbox and also emits an inbox event
+    let legacy = TxLegacy {
+        chain_id: Some(1u64),
+        nonce: 0,
+        gas_price: 1,
+        gas_limit: 100_000,
+        to: TxKind::Call(FACET_INBOX_ADDRESS),
+        value: U256::ZERO,
+        input: calldata,
+    };
+    let envelope = TxEnvelope::Legacy(Signed::new_unchecked(
+        legacy,
+        Signature::test_signature(),
+        Default::default(),
+    ));
+    let emitter = Address::from_slice(&[0x33; 20]);
+    let receipt = Receipt {
+        status: Eip658Value::Eip658(true),
+        logs: vec![Log {
+            address: emitter,
+            data: LogData::new(vec![FACET_LOG_INBOX_EVENT_SIG], log_payload)
+                .expect("valid log data"),
+        }],
+        ..Default::default()
+    };
+
+    let (deposits, _, _) =
+        derive_facet_deposits(&[envelope.clone()], &[receipt.clone()], chain_id, 1, 0u128, 0u128)
+            .expect("derive failed");
+    assert_eq!(deposits.len(), 2);
+
+    // Calldata first, sent by the L1 signer; then the log, sent by the emitter's alias
+    let decoded: Vec<TxDeposit> = deposits
+        .iter()
+        .map(|d| TxDeposit::decode_2718(&mut d.as_ref()).expect("valid deposit"))
+        .collect();
+    assert_eq!(decoded[0].from, envelope.recover_signer().expect("recoverable signer"));
+    assert_eq!(decoded[0].to, TxKind::Call(Address::repeat_byte(0x11)));
+    assert_eq!(decoded[1].from, alias_l1_to_l2(emitter));
+    assert_eq!(decoded[1].to, TxKind::Call(Address::repeat_byte(0x44)));
+    assert_ne!(decoded[0].from, decoded[1].from);
+
+    // Both deposits stem from one tx, but their source hashes are domain separated
+    let tx_hash = *envelope.tx_hash();
+    assert_ne!(decoded[0].source_hash, tx_hash);
+    assert_eq!(decoded[0].source_hash, facet_source_hash(FACET_CALLDATA_SOURCE_DOMAIN, tx_hash, 0));
+    assert_eq!(decoded[1].source_hash, facet_source_hash(FACET_LOG_SOURCE_DOMAIN, tx_hash, 0));
+    assert_ne!(decoded[0].source_hash, decoded[1].source_hash);
+
+    let scan = check_facet_deposit_count(
+        &[envelope],
+        &[receipt],
+        &[],
+        &FacetDepositConfig::mainnet(chain_id),
+        deposits.len(),
+    )
+    .expect("count mismatch");
+    assert_eq!((scan.inbox_calldata_txs, scan.inbox_logs), (1, 1));
+}
+
 #[test]
 fn test_address_aliasing() {
     // Test the specific address aliasing case
-    let emitting_contract = Address::from_slice(&hex::decode("db8dc4ac38c094746529a14be18d99c18ecaedac").expect("valid hex"));
-    let expected_aliased = Address::from_slice(&hex::decode("ec9ec4ac38c094746529a14be18d99c18ecafebd").expect("valid hex"));
-    
+    let emitting_contract = Address::from_slice(
+        &hex::decode("db8dc4ac38c094746529a14be18d99c18ecaedac").expect("valid hex"),
+    );
+    let expected_aliased = Address::from_slice(
+        &hex::decode("ec9ec4ac38c094746529a14be18d99c18ecafebd").expect("valid hex"),
+    );
+
     let aliased_from = alias_l1_to_l2(emitting_contract);
-    
-    assert_eq!(aliased_from, expected_aliased, 
-        "Address aliasing failed: expected 0x{}, got 0x{}", 
-        hex::encode(expected_aliased), 
+
+    assert_eq!(
+        aliased_from,
+        expected_aliased,
+        "Address aliasing failed: expected 0x{}, got 0x{}",
+        hex::encode(expected_aliased),
         hex::encode(aliased_from)
     );
 }
@@ -109,9 +409,7 @@ fn test_address_aliasing() {
 #[test]
 fn test_facet_deposits_different_from_addresses() {
     // Test that calldata and log cases produce different "from" addresses
-    let known_valid_payload = "46e283face7a94111111111111111111111111111111111111111180830f424082123480";
-    let facet_data = hex::decode(known_valid_payload).expect("invalid hex");
-    let input = Bytes::from(facet_data);
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
 
     // Calldata case
     let legacy = TxLegacy {
@@ -127,7 +425,9 @@ fn test_facet_deposits_different_from_addresses() {
     let signed = Signed::new_unchecked(legacy, sig, Default::default());
     let envelope = TxEnvelope::Legacy(signed);
     let receipt = Receipt { status: Eip658Value::Eip658(true), ..Default::default() };
-    let (deposits_calldata, _, _) = derive_facet_deposits(&[envelope], &[receipt], 16436858, 1, 0u128, 0u128).expect("derive failed");
+    let (deposits_calldata, _, _) =
+        derive_facet_deposits(&[envelope], &[receipt], 16436858, 1, 0u128, 0u128)
+            .expect("derive failed");
 
     // Log case
     let dummy_contract = Address::from_slice(&[0x22; 20]);
@@ -144,36 +444,34 @@ fn test_facet_deposits_different_from_addresses() {
     let signed_log = Signed::new_unchecked(legacy_log, sig_log, Default::default());
     let envelope_log = TxEnvelope::Legacy(signed_log);
 
-    let emitting_contract = Address::from_slice(&hex::decode("db8dc4ac38c094746529a14be18d99c18ecaedac").expect("valid hex"));
+    let emitting_contract = Address::from_slice(
+        &hex::decode("db8dc4ac38c094746529a14be18d99c18ecaedac").expect("valid hex"),
+    );
     let log = Log {
         address: emitting_contract,
-        data: LogData::new(
-            vec![FACET_LOG_INBOX_EVENT_SIG],
-            input.clone(),
-        ).expect("valid log data"),
+        data: LogData::new(vec![FACET_LOG_INBOX_EVENT_SIG], input.clone()).expect("valid log data"),
     };
-    let receipt_log = Receipt {
-        status: Eip658Value::Eip658(true),
-        logs: vec![log],
-        ..Default::default()
-    };
-    let (deposits_log, _, _) = derive_facet_deposits(&[envelope_log], &[receipt_log], 16436858, 1, 0u128, 0u128).expect("derive failed");
+    let receipt_log =
+        Receipt { status: Eip658Value::Eip658(true), logs: vec![log], ..Default::default() };
+    let (deposits_log, _, _) =
+        derive_facet_deposits(&[envelope_log], &[receipt_log], 16436858, 1, 0u128, 0u128)
+            .expect("derive failed");
 
     // Both should produce deposits
     assert_eq!(deposits_calldata.len(), 1);
     assert_eq!(deposits_log.len(), 1);
-    
+
     // But they should be different (different "from" addresses)
-    assert_ne!(deposits_calldata[0], deposits_log[0], 
-        "Calldata and log deposits should be different due to different 'from' addresses");
+    assert_ne!(
+        deposits_calldata[0], deposits_log[0],
+        "Calldata and log deposits should be different due to different 'from' addresses"
+    );
 }
 
 #[test]
 fn test_failed_transaction_no_deposits() {
     // Test that failed transactions don't produce deposits
-    let known_valid_payload = "46e283face7a94111111111111111111111111111111111111111180830f424082123480";
-    let facet_data = hex::decode(known_valid_payload).expect("invalid hex");
-    let input = Bytes::from(facet_data);
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
 
     let legacy = TxLegacy {
         chain_id: Some(1u64),
@@ -189,12 +487,14 @@ fn test_failed_transaction_no_deposits() {
     let envelope = TxEnvelope::Legacy(signed);
 
     // Build receipt with FAILED status
-    let receipt = Receipt { 
+    let receipt = Receipt {
         status: Eip658Value::Eip658(false), // Failed transaction
-        ..Default::default() 
+        ..Default::default()
     };
 
-    let (deposits, _, _) = derive_facet_deposits(&[envelope], &[receipt], 16436858, 1, 0u128, 0u128).expect("derive failed");
+    let (deposits, _, _) =
+        derive_facet_deposits(&[envelope], &[receipt], 16436858, 1, 0u128, 0u128)
+            .expect("derive failed");
 
     // Should produce no deposits for failed transactions
     assert_eq!(deposits.len(), 0);
@@ -204,34 +504,32 @@ fn test_failed_transaction_no_deposits() {
 fn test_facet_payload_values() {
     // Test that the known valid payload decodes to the expected values
     use kona_protocol::decode_facet_payload;
-    
-    let known_valid_payload = "46e283face7a94111111111111111111111111111111111111111180830f424082123480";
-    let facet_data = hex::decode(known_valid_payload).expect("invalid hex");
 
-    let payload = decode_facet_payload(&facet_data, 16436858, false).expect("decode failed");
-    
+    let payload =
+        decode_facet_payload(&KNOWN_VALID_PAYLOAD, 16436858, false).expect("decode failed");
+
     // Check the expected values
-    assert_eq!(payload.data, hex::decode("1234").expect("valid hex"), 
-        "Data should be 0x1234");
-    
-    assert_eq!(payload.to, Some(Address::from_slice(&hex::decode("1111111111111111111111111111111111111111").expect("valid hex"))), 
-        "To should be 0x1111111111111111111111111111111111111111");
-    
-    assert_eq!(payload.gas_limit, 1_000_000, 
-        "Gas limit should be 1,000,000");
-    
-    assert_eq!(payload.value, U256::ZERO, 
-        "Value should be 0");
+    assert_eq!(payload.data, hex::decode("1234").expect("valid hex"), "Data should be 0x1234");
+
+    assert_eq!(
+        payload.to,
+        Some(Address::from_slice(
+            &hex::decode("1111111111111111111111111111111111111111").expect("valid hex")
+        )),
+        "To should be 0x1111111111111111111111111111111111111111"
+    );
+
+    assert_eq!(payload.gas_limit, 1_000_000, "Gas limit should be 1,000,000");
+
+    assert_eq!(payload.value, U256::ZERO, "Value should be 0");
 }
 
 #[test]
 fn test_facet_mint_calculation() {
     use kona_protocol::FctMintCalculator;
-    
+
     // Use the known valid payload
-    let known_valid_payload = "46e283face7a94111111111111111111111111111111111111111180830f424082123480";
-    let facet_data = hex::decode(known_valid_payload).expect("invalid hex");
-    let input = Bytes::from(facet_data);
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
 
     // Build a dummy legacy tx to FACET_INBOX_ADDRESS
     let legacy = TxLegacy {
@@ -254,32 +552,290 @@ fn test_facet_mint_calculation() {
     let mint_rate = FctMintCalculator::INITIAL_RATE; // 800_000_000_000_000
     let cumulative_data_gas = 0u128;
 
-    let (deposits, _, _) = derive_facet_deposits(&[envelope], &[receipt], 16436858, 1, mint_rate, cumulative_data_gas).expect("derive failed");
+    let (deposits, _, _) =
+        derive_facet_deposits(&[envelope], &[receipt], 16436858, 1, mint_rate, cumulative_data_gas)
+            .expect("derive failed");
 
     // Verify we got exactly one deposit
     assert_eq!(deposits.len(), 1);
-    
+
     // Verify it's a deposit transaction (type 0x7e)
     assert!(!deposits[0].is_empty());
     assert_eq!(deposits[0][0], 0x7e);
-    
+
     // Decode the deposit transaction to extract the mint amount
-    use alloy_eips::eip2718::Decodable2718;
-    use op_alloy_consensus::TxDeposit;
-    
     let deposit_data = &deposits[0][1..]; // Skip the 0x7e prefix
-    let deposit_tx = TxDeposit::decode_2718(&mut &deposit_data[..]).expect("failed to decode deposit tx");
-    
+    let deposit_tx =
+        TxDeposit::decode_2718(&mut &deposit_data[..]).expect("failed to decode deposit tx");
+
     // Verify the mint amount matches the expected value
     let expected_mint = 460800000000000000u128;
-    assert_eq!(deposit_tx.mint, Some(expected_mint), 
-        "Expected mint amount {} but got {:?}", expected_mint, deposit_tx.mint);
-    
+    assert_eq!(
+        deposit_tx.mint,
+        Some(expected_mint),
+        "Expected mint amount {} but got {:?}",
+        expected_mint,
+        deposit_tx.mint
+    );
+
     // Verify the calculation: data_gas_used * mint_rate = expected_mint
-    let facet_data_for_verification = hex::decode(known_valid_payload).expect("invalid hex");
-    let data_gas_used = FctMintCalculator::calculate_data_gas_used(&facet_data_for_verification, false);
+    let data_gas_used = FctMintCalculator::calculate_data_gas_used(&KNOWN_VALID_PAYLOAD, false);
     let calculated_mint = FctMintCalculator::calculate_mint_amount(data_gas_used, mint_rate);
-    assert_eq!(calculated_mint, expected_mint, 
-        "Mint calculation verification failed: {} * {} = {} (expected {})", 
-        data_gas_used, mint_rate, calculated_mint, expected_mint);
-}
\ No newline at end of file
+    assert_eq!(
+        calculated_mint, expected_mint,
+        "Mint calculation verification failed: {} * {} = {} (expected {})",
+        data_gas_used, mint_rate, calculated_mint, expected_mint
+    );
+}
+
+#[test]
+fn test_independent_inbox_scan_matches_derivation() {
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
+    let legacy_tx = |to: Address, input: Bytes| {
+        let legacy = TxLegacy {
+            chain_id: Some(1u64),
+            nonce: 0,
+            gas_price: 1,
+            gas_limit: 21000,
+            to: TxKind::Call(to),
+            value: U256::ZERO,
+            input,
+        };
+        TxEnvelope::Legacy(Signed::new_unchecked(
+            legacy,
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    };
+    let emitting_contract = Address::from_slice(&[0x33; 20]);
+    let unrelated_log = Log {
+        address: emitting_contract,
+        data: LogData::new(
+            vec![b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")],
+            Bytes::from_static(&[0u8; 32]),
+        )
+        .expect("valid log data"),
+    };
+    let inbox_log = Log {
+        address: emitting_contract,
+        data: LogData::new(vec![FACET_LOG_INBOX_EVENT_SIG], input.clone()).expect("valid log data"),
+    };
+    let success =
+        |logs: Vec<Log>| Receipt { status: Eip658Value::Eip658(true), logs, ..Default::default() };
+
+    let txs = vec![
+        // Inbox calldata, with an unrelated log alongside
+        legacy_tx(FACET_INBOX_ADDRESS, input.clone()),
+        // Unrelated log placed before the inbox log
+        legacy_tx(Address::from_slice(&[0x22; 20]), Bytes::new()),
+        // Unrelated log only
+        legacy_tx(Address::from_slice(&[0x22; 20]), Bytes::new()),
+        // Failed inbox tx
+        legacy_tx(FACET_INBOX_ADDRESS, input.clone()),
+    ];
+    let receipts = vec![
+        success(vec![unrelated_log.clone()]),
+        success(vec![unrelated_log.clone(), inbox_log]),
+        success(vec![unrelated_log]),
+        Receipt { status: Eip658Value::Eip658(false), ..Default::default() },
+    ];
+
+    let (deposits, _, _) =
+        derive_facet_deposits(&txs, &receipts, 16436858, 1, 0u128, 0u128).expect("derive failed");
+
+    let cfg = FacetDepositConfig::mainnet(16436858);
+    let scan = scan_facet_inbox(&txs, &receipts, &[], &cfg);
+    assert_eq!(scan.inbox_calldata_txs, 1);
+    assert_eq!(scan.inbox_logs, 1);
+    assert_eq!(scan.failed_txs, 1);
+    assert_eq!(deposits.len(), scan.expected_deposits());
+
+    assert!(check_facet_deposit_count(&txs, &receipts, &[], &cfg, deposits.len()).is_ok());
+    assert!(check_facet_deposit_count(&txs, &receipts, &[], &cfg, deposits.len() + 1).is_err());
+}
+
+#[test]
+fn test_derive_facet_deposits_detailed_reports_skipped_payloads() {
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
+    let legacy_tx = |input: Bytes| {
+        let legacy = TxLegacy {
+            chain_id: Some(1u64),
+            gas_limit: 21000,
+            to: TxKind::Call(FACET_INBOX_ADDRESS),
+            input,
+            ..Default::default()
+        };
+        TxEnvelope::Legacy(Signed::new_unchecked(
+            legacy,
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    };
+    // A gzipped blob sent to the inbox, and a payload for another chain emitted as a log
+    let gzipped_tx = legacy_tx(Bytes::from_static(&[0x1f, 0x8b, 0x08, 0x00]));
+    let wrong_chain_log = Log {
+        address: Address::repeat_byte(0x22),
+        data: LogData::new(
+            vec![FACET_LOG_INBOX_EVENT_SIG],
+            encode_facet_payload(1, None, U256::ZERO, 50_000, &[0x60], &[]),
+        )
+        .expect("valid log data"),
+    };
+    let txs = vec![legacy_tx(input), gzipped_tx];
+    let receipts = vec![
+        Receipt { status: Eip658Value::Eip658(true), ..Default::default() },
+        Receipt {
+            status: Eip658Value::Eip658(true),
+            logs: vec![wrong_chain_log],
+            ..Default::default()
+        },
+    ];
+
+    let outcome = derive_facet_deposits_detailed(&txs, &receipts, 16436858, 1, 0u128, 0u128)
+        .expect("derive failed");
+    assert_eq!(outcome.deposits.len(), 1);
+    assert_eq!(
+        outcome.skipped,
+        vec![
+            SkippedPayload {
+                tx_hash: *txs[1].tx_hash(),
+                reason: FacetDecodeError::WrongPrefix(0x1f)
+            },
+            SkippedPayload {
+                tx_hash: *txs[1].tx_hash(),
+                reason: FacetDecodeError::BadChainId(1, 16436858)
+            },
+        ]
+    );
+
+    // The tuple-returning wrapper derives the same deposits
+    let (deposits, rate, gas) =
+        derive_facet_deposits(&txs, &receipts, 16436858, 1, 0u128, 0u128).expect("derive failed");
+    assert_eq!(deposits, outcome.deposits);
+    assert_eq!((rate, gas), (outcome.new_mint_rate, outcome.new_cumulative_l1_data_gas));
+}
+
+#[test]
+fn test_derive_facet_deposits_counts_rejections_by_reason() {
+    // The known payload with a 19 byte `to` field
+    let short_to_payload = "46e183face7a931111111111111111111111111111111111111180830f424082123480";
+    let legacy_tx = |input: Vec<u8>| {
+        let legacy = TxLegacy {
+            chain_id: Some(1u64),
+            gas_limit: 21000,
+            to: TxKind::Call(FACET_INBOX_ADDRESS),
+            input: input.into(),
+            ..Default::default()
+        };
+        TxEnvelope::Legacy(Signed::new_unchecked(
+            legacy,
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    };
+    let txs = vec![
+        legacy_tx(KNOWN_VALID_PAYLOAD.to_vec()),
+        legacy_tx(vec![0x1f, 0x8b, 0x08, 0x00]),
+        legacy_tx(hex::decode(short_to_payload).expect("invalid hex")),
+    ];
+    let receipts = vec![Receipt { status: Eip658Value::Eip658(true), ..Default::default() }; 3];
+
+    let outcome = derive_facet_deposits_detailed(&txs, &receipts, 16436858, 1, 0u128, 0u128)
+        .expect("derive failed");
+    assert_eq!(outcome.deposits.len(), 1);
+    assert_eq!(outcome.skipped[1].reason, FacetDecodeError::InvalidToLength(19));
+    assert_eq!(
+        outcome.rejections,
+        FacetRejectionCounts { wrong_prefix: 1, invalid_to_length: 1, ..Default::default() }
+    );
+    assert_eq!(outcome.rejections.total(), 2);
+    let by_reason = outcome.rejections.by_reason();
+    assert!(by_reason.contains(&("wrong_prefix", 1)));
+    assert!(by_reason.contains(&(FacetDecodeError::InvalidToLength(19).reason(), 1)));
+    assert!(by_reason.contains(&(FacetDecodeError::BadChainId(1, 2).reason(), 0)));
+}
+
+#[test]
+fn test_derive_facet_deposits_trace_for_mixed_block() {
+    let input = Bytes::from_static(&KNOWN_VALID_PAYLOAD);
+    let legacy_tx = |to: Address, input: Bytes| {
+        let legacy = TxLegacy {
+            chain_id: Some(1u64),
+            gas_limit: 21000,
+            to: TxKind::Call(to),
+            input,
+            ..Default::default()
+        };
+        TxEnvelope::Legacy(Signed::new_unchecked(
+            legacy,
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    };
+    let emitter = Address::repeat_byte(0x22);
+    let unrelated = Address::repeat_byte(0x33);
+    let unrelated_log = Log {
+        address: emitter,
+        data: LogData::new(
+            vec![b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")],
+            Bytes::new(),
+        )
+        .expect("valid log data"),
+    };
+    let inbox_log = Log {
+        address: emitter,
+        data: LogData::new(vec![FACET_LOG_INBOX_EVENT_SIG], input.clone()).expect("valid log data"),
+    };
+    let success =
+        |logs: Vec<Log>| Receipt { status: Eip658Value::Eip658(true), logs, ..Default::default() };
+
+    let txs = vec![
+        // Unrelated transfer
+        legacy_tx(unrelated, Bytes::new()),
+        // Valid inbox calldata
+        legacy_tx(FACET_INBOX_ADDRESS, input.clone()),
+        // Reverted inbox tx
+        legacy_tx(FACET_INBOX_ADDRESS, input.clone()),
+        // Contract call emitting an inbox log after an unrelated log
+        legacy_tx(emitter, Bytes::new()),
+        // Garbage sent to the inbox
+        legacy_tx(FACET_INBOX_ADDRESS, Bytes::from_static(&[0x00, 0x01])),
+    ];
+    let receipts = vec![
+        success(vec![]),
+        success(vec![]),
+        Receipt { status: Eip658Value::Eip658(false), ..Default::default() },
+        success(vec![unrelated_log, inbox_log]),
+        success(vec![]),
+    ];
+    let hashes: Vec<_> = txs.iter().map(|tx| *tx.tx_hash()).collect();
+    let signer = txs[1].recover_signer().expect("recoverable signer");
+
+    let mut trace = Vec::new();
+    let outcome =
+        derive_facet_deposits_traced(&txs, &receipts, 16436858, 1, 0u128, 0u128, &mut trace)
+            .expect("derive failed");
+    assert_eq!(outcome.deposits.len(), 2);
+    assert_eq!(
+        trace,
+        vec![
+            DeriveTraceEvent::ConsideredTx { tx_hash: hashes[0], to: Some(unrelated) },
+            DeriveTraceEvent::ConsideredTx { tx_hash: hashes[1], to: Some(FACET_INBOX_ADDRESS) },
+            DeriveTraceEvent::MatchedInboxCalldata { tx_hash: hashes[1] },
+            DeriveTraceEvent::DecodedPayload { tx_hash: hashes[1], from: signer },
+            DeriveTraceEvent::ConsideredTx { tx_hash: hashes[2], to: Some(FACET_INBOX_ADDRESS) },
+            DeriveTraceEvent::SkippedFailedTx { tx_hash: hashes[2] },
+            DeriveTraceEvent::ConsideredTx { tx_hash: hashes[3], to: Some(emitter) },
+            DeriveTraceEvent::MatchedInboxLog { tx_hash: hashes[3], log_index: 1 },
+            DeriveTraceEvent::DecodedPayload { tx_hash: hashes[3], from: alias_l1_to_l2(emitter) },
+            DeriveTraceEvent::ConsideredTx { tx_hash: hashes[4], to: Some(FACET_INBOX_ADDRESS) },
+            DeriveTraceEvent::MatchedInboxCalldata { tx_hash: hashes[4] },
+            DeriveTraceEvent::SkippedPayload {
+                tx_hash: hashes[4],
+                reason: FacetDecodeError::WrongPrefix(0x00)
+            },
+            DeriveTraceEvent::ProducedDeposit { tx_hash: hashes[1], index: 0 },
+            DeriveTraceEvent::ProducedDeposit { tx_hash: hashes[3], index: 1 },
+        ]
+    );
+}
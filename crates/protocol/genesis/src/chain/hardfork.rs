@@ -69,6 +69,29 @@ pub struct HardForkConfig {
     pub interop_time: Option<u64>,
 }
 
+impl HardForkConfig {
+    /// Returns the canonical Facet hardfork activation schedule.
+    ///
+    /// Facet launched with every upgrade up to and including Granite active from genesis.
+    /// Holocene and Isthmus are not scheduled: Facet keeps its own L1 block info transaction
+    /// format (see `L1BlockInfoFacet`) which Isthmus would replace, and Facet blocks do not carry
+    /// the Holocene EIP-1559 parameters in the header extra data. Interop is not scheduled.
+    pub const fn facet_all_active_from_genesis() -> Self {
+        Self {
+            regolith_time: Some(0),
+            canyon_time: Some(0),
+            delta_time: Some(0),
+            ecotone_time: Some(0),
+            fjord_time: Some(0),
+            granite_time: Some(0),
+            holocene_time: None,
+            pectra_blob_schedule_time: None,
+            isthmus_time: None,
+            interop_time: None,
+        }
+    }
+}
+
 impl Display for HardForkConfig {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         #[inline(always)]
@@ -188,4 +211,44 @@ mod tests {
         "#;
         toml::from_str::<HardForkConfig>(raw).unwrap_err();
     }
+
+    #[test]
+    fn test_facet_schedule() {
+        let forks = HardForkConfig::facet_all_active_from_genesis();
+        assert_eq!(forks.regolith_time, Some(0));
+        assert_eq!(forks.canyon_time, Some(0));
+        assert_eq!(forks.delta_time, Some(0));
+        assert_eq!(forks.ecotone_time, Some(0));
+        assert_eq!(forks.fjord_time, Some(0));
+        assert_eq!(forks.granite_time, Some(0));
+        assert_eq!(forks.holocene_time, None);
+        assert_eq!(forks.pectra_blob_schedule_time, None);
+        assert_eq!(forks.isthmus_time, None);
+        assert_eq!(forks.interop_time, None);
+    }
+
+    #[test]
+    fn test_facet_schedule_monotonic() {
+        let forks = HardForkConfig::facet_all_active_from_genesis();
+        // Fork order, excluding the optional Sepolia-only pectra blob schedule.
+        let ordered = [
+            forks.regolith_time,
+            forks.canyon_time,
+            forks.delta_time,
+            forks.ecotone_time,
+            forks.fjord_time,
+            forks.granite_time,
+            forks.holocene_time,
+            forks.isthmus_time,
+            forks.interop_time,
+        ];
+
+        // Once a fork is unscheduled, no later fork may be scheduled.
+        let scheduled = ordered.iter().take_while(|t| t.is_some()).count();
+        assert!(ordered[scheduled..].iter().all(Option::is_none));
+
+        // Scheduled forks activate in non-decreasing order.
+        let times = ordered.iter().flatten().collect::<alloc::vec::Vec<_>>();
+        assert!(times.windows(2).all(|w| w[0] <= w[1]));
+    }
 }
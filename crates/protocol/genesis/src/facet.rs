@@ -0,0 +1,172 @@
+//! A validating builder for the Facet [RollupConfig].
+
+use crate::{ChainGenesis, HardForkConfig, RollupConfig};
+use alloy_primitives::Address;
+
+/// An invalid Facet rollup configuration, returned by [FacetRollupConfigBuilder::build].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum FacetConfigError {
+    /// The block time is zero.
+    #[error("Block time must be greater than zero")]
+    ZeroBlockTime,
+    /// The batch inbox and the deposit contract share an address.
+    #[error("Batch inbox address and deposit contract address are both {0}")]
+    InboxIsDepositContract(Address),
+    /// A hardfork is scheduled before, or without, the hardfork preceding it.
+    #[error("{fork} is scheduled at {time} but {previous} is scheduled at {previous_time:?}")]
+    ForkOutOfOrder {
+        /// The out of order hardfork.
+        fork: &'static str,
+        /// The activation time of the out of order hardfork.
+        time: u64,
+        /// The preceding hardfork.
+        previous: &'static str,
+        /// The activation time of the preceding hardfork, if scheduled.
+        previous_time: Option<u64>,
+    },
+}
+
+/// Builds a Facet [RollupConfig] from a [ChainGenesis] and a [HardForkConfig].
+///
+/// Every parameter not set on the builder is taken from [RollupConfig::facet]. The invariants
+/// checked by [Self::build] are:
+/// - the block time is non-zero,
+/// - the batch inbox address differs from the deposit contract address,
+/// - every scheduled hardfork activates no earlier than the hardfork preceding it, which must
+///   also be scheduled. The optional Pectra blob schedule fork is not part of the ordering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetRollupConfigBuilder {
+    config: RollupConfig,
+}
+
+impl FacetRollupConfigBuilder {
+    /// Creates a new builder from the chain genesis and hardfork schedule.
+    pub fn new(genesis: ChainGenesis, hardforks: HardForkConfig) -> Self {
+        Self { config: RollupConfig { genesis, hardforks, ..RollupConfig::facet() } }
+    }
+
+    /// Sets the L1 chain ID.
+    pub const fn l1_chain_id(mut self, l1_chain_id: u64) -> Self {
+        self.config.l1_chain_id = l1_chain_id;
+        self
+    }
+
+    /// Sets the L2 chain ID.
+    pub const fn l2_chain_id(mut self, l2_chain_id: u64) -> Self {
+        self.config.l2_chain_id = l2_chain_id;
+        self
+    }
+
+    /// Sets the L2 block time, in seconds.
+    pub const fn block_time(mut self, block_time: u64) -> Self {
+        self.config.block_time = block_time;
+        self
+    }
+
+    /// Sets the Facet inbox address.
+    pub const fn batch_inbox_address(mut self, address: Address) -> Self {
+        self.config.batch_inbox_address = address;
+        self
+    }
+
+    /// Sets the address that Facet deposits are sent from.
+    pub const fn deposit_contract_address(mut self, address: Address) -> Self {
+        self.config.deposit_contract_address = address;
+        self
+    }
+
+    /// Validates the configuration and returns the [RollupConfig].
+    pub fn build(self) -> Result<RollupConfig, FacetConfigError> {
+        let config = self.config;
+        if config.block_time == 0 {
+            return Err(FacetConfigError::ZeroBlockTime);
+        }
+        if config.batch_inbox_address == config.deposit_contract_address {
+            return Err(FacetConfigError::InboxIsDepositContract(config.batch_inbox_address));
+        }
+        check_fork_order(&config.hardforks)?;
+        Ok(config)
+    }
+}
+
+/// Checks that the scheduled hardforks activate in order.
+fn check_fork_order(forks: &HardForkConfig) -> Result<(), FacetConfigError> {
+    let schedule = [
+        ("Regolith", forks.regolith_time),
+        ("Canyon", forks.canyon_time),
+        ("Delta", forks.delta_time),
+        ("Ecotone", forks.ecotone_time),
+        ("Fjord", forks.fjord_time),
+        ("Granite", forks.granite_time),
+        ("Holocene", forks.holocene_time),
+        ("Isthmus", forks.isthmus_time),
+        ("Interop", forks.interop_time),
+    ];
+    for window in schedule.windows(2) {
+        let [(previous, previous_time), (fork, Some(time))] = *window else { continue };
+        if previous_time.is_none_or(|previous_time| previous_time > time) {
+            return Err(FacetConfigError::ForkOutOfOrder { fork, time, previous, previous_time });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder() -> FacetRollupConfigBuilder {
+        let facet = RollupConfig::facet();
+        FacetRollupConfigBuilder::new(facet.genesis, facet.hardforks)
+    }
+
+    #[test]
+    fn test_builds_canonical_facet_config() {
+        assert_eq!(builder().build(), Ok(RollupConfig::facet()));
+        assert_eq!(builder().l2_chain_id(0xface7).build().unwrap().l2_chain_id, 0xface7);
+    }
+
+    #[test]
+    fn test_rejects_zero_block_time() {
+        assert_eq!(builder().block_time(0).build(), Err(FacetConfigError::ZeroBlockTime));
+    }
+
+    #[test]
+    fn test_rejects_inbox_as_deposit_contract() {
+        let inbox = RollupConfig::facet().batch_inbox_address;
+        assert_eq!(
+            builder().deposit_contract_address(inbox).build(),
+            Err(FacetConfigError::InboxIsDepositContract(inbox))
+        );
+    }
+
+    #[test]
+    fn test_rejects_out_of_order_forks() {
+        let mut forks = HardForkConfig::facet_all_active_from_genesis();
+        forks.fjord_time = Some(10);
+        forks.granite_time = Some(5);
+        let genesis = RollupConfig::facet().genesis;
+        assert_eq!(
+            FacetRollupConfigBuilder::new(genesis, forks).build(),
+            Err(FacetConfigError::ForkOutOfOrder {
+                fork: "Granite",
+                time: 5,
+                previous: "Fjord",
+                previous_time: Some(10),
+            })
+        );
+
+        // Scheduling a hardfork without its predecessor is also out of order.
+        let mut forks = HardForkConfig::facet_all_active_from_genesis();
+        forks.isthmus_time = Some(20);
+        assert_eq!(
+            FacetRollupConfigBuilder::new(genesis, forks).build(),
+            Err(FacetConfigError::ForkOutOfOrder {
+                fork: "Isthmus",
+                time: 20,
+                previous: "Holocene",
+                previous_time: None,
+            })
+        );
+    }
+}
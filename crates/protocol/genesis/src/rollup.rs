@@ -1,9 +1,13 @@
 //! Rollup Config Types
 
-use crate::{AltDAConfig, BaseFeeConfig, ChainGenesis, HardForkConfig, OP_MAINNET_BASE_FEE_CONFIG};
+use crate::{
+    AltDAConfig, BaseFeeConfig, ChainGenesis, HardForkConfig, OP_MAINNET_BASE_FEE_CONFIG,
+    SystemConfig,
+};
+use alloy_eips::BlockNumHash;
 use alloy_hardforks::{EthereumHardfork, EthereumHardforks, ForkCondition};
 use alloy_op_hardforks::{OpHardfork, OpHardforks};
-use alloy_primitives::Address;
+use alloy_primitives::{Address, U256, address, b256};
 
 /// The max rlp bytes per channel for the Bedrock hardfork.
 pub const MAX_RLP_BYTES_PER_CHANNEL_BEDROCK: u64 = 10_000_000;
@@ -303,6 +307,66 @@ impl RollupConfig {
         self.l2_chain_id == 1027303 || self.l2_chain_id == 16436858
     }
 
+    /// Returns the Facet mainnet rollup config.
+    ///
+    /// This is the single source of the Facet config for the binaries and examples in this
+    /// repository. Facet derives its L2 from the facet inbox rather than from batches, so the batch
+    /// inbox and deposit contract addresses are only carried for compatibility with the OP Stack
+    /// rollup config format. There is no L1 system config contract, so the system config address
+    /// is zero and no system config updates are derived from L1 logs. All hardforks up to Granite
+    /// are active from genesis, see [`HardForkConfig::facet_all_active_from_genesis`].
+    ///
+    /// Unlike a config built on top of [`RollupConfig::default`], this carries the mainnet genesis
+    /// block references, the genesis [`SystemConfig`] (batcher, fee scalars and gas limit), the L1
+    /// chain id and Facet's EIP-1559 parameters, so the L2 genesis block is recognised and its
+    /// system config is served from the genesis rather than read from an empty default.
+    pub fn facet() -> Self {
+        Self {
+            genesis: ChainGenesis {
+                l1: BlockNumHash {
+                    hash: b256!("481724ee99b1f4cb71d826e2ec5a37265f460e9b112315665c977f4050b0af54"),
+                    number: 10,
+                },
+                l2: BlockNumHash {
+                    hash: b256!("88aedfbf7dea6bfa2c4ff315784ad1a7f145d8f650969359c003bbed68c87631"),
+                    number: 0,
+                },
+                l2_time: 1725557164,
+                system_config: Some(SystemConfig {
+                    batcher_address: address!("c81f87a644b41e49b3221f41251f15c6cb00ce03"),
+                    overhead: U256::ZERO,
+                    scalar: U256::from(1_000_000u64),
+                    gas_limit: 30_000_000,
+                    base_fee_scalar: Some(1368),
+                    blob_base_fee_scalar: Some(810949),
+                    ..Default::default()
+                }),
+            },
+            block_time: 12,
+            max_sequencer_drift: 600,
+            seq_window_size: 3600,
+            channel_timeout: 300,
+            granite_channel_timeout: GRANITE_CHANNEL_TIMEOUT,
+            l1_chain_id: 1,
+            l2_chain_id: 1027303,
+            hardforks: HardForkConfig::facet_all_active_from_genesis(),
+            batch_inbox_address: address!("facec003e8e0cf7152467c26d37634925a9ce65b"),
+            deposit_contract_address: address!("00000000000000000000000000000000000face7"),
+            l1_system_config_address: Address::ZERO,
+            protocol_versions_address: Address::ZERO,
+            superchain_config_address: Some(Address::ZERO),
+            blobs_enabled_l1_timestamp: None,
+            da_challenge_address: Some(Address::ZERO),
+            interop_message_expiry_window: 3600,
+            alt_da_config: None,
+            chain_op_config: BaseFeeConfig {
+                eip1559_elasticity: 2,
+                eip1559_denominator: 8,
+                eip1559_denominator_canyon: 8,
+            },
+        }
+    }
+
     /// Returns true if Interop is active at the given timestamp.
     pub fn is_interop_active(&self, timestamp: u64) -> bool {
         self.hardforks.interop_time.is_some_and(|t| timestamp >= t)
@@ -460,11 +524,6 @@ impl OpHardforks for RollupConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    #[cfg(feature = "serde")]
-    use alloy_eips::BlockNumHash;
-    use alloy_primitives::address;
-    #[cfg(feature = "serde")]
-    use alloy_primitives::{U256, b256};
 
     #[test]
     #[cfg(feature = "arbitrary")]
@@ -834,6 +893,85 @@ mod tests {
         assert_eq!(deserialized, expected);
     }
 
+    #[test]
+    fn test_facet_preset() {
+        let config = RollupConfig::facet();
+        assert!(config.is_facet());
+        assert_eq!(config.l2_chain_id, 0xface7);
+        assert_eq!(config.block_time, 12);
+        assert_eq!(
+            config.batch_inbox_address,
+            address!("facec003e8e0cf7152467c26d37634925a9ce65b")
+        );
+        assert_eq!(config.l1_system_config_address, Address::ZERO);
+        assert!(config.is_granite_active(config.genesis.l2_time));
+        assert!(!config.is_holocene_active(u64::MAX));
+        assert!(!config.is_alt_da_enabled());
+    }
+
+    #[test]
+    fn test_facet_preset_genesis_and_fee_config() {
+        let config = RollupConfig::facet();
+        assert_eq!(config.l1_chain_id, 1);
+        assert_eq!(
+            config.genesis.l1,
+            BlockNumHash {
+                hash: b256!("481724ee99b1f4cb71d826e2ec5a37265f460e9b112315665c977f4050b0af54"),
+                number: 10,
+            }
+        );
+        assert_eq!(
+            config.genesis.l2,
+            BlockNumHash {
+                hash: b256!("88aedfbf7dea6bfa2c4ff315784ad1a7f145d8f650969359c003bbed68c87631"),
+                number: 0,
+            }
+        );
+        assert_eq!(config.genesis.l2_time, 1725557164);
+
+        let system_config = config.genesis.system_config.unwrap();
+        assert_eq!(
+            system_config.batcher_address,
+            address!("c81f87a644b41e49b3221f41251f15c6cb00ce03")
+        );
+        assert_eq!(system_config.gas_limit, 30_000_000);
+        assert_eq!(system_config.scalar, U256::from(1_000_000u64));
+        assert_eq!(system_config.base_fee_scalar, Some(1368));
+        assert_eq!(system_config.blob_base_fee_scalar, Some(810949));
+
+        assert_eq!(
+            config.chain_op_config,
+            BaseFeeConfig {
+                eip1559_elasticity: 2,
+                eip1559_denominator: 8,
+                eip1559_denominator_canyon: 8,
+            }
+        );
+        assert_ne!(config.chain_op_config, RollupConfig::default().chain_op_config);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_facet_preset_serde_roundtrip() {
+        let config = RollupConfig::facet();
+        let json = serde_json::to_string(&config).unwrap();
+
+        // Fields the host relies on when serving the rollup config to the client
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["alt_da"].is_null());
+        assert!(value["chain_op_config"].is_object());
+        assert!(value.get("superchain_config_address").is_some());
+        assert!(value.get("da_challenge_address").is_some());
+        assert!(value.get("blobs_data").is_none());
+
+        let decoded: RollupConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, config);
+        assert_eq!(decoded.chain_op_config, config.chain_op_config);
+        assert_eq!(decoded.alt_da_config, None);
+        assert_eq!(decoded.superchain_config_address, Some(Address::ZERO));
+        assert_eq!(decoded.da_challenge_address, Some(Address::ZERO));
+    }
+
     #[test]
     fn test_rollup_config_unknown_field() {
         let raw: &str = r#"
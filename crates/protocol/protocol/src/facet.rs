@@ -1,15 +1,52 @@
-use alloy_primitives::{Address, Bytes, TxKind, B256, U256};
-use alloy_rlp::{RlpDecodable, RlpEncodable, Decodable};
+use alloy_primitives::{keccak256, Address, Bytes, TxKind, B256, U256};
+use alloy_rlp::{RlpDecodable, RlpEncodable, Decodable, Encodable};
 use op_alloy_consensus::TxDeposit;
 use crate::FctMintCalculator;
 use alloc::string::{String, ToString};
-use alloc::format;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+use lru::LruCache;
 
 /// Prefix byte identifying a Facet payload.
 pub const FACET_TX_TYPE: u8 = 0x46;
 /// Prefix byte for an Optimism deposit.
 pub const DEPOSIT_TX_TYPE: u8 = 0x7e;
 
+/// Whether the leading [`FACET_TX_TYPE`] byte counts toward a payload's L1 data gas.
+///
+/// The canonical Facet node charges FCT data gas over the full inbox calldata (or log data), i.e.
+/// exactly the bytes L1 itself charges calldata gas for, which includes the `0x46` prefix. The
+/// prefix contributes 16 gas (8 when contract-initiated) to every payload, which is material for
+/// the mint of small payloads.
+///
+/// On chain, an inbox transaction to [`FACET_INBOX_ADDRESS`](crate::FACET_INBOX_ADDRESS) pays
+/// EIP-2028 calldata gas for its whole input, prefix included. The data gas the canonical node
+/// charged is recorded in every L2 block, as the `fct_mint_period_l1_data_gas` running total of the
+/// block's L1 info deposit (see [`L1BlockInfoFacet`](crate::L1BlockInfoFacet)), which
+/// `validate-facet` checks derivation against.
+pub const FACET_PREFIX_COUNTS_TOWARD_DATA_GAS: bool = true;
+
+/// L1 timestamp from which payload data gas includes the EIP-7623 calldata floor, per
+/// [`FctMintCalculator::calculate_data_gas_used_eip7623`].
+///
+/// `None`: the canonical Facet node keeps charging the standard calldata schedule after Prague.
+pub const FACET_EIP7623_L1_TIME: Option<u64> = None;
+
+/// Returns `true` if payloads of an L1 block with the given timestamp are charged the EIP-7623
+/// calldata floor.
+pub const fn is_facet_eip7623_active(l1_timestamp: u64) -> bool {
+    matches!(FACET_EIP7623_L1_TIME, Some(time) if l1_timestamp >= time)
+}
+
+/// Maximum length of a Facet payload, including the [`FACET_TX_TYPE`] prefix, and maximum length
+/// of an inflated compressed payload. Longer payloads are rejected with [`DecodeError::TooLong`].
+///
+/// This is geth's 128 KiB transaction size limit, the largest calldata an inbox transaction can
+/// carry through the L1 mempool. Log payloads are held to the same bound so both inbox sources
+/// share one limit, and so an oversized payload cannot inflate the data gas, and with it the FCT
+/// mint, of a single deposit.
+pub const MAX_FACET_PAYLOAD_BYTES: usize = 128 * 1024;
+
 /// 0x1111000000000000000000000000000000001111 per OP Stack address aliasing rule.
 const ALIAS_OFFSET: U256 = U256::from_be_bytes([
   0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // 8
@@ -33,16 +70,43 @@ pub fn alias_l1_to_l2(addr: Address) -> Address {
   Address::from_slice(&bytes[12..])
 }
 
-#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
 pub enum DecodeError {
     #[error("payload too short")]
     Short,
+    #[error("payload of {0} bytes exceeds maximum {1}")]
+    TooLong(usize, usize),
     #[error("expected 0x46 prefix, got 0x{0:02x}")]
     WrongPrefix(u8),
     #[error("RLP decode error: {0}")]
     Rlp(String),
     #[error("chain-id {0} does not equal expected {1}")]
     BadChainId(u64, u64),
+    #[error("gas limit {0} exceeds maximum {1}")]
+    GasLimitTooHigh(u64, u64),
+    #[error("invalid 'to' field length: {0}")]
+    InvalidToLength(usize),
+    #[error("invalid compressed payload")]
+    Decompress,
+    #[error("decompressed payload exceeds maximum {0}")]
+    DecompressedTooLong(usize),
+}
+
+impl DecodeError {
+    /// Short snake case label of the error kind, suitable as a metrics label.
+    pub const fn reason(&self) -> &'static str {
+        match self {
+            Self::Short => "short",
+            Self::TooLong(..) => "too_long",
+            Self::WrongPrefix(_) => "wrong_prefix",
+            Self::Rlp(_) => "rlp",
+            Self::BadChainId(..) => "bad_chain_id",
+            Self::GasLimitTooHigh(..) => "gas_limit_too_high",
+            Self::InvalidToLength(_) => "invalid_to_length",
+            Self::Decompress => "decompress",
+            Self::DecompressedTooLong(_) => "decompressed_too_long",
+        }
+    }
 }
 
 /// Internal RLP structure matching the format: [chain_id, to, value, gas, data, mine_boost]
@@ -56,25 +120,216 @@ struct FacetPayloadRlp {
     mine_boost: Bytes,  // Additional data that counts toward FCT mint
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FacetPayload {
     pub to: Option<Address>,
     pub value: U256,
     pub gas_limit: u64,
     pub data: Bytes,
+    /// Extra bytes carried only to increase the payload's L1 data gas, and with it the FCT mint.
+    pub mine_boost: Bytes,
+    /// L1 data gas of the raw payload bytes. The RLP-encoded `mine_boost` is part of those bytes,
+    /// so it is already included here and is not counted a second time.
     pub l1_data_gas_used: u64,
     pub mint: u128,
 }
 
+/// Decodes a Facet payload `0x46 || RLP([chain_id, to, value, gas, data, mine_boost])`.
+///
+/// Arbitrary input is rejected with a [`DecodeError`], never a panic; empty input and a lone
+/// [`FACET_TX_TYPE`] prefix are [`DecodeError::Short`], and input longer than
+/// [`MAX_FACET_PAYLOAD_BYTES`] is [`DecodeError::TooLong`].
 pub fn decode_facet_payload(bytes: &[u8], l2_chain_id: u64, contract_initiated: bool) -> Result<FacetPayload, DecodeError> {
-    if bytes.is_empty() {
+    decode_facet_payload_with_prefix_accounting(
+        bytes,
+        l2_chain_id,
+        contract_initiated,
+        FACET_PREFIX_COUNTS_TOWARD_DATA_GAS,
+    )
+}
+
+/// Decodes a Facet payload like [`decode_facet_payload`], rejecting payloads whose `gas_limit`
+/// exceeds `max_gas_limit` (e.g. the block gas limit).
+///
+/// Such a deposit could never fit in a block, and would otherwise only fail later in execution.
+pub fn decode_facet_payload_checked(
+    bytes: &[u8],
+    l2_chain_id: u64,
+    contract_initiated: bool,
+    max_gas_limit: u64,
+) -> Result<FacetPayload, DecodeError> {
+    let payload = decode_facet_payload(bytes, l2_chain_id, contract_initiated)?;
+    if payload.gas_limit > max_gas_limit {
+        return Err(DecodeError::GasLimitTooHigh(payload.gas_limit, max_gas_limit));
+    }
+    Ok(payload)
+}
+
+/// Decodes a Facet payload like [`decode_facet_payload`], rejecting payloads longer than
+/// `max_len` bytes with [`DecodeError::TooLong`].
+///
+/// The default [`MAX_FACET_PAYLOAD_BYTES`] cap still applies, so `max_len` can only tighten it.
+pub fn decode_facet_payload_with_max_len(
+    bytes: &[u8],
+    l2_chain_id: u64,
+    contract_initiated: bool,
+    max_len: usize,
+) -> Result<FacetPayload, DecodeError> {
+    if bytes.len() > max_len {
+        return Err(DecodeError::TooLong(bytes.len(), max_len));
+    }
+    decode_facet_payload(bytes, l2_chain_id, contract_initiated)
+}
+
+/// Decodes a Facet payload like [`decode_facet_payload`], charging `l1_data_gas_used` with the
+/// EIP-7623 calldata floor if `eip7623` is set (see [`is_facet_eip7623_active`]).
+pub fn decode_facet_payload_with_eip7623(
+    bytes: &[u8],
+    l2_chain_id: u64,
+    contract_initiated: bool,
+    eip7623: bool,
+) -> Result<FacetPayload, DecodeError> {
+    decode_facet_payload_inner(
+        bytes,
+        l2_chain_id,
+        contract_initiated,
+        FACET_PREFIX_COUNTS_TOWARD_DATA_GAS,
+        eip7623,
+    )
+}
+
+/// Magic bytes of a gzip member header (RFC 1952).
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// Flags of the optional gzip header fields.
+const GZIP_FHCRC: u8 = 0x02;
+const GZIP_FEXTRA: u8 = 0x04;
+const GZIP_FNAME: u8 = 0x08;
+const GZIP_FCOMMENT: u8 = 0x10;
+
+/// Decodes a Facet payload like [`decode_facet_payload`], first inflating it if
+/// `allow_compressed` is set and the bytes start with a gzip or zlib header.
+///
+/// The decompressed payload must start with the [`FACET_TX_TYPE`] prefix, and may be at most
+/// [`MAX_FACET_PAYLOAD_BYTES`] long, like the compressed bytes; inflation stops at that limit, so a
+/// small compressed input cannot expand without bound. `l1_data_gas_used` is charged over the compressed bytes, as those
+/// are the bytes L1 charges calldata gas for.
+///
+/// With `allow_compressed` unset this is exactly [`decode_facet_payload`], which rejects a
+/// compressed payload as [`DecodeError::WrongPrefix`].
+pub fn decode_facet_payload_with_compression(
+    bytes: &[u8],
+    l2_chain_id: u64,
+    contract_initiated: bool,
+    allow_compressed: bool,
+) -> Result<FacetPayload, DecodeError> {
+    if bytes.len() > MAX_FACET_PAYLOAD_BYTES {
+        return Err(DecodeError::TooLong(bytes.len(), MAX_FACET_PAYLOAD_BYTES));
+    }
+    let decompressed = if allow_compressed { decompress_facet_payload(bytes)? } else { None };
+    let Some(decompressed) = decompressed else {
+        return decode_facet_payload(bytes, l2_chain_id, contract_initiated);
+    };
+
+    let mut payload = decode_facet_payload(&decompressed, l2_chain_id, contract_initiated)?;
+    payload.l1_data_gas_used =
+        FctMintCalculator::calculate_data_gas_used(bytes, contract_initiated);
+    Ok(payload)
+}
+
+/// Inflates a gzip or zlib wrapped payload, returning `None` if `bytes` has neither header.
+///
+/// The gzip trailer (CRC-32 and size) is not checked: the inflated bytes are validated by the
+/// payload decoding itself.
+fn decompress_facet_payload(bytes: &[u8]) -> Result<Option<Vec<u8>>, DecodeError> {
+    use miniz_oxide::inflate::{
+        DecompressError, TINFLStatus, decompress_to_vec_with_limit,
+        decompress_to_vec_zlib_with_limit,
+    };
+
+    let inflated = if bytes.starts_with(&GZIP_MAGIC) {
+        let deflate = gzip_deflate_stream(bytes).ok_or(DecodeError::Decompress)?;
+        decompress_to_vec_with_limit(deflate, MAX_FACET_PAYLOAD_BYTES)
+    } else if is_zlib_header(bytes) {
+        decompress_to_vec_zlib_with_limit(bytes, MAX_FACET_PAYLOAD_BYTES)
+    } else {
+        return Ok(None);
+    };
+
+    match inflated {
+        Ok(data) => Ok(Some(data)),
+        Err(DecompressError { status: TINFLStatus::HasMoreOutput, .. }) => {
+            Err(DecodeError::DecompressedTooLong(MAX_FACET_PAYLOAD_BYTES))
+        }
+        Err(_) => Err(DecodeError::Decompress),
+    }
+}
+
+/// Returns `true` if `bytes` starts with a valid zlib header using the deflate method.
+fn is_zlib_header(bytes: &[u8]) -> bool {
+    // CM (low nibble of CMF) is 8 for deflate, CINFO at most 7, and FCHECK makes the header a
+    // multiple of 31.
+    let [cmf, flg, ..] = *bytes else { return false };
+    cmf & 0x0f == 8 && cmf >> 4 <= 7 && u16::from_be_bytes([cmf, flg]) % 31 == 0
+}
+
+/// Skips the header of a gzip member, returning its raw deflate stream.
+fn gzip_deflate_stream(bytes: &[u8]) -> Option<&[u8]> {
+    // ID1, ID2, CM (8 = deflate), FLG, MTIME (4), XFL, OS
+    let (header, mut rest) = bytes.split_at_checked(10)?;
+    if header[2] != 8 {
+        return None;
+    }
+    let flags = header[3];
+    if flags & GZIP_FEXTRA != 0 {
+        let (len, tail) = rest.split_at_checked(2)?;
+        rest = tail.get(u16::from_le_bytes([len[0], len[1]]) as usize..)?;
+    }
+    for flag in [GZIP_FNAME, GZIP_FCOMMENT] {
+        if flags & flag != 0 {
+            let end = rest.iter().position(|b| *b == 0)?;
+            rest = &rest[end + 1..];
+        }
+    }
+    if flags & GZIP_FHCRC != 0 {
+        rest = rest.get(2..)?;
+    }
+    Some(rest)
+}
+
+/// Decodes a Facet payload, explicitly choosing whether the [`FACET_TX_TYPE`] prefix byte is
+/// included in the `l1_data_gas_used` accounting.
+///
+/// [`decode_facet_payload`] uses the canonical [`FACET_PREFIX_COUNTS_TOWARD_DATA_GAS`] setting.
+pub fn decode_facet_payload_with_prefix_accounting(
+    bytes: &[u8],
+    l2_chain_id: u64,
+    contract_initiated: bool,
+    count_prefix: bool,
+) -> Result<FacetPayload, DecodeError> {
+    decode_facet_payload_inner(bytes, l2_chain_id, contract_initiated, count_prefix, false)
+}
+
+fn decode_facet_payload_inner(
+    bytes: &[u8],
+    l2_chain_id: u64,
+    contract_initiated: bool,
+    count_prefix: bool,
+    eip7623: bool,
+) -> Result<FacetPayload, DecodeError> {
+    if bytes.len() > MAX_FACET_PAYLOAD_BYTES {
+        return Err(DecodeError::TooLong(bytes.len(), MAX_FACET_PAYLOAD_BYTES));
+    }
+    let Some((&prefix, rlp_data)) = bytes.split_first() else {
         return Err(DecodeError::Short);
+    };
+    if prefix != FACET_TX_TYPE {
+        return Err(DecodeError::WrongPrefix(prefix));
     }
-    if bytes[0] != FACET_TX_TYPE {
-        return Err(DecodeError::WrongPrefix(bytes[0]));
+    // A lone prefix carries no payload at all, rather than a malformed one.
+    if rlp_data.is_empty() {
+        return Err(DecodeError::Short);
     }
     
-    let rlp_data = &bytes[1..];
     let rlp_payload = FacetPayloadRlp::decode(&mut &rlp_data[..]).map_err(|e| DecodeError::Rlp(e.to_string()))?;
     
     if rlp_payload.chain_id != l2_chain_id {
@@ -87,23 +342,164 @@ pub fn decode_facet_payload(bytes: &[u8], l2_chain_id: u64, contract_initiated:
         Some(Address::from_slice(&rlp_payload.to))
     } else {
         // Invalid "to" field - must be either empty or exactly 20 bytes
-        return Err(DecodeError::Rlp(format!("invalid 'to' field length: {}", rlp_payload.to.len())));
+        return Err(DecodeError::InvalidToLength(rlp_payload.to.len()));
     };
     
-    // Calculate L1 data gas used based on the entire transaction payload
-    let l1_data_gas_used = FctMintCalculator::calculate_data_gas_used(bytes, contract_initiated);
+    // Calculate L1 data gas used based on the transaction payload, with or without the prefix.
+    // `mine_boost` is encoded inside these bytes, so it counts toward the gas exactly once.
+    let gas_bytes = if count_prefix { bytes } else { rlp_data };
+    let l1_data_gas_used = if eip7623 {
+        FctMintCalculator::calculate_data_gas_used_eip7623(gas_bytes, contract_initiated)
+    } else {
+        FctMintCalculator::calculate_data_gas_used(gas_bytes, contract_initiated)
+    };
     
     Ok(FacetPayload {
         to,
         value: rlp_payload.value,
         gas_limit: rlp_payload.gas_limit,
         data: rlp_payload.data,
+        mine_boost: rlp_payload.mine_boost,
         l1_data_gas_used,
         mint: 0u128, // Will be set later by mint calculation
     })
 }
 
+/// Memoizes [`decode_facet_payload`] in an LRU cache, for paths that decode the same L1 calldata
+/// repeatedly (e.g. across retries).
+///
+/// Entries are keyed by `keccak256(bytes || chain_id || contract_initiated)`. Only successful
+/// decodes are cached; decoding is a pure function of the key, so a hit is always equal to a fresh
+/// decode.
+#[derive(Debug)]
+pub struct CachingFacetDecoder {
+    cache: LruCache<B256, FacetPayload>,
+    hits: u64,
+    misses: u64,
+}
+
+impl Default for CachingFacetDecoder {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl CachingFacetDecoder {
+    /// The default number of cached payloads.
+    pub const DEFAULT_CAPACITY: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+
+    /// Creates a decoder caching up to `capacity` payloads.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self { cache: LruCache::new(capacity), hits: 0, misses: 0 }
+    }
+
+    /// Decodes a Facet payload like [`decode_facet_payload`], returning a cached copy if the same
+    /// input was decoded before.
+    pub fn decode(
+        &mut self,
+        bytes: &[u8],
+        l2_chain_id: u64,
+        contract_initiated: bool,
+    ) -> Result<FacetPayload, DecodeError> {
+        let key = Self::cache_key(bytes, l2_chain_id, contract_initiated);
+        if let Some(payload) = self.cache.get(&key) {
+            self.hits += 1;
+            return Ok(payload.clone());
+        }
+
+        self.misses += 1;
+        let payload = decode_facet_payload(bytes, l2_chain_id, contract_initiated)?;
+        self.cache.put(key, payload.clone());
+        Ok(payload)
+    }
+
+    /// The number of decodes served from the cache.
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of decodes that missed the cache.
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    fn cache_key(bytes: &[u8], l2_chain_id: u64, contract_initiated: bool) -> B256 {
+        let mut buf = Vec::with_capacity(bytes.len() + 9);
+        buf.extend_from_slice(bytes);
+        buf.extend_from_slice(&l2_chain_id.to_be_bytes());
+        buf.push(contract_initiated as u8);
+        keccak256(buf)
+    }
+}
+
+/// Encodes a Facet payload as `0x46 || RLP([chain_id, to, value, gas, data, mine_boost])`, the
+/// inverse of [`decode_facet_payload`].
+///
+/// A `to` of `None` is encoded as an empty string, marking a contract creation.
+pub fn encode_facet_payload(
+    chain_id: u64,
+    to: Option<Address>,
+    value: U256,
+    gas_limit: u64,
+    data: &[u8],
+    mine_boost: &[u8],
+) -> Bytes {
+    let rlp_payload = FacetPayloadRlp {
+        chain_id,
+        to: to.map(|addr| Bytes::copy_from_slice(addr.as_slice())).unwrap_or_default(),
+        value,
+        gas_limit,
+        data: Bytes::copy_from_slice(data),
+        mine_boost: Bytes::copy_from_slice(mine_boost),
+    };
+    let mut buf = Vec::with_capacity(1 + rlp_payload.length());
+    buf.push(FACET_TX_TYPE);
+    rlp_payload.encode(&mut buf);
+    buf.into()
+}
+
+/// Source domain of deposits decoded from calldata sent to the Facet inbox.
+///
+/// The Facet domains are kept clear of the OP Stack deposit source domains (0 to 3).
+pub const FACET_CALLDATA_SOURCE_DOMAIN: u64 = 0xface0;
+
+/// Source domain of deposits decoded from Facet inbox event logs.
+pub const FACET_LOG_SOURCE_DOMAIN: u64 = 0xface1;
+
+/// Source domain of deposits decoded from the blobs of EIP-4844 transactions sent to the inbox.
+pub const FACET_BLOB_SOURCE_DOMAIN: u64 = 0xface2;
+
+/// Canonical source hash of a Facet deposit.
+///
+/// Follows the layout of the OP Stack deposit source hashes:
+/// `keccak256(bytes32(domain) ++ keccak256(tx_hash ++ bytes32(index)))`. The `index` of a log
+/// deposit is the index of the log in its receipt and the `index` of a blob deposit is the index
+/// of the blob in its transaction, so every deposit of an L1 block has a distinct source hash, and
+/// none equals an L1 transaction hash.
+pub fn facet_source_hash(domain: u64, tx_hash: B256, index: u64) -> B256 {
+    let mut deposit_id = [0u8; 64];
+    deposit_id[..32].copy_from_slice(tx_hash.as_slice());
+    deposit_id[56..].copy_from_slice(&index.to_be_bytes());
+
+    let mut input = [0u8; 64];
+    input[24..32].copy_from_slice(&domain.to_be_bytes());
+    input[32..].copy_from_slice(keccak256(deposit_id).as_slice());
+    keccak256(input)
+}
+
 impl FacetPayload {
+    /// Encodes the payload for the given chain with [`encode_facet_payload`].
+    pub fn encode(&self, chain_id: u64) -> Bytes {
+        encode_facet_payload(
+            chain_id,
+            self.to,
+            self.value,
+            self.gas_limit,
+            &self.data,
+            &self.mine_boost,
+        )
+    }
+
     pub fn into_deposit(self, from: Address, source_hash: B256) -> TxDeposit {
         TxDeposit {
             from,
@@ -125,4 +521,344 @@ impl FacetPayload {
     pub fn set_mint(&mut self, mint: u128) {
         self.mint = mint;
     }
-}
\ No newline at end of file
+
+    /// FCT this payload mints at `mint_rate`, per [`FctMintCalculator::calculate_mint_amount`].
+    pub fn estimated_mint(&self, mint_rate: u128) -> u128 {
+        FctMintCalculator::calculate_mint_amount(self.l1_data_gas_used, mint_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::hex;
+    use proptest::{collection::vec, prelude::any, proptest};
+
+    const KNOWN_PAYLOAD: &str =
+        "46e283face7a94111111111111111111111111111111111111111180830f424082123480";
+    const KNOWN_CHAIN_ID: u64 = 16436858;
+
+    #[test]
+    fn test_prefix_data_gas_accounting() {
+        let bytes = hex::decode(KNOWN_PAYLOAD).unwrap();
+
+        let with_prefix =
+            decode_facet_payload_with_prefix_accounting(&bytes, KNOWN_CHAIN_ID, false, true)
+                .unwrap();
+        let without_prefix =
+            decode_facet_payload_with_prefix_accounting(&bytes, KNOWN_CHAIN_ID, false, false)
+                .unwrap();
+
+        // 36 non-zero bytes vs 35 non-zero bytes.
+        assert_eq!(with_prefix.l1_data_gas_used, 576);
+        assert_eq!(without_prefix.l1_data_gas_used, 560);
+
+        let rate = FctMintCalculator::INITIAL_RATE;
+        let mint_with = FctMintCalculator::calculate_mint_amount(with_prefix.l1_data_gas_used, rate);
+        let mint_without =
+            FctMintCalculator::calculate_mint_amount(without_prefix.l1_data_gas_used, rate);
+        assert_eq!(mint_with - mint_without, 16 * rate);
+
+        // The default decoder uses the canonical choice.
+        let canonical = decode_facet_payload(&bytes, KNOWN_CHAIN_ID, false).unwrap();
+        assert_eq!(canonical.l1_data_gas_used, with_prefix.l1_data_gas_used);
+    }
+
+    #[test]
+    fn test_eip7623_data_gas() {
+        let bytes = hex::decode(KNOWN_PAYLOAD).unwrap();
+        let standard =
+            decode_facet_payload_with_eip7623(&bytes, KNOWN_CHAIN_ID, false, false).unwrap();
+        let floored =
+            decode_facet_payload_with_eip7623(&bytes, KNOWN_CHAIN_ID, false, true).unwrap();
+
+        assert_eq!(standard, decode_facet_payload(&bytes, KNOWN_CHAIN_ID, false).unwrap());
+        // 36 non-zero bytes make 144 tokens.
+        assert_eq!(standard.l1_data_gas_used, 576);
+        assert_eq!(floored.l1_data_gas_used, 1440);
+        assert_eq!((floored.to, floored.gas_limit), (standard.to, standard.gas_limit));
+
+        assert!(!is_facet_eip7623_active(u64::MAX));
+    }
+
+    #[test]
+    fn test_estimated_mint() {
+        let bytes = hex::decode(KNOWN_PAYLOAD).unwrap();
+        let payload = decode_facet_payload(&bytes, KNOWN_CHAIN_ID, false).unwrap();
+
+        let rate = FctMintCalculator::INITIAL_RATE;
+        assert_eq!(
+            payload.estimated_mint(rate),
+            FctMintCalculator::calculate_mint_amount(payload.l1_data_gas_used, rate)
+        );
+        assert_eq!(payload.estimated_mint(rate), 576 * rate);
+        assert_eq!(payload.estimated_mint(0), 0);
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        let bytes = hex::decode(KNOWN_PAYLOAD).unwrap();
+        let payload = decode_facet_payload(&bytes, KNOWN_CHAIN_ID, false).unwrap();
+
+        assert_eq!(payload.encode(KNOWN_CHAIN_ID).as_ref(), bytes.as_slice());
+        assert_eq!(
+            encode_facet_payload(
+                KNOWN_CHAIN_ID,
+                payload.to,
+                payload.value,
+                payload.gas_limit,
+                &payload.data,
+                &payload.mine_boost,
+            )
+            .as_ref(),
+            bytes.as_slice()
+        );
+
+        // Contract creation and a mine boost survive the round trip too.
+        let create = encode_facet_payload(KNOWN_CHAIN_ID, None, U256::from(7), 21_000, &[0xab], &[1, 2]);
+        let decoded = decode_facet_payload(&create, KNOWN_CHAIN_ID, false).unwrap();
+        assert_eq!(decoded.to, None);
+        assert_eq!(decoded.value, U256::from(7));
+        assert_eq!(decoded.data.as_ref(), &[0xab]);
+    }
+
+    #[test]
+    fn test_mine_boost_counts_toward_data_gas() {
+        let to = Some(Address::repeat_byte(0x11));
+        let plain = encode_facet_payload(KNOWN_CHAIN_ID, to, U256::ZERO, 1_000_000, &[0x12, 0x34], &[]);
+        let boosted = encode_facet_payload(
+            KNOWN_CHAIN_ID,
+            to,
+            U256::ZERO,
+            1_000_000,
+            &[0x12, 0x34],
+            &[0xff, 0xff, 0xff, 0x00],
+        );
+        assert_eq!(plain.as_ref(), hex::decode(KNOWN_PAYLOAD).unwrap().as_slice());
+
+        let plain = decode_facet_payload(&plain, KNOWN_CHAIN_ID, false).unwrap();
+        let boosted = decode_facet_payload(&boosted, KNOWN_CHAIN_ID, false).unwrap();
+        assert!(plain.mine_boost.is_empty());
+        assert_eq!(boosted.mine_boost.as_ref(), &[0xff, 0xff, 0xff, 0x00]);
+
+        // The empty-string marker `0x80` becomes the `0x84` header at the same cost, leaving three
+        // non-zero bytes and one zero byte of boost.
+        assert_eq!(boosted.l1_data_gas_used, plain.l1_data_gas_used + 3 * 16 + 4);
+    }
+
+    #[test]
+    fn test_checked_decode_gas_limit_ceiling() {
+        let bytes = hex::decode(KNOWN_PAYLOAD).unwrap();
+        let payload = decode_facet_payload(&bytes, KNOWN_CHAIN_ID, false).unwrap();
+        assert_eq!(payload.gas_limit, 1_000_000);
+
+        // A gas limit exactly at the ceiling is accepted.
+        let checked = decode_facet_payload_checked(&bytes, KNOWN_CHAIN_ID, false, 1_000_000).unwrap();
+        assert_eq!(checked, payload);
+
+        assert_eq!(
+            decode_facet_payload_checked(&bytes, KNOWN_CHAIN_ID, false, 999_999),
+            Err(DecodeError::GasLimitTooHigh(1_000_000, 999_999))
+        );
+
+        // Other decode errors take precedence.
+        assert_eq!(
+            decode_facet_payload_checked(&bytes, KNOWN_CHAIN_ID + 1, false, 0),
+            Err(DecodeError::BadChainId(KNOWN_CHAIN_ID, KNOWN_CHAIN_ID + 1))
+        );
+    }
+
+    #[test]
+    fn test_payload_size_limit() {
+        let to = Some(Address::repeat_byte(0x11));
+        let encode = |len: usize| {
+            encode_facet_payload(KNOWN_CHAIN_ID, to, U256::ZERO, 21_000, &vec![0xab; len], &[])
+        };
+        // The RLP headers are the same size for any data length close to the limit.
+        let probe = MAX_FACET_PAYLOAD_BYTES - 100;
+        let overhead = encode(probe).len() - probe;
+
+        let at_limit = encode(MAX_FACET_PAYLOAD_BYTES - overhead);
+        assert_eq!(at_limit.len(), MAX_FACET_PAYLOAD_BYTES);
+        let over_limit = encode(MAX_FACET_PAYLOAD_BYTES - overhead + 1);
+        let too_long =
+            Err(DecodeError::TooLong(MAX_FACET_PAYLOAD_BYTES + 1, MAX_FACET_PAYLOAD_BYTES));
+
+        // The default decoder enforces the cap, for calldata and logs alike
+        for contract_initiated in [false, true] {
+            let decode =
+                |bytes: &[u8]| decode_facet_payload(bytes, KNOWN_CHAIN_ID, contract_initiated);
+            assert!(decode(&at_limit).is_ok());
+            assert_eq!(decode(&over_limit), too_long);
+        }
+        assert_eq!(
+            decode_facet_payload_with_eip7623(&over_limit, KNOWN_CHAIN_ID, false, true),
+            too_long
+        );
+
+        // A tighter cap is applied on top of the default one, a looser one is not
+        let decode_with_max_len = |bytes: &[u8], max_len: usize| {
+            decode_facet_payload_with_max_len(bytes, KNOWN_CHAIN_ID, false, max_len)
+        };
+        assert_eq!(
+            decode_with_max_len(&at_limit, MAX_FACET_PAYLOAD_BYTES - 1),
+            Err(DecodeError::TooLong(MAX_FACET_PAYLOAD_BYTES, MAX_FACET_PAYLOAD_BYTES - 1))
+        );
+        assert_eq!(decode_with_max_len(&over_limit, usize::MAX), too_long);
+    }
+
+    /// Wraps `data` in a minimal gzip member, with a zeroed CRC-32.
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut gz = vec![0x1f, 0x8b, 0x08, 0x00, 0, 0, 0, 0, 0x00, 0xff];
+        gz.extend(miniz_oxide::deflate::compress_to_vec(data, 9));
+        gz.extend([0; 4]);
+        gz.extend((data.len() as u32).to_le_bytes());
+        gz
+    }
+
+    #[test]
+    fn test_compressed_payload() {
+        let bytes = hex::decode(KNOWN_PAYLOAD).unwrap();
+        let expected = decode_facet_payload(&bytes, KNOWN_CHAIN_ID, false).unwrap();
+        let gzipped = gzip(&bytes);
+        let zlib = miniz_oxide::deflate::compress_to_vec_zlib(&bytes, 9);
+
+        for compressed in [&gzipped, &zlib] {
+            let payload =
+                decode_facet_payload_with_compression(compressed, KNOWN_CHAIN_ID, false, true)
+                    .unwrap();
+            assert_eq!(payload.to, expected.to);
+            assert_eq!(payload.data, expected.data);
+            assert_eq!(
+                payload.l1_data_gas_used,
+                FctMintCalculator::calculate_data_gas_used(compressed, false)
+            );
+        }
+
+        // Without the flag, the compressed payload is rejected as before.
+        assert_eq!(
+            decode_facet_payload_with_compression(&gzipped, KNOWN_CHAIN_ID, false, false),
+            Err(DecodeError::WrongPrefix(0x1f))
+        );
+        assert_eq!(
+            decode_facet_payload_with_compression(&bytes, KNOWN_CHAIN_ID, false, true),
+            Ok(expected)
+        );
+        assert_eq!(
+            decode_facet_payload_with_compression(&gzipped[..12], KNOWN_CHAIN_ID, false, true),
+            Err(DecodeError::Decompress)
+        );
+    }
+
+    #[test]
+    fn test_compressed_payload_size_cap() {
+        // A gzip bomb: 16 MiB of zeros compresses to a few KiB.
+        let bomb = gzip(&vec![0; 16 * 1024 * 1024]);
+        assert!(bomb.len() < MAX_FACET_PAYLOAD_BYTES);
+        assert_eq!(
+            decode_facet_payload_with_compression(&bomb, KNOWN_CHAIN_ID, false, true),
+            Err(DecodeError::DecompressedTooLong(MAX_FACET_PAYLOAD_BYTES))
+        );
+    }
+
+    #[test]
+    fn test_decode_short_inputs() {
+        for contract_initiated in [false, true] {
+            let decode =
+                |bytes: &[u8]| decode_facet_payload(bytes, KNOWN_CHAIN_ID, contract_initiated);
+            assert_eq!(decode(&[]), Err(DecodeError::Short));
+            assert_eq!(decode(&[0x00]), Err(DecodeError::WrongPrefix(0x00)));
+            assert_eq!(decode(&[DEPOSIT_TX_TYPE]), Err(DecodeError::WrongPrefix(DEPOSIT_TX_TYPE)));
+            assert_eq!(decode(&[FACET_TX_TYPE]), Err(DecodeError::Short));
+
+            // The prefix followed by only a list header, or a truncated list, is malformed RLP.
+            let bytes = hex::decode(KNOWN_PAYLOAD).unwrap();
+            for len in 2..bytes.len() {
+                assert!(matches!(decode(&bytes[..len]), Err(DecodeError::Rlp(_))), "{len}");
+            }
+            assert!(decode(&bytes).is_ok());
+        }
+
+        for short in [&[][..], &GZIP_MAGIC[..1], &GZIP_MAGIC[..], &[0x78][..]] {
+            assert!(
+                decode_facet_payload_with_compression(short, KNOWN_CHAIN_ID, false, true).is_err()
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_decode_arbitrary_bytes(bytes in vec(any::<u8>(), 0..512), contract_initiated in any::<bool>()) {
+            let chain_id = KNOWN_CHAIN_ID;
+            let _ = decode_facet_payload(&bytes, chain_id, contract_initiated);
+            let _ = decode_facet_payload_with_eip7623(&bytes, chain_id, contract_initiated, true);
+            let _ = decode_facet_payload_with_compression(&bytes, chain_id, contract_initiated, true);
+        }
+
+        #[test]
+        fn test_decode_arbitrary_prefixed_bytes(tail in vec(any::<u8>(), 0..512), gzipped in any::<bool>()) {
+            let mut bytes = if gzipped { GZIP_MAGIC.to_vec() } else { vec![FACET_TX_TYPE] };
+            bytes.extend(tail);
+            let _ = decode_facet_payload_with_compression(&bytes, KNOWN_CHAIN_ID, false, true);
+        }
+
+        #[test]
+        fn test_decode_mutated_payload(index in any::<usize>(), byte in any::<u8>(), len in any::<usize>()) {
+            let mut bytes = hex::decode(KNOWN_PAYLOAD).unwrap();
+            let index = index % bytes.len();
+            bytes[index] = byte;
+            bytes.truncate(len % (bytes.len() + 1));
+            if let Ok(payload) = decode_facet_payload(&bytes, KNOWN_CHAIN_ID, false) {
+                assert_eq!(
+                    payload.l1_data_gas_used,
+                    FctMintCalculator::calculate_data_gas_used(&bytes, false)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_source_hashes_are_domain_separated() {
+        let tx_hash = B256::repeat_byte(0xaa);
+        let calldata = facet_source_hash(FACET_CALLDATA_SOURCE_DOMAIN, tx_hash, 0);
+        let log = facet_source_hash(FACET_LOG_SOURCE_DOMAIN, tx_hash, 0);
+
+        assert_ne!(calldata, tx_hash);
+        assert_ne!(calldata, log);
+        assert_ne!(log, facet_source_hash(FACET_LOG_SOURCE_DOMAIN, tx_hash, 1));
+        // The domain separates hashes at index 0 too
+        assert_ne!(facet_source_hash(0, tx_hash, 0), facet_source_hash(1, tx_hash, 0));
+        // Same layout as the OP Stack user deposits, which use domain 0
+        for index in [0, 1] {
+            assert_eq!(
+                facet_source_hash(0, tx_hash, index),
+                op_alloy_consensus::UserDepositSource::new(tx_hash, index).source_hash()
+            );
+        }
+    }
+
+    #[test]
+    fn test_caching_decoder_hits() {
+        let bytes = hex::decode(KNOWN_PAYLOAD).unwrap();
+        let mut decoder = CachingFacetDecoder::default();
+
+        let first = decoder.decode(&bytes, KNOWN_CHAIN_ID, false).unwrap();
+        assert_eq!((decoder.hits(), decoder.misses()), (0, 1));
+
+        let second = decoder.decode(&bytes, KNOWN_CHAIN_ID, false).unwrap();
+        assert_eq!((decoder.hits(), decoder.misses()), (1, 1));
+        assert_eq!(first, second);
+        assert_eq!(second, decode_facet_payload(&bytes, KNOWN_CHAIN_ID, false).unwrap());
+
+        // Contract-initiated payloads are charged differently, so they are cached separately.
+        let contract = decoder.decode(&bytes, KNOWN_CHAIN_ID, true).unwrap();
+        assert_eq!((decoder.hits(), decoder.misses()), (1, 2));
+        assert_ne!(contract.l1_data_gas_used, first.l1_data_gas_used);
+
+        // Failed decodes are not cached.
+        assert!(decoder.decode(&bytes, KNOWN_CHAIN_ID + 1, false).is_err());
+        assert!(decoder.decode(&bytes, KNOWN_CHAIN_ID + 1, false).is_err());
+        assert_eq!((decoder.hits(), decoder.misses()), (1, 4));
+    }
+}
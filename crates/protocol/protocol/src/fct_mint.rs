@@ -7,8 +7,24 @@
 //! - Dynamic mint rate calculations
 
 /// FCT mint calculation constants and logic
-#[derive(Debug)]
-pub struct FctMintCalculator;
+///
+/// The halving schedule is expressed in seconds, so the number of L2 blocks per halving depends on
+/// the chain's block time. Construct the calculator from the rollup config's `block_time` with
+/// [`FctMintCalculator::new`]; [`FctMintCalculator::default`] matches the associated constants,
+/// which assume [`FctMintCalculator::L2_BLOCK_TIME`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FctMintCalculator {
+    block_time: u64,
+    raw_halving_period_in_blocks: u64,
+    adjustment_periods_per_halving: u64,
+    halving_period_in_blocks: u64,
+}
+
+impl Default for FctMintCalculator {
+    fn default() -> Self {
+        Self::new(Self::L2_BLOCK_TIME)
+    }
+}
 
 impl FctMintCalculator {
     /// Number of blocks in an adjustment period
@@ -20,16 +36,16 @@ impl FctMintCalculator {
     /// Halving period in seconds (1 year)
     pub const HALVING_PERIOD_IN_SECONDS: u64 = 1 * Self::SECONDS_PER_YEAR;
     
-    /// L2 block time in seconds (assumed 2 seconds)
+    /// Default L2 block time in seconds (12 seconds on Facet)
     pub const L2_BLOCK_TIME: u64 = 12;
     
-    /// Raw halving period in blocks
+    /// Raw halving period in blocks, at [`Self::L2_BLOCK_TIME`]
     pub const RAW_HALVING_PERIOD_IN_BLOCKS: u64 = Self::HALVING_PERIOD_IN_SECONDS / Self::L2_BLOCK_TIME;
     
-    /// Number of adjustment periods per halving
+    /// Number of adjustment periods per halving, at [`Self::L2_BLOCK_TIME`]
     pub const ADJUSTMENT_PERIODS_PER_HALVING: u64 = Self::RAW_HALVING_PERIOD_IN_BLOCKS / Self::ADJUSTMENT_PERIOD;
     
-    /// Actual halving period in blocks (rounded to adjustment periods)
+    /// Actual halving period in blocks (rounded to adjustment periods), at [`Self::L2_BLOCK_TIME`]
     pub const HALVING_PERIOD_IN_BLOCKS: u64 = Self::ADJUSTMENT_PERIOD * Self::ADJUSTMENT_PERIODS_PER_HALVING;
     
     /// Target FCT mint per L1 block (40 ETH in wei)
@@ -50,15 +66,63 @@ impl FctMintCalculator {
     /// Minimum mint rate
     pub const MIN_RATE: u128 = 1;
     
+    /// EIP-7623 floor gas per calldata token (a zero byte is one token, a non-zero byte four)
+    pub const TOTAL_COST_FLOOR_PER_TOKEN: u64 = 10;
+    
+    /// Create a calculator for a chain with the given L2 block time in seconds.
+    ///
+    /// A `block_time` of zero (an unset rollup config field) falls back to
+    /// [`Self::L2_BLOCK_TIME`]. A block time so long that a year holds less than one adjustment
+    /// period still halves once every adjustment period.
+    pub const fn new(block_time: u64) -> Self {
+        let block_time = if block_time == 0 { Self::L2_BLOCK_TIME } else { block_time };
+        let raw_halving_period_in_blocks = Self::HALVING_PERIOD_IN_SECONDS / block_time;
+        let adjustment_periods_per_halving = raw_halving_period_in_blocks / Self::ADJUSTMENT_PERIOD;
+        let adjustment_periods_per_halving =
+            if adjustment_periods_per_halving == 0 { 1 } else { adjustment_periods_per_halving };
+        Self {
+            block_time,
+            raw_halving_period_in_blocks,
+            adjustment_periods_per_halving,
+            halving_period_in_blocks: Self::ADJUSTMENT_PERIOD * adjustment_periods_per_halving,
+        }
+    }
+    
+    /// L2 block time in seconds
+    pub const fn block_time(&self) -> u64 {
+        self.block_time
+    }
+    
+    /// Raw halving period in blocks
+    pub const fn raw_halving_period_in_blocks(&self) -> u64 {
+        self.raw_halving_period_in_blocks
+    }
+    
+    /// Number of adjustment periods per halving
+    pub const fn adjustment_periods_per_halving(&self) -> u64 {
+        self.adjustment_periods_per_halving
+    }
+    
+    /// Actual halving period in blocks (rounded to adjustment periods)
+    pub const fn halving_period_in_blocks(&self) -> u64 {
+        self.halving_period_in_blocks
+    }
+    
     /// Calculate how many halving periods have passed for a given L2 block number
-    pub fn halving_periods_passed(current_l2_block: u64) -> u64 {
-        current_l2_block / Self::HALVING_PERIOD_IN_BLOCKS
+    pub const fn halving_periods_passed(&self, current_l2_block: u64) -> u64 {
+        current_l2_block / self.halving_period_in_blocks
     }
     
     /// Calculate the halving factor (2^halving_periods)
-    pub fn halving_factor(l2_block_number: u64) -> u128 {
-        let periods = Self::halving_periods_passed(l2_block_number);
-        2_u128.pow(periods as u32)
+    ///
+    /// Saturates to [`u128::MAX`] once the number of periods passed no longer fits in a `u128`
+    /// (128 or more halvings).
+    pub const fn halving_factor(&self, l2_block_number: u64) -> u128 {
+        let periods = self.halving_periods_passed(l2_block_number);
+        if periods >= u128::BITS as u64 {
+            return u128::MAX;
+        }
+        1 << periods
     }
     
     /// Check if this is the first block in an adjustment period
@@ -67,16 +131,44 @@ impl FctMintCalculator {
     }
     
     /// Calculate the halving-adjusted target mint for a period
-    pub fn halving_adjusted_target(l2_block_number: u64) -> u128 {
-        let factor = Self::halving_factor(l2_block_number);
-        if factor == 0 {
+    ///
+    /// Returns 0 once the halving factor has saturated.
+    pub const fn halving_adjusted_target(&self, l2_block_number: u64) -> u128 {
+        let factor = self.halving_factor(l2_block_number);
+        if factor == 0 || factor == u128::MAX {
             return 0;
         }
         Self::TARGET_MINT_PER_PERIOD / factor
     }
     
+    /// Theoretical FCT emission over every adjustment period completed before `l2_block_number`
+    ///
+    /// Sums [`Self::halving_adjusted_target`] over each completed adjustment period, halving the
+    /// per-period target at every halving boundary. This is the target emission implied by the
+    /// schedule, not the amount actually minted, which depends on the L1 data gas each period.
+    pub const fn cumulative_target_mint(&self, l2_block_number: u64) -> u128 {
+        let mut remaining = l2_block_number / Self::ADJUSTMENT_PERIOD;
+        let mut target = Self::TARGET_MINT_PER_PERIOD;
+        let mut total: u128 = 0;
+        
+        // Each iteration covers one halving period; the target reaches zero long before the
+        // halving factor saturates.
+        while remaining > 0 && target > 0 {
+            let periods = if remaining < self.adjustment_periods_per_halving {
+                remaining
+            } else {
+                self.adjustment_periods_per_halving
+            };
+            total = total.saturating_add(target * periods as u128);
+            remaining -= periods;
+            target /= 2;
+        }
+        total
+    }
+    
     /// Compute the new FCT mint rate based on current conditions
     pub fn compute_new_rate(
+        &self,
         l2_block_number: u64,
         prev_rate: u128,
         cumulative_l1_data_gas: u128,
@@ -85,7 +177,7 @@ impl FctMintCalculator {
             let new_rate = if cumulative_l1_data_gas == 0 {
                 Self::MAX_RATE
             } else {
-                let halving_adjusted_target = Self::halving_adjusted_target(l2_block_number);
+                let halving_adjusted_target = self.halving_adjusted_target(l2_block_number);
                 if halving_adjusted_target == 0 {
                     return 0;
                 }
@@ -113,6 +205,23 @@ impl FctMintCalculator {
         }
     }
     
+    /// Calculate L1 data gas used for a transaction like [`Self::calculate_data_gas_used`], with
+    /// the EIP-7623 calldata floor (Prague) applied.
+    ///
+    /// EIP-7623 charges the larger of the standard cost and the floor cost, but the standard cost
+    /// only exceeds the floor once execution gas is added, which Facet does not charge. Over the
+    /// calldata alone the floor always wins, so it is charged directly. Contract-initiated payloads
+    /// are not calldata of the L1 transaction, so the floor does not apply to them.
+    pub fn calculate_data_gas_used_eip7623(input_data: &[u8], contract_initiated: bool) -> u64 {
+        if contract_initiated {
+            return Self::calculate_data_gas_used(input_data, true);
+        }
+        let zero_count = input_data.iter().filter(|&&b| b == 0).count() as u64;
+        let non_zero_count = input_data.len() as u64 - zero_count;
+        let tokens = zero_count + non_zero_count * 4;
+        tokens * Self::TOTAL_COST_FLOOR_PER_TOKEN
+    }
+    
     /// Calculate the mint amount for a transaction
     pub fn calculate_mint_amount(l1_data_gas_used: u64, mint_rate: u128) -> u128 {
         (l1_data_gas_used as u128).saturating_mul(mint_rate)
@@ -123,25 +232,78 @@ impl FctMintCalculator {
 mod tests {
     use super::*;
     use alloc::vec;
+    use rstest::rstest;
 
     #[test]
     fn test_halving_periods_calculation() {
         // Test genesis block
-        assert_eq!(FctMintCalculator::halving_periods_passed(0), 0);
+        assert_eq!(FctMintCalculator::default().halving_periods_passed(0), 0);
         
         // Test first halving period
         let halving_block = FctMintCalculator::HALVING_PERIOD_IN_BLOCKS;
-        assert_eq!(FctMintCalculator::halving_periods_passed(halving_block), 1);
+        assert_eq!(FctMintCalculator::default().halving_periods_passed(halving_block), 1);
         
         // Test before first halving
-        assert_eq!(FctMintCalculator::halving_periods_passed(halving_block - 1), 0);
+        assert_eq!(FctMintCalculator::default().halving_periods_passed(halving_block - 1), 0);
     }
     
     #[test]
     fn test_halving_factor() {
-        assert_eq!(FctMintCalculator::halving_factor(0), 1);
-        assert_eq!(FctMintCalculator::halving_factor(FctMintCalculator::HALVING_PERIOD_IN_BLOCKS), 2);
-        assert_eq!(FctMintCalculator::halving_factor(FctMintCalculator::HALVING_PERIOD_IN_BLOCKS * 2), 4);
+        assert_eq!(FctMintCalculator::default().halving_factor(0), 1);
+        assert_eq!(FctMintCalculator::default().halving_factor(FctMintCalculator::HALVING_PERIOD_IN_BLOCKS), 2);
+        assert_eq!(FctMintCalculator::default().halving_factor(FctMintCalculator::HALVING_PERIOD_IN_BLOCKS * 2), 4);
+    }
+    
+    #[test]
+    fn test_halving_factor_saturates() {
+        let calc = FctMintCalculator::default();
+        let period = FctMintCalculator::HALVING_PERIOD_IN_BLOCKS;
+        
+        // Period 127 is the last representable power of two
+        assert_eq!(calc.halving_factor(period * 127), 1 << 127);
+        assert_eq!(calc.halving_factor(period * 128 - 1), 1 << 127);
+        assert_eq!(calc.halving_factor(period * 128), u128::MAX);
+        assert_eq!(calc.halving_factor(period * 200), u128::MAX);
+        assert_eq!(calc.halving_factor(u64::MAX), u128::MAX);
+        
+        assert_eq!(
+            calc.halving_adjusted_target(period * 127),
+            FctMintCalculator::TARGET_MINT_PER_PERIOD >> 127
+        );
+        assert_eq!(calc.halving_adjusted_target(period * 128), 0);
+        assert_eq!(calc.halving_adjusted_target(period * 200), 0);
+    }
+    
+    #[test]
+    fn test_cumulative_target_mint() {
+        let calc = FctMintCalculator::default();
+        let target = FctMintCalculator::TARGET_MINT_PER_PERIOD;
+        let per_halving = FctMintCalculator::ADJUSTMENT_PERIODS_PER_HALVING as u128;
+        let halving = FctMintCalculator::HALVING_PERIOD_IN_BLOCKS;
+        let period = FctMintCalculator::ADJUSTMENT_PERIOD;
+        
+        assert_eq!(calc.cumulative_target_mint(0), 0);
+        // The period containing the block is not yet complete
+        assert_eq!(calc.cumulative_target_mint(period - 1), 0);
+        assert_eq!(calc.cumulative_target_mint(period), target);
+        
+        // One full halving period at the undiminished target
+        assert_eq!(calc.cumulative_target_mint(halving), per_halving * target);
+        
+        // Periods past the boundary are credited at half the target
+        assert_eq!(
+            calc.cumulative_target_mint(halving + 3 * period),
+            per_halving * target + 3 * (target / 2)
+        );
+        assert_eq!(
+            calc.cumulative_target_mint(halving + 3 * period),
+            (0..(halving + 3 * period) / period)
+                .map(|p| calc.halving_adjusted_target(p * period))
+                .sum::<u128>()
+        );
+        
+        // Converges to (just under) twice the first halving's emission
+        assert!(calc.cumulative_target_mint(u64::MAX) < 2 * per_halving * target);
     }
     
     #[test]
@@ -172,19 +334,41 @@ mod tests {
         assert_eq!(FctMintCalculator::calculate_data_gas_used(&data, true), 80); // 10 * 8
     }
     
+    #[test]
+    fn test_data_gas_calculation_eip7623() {
+        // 2 zero and 2 non-zero bytes make 10 tokens
+        let mixed_data = vec![0, 1, 0, 1];
+        let standard = FctMintCalculator::calculate_data_gas_used(&mixed_data, false);
+        let floored = FctMintCalculator::calculate_data_gas_used_eip7623(&mixed_data, false);
+        assert_eq!(standard, 40); // 10 * 4
+        assert_eq!(floored, 100); // 10 * 10
+        
+        // The floor scales the standard cost by the same factor for any byte mix
+        let payload = [0x46, 0x00, 0x00, 0xff, 0x12, 0x00, 0x34];
+        assert_eq!(
+            FctMintCalculator::calculate_data_gas_used_eip7623(&payload, false) * 4,
+            FctMintCalculator::calculate_data_gas_used(&payload, false) * 10
+        );
+        
+        // Contract-initiated data is not calldata and keeps its flat cost
+        let data = vec![1u8; 10];
+        assert_eq!(FctMintCalculator::calculate_data_gas_used_eip7623(&data, true), 80);
+        assert_eq!(FctMintCalculator::calculate_data_gas_used_eip7623(&[], false), 0);
+    }
+    
     #[test]
     fn test_compute_new_rate_first_period() {
         let block_number = FctMintCalculator::ADJUSTMENT_PERIOD; // First adjustment
         let prev_rate = FctMintCalculator::INITIAL_RATE;
         
         // Test with zero cumulative gas
-        let new_rate = FctMintCalculator::compute_new_rate(block_number, prev_rate, 0);
+        let new_rate = FctMintCalculator::default().compute_new_rate(block_number, prev_rate, 0);
         assert_eq!(new_rate, FctMintCalculator::MAX_RATE);
         
         // Test with some cumulative gas
         let cumulative_gas = 1_000_000;
-        let new_rate = FctMintCalculator::compute_new_rate(block_number, prev_rate, cumulative_gas);
-        let expected_target = FctMintCalculator::halving_adjusted_target(block_number);
+        let new_rate = FctMintCalculator::default().compute_new_rate(block_number, prev_rate, cumulative_gas);
+        let expected_target = FctMintCalculator::default().halving_adjusted_target(block_number);
         let expected_rate = expected_target / cumulative_gas;
         
         // Should be clamped by adjustment factor
@@ -201,7 +385,7 @@ mod tests {
         let cumulative_gas = 1_000_000;
         
         // Should return previous rate unchanged
-        let new_rate = FctMintCalculator::compute_new_rate(block_number, prev_rate, cumulative_gas);
+        let new_rate = FctMintCalculator::default().compute_new_rate(block_number, prev_rate, cumulative_gas);
         assert_eq!(new_rate, prev_rate);
     }
     
@@ -229,4 +413,49 @@ mod tests {
         assert!(!FctMintCalculator::is_first_block_in_period(FctMintCalculator::ADJUSTMENT_PERIOD - 1));
         assert!(!FctMintCalculator::is_first_block_in_period(FctMintCalculator::ADJUSTMENT_PERIOD + 1));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_default_matches_constants() {
+        let calc = FctMintCalculator::default();
+        assert_eq!(calc.block_time(), FctMintCalculator::L2_BLOCK_TIME);
+        assert_eq!(calc.raw_halving_period_in_blocks(), FctMintCalculator::RAW_HALVING_PERIOD_IN_BLOCKS);
+        assert_eq!(calc.adjustment_periods_per_halving(), FctMintCalculator::ADJUSTMENT_PERIODS_PER_HALVING);
+        assert_eq!(calc.halving_period_in_blocks(), FctMintCalculator::HALVING_PERIOD_IN_BLOCKS);
+        assert_eq!(FctMintCalculator::new(0), calc);
+    }
+    
+    #[rstest]
+    #[case::two_second_blocks(2, 15_778_476, 15_770_000)]
+    #[case::twelve_second_blocks(12, 2_629_746, 2_620_000)]
+    #[case::hour_blocks(3_600, 8_765, 10_000)]
+    fn test_halving_schedule_by_block_time(
+        #[case] block_time: u64,
+        #[case] raw_halving_period: u64,
+        #[case] halving_period: u64,
+    ) {
+        let calc = FctMintCalculator::new(block_time);
+        assert_eq!(calc.raw_halving_period_in_blocks(), raw_halving_period);
+        assert_eq!(calc.halving_period_in_blocks(), halving_period);
+        
+        // The first halving lands on the chain's own halving boundary
+        assert_eq!(calc.halving_factor(halving_period - 1), 1);
+        assert_eq!(calc.halving_factor(halving_period), 2);
+        assert_eq!(
+            calc.halving_adjusted_target(halving_period),
+            FctMintCalculator::TARGET_MINT_PER_PERIOD / 2
+        );
+    }
+    
+    #[test]
+    fn test_block_time_changes_mint_rate() {
+        // Past the 12s chain's first halving but before the 2s chain's
+        let block = FctMintCalculator::new(12).halving_period_in_blocks();
+        let cumulative_gas = 1_000_000_000;
+        // Low enough that neither result is clamped by the adjustment factor
+        let prev_rate = 300_000_000_000_000;
+        
+        let fast = FctMintCalculator::new(2).compute_new_rate(block, prev_rate, cumulative_gas);
+        let slow = FctMintCalculator::new(12).compute_new_rate(block, prev_rate, cumulative_gas);
+        assert_eq!(fast, 2 * slow);
+    }
+}
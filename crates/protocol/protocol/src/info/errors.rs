@@ -23,6 +23,27 @@ pub enum BlockInfoError {
     OperatorFeeConstant,
 }
 
+/// An error reading the FCT state of a parent L2 block with
+/// [L1BlockInfoFacet::from_l2_parent](crate::L1BlockInfoFacet::from_l2_parent).
+#[derive(Debug, thiserror::Error)]
+pub enum FacetParentError<E> {
+    /// The provider failed to return the parent block.
+    #[error("Failed to fetch the parent L2 block: {0}")]
+    Provider(E),
+    /// The parent block has no transactions, so no L1 info deposit transaction.
+    #[error("L2 block {0} is missing the L1 info deposit transaction")]
+    MissingL1InfoDeposit(u64),
+    /// The first transaction of the parent block is not a deposit transaction.
+    #[error("First transaction of L2 block {0} is not a deposit transaction")]
+    FirstTxNonDeposit(u64),
+    /// Failed to decode the [L1BlockInfoTx](crate::L1BlockInfoTx) of the parent block.
+    #[error("Failed to decode the L1 info transaction of L2 block {0}: {1}")]
+    BlockInfoDecodeError(u64, DecodeError),
+    /// The L1 info transaction of the parent block is not the Facet variant.
+    #[error("L1 info transaction of L2 block {0} is not in the Facet format")]
+    NotFacet(u64),
+}
+
 /// An error decoding an L1 block info transaction.
 #[derive(Debug, Eq, PartialEq, Clone, thiserror::Error)]
 pub enum DecodeError {
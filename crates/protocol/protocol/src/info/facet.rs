@@ -1,7 +1,14 @@
 //! Contains facet-specific L1 block info types.
 
-use crate::DecodeError;
-use alloc::vec::Vec;
+use crate::{
+    BatchValidationProvider, DecodeError, FacetParentError, FctMintCalculator, L1BlockInfoTx,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_consensus::Transaction;
 use alloy_primitives::{Address, B256, Bytes, U256};
 
 /// Represents the fields within a Facet L1 block info transaction.
@@ -47,6 +54,11 @@ pub struct L1BlockInfoFacet {
     /// Indicates that the scalars are empty.
     /// This is an edge case where the first block in ecotone has no scalars,
     /// so the bedrock tx l1 cost function needs to be used.
+    ///
+    /// As with Ecotone, this flag is never part of the calldata and derivation always sets it to
+    /// `false`; the EVM instead detects the edge case from the decoded scalars (see
+    /// [`L1BlockInfoFacet::scalars_empty`]). Facet pins `blob_base_fee_scalar` to `1`, so the
+    /// split base/blob cost function is always selected.
     pub empty_scalars: bool,
     /// The l1 fee overhead used along with the `empty_scalars` field for the
     /// bedrock tx l1 cost function.
@@ -69,6 +81,87 @@ impl L1BlockInfoFacet {
     /// The 4 byte selector of "setL1BlockValuesEcotone()"
     pub const L1_INFO_TX_SELECTOR: [u8; 4] = [0x44, 0x0a, 0x5e, 0x20];
 
+    /// Returns whether the scalars are unset, which is how the EVM selects the bedrock l1 cost
+    /// function over the Ecotone one.
+    ///
+    /// Matches the Ecotone rule: the scalars are empty only if both the base fee scalar and the
+    /// blob base fee scalar are zero.
+    pub const fn scalars_empty(&self) -> bool {
+        self.base_fee_scalar == 0 && self.blob_base_fee_scalar == 0
+    }
+
+    /// Reads the FCT state a child of the L2 block `parent_number` is derived from, returned as
+    /// `(fct_mint_rate, fct_mint_period_l1_data_gas)` of the parent's L1 info transaction.
+    ///
+    /// The genesis block has no L1 info transaction, so its child starts from
+    /// [FctMintCalculator::INITIAL_RATE] and an empty mint period.
+    pub async fn from_l2_parent<P: BatchValidationProvider>(
+        provider: &mut P,
+        parent_number: u64,
+    ) -> Result<(u128, u128), FacetParentError<P::Error>> {
+        if parent_number == 0 {
+            return Ok((FctMintCalculator::INITIAL_RATE, 0));
+        }
+
+        let parent =
+            provider.block_by_number(parent_number).await.map_err(FacetParentError::Provider)?;
+        let first_tx = parent
+            .body
+            .transactions
+            .first()
+            .ok_or(FacetParentError::MissingL1InfoDeposit(parent_number))?;
+        let deposit =
+            first_tx.as_deposit().ok_or(FacetParentError::FirstTxNonDeposit(parent_number))?;
+
+        match L1BlockInfoTx::decode_calldata(deposit.input().as_ref())
+            .map_err(|e| FacetParentError::BlockInfoDecodeError(parent_number, e))?
+        {
+            L1BlockInfoTx::Facet(info) => {
+                Ok((info.fct_mint_rate, info.fct_mint_period_l1_data_gas))
+            }
+            _ => Err(FacetParentError::NotFacet(parent_number)),
+        }
+    }
+
+    /// Compares `self` against `other` field by field, returning one [FieldDiff] per field whose
+    /// value differs, in calldata order.
+    pub fn diff(&self, other: &Self) -> Vec<FieldDiff> {
+        let mut diffs = Vec::new();
+        let mut cmp = |field: &'static str, left: String, right: String| {
+            if left != right {
+                diffs.push(FieldDiff { field, left, right });
+            }
+        };
+
+        cmp("base_fee_scalar", self.base_fee_scalar.to_string(), other.base_fee_scalar.to_string());
+        cmp(
+            "blob_base_fee_scalar",
+            self.blob_base_fee_scalar.to_string(),
+            other.blob_base_fee_scalar.to_string(),
+        );
+        cmp("sequence_number", self.sequence_number.to_string(), other.sequence_number.to_string());
+        cmp("time", self.time.to_string(), other.time.to_string());
+        cmp("number", self.number.to_string(), other.number.to_string());
+        cmp("base_fee", self.base_fee.to_string(), other.base_fee.to_string());
+        cmp("blob_base_fee", self.blob_base_fee.to_string(), other.blob_base_fee.to_string());
+        cmp("block_hash", format!("{}", self.block_hash), format!("{}", other.block_hash));
+        cmp(
+            "batcher_address",
+            format!("{}", self.batcher_address),
+            format!("{}", other.batcher_address),
+        );
+        cmp(
+            "fct_mint_period_l1_data_gas",
+            self.fct_mint_period_l1_data_gas.to_string(),
+            other.fct_mint_period_l1_data_gas.to_string(),
+        );
+        cmp("fct_mint_rate", self.fct_mint_rate.to_string(), other.fct_mint_rate.to_string());
+        cmp("empty_scalars", self.empty_scalars.to_string(), other.empty_scalars.to_string());
+        cmp("l1_fee_overhead", self.l1_fee_overhead.to_string(), other.l1_fee_overhead.to_string());
+
+        diffs
+    }
+
     /// Encodes the [L1BlockInfoFacet] object into Ethereum transaction calldata.
     pub fn encode_calldata(&self) -> Bytes {
         let mut buf = Vec::with_capacity(Self::L1_INFO_TX_LEN);
@@ -170,10 +263,43 @@ impl L1BlockInfoFacet {
     }
 }
 
+/// A single field that differs between two [L1BlockInfoFacet]s, as returned by
+/// [L1BlockInfoFacet::diff].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// The name of the differing field.
+    pub field: &'static str,
+    /// The formatted value of the field on the left-hand side (`self`).
+    pub left: String,
+    /// The formatted value of the field on the right-hand side (`other`).
+    pub right: String,
+}
+
+impl core::fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {} != {}", self.field, self.left, self.right)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{RAW_BEDROCK_INFO_TX, SYNTHETIC_FACET_INFO_TX, TestBatchValidator};
     use alloc::vec;
+    use alloy_consensus::{BlockBody, Header};
+    use alloy_primitives::Sealed;
+    use op_alloy_consensus::{OpBlock, OpTxEnvelope, TxDeposit};
+
+    fn parent_with_l1_info(number: u64, calldata: &[u8]) -> OpBlock {
+        let deposit = TxDeposit { input: Bytes::copy_from_slice(calldata), ..Default::default() };
+        OpBlock {
+            header: Header { number, ..Default::default() },
+            body: BlockBody {
+                transactions: vec![OpTxEnvelope::Deposit(Sealed::new(deposit))],
+                ..Default::default()
+            },
+        }
+    }
 
     #[test]
     fn test_decode_calldata_facet_invalid_length() {
@@ -206,4 +332,99 @@ mod tests {
         let decoded_info = L1BlockInfoFacet::decode_calldata(&calldata).unwrap();
         assert_eq!(info, decoded_info);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_synthetic_facet_info_tx_roundtrip() {
+        let info = L1BlockInfoFacet::decode_calldata(&SYNTHETIC_FACET_INFO_TX).unwrap();
+        assert_eq!(info.number, 19_655_712);
+        assert_eq!(info.sequence_number, 5);
+        assert_eq!(info.fct_mint_rate, FctMintCalculator::INITIAL_RATE);
+        assert_eq!(info.fct_mint_period_l1_data_gas, 123_456);
+
+        let calldata = info.encode_calldata();
+        assert_eq!(calldata.as_ref(), SYNTHETIC_FACET_INFO_TX.as_ref());
+
+        // The FCT fields are the trailing two big-endian u128 words, period data gas first
+        let len = L1BlockInfoFacet::L1_INFO_TX_LEN;
+        assert_eq!(calldata[len - 32..len - 16], 123_456u128.to_be_bytes());
+        assert_eq!(calldata[len - 16..], FctMintCalculator::INITIAL_RATE.to_be_bytes());
+
+        let tx = L1BlockInfoTx::decode_calldata(&SYNTHETIC_FACET_INFO_TX).unwrap();
+        assert_eq!(tx, L1BlockInfoTx::Facet(info));
+        assert_eq!(tx.encode_calldata(), calldata);
+    }
+
+    #[test]
+    fn test_diff_reports_fct_mint_rate() {
+        let geth = L1BlockInfoFacet {
+            number: 1,
+            time: 2,
+            base_fee: 3,
+            block_hash: B256::from([4u8; 32]),
+            sequence_number: 5,
+            batcher_address: Address::from([6u8; 20]),
+            blob_base_fee: 7,
+            blob_base_fee_scalar: 1,
+            base_fee_scalar: 9,
+            fct_mint_rate: 800_000_000_000_000,
+            fct_mint_period_l1_data_gas: 576,
+            ..Default::default()
+        };
+        let kona = L1BlockInfoFacet { fct_mint_rate: 400_000_000_000_000, ..geth };
+
+        // Diff the decoded calldata, as the inspector does with the two derived txs.
+        let geth = L1BlockInfoFacet::decode_calldata(&geth.encode_calldata()).unwrap();
+        let kona = L1BlockInfoFacet::decode_calldata(&kona.encode_calldata()).unwrap();
+
+        let diffs = geth.diff(&kona);
+        assert_eq!(
+            diffs,
+            vec![FieldDiff {
+                field: "fct_mint_rate",
+                left: "800000000000000".into(),
+                right: "400000000000000".into(),
+            }]
+        );
+        assert_eq!(diffs[0].to_string(), "fct_mint_rate: 800000000000000 != 400000000000000");
+        assert!(geth.diff(&geth).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_from_l2_parent_reads_fct_fields() {
+        let mut empty = OpBlock::default();
+        empty.header.number = 7;
+        let mut provider = TestBatchValidator::new(
+            vec![],
+            vec![
+                parent_with_l1_info(5, &SYNTHETIC_FACET_INFO_TX),
+                parent_with_l1_info(6, &RAW_BEDROCK_INFO_TX),
+                empty,
+                parent_with_l1_info(9, &[0xde, 0xad, 0xbe, 0xef]),
+            ],
+        );
+
+        let fct = L1BlockInfoFacet::from_l2_parent(&mut provider, 5).await.unwrap();
+        assert_eq!(fct, (FctMintCalculator::INITIAL_RATE, 123_456));
+
+        // The genesis block has no L1 info transaction to read
+        let fct = L1BlockInfoFacet::from_l2_parent(&mut provider, 0).await.unwrap();
+        assert_eq!(fct, (FctMintCalculator::INITIAL_RATE, 0));
+
+        assert!(matches!(
+            L1BlockInfoFacet::from_l2_parent(&mut provider, 6).await,
+            Err(FacetParentError::NotFacet(6))
+        ));
+        assert!(matches!(
+            L1BlockInfoFacet::from_l2_parent(&mut provider, 9).await,
+            Err(FacetParentError::BlockInfoDecodeError(9, DecodeError::InvalidSelector))
+        ));
+        assert!(matches!(
+            L1BlockInfoFacet::from_l2_parent(&mut provider, 7).await,
+            Err(FacetParentError::MissingL1InfoDeposit(7))
+        ));
+        assert!(matches!(
+            L1BlockInfoFacet::from_l2_parent(&mut provider, 8).await,
+            Err(FacetParentError::Provider(_))
+        ));
+    }
+}
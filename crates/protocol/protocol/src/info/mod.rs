@@ -13,7 +13,7 @@ mod ecotone;
 pub use ecotone::L1BlockInfoEcotone;
 
 mod facet;
-pub use facet::L1BlockInfoFacet;
+pub use facet::{FieldDiff, L1BlockInfoFacet};
 
 mod errors;
-pub use errors::{BlockInfoError, DecodeError};
+pub use errors::{BlockInfoError, DecodeError, FacetParentError};
@@ -2,10 +2,10 @@
 //! transaction.
 
 use alloy_consensus::Header;
-use alloy_eips::{BlockNumHash, eip7840::BlobParams};
+use alloy_eips::{BlockNumHash, eip2718::Decodable2718, eip7840::BlobParams};
 use alloy_primitives::{Address, B256, Bytes, Sealable, Sealed, TxKind, U256, address};
 use kona_genesis::{RollupConfig, SystemConfig};
-use op_alloy_consensus::{DepositSourceDomain, L1InfoDepositSource, TxDeposit};
+use op_alloy_consensus::{DepositSourceDomain, L1InfoDepositSource, OpTxEnvelope, TxDeposit};
 
 use crate::{
     BlockInfoError, DecodeError, L1BlockInfoBedrock, L1BlockInfoEcotone, L1BlockInfoIsthmus,
@@ -180,7 +180,18 @@ impl L1BlockInfoTx {
         Ok((l1_info, deposit_tx.seal_slow()))
     }
 
-    /// Decodes the [L1BlockInfoEcotone] object from Ethereum transaction calldata.
+    /// Decodes the [L1BlockInfoTx] variant selected by the function selector of the calldata.
+    ///
+    /// | Selector     | Length | Variant                    |
+    /// |--------------|--------|----------------------------|
+    /// | `0x015d8eb9` | 260    | [L1BlockInfoTx::Bedrock]   |
+    /// | `0x440a5e20` | 164    | [L1BlockInfoTx::Ecotone]   |
+    /// | `0x440a5e20` | 196    | [L1BlockInfoTx::Facet]     |
+    /// | `0x098999be` | 176    | [L1BlockInfoTx::Isthmus]   |
+    ///
+    /// Facet extends the Ecotone calldata with its FCT trailer under the same selector, so only the
+    /// exact Ecotone length selects Ecotone. Any other length is decoded, and rejected, as Facet.
+    /// Unknown selectors are a [DecodeError::InvalidSelector].
     pub fn decode_calldata(r: &[u8]) -> Result<Self, DecodeError> {
         if r.len() < 4 {
             return Err(DecodeError::MissingSelector);
@@ -189,13 +200,33 @@ impl L1BlockInfoTx {
         let mut selector = [0u8; 4];
         selector.copy_from_slice(&r[0..4]);
         match selector {
+            L1BlockInfoBedrock::L1_INFO_TX_SELECTOR => {
+                L1BlockInfoBedrock::decode_calldata(r).map(Self::Bedrock)
+            }
+            L1BlockInfoEcotone::L1_INFO_TX_SELECTOR
+                if r.len() == L1BlockInfoEcotone::L1_INFO_TX_LEN =>
+            {
+                L1BlockInfoEcotone::decode_calldata(r).map(Self::Ecotone)
+            }
             L1BlockInfoFacet::L1_INFO_TX_SELECTOR => {
                 L1BlockInfoFacet::decode_calldata(r).map(Self::Facet)
             }
+            L1BlockInfoIsthmus::L1_INFO_TX_SELECTOR => {
+                L1BlockInfoIsthmus::decode_calldata(r).map(Self::Isthmus)
+            }
             _ => Err(DecodeError::InvalidSelector),
         }
     }
 
+    /// Returns `true` if `encoded` is an EIP-2718 encoded deposit transaction whose calldata
+    /// decodes to an [L1BlockInfoTx::Facet].
+    pub fn is_facet_info_tx(encoded: &[u8]) -> bool {
+        let Ok(tx) = OpTxEnvelope::decode_2718(&mut &encoded[..]) else { return false };
+        tx.as_deposit().is_some_and(|deposit| {
+            matches!(Self::decode_calldata(&deposit.input), Ok(Self::Facet(_)))
+        })
+    }
+
     /// Returns whether the scalars are empty.
     pub const fn empty_scalars(&self) -> bool {
         match self {
@@ -326,6 +357,30 @@ impl L1BlockInfoTx {
             Self::Facet(L1BlockInfoFacet { sequence_number, .. }) => *sequence_number,
         }
     }
+
+    /// Returns whether this is a Facet L1 info transaction.
+    pub const fn is_facet(&self) -> bool {
+        matches!(self, Self::Facet(_))
+    }
+
+    /// Returns the FCT mint rate, or `None` if this is not a Facet L1 info transaction.
+    pub const fn fct_mint_rate(&self) -> Option<u128> {
+        match self {
+            Self::Facet(L1BlockInfoFacet { fct_mint_rate, .. }) => Some(*fct_mint_rate),
+            _ => None,
+        }
+    }
+
+    /// Returns the L1 data gas of the FCT mint period, or `None` if this is not a Facet L1 info
+    /// transaction.
+    pub const fn fct_mint_period_l1_data_gas(&self) -> Option<u128> {
+        match self {
+            Self::Facet(L1BlockInfoFacet { fct_mint_period_l1_data_gas, .. }) => {
+                Some(*fct_mint_period_l1_data_gas)
+            }
+            _ => None,
+        }
+    }
     
     /// Sets the FCT mint rate and cumulative L1 data gas for Facet variants.
     /// This is used by the StatefulAttributesBuilder to update the L1 block info
@@ -379,9 +434,11 @@ impl L1BlockInfoTx {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::test_utils::{RAW_BEDROCK_INFO_TX, RAW_ECOTONE_INFO_TX, RAW_ISTHMUS_INFO_TX};
+    use crate::test_utils::{
+        RAW_BEDROCK_INFO_TX, RAW_ECOTONE_INFO_TX, RAW_ISTHMUS_INFO_TX, SYNTHETIC_FACET_INFO_TX,
+    };
     use alloc::{string::ToString, vec::Vec};
-    use alloy_primitives::{address, b256};
+    use alloy_primitives::{address, b256, hex};
     use kona_genesis::HardForkConfig;
     use rstest::rstest;
 
@@ -404,17 +461,6 @@ mod test {
             "Invalid bedrock data length. Expected 260, got 6"
         );
 
-        let calldata = L1BlockInfoEcotone::L1_INFO_TX_SELECTOR
-            .into_iter()
-            .chain([0xde, 0xad])
-            .collect::<Vec<u8>>();
-        let err = L1BlockInfoTx::decode_calldata(&calldata);
-        assert!(err.is_err());
-        assert_eq!(
-            err.err().unwrap().to_string(),
-            "Invalid ecotone data length. Expected 164, got 6"
-        );
-
         let calldata = L1BlockInfoIsthmus::L1_INFO_TX_SELECTOR
             .into_iter()
             .chain([0xde, 0xad])
@@ -426,6 +472,7 @@ mod test {
             "Invalid isthmus data length. Expected 176, got 6"
         );
 
+        // Ecotone shares the Facet selector, so a wrong length is reported against the Facet layout
         let calldata = L1BlockInfoFacet::L1_INFO_TX_SELECTOR
             .into_iter()
             .chain([0xde, 0xad])
@@ -474,6 +521,25 @@ mod test {
         assert_eq!(err, Err(DecodeError::InvalidSelector));
     }
 
+    #[rstest]
+    #[case::bedrock(&RAW_BEDROCK_INFO_TX, "bedrock")]
+    #[case::ecotone(&RAW_ECOTONE_INFO_TX, "ecotone")]
+    #[case::isthmus(&RAW_ISTHMUS_INFO_TX, "isthmus")]
+    #[case::facet(&SYNTHETIC_FACET_INFO_TX, "facet")]
+    fn test_decode_calldata_selects_variant(#[case] calldata: &[u8], #[case] expected: &str) {
+        let variant = match L1BlockInfoTx::decode_calldata(calldata).unwrap() {
+            L1BlockInfoTx::Bedrock(_) => "bedrock",
+            L1BlockInfoTx::Ecotone(_) => "ecotone",
+            L1BlockInfoTx::Isthmus(_) => "isthmus",
+            L1BlockInfoTx::Facet(facet) => {
+                // The FCT trailer is decoded rather than dropped with the Ecotone layout
+                assert_eq!(facet.fct_mint_period_l1_data_gas, 123_456);
+                "facet"
+            }
+        };
+        assert_eq!(variant, expected);
+    }
+
     #[test]
     fn test_l1_block_info_id() {
         let bedrock = L1BlockInfoTx::Bedrock(L1BlockInfoBedrock {
@@ -556,6 +622,28 @@ mod test {
         assert_eq!(facet.sequence_number(), 202224);
     }
 
+    #[test]
+    fn test_fct_fields_only_on_facet() {
+        let facet = L1BlockInfoTx::Facet(L1BlockInfoFacet {
+            fct_mint_rate: 1000,
+            fct_mint_period_l1_data_gas: 2000,
+            ..Default::default()
+        });
+        assert!(facet.is_facet());
+        assert_eq!(facet.fct_mint_rate(), Some(1000));
+        assert_eq!(facet.fct_mint_period_l1_data_gas(), Some(2000));
+
+        for other in [
+            L1BlockInfoTx::Bedrock(L1BlockInfoBedrock::default()),
+            L1BlockInfoTx::Ecotone(L1BlockInfoEcotone::default()),
+            L1BlockInfoTx::Isthmus(L1BlockInfoIsthmus::default()),
+        ] {
+            assert!(!other.is_facet());
+            assert_eq!(other.fct_mint_rate(), None);
+            assert_eq!(other.fct_mint_period_l1_data_gas(), None);
+        }
+    }
+
     #[test]
     fn test_operator_fee_constant() {
         let bedrock = L1BlockInfoTx::Bedrock(L1BlockInfoBedrock::default());
@@ -1072,6 +1160,66 @@ mod test {
         );
     }
 
+    /// System config scalar in the pre-Ecotone (version 0) format, which carries no blob scalar.
+    const LEGACY_SCALAR: U256 = U256::ZERO;
+
+    /// System config scalar in the Ecotone (version 1) format: blob scalar 0x0e00, base scalar
+    /// 0x0dd0.
+    const SPLIT_SCALAR: U256 =
+        U256::from_be_bytes(hex!("01000000000000000000000000000000000000000000000000000e0000000dd0"));
+
+    #[rstest]
+    #[case::empty_scalars(LEGACY_SCALAR)]
+    #[case::split_scalars(SPLIT_SCALAR)]
+    fn test_try_new_facet_empty_scalars(#[case] scalar: U256) {
+        let rollup_config = RollupConfig {
+            hardforks: HardForkConfig::facet_all_active_from_genesis(),
+            ..Default::default()
+        };
+        let system_config = SystemConfig { scalar, ..Default::default() };
+        let l1_header = Header { number: 21_000_000, timestamp: 1_730_000_000, ..Default::default() };
+
+        let l1_info =
+            L1BlockInfoTx::try_new(&rollup_config, &system_config, 0, &l1_header, 12).unwrap();
+        let L1BlockInfoTx::Facet(facet) = l1_info else {
+            panic!("Wrong fork");
+        };
+
+        // Facet ignores the system config scalars, so the split cost function is always used.
+        assert_eq!((facet.base_fee_scalar, facet.blob_base_fee_scalar), (0, 1));
+        assert!(!facet.scalars_empty());
+        assert_eq!(facet.empty_scalars, facet.scalars_empty());
+        assert!(!l1_info.empty_scalars());
+
+        // Same as Ecotone, the flag does not survive a calldata roundtrip.
+        let decoded = L1BlockInfoTx::decode_calldata(&l1_info.encode_calldata()).unwrap();
+        assert_eq!(decoded, l1_info);
+
+        let ecotone = L1BlockInfoEcotone::decode_calldata(
+            &L1BlockInfoEcotone {
+                base_fee_scalar: facet.base_fee_scalar,
+                blob_base_fee_scalar: facet.blob_base_fee_scalar,
+                empty_scalars: true,
+                ..Default::default()
+            }
+            .encode_calldata(),
+        )
+        .unwrap();
+        assert_eq!(ecotone.empty_scalars, facet.empty_scalars);
+    }
+
+    #[test]
+    fn test_facet_scalars_empty_rule() {
+        let unset = L1BlockInfoFacet::default();
+        assert!(unset.scalars_empty());
+
+        let base_only = L1BlockInfoFacet { base_fee_scalar: 1, ..Default::default() };
+        assert!(!base_only.scalars_empty());
+
+        let blob_only = L1BlockInfoFacet { blob_base_fee_scalar: 1, ..Default::default() };
+        assert!(!blob_only.scalars_empty());
+    }
+
     #[test]
     fn test_try_new_with_deposit_tx() {
         let rollup_config = RollupConfig {
@@ -1111,4 +1259,24 @@ mod test {
         assert!(!deposit_tx.is_system_transaction);
         assert_eq!(deposit_tx.input, l1_info.encode_calldata());
     }
+
+    #[test]
+    fn test_is_facet_info_tx() {
+        use alloy_eips::eip2718::Encodable2718;
+
+        let encode = |calldata: &[u8]| {
+            let deposit = TxDeposit {
+                from: L1_INFO_DEPOSITOR_ADDRESS,
+                to: TxKind::Call(Predeploys::L1_BLOCK_INFO),
+                input: Bytes::copy_from_slice(calldata),
+                ..Default::default()
+            };
+            OpTxEnvelope::Deposit(Sealed::new(deposit)).encoded_2718()
+        };
+
+        assert!(L1BlockInfoTx::is_facet_info_tx(&encode(&SYNTHETIC_FACET_INFO_TX)));
+        assert!(!L1BlockInfoTx::is_facet_info_tx(&encode(&RAW_ECOTONE_INFO_TX)));
+        assert!(!L1BlockInfoTx::is_facet_info_tx(&SYNTHETIC_FACET_INFO_TX));
+        assert!(!L1BlockInfoTx::is_facet_info_tx(&[]));
+    }
 }
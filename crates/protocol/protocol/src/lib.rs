@@ -57,8 +57,8 @@ pub use deposits::{
 
 mod info;
 pub use info::{
-    BlockInfoError, DecodeError, L1BlockInfoBedrock, L1BlockInfoEcotone, L1BlockInfoIsthmus,
-    L1BlockInfoTx,
+    BlockInfoError, DecodeError, FacetParentError, FieldDiff, L1BlockInfoBedrock,
+    L1BlockInfoEcotone, L1BlockInfoFacet, L1BlockInfoIsthmus, L1BlockInfoTx,
 };
 
 mod predeploys;
@@ -75,5 +75,13 @@ mod facet;
 mod fct_mint;
 
 pub use consts::{FACET_INBOX_ADDRESS, FACET_LOG_INBOX_EVENT_SIG};
-pub use facet::{decode_facet_payload, alias_l1_to_l2, FacetPayload, DecodeError as FacetDecodeError, FACET_TX_TYPE, DEPOSIT_TX_TYPE};
+pub use facet::{
+    CachingFacetDecoder, DEPOSIT_TX_TYPE, DecodeError as FacetDecodeError,
+    FACET_BLOB_SOURCE_DOMAIN, FACET_CALLDATA_SOURCE_DOMAIN, FACET_EIP7623_L1_TIME,
+    FACET_LOG_SOURCE_DOMAIN, FACET_PREFIX_COUNTS_TOWARD_DATA_GAS, FACET_TX_TYPE, FacetPayload,
+    MAX_FACET_PAYLOAD_BYTES, alias_l1_to_l2, decode_facet_payload, decode_facet_payload_checked,
+    decode_facet_payload_with_compression, decode_facet_payload_with_eip7623,
+    decode_facet_payload_with_max_len, decode_facet_payload_with_prefix_accounting,
+    encode_facet_payload, facet_source_hash, is_facet_eip7623_active,
+};
 pub use fct_mint::FctMintCalculator;
@@ -9,8 +9,8 @@ use tracing::{Event, Level, Subscriber};
 use tracing_subscriber::{Layer, layer::Context};
 
 use crate::{
-    BatchValidationProvider, L1BlockInfoBedrock, L1BlockInfoEcotone, L1BlockInfoIsthmus,
-    L2BlockInfo,
+    BatchValidationProvider, L1BlockInfoBedrock, L1BlockInfoEcotone, L1BlockInfoFacet,
+    L1BlockInfoIsthmus, L2BlockInfo,
 };
 
 /// Raw encoded bedrock L1 block info transaction.
@@ -28,6 +28,13 @@ pub const RAW_ISTHMUS_INFO_TX: [u8; L1BlockInfoIsthmus::L1_INFO_TX_LEN] = hex!(
     "098999be00000558000c5fc5000000000000000500000000661c277300000000012bec20000000000000000000000000000000000000000000000000000000026e9f109900000000000000000000000000000000000000000000000000000000000000011c4c84c50740386c7dc081efddd644405f04cde73e30a2e381737acce9f5add30000000000000000000000006887246668a3b87f54deb3b94ba47a6f63f329850000abcd000000000000dcba"
 );
 
+/// Synthetic facet L1 block info transaction, not taken from a Facet block: the ecotone
+/// transaction above, followed by a mint period L1 data gas of `123_456` and the initial FCT mint
+/// rate.
+pub const SYNTHETIC_FACET_INFO_TX: [u8; L1BlockInfoFacet::L1_INFO_TX_LEN] = hex!(
+    "440a5e2000000558000c5fc5000000000000000500000000661c277300000000012bec20000000000000000000000000000000000000000000000000000000026e9f109900000000000000000000000000000000000000000000000000000000000000011c4c84c50740386c7dc081efddd644405f04cde73e30a2e381737acce9f5add30000000000000000000000006887246668a3b87f54deb3b94ba47a6f63f329850000000000000000000000000001e24000000000000000000002d79883d20000"
+);
+
 /// An error for implementations of the [BatchValidationProvider] trait.
 #[derive(Debug, thiserror::Error)]
 pub enum TestBatchValidatorError {
@@ -67,6 +67,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_facet_rollup_config() {
+        let facet = ROLLUP_CONFIGS.get(&1027303).unwrap();
+        assert_eq!(*facet, RollupConfig::facet());
+        assert_eq!(
+            facet.batch_inbox_address,
+            alloy_primitives::address!("facec003e8e0cf7152467c26d37634925a9ce65b")
+        );
+        assert_eq!(
+            facet.deposit_contract_address,
+            alloy_primitives::address!("00000000000000000000000000000000000face7")
+        );
+    }
+
     #[test]
     fn test_chain_by_ident() {
         const ALLOY_BASE: AlloyChain = AlloyChain::base_mainnet();
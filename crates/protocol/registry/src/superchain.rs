@@ -1,6 +1,7 @@
 //! Contains the full superchain data.
 
 use super::ChainList;
+use alloc::vec::Vec;
 use alloy_primitives::map::HashMap;
 use kona_genesis::{ChainConfig, RollupConfig, Superchains};
 
@@ -30,6 +31,9 @@ impl Registry {
     }
 
     /// Initialize the superchain configurations from the chain list.
+    ///
+    /// Facet is not part of the superchain registry, so its rollup config
+    /// ([`RollupConfig::facet`]) is added alongside the superchain rollup configs.
     pub fn from_chain_list() -> Self {
         let chain_list = Self::read_chain_list();
         let superchains = Self::read_superchain_configs();
@@ -53,14 +57,39 @@ impl Registry {
             }
         }
 
+        let facet = RollupConfig::facet();
+        rollup_configs.insert(facet.l2_chain_id, facet);
+
         Self { chain_list, op_chains, rollup_configs }
     }
+
+    /// Returns the IDs of the chains whose parent block at `timestamp` is already past Interop
+    /// activation, in ascending order.
+    ///
+    /// A chain counts as post-Interop when Interop is active at `timestamp - block_time`, so the
+    /// Interop activation block itself is excluded. Chains without an Interop time are never
+    /// included.
+    pub fn active_interop_chains(&self, timestamp: u64) -> Vec<u64> {
+        let mut chains = self
+            .rollup_configs
+            .iter()
+            .filter(|(_, cfg)| {
+                timestamp.checked_sub(cfg.block_time).is_some_and(|t| cfg.is_interop_active(t))
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        chains.sort_unstable();
+        chains
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloc::string::{String, ToString};
+    use alloc::{
+        string::{String, ToString},
+        vec,
+    };
     use alloy_primitives::address;
     use kona_genesis::{AddressList, OP_MAINNET_BASE_FEE_CONFIG, Roles, SuperchainLevel};
 
@@ -137,6 +166,21 @@ mod tests {
         assert_eq!(*superchains.op_chains.get(&8453).unwrap(), base_config);
     }
 
+    #[test]
+    fn test_active_interop_chains() {
+        let mut registry = Registry::default();
+        for (chain_id, interop_time) in [(1, Some(100)), (2, Some(98)), (3, None)] {
+            let mut cfg = RollupConfig { block_time: 2, ..Default::default() };
+            cfg.hardforks.interop_time = interop_time;
+            registry.rollup_configs.insert(chain_id, cfg);
+        }
+
+        // Chain 1 activates at 100, so 102 is its first post-Interop block.
+        assert_eq!(registry.active_interop_chains(100), vec![2]);
+        assert_eq!(registry.active_interop_chains(102), vec![1, 2]);
+        assert!(registry.active_interop_chains(0).is_empty());
+    }
+
     #[test]
     fn test_read_rollup_configs() {
         let superchains = Registry::from_chain_list();
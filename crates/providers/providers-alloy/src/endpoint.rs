@@ -0,0 +1,96 @@
+//! Connecting to RPC endpoints over HTTP, WebSocket, or IPC.
+
+use alloy_provider::{Network, RootProvider};
+use alloy_rpc_client::{ClientBuilder, IpcConnect, WsConnect};
+use alloy_transport::TransportError;
+use std::{path::PathBuf, str::FromStr};
+
+/// An RPC endpoint, with the transport chosen by the scheme of its URL.
+///
+/// `http(s)://` and `ws(s)://` URLs are served over HTTP and WebSocket. A filesystem path, or a
+/// `file://` URL, is an IPC socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpcEndpoint {
+    /// An HTTP or HTTPS endpoint.
+    Http(reqwest::Url),
+    /// A WebSocket endpoint.
+    Ws(reqwest::Url),
+    /// The path of an IPC socket.
+    Ipc(PathBuf),
+}
+
+/// An error for the [RpcEndpoint].
+#[derive(Debug, thiserror::Error)]
+pub enum RpcEndpointError {
+    /// The URL scheme has no supported transport.
+    #[error("Unsupported RPC endpoint scheme `{0}`: expected http(s)://, ws(s)://, or an IPC path")]
+    UnsupportedScheme(String),
+    /// Connecting to the endpoint failed.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+}
+
+impl FromStr for RpcEndpoint {
+    type Err = RpcEndpointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Anything that does not parse as an absolute URL is a path to an IPC socket.
+        let Ok(url) = reqwest::Url::parse(s) else {
+            return Ok(Self::Ipc(PathBuf::from(s)));
+        };
+        match url.scheme() {
+            "http" | "https" => Ok(Self::Http(url)),
+            "ws" | "wss" => Ok(Self::Ws(url)),
+            "file" => Ok(Self::Ipc(PathBuf::from(url.path()))),
+            scheme => Err(RpcEndpointError::UnsupportedScheme(scheme.to_string())),
+        }
+    }
+}
+
+impl RpcEndpoint {
+    /// Connects to the endpoint over its transport and returns a [RootProvider] for it.
+    pub async fn connect<N: Network>(&self) -> Result<RootProvider<N>, RpcEndpointError> {
+        let client = match self {
+            Self::Http(url) => ClientBuilder::default().http(url.clone()),
+            Self::Ws(url) => ClientBuilder::default().ws(WsConnect::new(url.as_str())).await?,
+            Self::Ipc(path) => ClientBuilder::default().ipc(IpcConnect::new(path.clone())).await?,
+        };
+        Ok(RootProvider::new(client))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_from_scheme() {
+        assert!(matches!(
+            "https://mainnet.facet.org".parse::<RpcEndpoint>().unwrap(),
+            RpcEndpoint::Http(_)
+        ));
+        assert!(matches!(
+            "ws://localhost:8546".parse::<RpcEndpoint>().unwrap(),
+            RpcEndpoint::Ws(_)
+        ));
+        assert!(matches!(
+            "wss://node.example/ws".parse::<RpcEndpoint>().unwrap(),
+            RpcEndpoint::Ws(_)
+        ));
+        assert_eq!(
+            "/tmp/geth.ipc".parse::<RpcEndpoint>().unwrap(),
+            RpcEndpoint::Ipc(PathBuf::from("/tmp/geth.ipc"))
+        );
+        assert_eq!(
+            "file:///tmp/geth.ipc".parse::<RpcEndpoint>().unwrap(),
+            RpcEndpoint::Ipc(PathBuf::from("/tmp/geth.ipc"))
+        );
+    }
+
+    #[test]
+    fn test_unsupported_scheme() {
+        let err = "ftp://node.example".parse::<RpcEndpoint>().unwrap_err();
+        assert!(matches!(err, RpcEndpointError::UnsupportedScheme(ref scheme) if scheme == "ftp"));
+        assert!(err.to_string().contains("ftp"));
+    }
+}
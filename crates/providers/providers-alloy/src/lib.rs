@@ -15,6 +15,9 @@ pub use beacon_client::{
 mod blobs;
 pub use blobs::{BlobSidecarProvider, OnlineBlobProvider};
 
+mod endpoint;
+pub use endpoint::{RpcEndpoint, RpcEndpointError};
+
 mod chain_provider;
 pub use chain_provider::{AlloyChainProvider, AlloyChainProviderError};
 
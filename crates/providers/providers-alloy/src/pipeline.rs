@@ -32,8 +32,11 @@ pub type OnlineDataProvider =
 
 /// An RPC-backed payload attributes builder for the `AttributesQueue` stage of the derivation
 /// pipeline.
-pub type OnlineAttributesBuilder =
-    StatefulAttributesBuilder<AlloyChainProvider, AlloyL2ChainProvider>;
+pub type OnlineAttributesBuilder = StatefulAttributesBuilder<
+    AlloyChainProvider,
+    AlloyL2ChainProvider,
+    OnlineBlobProvider<OnlineBeaconClient>,
+>;
 
 /// An oracle-backed derivation pipeline.
 #[derive(Debug)]
@@ -56,7 +59,8 @@ impl OnlinePipeline {
             cfg.clone(),
             l2_chain_provider.clone(),
             chain_provider.clone(),
-        );
+        )
+        .with_blob_provider(blob_provider.clone());
         let dap = EthereumDataSource::new_from_parts(chain_provider.clone(), blob_provider, &cfg);
 
         let mut pipeline = PipelineBuilder::new()